@@ -0,0 +1,184 @@
+use std::{collections::VecDeque, hint::black_box};
+
+use criterion::{BatchSize, BenchmarkId, Criterion, criterion_group, criterion_main};
+use zenoh_sandbox_lib::{
+    RuntimeId,
+    logs::{LOG_PAGE_SIZE, LogEntry, LogFilter, LogStorage},
+    ts::log::{LogEntryLevel, LogSearch},
+};
+
+/// Runtime the entries are stored for
+const RUNTIME_ID: RuntimeId = 1;
+
+/// Sizes of the ring buffer of a runtime
+const MAX_ENTRIES: &[usize] = &[10_000, 100_000];
+
+/// Number of entries of a batch, as the runtime process sends them
+const BATCH_SIZE: usize = 256;
+
+/// Entry with a level, target and message varying with `i`, so that filters match a part
+fn entry(i: usize) -> LogEntry {
+    let level = match i % 10 {
+        0 => LogEntryLevel::ERROR,
+        1 | 2 => LogEntryLevel::WARN,
+        3..=6 => LogEntryLevel::INFO,
+        _ => LogEntryLevel::DEBUG,
+    };
+    let target = match i % 3 {
+        0 => "zenoh::net::runtime",
+        1 => "zenoh_transport::unicast",
+        _ => "zenoh_plugin_remote_api",
+    };
+    LogEntry::new(level, target, format!("Message {} of the benchmark", i))
+}
+
+/// Storage whose ring buffer is full
+fn full_storage(max_entries: usize) -> LogStorage {
+    let storage = LogStorage::new(max_entries);
+    storage.add_logs(RUNTIME_ID, (0..max_entries).map(entry).collect());
+    storage
+}
+
+/// Sequence number of the entry in the middle of the ring buffer
+fn middle_seq(storage: &LogStorage, max_entries: usize) -> u64 {
+    storage
+        .get_page_by_cursor(RUNTIME_ID, &LogFilter::level(None), None, None)
+        .entries
+        .first()
+        .map_or(0, |entry| entry.seq)
+        .saturating_sub(max_entries as u64 / 2)
+}
+
+/// Adding to a full ring buffer, against inserting at the front of a full `Vec` as the
+/// storage did before
+fn bench_add(c: &mut Criterion) {
+    let mut group = c.benchmark_group("add");
+    for &max_entries in MAX_ENTRIES {
+        let storage = full_storage(max_entries);
+        let mut i = max_entries;
+        group.bench_with_input(
+            BenchmarkId::new("add_log", max_entries),
+            &storage,
+            |b, storage| {
+                b.iter(|| {
+                    i += 1;
+                    storage.add_log(RUNTIME_ID, entry(i));
+                })
+            },
+        );
+        group.bench_with_input(
+            BenchmarkId::new("add_logs", max_entries),
+            &storage,
+            |b, storage| {
+                b.iter_batched(
+                    || (0..BATCH_SIZE).map(entry).collect::<Vec<_>>(),
+                    |entries| storage.add_logs(RUNTIME_ID, entries),
+                    BatchSize::SmallInput,
+                )
+            },
+        );
+
+        let mut vec: Vec<LogEntry> = (0..max_entries).map(entry).collect();
+        group.bench_function(BenchmarkId::new("vec_insert_front", max_entries), |b| {
+            b.iter(|| {
+                i += 1;
+                vec.insert(0, entry(i));
+                vec.truncate(max_entries);
+            })
+        });
+        let mut deque: VecDeque<LogEntry> = (0..max_entries).map(entry).collect();
+        group.bench_function(BenchmarkId::new("deque_push_back", max_entries), |b| {
+            b.iter(|| {
+                i += 1;
+                deque.pop_front();
+                deque.push_back(entry(i));
+            })
+        });
+    }
+    group.finish();
+}
+
+/// Filters of the reading benchmarks, by name
+fn filters() -> Vec<(&'static str, LogFilter)> {
+    vec![
+        ("unfiltered", LogFilter::level(None)),
+        ("level", LogFilter::level(Some(LogEntryLevel::WARN))),
+        (
+            "search",
+            LogFilter::new(
+                None,
+                Some(LogSearch {
+                    query: "message 4".to_string(),
+                    regex: false,
+                }),
+                None,
+            )
+            .expect("valid filter"),
+        ),
+    ]
+}
+
+fn bench_get_page(c: &mut Criterion) {
+    let mut group = c.benchmark_group("get_page");
+    for &max_entries in MAX_ENTRIES {
+        let storage = full_storage(max_entries);
+        let last_page = max_entries / LOG_PAGE_SIZE - 1;
+        for (name, filter) in &filters() {
+            for (position, page) in [("first", 0), ("last", last_page)] {
+                group.bench_with_input(
+                    BenchmarkId::new(format!("{}/{}", position, name), max_entries),
+                    &storage,
+                    |b, storage| b.iter(|| storage.get_page(RUNTIME_ID, filter, black_box(page))),
+                );
+            }
+        }
+    }
+    group.finish();
+}
+
+fn bench_page_by_cursor(c: &mut Criterion) {
+    let mut group = c.benchmark_group("get_page_by_cursor");
+    for &max_entries in MAX_ENTRIES {
+        let storage = full_storage(max_entries);
+        let middle = middle_seq(&storage, max_entries);
+        for (name, filter) in &filters() {
+            group.bench_with_input(
+                BenchmarkId::new(format!("latest/{}", name), max_entries),
+                &storage,
+                |b, storage| b.iter(|| storage.get_page_by_cursor(RUNTIME_ID, filter, None, None)),
+            );
+            group.bench_with_input(
+                BenchmarkId::new(format!("before/{}", name), max_entries),
+                &storage,
+                |b, storage| {
+                    b.iter(|| {
+                        storage.get_page_by_cursor(
+                            RUNTIME_ID,
+                            filter,
+                            Some(black_box(middle)),
+                            None,
+                        )
+                    })
+                },
+            );
+            group.bench_with_input(
+                BenchmarkId::new(format!("after/{}", name), max_entries),
+                &storage,
+                |b, storage| {
+                    b.iter(|| {
+                        storage.get_page_by_cursor(
+                            RUNTIME_ID,
+                            filter,
+                            None,
+                            Some(black_box(middle)),
+                        )
+                    })
+                },
+            );
+        }
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_add, bench_get_page, bench_page_by_cursor);
+criterion_main!(benches);
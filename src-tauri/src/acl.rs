@@ -0,0 +1,227 @@
+use std::{collections::HashSet, str::FromStr};
+
+use zenoh::{key_expr::KeyExpr, session::ZenohId};
+
+use crate::{
+    error::SandboxError,
+    ts::{
+        acl::AclConfig,
+        config::{ConfigValidationError, ZenohConfigJson, json_set_path},
+    },
+};
+
+// ============================================================================
+// Access control validation
+// ============================================================================
+//
+// zenoh rejects an invalid `access_control` section only when the runtime starts, and
+// without saying which rule, subject or policy is wrong. The section is checked here
+// field by field, and the ids referenced by the policies are checked to exist.
+
+/// Link protocols a subject can match, as named in zenoh configs
+const LINK_PROTOCOLS: &[&str] = &[
+    "tcp",
+    "udp",
+    "tls",
+    "quic",
+    "serial",
+    "unixpipe",
+    "unixsock-stream",
+    "vsock",
+    "ws",
+];
+
+/// Config path of the access control section
+const ACL_PATH: &str = "access_control";
+
+fn error(path: String, message: String) -> ConfigValidationError {
+    ConfigValidationError { path, message }
+}
+
+/// Check that the ids of a list are unique and not empty, unset ids being skipped.
+/// Returns the ids.
+fn check_ids<'a>(
+    list: &str,
+    ids: impl Iterator<Item = Option<&'a str>>,
+    errors: &mut Vec<ConfigValidationError>,
+) -> HashSet<&'a str> {
+    let mut seen = HashSet::new();
+    for (index, id) in ids.enumerate() {
+        let Some(id) = id else {
+            continue;
+        };
+        let path = format!("{ACL_PATH}/{list}/{index}/id");
+        if id.is_empty() {
+            errors.push(error(path, "Id must not be empty".to_string()));
+        } else if !seen.insert(id) {
+            errors.push(error(path, format!("Duplicate id '{}'", id)));
+        }
+    }
+    seen
+}
+
+/// Check that an optional list is not empty when set
+fn check_not_empty<T>(
+    path: String,
+    list: &Option<Vec<T>>,
+    errors: &mut Vec<ConfigValidationError>,
+) {
+    if list.as_ref().is_some_and(Vec::is_empty) {
+        errors.push(error(path, "List must not be empty when set".to_string()));
+    }
+}
+
+/// Validate an access control section, returning the errors with the paths of the
+/// offending fields, e.g. `access_control/rules/0/key_exprs/1`.
+/// Returns no errors if the section is valid.
+pub fn validate_acl(acl: &AclConfig) -> Vec<ConfigValidationError> {
+    let mut errors = Vec::new();
+
+    let rule_ids = check_ids(
+        "rules",
+        acl.rules.iter().map(|rule| Some(rule.id.as_str())),
+        &mut errors,
+    );
+    for (index, rule) in acl.rules.iter().enumerate() {
+        let path = format!("{ACL_PATH}/rules/{index}");
+        if rule.key_exprs.is_empty() {
+            errors.push(error(
+                format!("{path}/key_exprs"),
+                "A rule needs at least one key expression".to_string(),
+            ));
+        }
+        for (i, key_expr) in rule.key_exprs.iter().enumerate() {
+            if let Err(e) = KeyExpr::try_from(key_expr.as_str()) {
+                errors.push(error(
+                    format!("{path}/key_exprs/{i}"),
+                    format!("Invalid key expression '{}': {}", key_expr, e),
+                ));
+            }
+        }
+        if rule.messages.is_empty() {
+            errors.push(error(
+                format!("{path}/messages"),
+                "A rule needs at least one message kind".to_string(),
+            ));
+        }
+        check_not_empty(format!("{path}/flows"), &rule.flows, &mut errors);
+    }
+
+    let subject_ids = check_ids(
+        "subjects",
+        acl.subjects.iter().map(|subject| Some(subject.id.as_str())),
+        &mut errors,
+    );
+    for (index, subject) in acl.subjects.iter().enumerate() {
+        let path = format!("{ACL_PATH}/subjects/{index}");
+        check_not_empty(
+            format!("{path}/interfaces"),
+            &subject.interfaces,
+            &mut errors,
+        );
+        check_not_empty(
+            format!("{path}/cert_common_names"),
+            &subject.cert_common_names,
+            &mut errors,
+        );
+        check_not_empty(format!("{path}/usernames"), &subject.usernames, &mut errors);
+        check_not_empty(
+            format!("{path}/link_protocols"),
+            &subject.link_protocols,
+            &mut errors,
+        );
+        check_not_empty(format!("{path}/zids"), &subject.zids, &mut errors);
+        for (i, protocol) in subject.link_protocols.iter().flatten().enumerate() {
+            if !LINK_PROTOCOLS.contains(&protocol.as_str()) {
+                errors.push(error(
+                    format!("{path}/link_protocols/{i}"),
+                    format!(
+                        "Unknown link protocol '{}', expected one of {}",
+                        protocol,
+                        LINK_PROTOCOLS.join(", ")
+                    ),
+                ));
+            }
+        }
+        for (i, zid) in subject.zids.iter().flatten().enumerate() {
+            if let Err(e) = ZenohId::from_str(zid) {
+                errors.push(error(
+                    format!("{path}/zids/{i}"),
+                    format!("Invalid ZenohId '{}': {}", zid, e),
+                ));
+            }
+        }
+    }
+
+    check_ids(
+        "policies",
+        acl.policies.iter().map(|policy| policy.id.as_deref()),
+        &mut errors,
+    );
+    for (index, policy) in acl.policies.iter().enumerate() {
+        let path = format!("{ACL_PATH}/policies/{index}");
+        for (list, ids, known) in [
+            ("rules", &policy.rules, &rule_ids),
+            ("subjects", &policy.subjects, &subject_ids),
+        ] {
+            if ids.is_empty() {
+                errors.push(error(
+                    format!("{path}/{list}"),
+                    format!("A policy needs at least one of the {}", list),
+                ));
+            }
+            for (i, id) in ids.iter().enumerate() {
+                if !known.contains(id.as_str()) {
+                    errors.push(error(
+                        format!("{path}/{list}/{i}"),
+                        format!("No entry of {} has id '{}'", list, id),
+                    ));
+                }
+            }
+        }
+    }
+    errors
+}
+
+/// Config with its access control section replaced by `acl`, once validated
+pub fn with_acl(config: &ZenohConfigJson, acl: &AclConfig) -> Result<ZenohConfigJson, String> {
+    let errors = validate_acl(acl);
+    if !errors.is_empty() {
+        let errors: Vec<String> = errors
+            .iter()
+            .map(|error| format!("{}: {}", error.path, error.message))
+            .collect();
+        return Err(format!("Invalid access control: {}", errors.join("; ")));
+    }
+    let mut json = config.as_json().clone();
+    let section = serde_json::to_value(acl)
+        .map_err(|e| format!("Failed to serialize access control: {}", e))?;
+    json_set_path(&mut json, ACL_PATH, section);
+    ZenohConfigJson::from_json(json)
+}
+
+// ============================================================================
+// Tauri commands
+// ============================================================================
+
+/// Validate an access control section, cross-checking the rule and subject ids
+/// referenced by the policies.
+/// Returns an empty list if the section is valid.
+#[tauri::command]
+#[tracing::instrument(skip_all)]
+pub async fn validate_acl_config(
+    acl: AclConfig,
+) -> Result<Vec<ConfigValidationError>, SandboxError> {
+    Ok(validate_acl(&acl))
+}
+
+/// Replace the `access_control` section of `config` by `acl`, once validated,
+/// to try deny and allow rules before declaring or restarting a runtime with the config
+#[tauri::command]
+#[tracing::instrument(skip_all)]
+pub async fn build_acl_config(
+    config: ZenohConfigJson,
+    acl: AclConfig,
+) -> Result<ZenohConfigJson, SandboxError> {
+    with_acl(&config, &acl).map_err(SandboxError::ConfigInvalid)
+}
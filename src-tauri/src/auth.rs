@@ -0,0 +1,331 @@
+use std::{
+    collections::{HashMap, HashSet},
+    path::PathBuf,
+};
+
+use serde_json::Value as JsonValue;
+use tauri::{AppHandle, Manager, State};
+
+use crate::{
+    RuntimeId, ZenohRuntimes,
+    error::SandboxError,
+    logs::LogStorage,
+    timeline::Timeline,
+    ts::{
+        auth::{AuthMismatch, AuthMismatchReason, UsrPwdCredentials},
+        config::{ZenohConfigJson, json_set_path},
+        timeline::ConfigChangeSource,
+    },
+};
+
+// ============================================================================
+// Constants
+// ============================================================================
+
+/// Directory of the user-password dictionaries, in the app data directory
+pub const AUTH_DIR_NAME: &str = "auth";
+
+/// Config path of the user-password authentication
+const USRPWD_PATH: &str = "transport/auth/usrpwd";
+
+// ============================================================================
+// User-password dictionaries
+// ============================================================================
+//
+// A runtime with a dictionary file only accepts links from runtimes whose user and
+// password are listed in it, one `<user>:<password>` entry per line. Runtimes configured
+// together share the same credentials and a dictionary holding them, so that they accept
+// each other.
+
+/// User-password dictionaries stored in a directory
+pub struct UsrPwdStore {
+    dir: PathBuf,
+}
+
+impl UsrPwdStore {
+    pub fn new(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+
+    /// Write the dictionary file accepting `user` with `password`.
+    /// Returns its path.
+    async fn write_dictionary(&self, user: &str, password: &str) -> Result<PathBuf, String> {
+        tokio::fs::create_dir_all(&self.dir)
+            .await
+            .map_err(|e| format!("Failed to create {}: {}", self.dir.display(), e))?;
+        let path = self.dir.join(format!("usrpwd-{}.txt", user));
+        tokio::fs::write(&path, format!("{}:{}\n", user, password))
+            .await
+            .map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            tokio::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o600))
+                .await
+                .map_err(|e| format!("Failed to restrict access to {}: {}", path.display(), e))?;
+        }
+        Ok(path)
+    }
+}
+
+/// Check credentials given to `configure_usrpwd`, as zenoh reads them from dictionaries
+fn check_credential(name: &str, value: &str) -> Result<(), String> {
+    if value.trim().is_empty() {
+        return Err(format!("The {} must not be empty", name));
+    }
+    if value.trim() != value || value.contains(['\n', '\r']) {
+        return Err(format!(
+            "The {} must not contain line breaks or surrounding whitespace",
+            name
+        ));
+    }
+    Ok(())
+}
+
+/// Parse a dictionary file into the password of each user, as zenoh does
+fn parse_dictionary(content: &str) -> Result<HashMap<&str, &str>, String> {
+    let mut dictionary = HashMap::new();
+    for line in content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+    {
+        let (user, password) = line
+            .split_once(':')
+            .ok_or_else(|| format!("Invalid entry '{}', expected <user>:<password>", line))?;
+        let (user, password) = (user.trim(), password.trim());
+        if user.is_empty() || password.is_empty() {
+            return Err(format!("Invalid entry '{}', empty user or password", line));
+        }
+        dictionary.insert(user, password);
+    }
+    Ok(dictionary)
+}
+
+/// User-password settings of a config
+struct UsrPwdSettings {
+    user: Option<String>,
+    password: Option<String>,
+    dictionary_file: Option<String>,
+}
+
+fn usrpwd_settings(config: &ZenohConfigJson) -> UsrPwdSettings {
+    let setting = |key: &str| {
+        config
+            .as_json()
+            .pointer(&format!("/{}/{}", USRPWD_PATH, key))
+            .and_then(JsonValue::as_str)
+            .map(str::to_string)
+    };
+    UsrPwdSettings {
+        user: setting("user"),
+        password: setting("password"),
+        dictionary_file: setting("dictionary_file"),
+    }
+}
+
+/// Config with the user-password authentication set to the given credentials
+fn with_usrpwd(
+    config: &ZenohConfigJson,
+    user: &str,
+    password: &str,
+    dictionary_file: &str,
+) -> Result<ZenohConfigJson, String> {
+    let mut json = config.as_json().clone();
+    for (key, value) in [
+        ("user", user),
+        ("password", password),
+        ("dictionary_file", dictionary_file),
+    ] {
+        json_set_path(
+            &mut json,
+            &format!("{}/{}", USRPWD_PATH, key),
+            JsonValue::String(value.to_string()),
+        );
+    }
+    ZenohConfigJson::from_json(json)
+}
+
+impl ZenohRuntimes {
+    /// Check the links between runtimes against their user-password settings: the runtime
+    /// opening a link must have credentials listed in the dictionary of the runtime
+    /// accepting it, if that one has a dictionary.
+    /// Returns the links whose authentication would fail.
+    async fn usrpwd_mismatches(&self) -> Vec<AuthMismatch> {
+        let links: Vec<_> = self.links.read().await.iter().cloned().collect();
+        let settings: HashMap<RuntimeId, UsrPwdSettings> = self
+            .runtimes
+            .read()
+            .await
+            .iter()
+            .map(|(id, runtime_process)| (*id, usrpwd_settings(&runtime_process.sandbox_config)))
+            .collect();
+
+        let mut dictionaries: HashMap<String, Result<String, String>> = HashMap::new();
+        let mut mismatches = Vec::new();
+        for link in links {
+            let (Some(from), Some(to)) = (settings.get(&link.from), settings.get(&link.to)) else {
+                continue;
+            };
+            let Some(dictionary_file) = &to.dictionary_file else {
+                continue;
+            };
+            let mismatch = |reason: AuthMismatchReason, message: String| AuthMismatch {
+                from: link.from,
+                to: link.to,
+                reason,
+                message,
+            };
+
+            if !dictionaries.contains_key(dictionary_file) {
+                let content = tokio::fs::read_to_string(dictionary_file)
+                    .await
+                    .map_err(|e| format!("Failed to read {}: {}", dictionary_file, e));
+                dictionaries.insert(dictionary_file.clone(), content);
+            }
+            let dictionary = match &dictionaries[dictionary_file] {
+                Ok(content) => parse_dictionary(content),
+                Err(e) => Err(e.clone()),
+            };
+            let dictionary = match dictionary {
+                Ok(dictionary) => dictionary,
+                Err(e) => {
+                    mismatches.push(mismatch(
+                        AuthMismatchReason::InvalidDictionary,
+                        format!("Runtime {} rejects every link: {}", link.to, e),
+                    ));
+                    continue;
+                }
+            };
+            let (Some(user), Some(password)) = (&from.user, &from.password) else {
+                mismatches.push(mismatch(
+                    AuthMismatchReason::MissingCredentials,
+                    format!(
+                        "Runtime {} needs a user and a password to connect to runtime {}",
+                        link.from, link.to
+                    ),
+                ));
+                continue;
+            };
+            if dictionary.get(user.as_str()) != Some(&password.as_str()) {
+                mismatches.push(mismatch(
+                    AuthMismatchReason::UnknownCredentials,
+                    format!(
+                        "The credentials of user '{}' of runtime {} are not in {}",
+                        user, link.from, dictionary_file
+                    ),
+                ));
+            }
+        }
+        mismatches
+    }
+}
+
+// ============================================================================
+// Tauri commands
+// ============================================================================
+
+/// Set the same user-password credentials in the declared configs of runtimes, with a
+/// dictionary file holding them, so that the runtimes authenticate each other's links.
+/// Credentials not given are generated. The changes are annotated in the timeline and the
+/// runtimes' log streams, and take effect the next time the runtimes are started.
+#[tauri::command]
+#[tracing::instrument(skip(password, app))]
+pub async fn configure_usrpwd(
+    runtime_ids: Vec<RuntimeId>,
+    user: Option<String>,
+    password: Option<String>,
+    app: AppHandle,
+) -> Result<UsrPwdCredentials, SandboxError> {
+    let runtime_ids: Vec<RuntimeId> = {
+        let mut seen = HashSet::new();
+        runtime_ids
+            .into_iter()
+            .filter(|id| seen.insert(*id))
+            .collect()
+    };
+    if runtime_ids.is_empty() {
+        return Err(SandboxError::ConfigInvalid(
+            "No runtime to configure".to_string(),
+        ));
+    }
+    let user = user.unwrap_or_else(|| format!("sandbox-{:08x}", rand::random::<u32>()));
+    let password = password.unwrap_or_else(|| format!("{:032x}", rand::random::<u128>()));
+    check_credential("user", &user).map_err(SandboxError::ConfigInvalid)?;
+    if user.contains(':') {
+        return Err(SandboxError::ConfigInvalid(
+            "The user must not contain ':'".to_string(),
+        ));
+    }
+    check_credential("password", &password).map_err(SandboxError::ConfigInvalid)?;
+
+    let runtimes = app.state::<ZenohRuntimes>();
+    {
+        let runtime_processes = runtimes.runtimes.read().await;
+        if let Some(id) = runtime_ids
+            .iter()
+            .find(|id| !runtime_processes.contains_key(id))
+        {
+            return Err(SandboxError::RuntimeNotFound(*id));
+        }
+    }
+    let dictionary_file = app
+        .state::<UsrPwdStore>()
+        .write_dictionary(&user, &password)
+        .await?
+        .to_string_lossy()
+        .to_string();
+
+    let mut changes = Vec::new();
+    {
+        let mut runtime_processes = runtimes.runtimes.write().await;
+        for &runtime_id in &runtime_ids {
+            let runtime_process = runtime_processes
+                .get_mut(&runtime_id)
+                .ok_or(SandboxError::RuntimeNotFound(runtime_id))?;
+            let new_config = with_usrpwd(
+                &runtime_process.sandbox_config,
+                &user,
+                &password,
+                &dictionary_file,
+            )
+            .map_err(SandboxError::ConfigInvalid)?;
+            let old_config =
+                runtime_process.replace_config(new_config.clone(), ConfigChangeSource::Patch);
+            changes.push((runtime_id, old_config, new_config));
+        }
+    }
+    let timeline = app.state::<Timeline>();
+    for (runtime_id, old_config, new_config) in &changes {
+        timeline.annotate_config_change(
+            *runtime_id,
+            ConfigChangeSource::Patch,
+            old_config,
+            new_config,
+            Some(app.state::<LogStorage>().inner()),
+        );
+    }
+    tracing::info!(
+        "User-password authentication of user '{}' set for runtimes {:?}",
+        user,
+        runtime_ids
+    );
+
+    Ok(UsrPwdCredentials {
+        user,
+        password,
+        dictionary_file,
+        runtime_ids,
+    })
+}
+
+/// Check that the runtimes opening links to runtimes with a user-password dictionary
+/// have matching credentials, to catch authentication failures before starting them.
+/// Returns the links whose authentication would fail.
+#[tauri::command]
+#[tracing::instrument(skip(state))]
+pub async fn check_usrpwd_links(
+    state: State<'_, ZenohRuntimes>,
+) -> Result<Vec<AuthMismatch>, SandboxError> {
+    Ok(state.usrpwd_mismatches().await)
+}
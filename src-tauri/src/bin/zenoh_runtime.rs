@@ -1,68 +1,128 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, LazyLock, OnceLock};
+use std::time::{Duration, Instant};
+
 use chrono::Utc;
-use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
-use tokio::net::unix::{OwnedReadHalf, OwnedWriteHalf};
-use tokio::net::UnixStream;
-use tokio::sync::mpsc;
-use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt, Layer};
-use zenoh::config::Config;
+use notify::{EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use parking_lot::Mutex;
+use sysinfo::{ProcessRefreshKind, ProcessesToUpdate, System};
+use tokio::sync::{mpsc, Notify};
+use tracing::span;
+use tracing_subscriber::{
+    layer::{Context, SubscriberExt},
+    registry::LookupSpan,
+    reload,
+    util::SubscriberInitExt,
+    EnvFilter, Layer, Registry,
+};
+use zenoh::config::{Config, Locator, WhatAmI};
 use zenoh::internal::{plugins::PluginsManager, runtime::Runtime, runtime::RuntimeBuilder};
+use zenoh::{
+    bytes::Encoding, liveliness::LivelinessToken, pubsub::Subscriber, qos::CongestionControl,
+    query::Queryable, sample::SampleKind, session::ZenohId, Session, Wait,
+};
 
-use zenoh_sandbox_lib::logs::LogEntry;
-use zenoh_sandbox_lib::protocol::{MainToRuntime, RuntimeToMain};
+use zenoh_sandbox_lib::ipc::{self, IpcReadHalf, IpcWriteHalf};
+use zenoh_sandbox_lib::logs::{record_span_fields, update_span_fields, LogEntry};
+use zenoh_sandbox_lib::protocol::{
+    echo_keyexpr, encode_runtime_message, max_message_size, write_frame, Codec, Frame, FrameReader,
+    MainToRuntime, QueryReply, RuntimeToMain, TestEntity, LOG_BATCH_INTERVAL, LOG_BATCH_SIZE,
+    LOG_FILTER_FILE_ENV, LOG_STATS_INTERVAL, MAX_MESSAGE_SIZE_ENV, MISSED_PINGS_LIMIT,
+    PING_INTERVAL, PROTOCOL_VERSION, STATS_INTERVAL, SUPPORTED_CODECS,
+};
+use zenoh_sandbox_lib::testing::{parse_recording, render_payload_template, sequence_payload};
+use zenoh_sandbox_lib::ts::{
+    config::ZenohMode, runtime::RuntimeStats, scouting::DiscoveredPeer, testing::TestSample,
+};
 
 // ============================================================================
 // Log Capture Layer
 // ============================================================================
 
+/// Maximum number of log entries waiting to be sent to the main process
+const LOG_QUEUE_CAPACITY: usize = 16 * 1024;
+
+/// Bounded queue of log entries between the log layer and the event loop.
+/// When full, the oldest entry is dropped to make room for the new one.
+struct LogQueue {
+    entries: Mutex<VecDeque<LogEntry>>,
+    /// Number of entries dropped since the runtime process started
+    dropped: AtomicU64,
+    notify: Notify,
+}
+
+impl LogQueue {
+    fn new() -> Self {
+        Self {
+            entries: Mutex::new(VecDeque::new()),
+            dropped: AtomicU64::new(0),
+            notify: Notify::new(),
+        }
+    }
+
+    fn push(&self, entry: LogEntry) {
+        {
+            let mut entries = self.entries.lock();
+            if entries.len() >= LOG_QUEUE_CAPACITY {
+                entries.pop_front();
+                self.dropped.fetch_add(1, Ordering::Relaxed);
+            }
+            entries.push_back(entry);
+        }
+        self.notify.notify_one();
+    }
+
+    /// Wait for entries and take up to `max` of them, oldest first. Cancel safe.
+    async fn recv(&self, max: usize) -> Vec<LogEntry> {
+        loop {
+            let entries = self.try_recv(max);
+            if !entries.is_empty() {
+                return entries;
+            }
+            self.notify.notified().await;
+        }
+    }
+
+    /// Take up to `max` of the queued entries, oldest first
+    fn try_recv(&self, max: usize) -> Vec<LogEntry> {
+        let mut entries = self.entries.lock();
+        let count = entries.len().min(max);
+        entries.drain(..count).collect()
+    }
+
+    fn dropped(&self) -> u64 {
+        self.dropped.load(Ordering::Relaxed)
+    }
+}
+
 struct RuntimeLogLayer {
-    log_tx: mpsc::UnboundedSender<LogEntry>,
+    log_queue: Arc<LogQueue>,
 }
 
 impl RuntimeLogLayer {
-    fn new(log_tx: mpsc::UnboundedSender<LogEntry>) -> Self {
-        Self { log_tx }
+    fn new(log_queue: Arc<LogQueue>) -> Self {
+        Self { log_queue }
     }
 }
 
 impl<S> Layer<S> for RuntimeLogLayer
 where
-    S: tracing::Subscriber,
+    S: tracing::Subscriber + for<'a> LookupSpan<'a>,
 {
-    fn on_event(
-        &self,
-        event: &tracing::Event<'_>,
-        _ctx: tracing_subscriber::layer::Context<'_, S>,
-    ) {
-        let entry = LogEntry {
-            timestamp: Utc::now(),
-            level: event.metadata().level().into(),
-            target: event.metadata().target().to_string(),
-            message: extract_message(event),
-        };
-
-        // Send log through channel (ignore errors if receiver dropped)
-        let _ = self.log_tx.send(entry);
+    fn on_new_span(&self, attrs: &span::Attributes<'_>, id: &span::Id, ctx: Context<'_, S>) {
+        record_span_fields(attrs, id, &ctx);
     }
-}
 
-/// Extract message field from a tracing event
-fn extract_message(event: &tracing::Event<'_>) -> String {
-    let mut message = String::new();
-    event.record(&mut MessageVisitor(&mut message));
-    message
-}
+    fn on_record(&self, id: &span::Id, values: &span::Record<'_>, ctx: Context<'_, S>) {
+        update_span_fields(id, values, &ctx);
+    }
 
-struct MessageVisitor<'a>(&'a mut String);
+    fn on_event(&self, event: &tracing::Event<'_>, ctx: Context<'_, S>) {
+        let entry = LogEntry::from_event(event, &ctx);
 
-impl tracing::field::Visit for MessageVisitor<'_> {
-    fn record_debug(&mut self, field: &tracing::field::Field, value: &dyn std::fmt::Debug) {
-        if field.name() == "message" {
-            *self.0 = format!("{:?}", value);
-            // Remove surrounding quotes if present
-            if self.0.starts_with('"') && self.0.ends_with('"') && self.0.len() >= 2 {
-                *self.0 = self.0[1..self.0.len() - 1].to_string();
-            }
-        }
+        self.log_queue.push(entry);
     }
 }
 
@@ -70,31 +130,96 @@ impl tracing::field::Visit for MessageVisitor<'_> {
 // Socket Communication Helpers
 // ============================================================================
 
-/// Send a message to the main process via the socket
-async fn send_message(writer: &mut OwnedWriteHalf, msg: &RuntimeToMain) -> Result<(), String> {
-    let json = serde_json::to_string(msg).map_err(|e| format!("Serialization error: {e}"))?;
-    writer
-        .write_all(format!("{json}\n").as_bytes())
+/// Maximum size of IPC messages, given by the main process
+static MAX_MESSAGE_SIZE: LazyLock<usize> = LazyLock::new(|| {
+    max_message_size(
+        std::env::var(MAX_MESSAGE_SIZE_ENV)
+            .ok()
+            .and_then(|size| size.parse().ok()),
+    )
+});
+
+/// Codec chosen by the main process during the handshake
+static CODEC: OnceLock<Codec> = OnceLock::new();
+
+/// Codec of IPC messages: JSON until the handshake is done
+fn codec() -> Codec {
+    CODEC.get().copied().unwrap_or_default()
+}
+
+/// Send a message to the main process via the IPC connection.
+/// Oversized messages are truncated or replaced, see `encode_runtime_message`.
+async fn send_message(writer: &mut IpcWriteHalf, msg: &RuntimeToMain) -> Result<(), String> {
+    let (encoded, notice) = encode_runtime_message(msg, codec(), *MAX_MESSAGE_SIZE)
+        .map_err(|e| format!("Serialization error: {e}"))?;
+    write_frame(writer, &encoded)
         .await
         .map_err(|e| format!("Write error: {e}"))?;
-    writer.flush().await.map_err(|e| format!("Flush error: {e}"))?;
+    if let Some(notice) = notice {
+        let encoded = codec()
+            .encode(&notice)
+            .map_err(|e| format!("Serialization error: {e}"))?;
+        write_frame(writer, &encoded)
+            .await
+            .map_err(|e| format!("Write error: {e}"))?;
+    }
+    Ok(())
+}
+
+/// Send log entries to the main process as a `LogBatch`, split into smaller batches
+/// while it exceeds the maximum message size
+async fn send_logs(writer: &mut IpcWriteHalf, entries: Vec<LogEntry>) -> Result<(), String> {
+    let mut batches = vec![entries];
+    while let Some(mut batch) = batches.pop() {
+        match batch.len() {
+            0 => continue,
+            // A single entry is truncated to fit if needed
+            1 => send_message(writer, &RuntimeToMain::Log(batch.remove(0))).await?,
+            _ => {
+                let msg = RuntimeToMain::LogBatch(batch);
+                let encoded = codec()
+                    .encode(&msg)
+                    .map_err(|e| format!("Serialization error: {e}"))?;
+                if encoded.len() <= *MAX_MESSAGE_SIZE {
+                    write_frame(writer, &encoded)
+                        .await
+                        .map_err(|e| format!("Write error: {e}"))?;
+                    continue;
+                }
+                let RuntimeToMain::LogBatch(mut batch) = msg else {
+                    unreachable!()
+                };
+                // Send the older half first
+                let newer = batch.split_off(batch.len() / 2);
+                batches.push(newer);
+                batches.push(batch);
+            }
+        }
+    }
     Ok(())
 }
 
-/// Read a message from the main process via the socket
-/// Returns None if socket is closed, Some(msg) on success
+/// Read a message from the main process via the IPC connection, skipping oversized ones
+/// Returns None if the connection is closed, Some(msg) on success
 async fn read_message(
-    reader: &mut BufReader<OwnedReadHalf>,
-    line: &mut String,
+    reader: &mut FrameReader<IpcReadHalf>,
 ) -> Result<Option<MainToRuntime>, String> {
-    line.clear();
-    match reader.read_line(line).await {
-        Ok(0) => Ok(None), // Socket closed
-        Ok(_) => {
-            let msg = serde_json::from_str(line).map_err(|e| format!("Parse error: {e}"))?;
-            Ok(Some(msg))
+    loop {
+        match reader.next_frame().await {
+            Ok(None) => return Ok(None), // Socket closed
+            Ok(Some(Frame::Message(frame))) => {
+                let msg = codec()
+                    .decode(&frame)
+                    .map_err(|e| format!("Parse error: {e}"))?;
+                return Ok(Some(msg));
+            }
+            Ok(Some(Frame::Oversized(size))) => {
+                tracing::warn!(
+                    "Dropped message of {size} bytes exceeding the maximum message size"
+                );
+            }
+            Err(e) => return Err(format!("Read error: {e}")),
         }
-        Err(e) => Err(format!("Read error: {e}")),
     }
 }
 
@@ -102,17 +227,30 @@ async fn read_message(
 // Logging Setup
 // ============================================================================
 
-/// Initialize the tracing subscriber with log capture
-fn setup_logging(log_tx: mpsc::UnboundedSender<LogEntry>) {
-    // Set RUST_LOG for maximum verbosity from Zenoh
-    unsafe {
-        std::env::set_var("RUST_LOG", "trace");
-    }
+/// Filter applied when neither a log filter file nor `RUST_LOG` is given
+const DEFAULT_LOG_FILTER: &str = "trace";
+
+/// Filter from `RUST_LOG` if set and valid, `DEFAULT_LOG_FILTER` otherwise
+fn default_log_filter() -> EnvFilter {
+    EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new(DEFAULT_LOG_FILTER))
+}
+
+/// Initialize the tracing subscriber with log capture.
+/// If a log filter file is given, it is watched and its content applied on every change;
+/// the returned watcher must be kept alive for that.
+fn setup_logging(log_queue: Arc<LogQueue>) -> Option<RecommendedWatcher> {
+    let log_filter_file = std::env::var(LOG_FILTER_FILE_ENV).ok().map(PathBuf::from);
+    let initial_filter = log_filter_file
+        .as_deref()
+        .and_then(|path| read_log_filter(path).ok())
+        .unwrap_or_else(default_log_filter);
+    let (filter_layer, filter_handle) = reload::Layer::new(initial_filter);
 
-    let log_layer = RuntimeLogLayer::new(log_tx)
-        .with_filter(tracing_subscriber::filter::LevelFilter::TRACE);
+    let log_layer =
+        RuntimeLogLayer::new(log_queue).with_filter(tracing_subscriber::filter::LevelFilter::TRACE);
 
     tracing_subscriber::registry()
+        .with(filter_layer)
         .with(
             tracing_subscriber::fmt::layer()
                 .with_target(true)
@@ -121,20 +259,101 @@ fn setup_logging(log_tx: mpsc::UnboundedSender<LogEntry>) {
         )
         .with(log_layer)
         .init();
+
+    let path = log_filter_file?;
+    match watch_log_filter_file(path.clone(), filter_handle) {
+        Ok(watcher) => {
+            tracing::info!("Watching log filter file {}", path.display());
+            Some(watcher)
+        }
+        Err(e) => {
+            tracing::warn!("Failed to watch log filter file {}: {}", path.display(), e);
+            None
+        }
+    }
+}
+
+/// Read an env-filter directive from a file.
+/// Lines starting with `#` are ignored, other lines are joined with commas.
+fn read_log_filter(path: &Path) -> Result<EnvFilter, String> {
+    let content = std::fs::read_to_string(path).map_err(|e| format!("Read error: {e}"))?;
+    let directives = content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .collect::<Vec<_>>()
+        .join(",");
+    if directives.is_empty() {
+        return Ok(default_log_filter());
+    }
+    EnvFilter::try_new(&directives).map_err(|e| format!("Invalid filter '{directives}': {e}"))
+}
+
+/// Watch the log filter file and reload the tracing filter whenever it changes.
+/// The parent directory is watched, so that files replaced by editors are picked up too.
+fn watch_log_filter_file(
+    path: PathBuf,
+    filter_handle: reload::Handle<EnvFilter, Registry>,
+) -> Result<RecommendedWatcher, String> {
+    let dir = path
+        .parent()
+        .map(Path::to_path_buf)
+        .unwrap_or_else(|| PathBuf::from("."));
+    let file_name = path.file_name().map(|name| name.to_os_string());
+
+    let mut watcher = notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+        let Ok(event) = event else {
+            return;
+        };
+        if matches!(event.kind, EventKind::Access(_))
+            || !event
+                .paths
+                .iter()
+                .any(|changed| changed.file_name() == file_name.as_deref())
+        {
+            return;
+        }
+        match read_log_filter(&path).and_then(|filter| {
+            filter_handle
+                .reload(filter)
+                .map_err(|e| format!("Reload error: {e}"))
+        }) {
+            Ok(()) => tracing::info!("Log filter reloaded from {}", path.display()),
+            Err(e) => tracing::warn!("Log filter not reloaded from {}: {}", path.display(), e),
+        }
+    })
+    .map_err(|e| format!("Watcher error: {e}"))?;
+
+    watcher
+        .watch(&dir, RecursiveMode::NonRecursive)
+        .map_err(|e| format!("Watch error: {e}"))?;
+    Ok(watcher)
 }
 
 // ============================================================================
 // Runtime Management
 // ============================================================================
 
-/// Build and start a Zenoh runtime with the given configuration
-async fn start_runtime(zenoh_config: Config) -> Result<(zenoh::session::ZenohId, Runtime), String> {
+/// Build and start a Zenoh runtime with the given configuration,
+/// and open the sandbox's own session on it
+async fn start_runtime(
+    zenoh_config: Config,
+) -> Result<(zenoh::session::ZenohId, Runtime, Session), String> {
     eprintln!("🟦 start_runtime: Setting up plugins manager");
     let mut plugins_mgr = PluginsManager::static_plugins_only();
     plugins_mgr.declare_static_plugin::<zenoh_plugin_remote_api::RemoteApiPlugin, &str>(
         "remote_api",
         true,
     );
+    if zenoh_config.get_json("plugins/rest").is_ok() {
+        plugins_mgr.declare_static_plugin::<zenoh_plugin_rest::RestPlugin, &str>("rest", true);
+    }
+    if zenoh_config.get_json("plugins/storage_manager").is_ok() {
+        plugins_mgr.declare_static_plugin::<zenoh_plugin_storage_manager::StoragesPlugin, &str>(
+            "storage_manager",
+            true,
+        );
+    }
 
     eprintln!("🟦 start_runtime: Building Zenoh runtime");
     tracing::info!("Building Zenoh runtime");
@@ -157,7 +376,458 @@ async fn start_runtime(zenoh_config: Config) -> Result<(zenoh::session::ZenohId,
 
     eprintln!("🟦 start_runtime: Runtime started successfully");
     tracing::info!("Runtime started successfully");
-    Ok((zid, runtime))
+
+    let session = open_session(&runtime, zid).await?;
+    Ok((zid, runtime, session))
+}
+
+/// Open a session sharing the runtime, used by the sandbox to run its own
+/// queries and publications. Declares the echo queryable used for latency measurements.
+async fn open_session(runtime: &Runtime, zid: zenoh::session::ZenohId) -> Result<Session, String> {
+    let session = zenoh::session::init(runtime.clone().into())
+        .await
+        .map_err(|e| format!("Failed to open sandbox session: {e}"))?;
+
+    session
+        .declare_queryable(echo_keyexpr(&zid.to_string()))
+        .callback(|query| {
+            let payload = query.payload().cloned().unwrap_or_default();
+            if let Err(e) = query.reply(query.key_expr().clone(), payload).wait() {
+                tracing::warn!("Failed to reply to echo query: {e}");
+            }
+        })
+        .background()
+        .await
+        .map_err(|e| format!("Failed to declare echo queryable: {e}"))?;
+
+    Ok(session)
+}
+
+/// Query the echo queryable of the runtime `target` `samples` times with a payload of
+/// `payload_size` bytes, returning the round-trip time of each query in microseconds
+async fn ping(
+    session: &Session,
+    target: &str,
+    samples: u32,
+    timeout: Duration,
+    payload_size: usize,
+) -> Result<Vec<u64>, String> {
+    let keyexpr = echo_keyexpr(target);
+    let payload = vec![0u8; payload_size];
+    let mut rtts = Vec::with_capacity(samples as usize);
+    for _ in 0..samples {
+        let start = Instant::now();
+        let replies = session
+            .get(&keyexpr)
+            .payload(payload.clone())
+            .timeout(timeout)
+            .await
+            .map_err(|e| format!("Query failed: {e}"))?;
+        let reply = replies
+            .recv_async()
+            .await
+            .map_err(|_| format!("No reply from {target} within {timeout:?}"))?;
+        reply
+            .result()
+            .map_err(|e| format!("Error reply from {target}: {e:?}"))?;
+        rtts.push(start.elapsed().as_micros() as u64);
+    }
+    Ok(rtts)
+}
+
+/// A declared test entity, undeclared when dropped
+enum TestEntityHandle {
+    Publisher(tokio::task::JoinHandle<()>),
+    Subscriber(#[allow(dead_code)] Subscriber<()>),
+    Queryable(#[allow(dead_code)] Queryable<()>),
+    LivelinessToken(#[allow(dead_code)] LivelinessToken),
+}
+
+impl Drop for TestEntityHandle {
+    fn drop(&mut self) {
+        if let TestEntityHandle::Publisher(task) = self {
+            task.abort();
+        }
+    }
+}
+
+/// Declare a test entity. Samples received by test subscribers are sent through `response_tx`.
+async fn declare_test_entity(
+    session: &Session,
+    id: u64,
+    entity: TestEntity,
+    response_tx: &mpsc::UnboundedSender<RuntimeToMain>,
+) -> Result<TestEntityHandle, String> {
+    match entity {
+        TestEntity::Publisher {
+            keyexpr,
+            payload,
+            period_ms,
+        } => {
+            let publisher = session
+                .declare_publisher(keyexpr)
+                .await
+                .map_err(|e| format!("Failed to declare publisher: {e}"))?;
+            let task = tokio::spawn(async move {
+                let mut interval = tokio::time::interval(Duration::from_millis(period_ms.max(1)));
+                loop {
+                    interval.tick().await;
+                    if let Err(e) = publisher.put(payload.clone()).await {
+                        tracing::warn!("Test publisher {id} failed to publish: {e}");
+                    }
+                }
+            });
+            Ok(TestEntityHandle::Publisher(task))
+        }
+        TestEntity::SequencePublisher {
+            keyexpr,
+            keys,
+            period_ms,
+            payload_size,
+            block,
+        } => {
+            let congestion_control = if block {
+                CongestionControl::Block
+            } else {
+                CongestionControl::Drop
+            };
+            let mut publishers = Vec::new();
+            for key in 0..keys {
+                let publisher = session
+                    .declare_publisher(format!("{keyexpr}/{key}"))
+                    .congestion_control(congestion_control)
+                    .await
+                    .map_err(|e| format!("Failed to declare publisher: {e}"))?;
+                publishers.push(publisher);
+            }
+            let task = tokio::spawn(async move {
+                let mut interval = tokio::time::interval(Duration::from_millis(period_ms.max(1)));
+                // All keys publish at each tick, so they share the sequence number
+                for seq in 0u64.. {
+                    interval.tick().await;
+                    for publisher in &publishers {
+                        if let Err(e) = publisher.put(sequence_payload(seq, payload_size)).await {
+                            tracing::warn!("Test publisher {id} failed to publish: {e}");
+                        }
+                    }
+                }
+            });
+            Ok(TestEntityHandle::Publisher(task))
+        }
+        TestEntity::PeriodicPublisher {
+            keyexpr,
+            payload_template,
+            period_ms,
+            jitter_ms,
+            burst,
+        } => {
+            let publisher = session
+                .declare_publisher(keyexpr)
+                .await
+                .map_err(|e| format!("Failed to declare publisher: {e}"))?;
+            let task = tokio::spawn(async move {
+                let mut seq = 0u64;
+                loop {
+                    for _ in 0..burst {
+                        let payload = render_payload_template(&payload_template, seq, Utc::now());
+                        if let Err(e) = publisher.put(payload).await {
+                            tracing::warn!("Test publisher {id} failed to publish: {e}");
+                        }
+                        seq += 1;
+                    }
+                    // Period shifted by up to the jitter either way
+                    let delay = period_ms + rand::random_range(0..=2 * jitter_ms) - jitter_ms;
+                    tokio::time::sleep(Duration::from_millis(delay.max(1))).await;
+                }
+            });
+            Ok(TestEntityHandle::Publisher(task))
+        }
+        TestEntity::Replay { path, speed } => {
+            let contents = tokio::fs::read_to_string(&path)
+                .await
+                .map_err(|e| format!("Failed to read {path}: {e}"))?;
+            let (_, samples) = parse_recording(&contents).map_err(|e| format!("{path}: {e}"))?;
+            let session = session.clone();
+            let task = tokio::spawn(async move {
+                let Some(first_received_at) = samples.first().map(|sample| sample.received_at)
+                else {
+                    return;
+                };
+                let start = tokio::time::Instant::now();
+                for sample in &samples {
+                    let offset = (sample.received_at - first_received_at)
+                        .to_std()
+                        .unwrap_or_default();
+                    tokio::time::sleep_until(start + offset.div_f64(speed)).await;
+                    let encoding = Encoding::from(sample.encoding.as_str());
+                    let result = if sample.kind.eq_ignore_ascii_case("delete") {
+                        session.delete(sample.key_expr.as_str()).await
+                    } else {
+                        // Payloads were checked when the recording was parsed
+                        let payload = sample.payload_bytes().unwrap_or_default();
+                        session
+                            .put(sample.key_expr.as_str(), payload)
+                            .encoding(encoding)
+                            .await
+                    };
+                    if let Err(e) = result {
+                        tracing::warn!("Replay {id} failed to publish on {}: {e}", sample.key_expr);
+                    }
+                }
+                tracing::info!("Replay {id} of {path} done: {} samples", samples.len());
+            });
+            Ok(TestEntityHandle::Publisher(task))
+        }
+        TestEntity::Subscriber { keyexpr } => {
+            let response_tx = response_tx.clone();
+            let subscriber = session
+                .declare_subscriber(keyexpr)
+                .callback(move |sample| {
+                    let payload = sample.payload().to_bytes();
+                    let sample = TestSample {
+                        sample_id: 0,
+                        received_at: Utc::now(),
+                        key_expr: sample.key_expr().to_string(),
+                        kind: sample.kind().to_string(),
+                        encoding: sample.encoding().to_string(),
+                        payload: String::from_utf8_lossy(&payload).to_string(),
+                        payload_len: payload.len(),
+                        timestamp: sample.timestamp().map(|ts| ts.to_string()),
+                    };
+                    let _ = response_tx.send(RuntimeToMain::TestSample {
+                        id,
+                        sample,
+                        payload: payload.to_vec(),
+                    });
+                })
+                .await
+                .map_err(|e| format!("Failed to declare subscriber: {e}"))?;
+            Ok(TestEntityHandle::Subscriber(subscriber))
+        }
+        TestEntity::Queryable {
+            keyexpr,
+            reply_payload,
+        } => {
+            let response_tx = response_tx.clone();
+            let queryable = session
+                .declare_queryable(keyexpr)
+                .callback(move |query| {
+                    let payload = query.payload().map(|payload| payload.to_bytes());
+                    let payload = payload.as_deref().unwrap_or_default();
+                    let sample = TestSample {
+                        sample_id: 0,
+                        received_at: Utc::now(),
+                        key_expr: query.selector().to_string(),
+                        kind: "query".to_string(),
+                        encoding: query
+                            .encoding()
+                            .map(|encoding| encoding.to_string())
+                            .unwrap_or_default(),
+                        payload: String::from_utf8_lossy(payload).to_string(),
+                        payload_len: payload.len(),
+                        timestamp: None,
+                    };
+                    let _ = response_tx.send(RuntimeToMain::TestSample {
+                        id,
+                        sample,
+                        payload: payload.to_vec(),
+                    });
+                    if let Err(e) = query
+                        .reply(query.key_expr().clone(), reply_payload.clone())
+                        .wait()
+                    {
+                        tracing::warn!("Test queryable {id} failed to reply: {e}");
+                    }
+                })
+                .await
+                .map_err(|e| format!("Failed to declare queryable: {e}"))?;
+            Ok(TestEntityHandle::Queryable(queryable))
+        }
+        TestEntity::LivelinessToken { keyexpr } => {
+            let token = session
+                .liveliness()
+                .declare_token(keyexpr)
+                .await
+                .map_err(|e| format!("Failed to declare liveliness token: {e}"))?;
+            Ok(TestEntityHandle::LivelinessToken(token))
+        }
+        TestEntity::LivelinessSubscriber { keyexpr, history } => {
+            let response_tx = response_tx.clone();
+            let subscriber = session
+                .liveliness()
+                .declare_subscriber(keyexpr)
+                .history(history)
+                .callback(move |sample| {
+                    let _ = response_tx.send(RuntimeToMain::LivelinessChange {
+                        id,
+                        key_expr: sample.key_expr().to_string(),
+                        alive: sample.kind() == SampleKind::Put,
+                    });
+                })
+                .await
+                .map_err(|e| format!("Failed to declare liveliness subscriber: {e}"))?;
+            Ok(TestEntityHandle::Subscriber(subscriber))
+        }
+    }
+}
+
+/// Run a `get` on `selector` and collect all replies received within `timeout`
+async fn query(
+    session: &Session,
+    selector: &str,
+    timeout: Duration,
+) -> Result<Vec<QueryReply>, String> {
+    let replies = session
+        .get(selector)
+        .timeout(timeout)
+        .await
+        .map_err(|e| format!("Query failed: {e}"))?;
+    let mut results = Vec::new();
+    while let Ok(reply) = replies.recv_async().await {
+        results.push(match reply.result() {
+            Ok(sample) => QueryReply {
+                key_expr: sample.key_expr().to_string(),
+                encoding: sample.encoding().to_string(),
+                payload: sample.payload().to_bytes().into_owned(),
+                is_error: false,
+            },
+            Err(err) => QueryReply {
+                key_expr: selector.to_string(),
+                encoding: err.encoding().to_string(),
+                payload: err.payload().to_bytes().into_owned(),
+                is_error: true,
+            },
+        });
+    }
+    Ok(results)
+}
+
+/// Put the bytes of the file at `path` on `keyexpr`, with `encoding` if given.
+/// Returns the number of bytes published.
+async fn publish_file(
+    session: &Session,
+    keyexpr: &str,
+    path: &str,
+    encoding: Option<String>,
+) -> Result<usize, String> {
+    let payload = tokio::fs::read(path)
+        .await
+        .map_err(|e| format!("Failed to read {path}: {e}"))?;
+    let len = payload.len();
+    let put = session.put(keyexpr, payload);
+    let put = match encoding {
+        Some(encoding) => put.encoding(Encoding::from(encoding)),
+        None => put,
+    };
+    put.await
+        .map_err(|e| format!("Failed to publish on {keyexpr}: {e}"))?;
+    tracing::info!("Published {len} bytes of {path} on {keyexpr}");
+    Ok(len)
+}
+
+/// Only these keys can be changed on a running runtime, as enforced by its config notifier
+const WRITABLE_CONFIG_PREFIX: &str = "plugins/";
+
+/// Apply (key, JSON5 value) pairs to the running configuration.
+/// The whole patch is first applied to a copy of the configuration, so that an invalid
+/// entry leaves the runtime untouched instead of half updated. The entries are then
+/// committed through the config notifier, which lets the runtime react to the changed keys.
+fn update_config(runtime: &Runtime, entries: &[(String, String)]) -> Result<(), String> {
+    let mut candidate = get_config(runtime);
+    for (key, value) in entries {
+        if !key.starts_with(WRITABLE_CONFIG_PREFIX) {
+            return Err(format!(
+                "Failed to set '{key}': only keys starting with '{WRITABLE_CONFIG_PREFIX}' can be updated on a running runtime"
+            ));
+        }
+        candidate
+            .insert_json5(key, value)
+            .map_err(|e| format!("Failed to set '{key}': {e}"))?;
+    }
+
+    for (key, value) in entries {
+        runtime
+            .config()
+            .insert_json5(key, value)
+            .map_err(|e| format!("Failed to set '{key}': {e}"))?;
+        tracing::info!("Config updated: {key} = {value}");
+    }
+    Ok(())
+}
+
+/// Open a transport to the runtime `zid` through any of `locators`,
+/// unless there is already one
+async fn connect(runtime: &Runtime, zid: &str, locators: &[String]) -> Result<(), String> {
+    let zid: ZenohId = zid
+        .parse()
+        .map_err(|e| format!("Invalid ZenohId {zid}: {e}"))?;
+    let locators = locators
+        .iter()
+        .map(|locator| {
+            locator
+                .parse::<Locator>()
+                .map_err(|e| format!("Invalid locator {locator}: {e}"))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    if runtime.connect_peer(&zid.into(), &locators).await {
+        tracing::info!("Connected to {zid}");
+        Ok(())
+    } else {
+        Err(format!("Failed to connect to {zid} via {locators:?}"))
+    }
+}
+
+/// Scout for peers and routers during `timeout` with the scouting settings of the running
+/// config, then add the nodes with a transport session that did not answer.
+/// The runtime itself is left out.
+async fn scout(
+    runtime: &Runtime,
+    session: &Session,
+    timeout: Duration,
+) -> Result<Vec<DiscoveredPeer>, String> {
+    let own_zid = session.zid();
+    let scout = zenoh::scout(WhatAmI::Peer | WhatAmI::Router, get_config(runtime))
+        .await
+        .map_err(|e| format!("Failed to scout: {e}"))?;
+
+    let info = session.info();
+    let peers: HashSet<ZenohId> = info.peers_zid().await.collect();
+    let routers: HashSet<ZenohId> = info.routers_zid().await.collect();
+
+    let mut discovered: HashMap<ZenohId, DiscoveredPeer> = HashMap::new();
+    let deadline = tokio::time::Instant::now() + timeout;
+    while let Ok(Ok(hello)) = tokio::time::timeout_at(deadline, scout.recv_async()).await {
+        let zid = hello.zid();
+        if zid == own_zid {
+            continue;
+        }
+        discovered.entry(zid).or_insert_with(|| DiscoveredPeer {
+            zid: zid.to_string(),
+            mode: ZenohMode::from(hello.whatami()),
+            locators: hello.locators().iter().map(|l| l.to_string()).collect(),
+            scouted: true,
+            connected: peers.contains(&zid) || routers.contains(&zid),
+            runtime_id: None,
+        });
+    }
+    scout.stop();
+
+    let connected = peers
+        .iter()
+        .map(|zid| (*zid, ZenohMode::Peer))
+        .chain(routers.iter().map(|zid| (*zid, ZenohMode::Router)));
+    for (zid, mode) in connected {
+        discovered.entry(zid).or_insert_with(|| DiscoveredPeer {
+            zid: zid.to_string(),
+            mode,
+            locators: Vec::new(),
+            scouted: false,
+            connected: true,
+            runtime_id: None,
+        });
+    }
+    tracing::info!("Scouting found {} nodes", discovered.len());
+    Ok(discovered.into_values().collect())
 }
 
 /// Get the current zenoh configuration
@@ -166,45 +836,238 @@ fn get_config(runtime: &Runtime) -> Config {
     config.clone()
 }
 
+// ============================================================================
+// Statistics
+// ============================================================================
+
+/// Time for the admin space to answer when counting routing entries
+const ROUTING_QUERY_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// Count the subscribers and queryables in the routing tables through the admin space
+async fn routing_entries(session: &Session) -> Option<usize> {
+    let zid = session.zid();
+    let mut count = 0;
+    for kind in ["subscriber", "queryable"] {
+        let selector = format!("@/{zid}/*/{kind}/**");
+        let replies = query(session, &selector, ROUTING_QUERY_TIMEOUT)
+            .await
+            .ok()?;
+        count += replies.iter().filter(|reply| !reply.is_error).count();
+    }
+    Some(count)
+}
+
+/// Sample the runtime metrics, with the process metrics refreshed in `system`.
+/// The CPU usage is measured since the previous refresh of `system`.
+async fn sample_stats(session: &Session, system: &mut System) -> RuntimeStats {
+    let info = session.info();
+    let transports = info.transports().await.count();
+    let links = info.links().await.count();
+    let peers = info.peers_zid().await.count();
+    let routers = info.routers_zid().await.count();
+    let routing_entries = routing_entries(session).await;
+
+    let process = sysinfo::get_current_pid().ok().and_then(|pid| {
+        system.refresh_processes_specifics(
+            ProcessesToUpdate::Some(&[pid]),
+            false,
+            ProcessRefreshKind::nothing().with_memory().with_cpu(),
+        );
+        system.process(pid)
+    });
+
+    RuntimeStats {
+        timestamp: Utc::now(),
+        transports,
+        links,
+        peers,
+        routers,
+        routing_entries,
+        rss_bytes: process.map(|process| process.memory()),
+        cpu_percent: process.map(|process| process.cpu_usage()),
+    }
+}
+
+/// Send the runtime metrics every `STATS_INTERVAL` until the event loop stops
+async fn report_stats(session: Session, response_tx: mpsc::UnboundedSender<RuntimeToMain>) {
+    let mut system = System::new();
+    let mut interval = tokio::time::interval(STATS_INTERVAL);
+    loop {
+        interval.tick().await;
+        let stats = sample_stats(&session, &mut system).await;
+        if response_tx.send(RuntimeToMain::Stats(stats)).is_err() {
+            break;
+        }
+    }
+}
+
 // ============================================================================
 // Event Loop
 // ============================================================================
 
 /// Main event loop: forwards logs and handles commands
 async fn run_event_loop(
-    reader: &mut BufReader<OwnedReadHalf>,
-    writer: &mut OwnedWriteHalf,
-    log_rx: &mut mpsc::UnboundedReceiver<LogEntry>,
+    reader: &mut FrameReader<IpcReadHalf>,
+    writer: &mut IpcWriteHalf,
+    log_queue: &LogQueue,
     runtime: &Runtime,
+    session: &Session,
 ) -> Result<(), String> {
-    let mut line = String::new();
+    // Responses produced by background tasks, forwarded to the main process
+    let (response_tx, mut response_rx) = mpsc::unbounded_channel::<RuntimeToMain>();
+    // Test publishers and subscribers by id
+    let mut test_entities: HashMap<u64, TestEntityHandle> = HashMap::new();
+    // Shut down if the main process stops pinging, e.g. because it was killed
+    let mut last_ping = Instant::now();
+    let mut ping_check = tokio::time::interval(PING_INTERVAL);
+    // Log entries waiting to be sent, flushed when full or `LOG_BATCH_INTERVAL` after
+    // the first one was received
+    let mut log_batch: Vec<LogEntry> = Vec::with_capacity(LOG_BATCH_SIZE);
+    let log_flush = tokio::time::sleep(LOG_BATCH_INTERVAL);
+    tokio::pin!(log_flush);
+    // Report the number of dropped log entries when it changes
+    let mut log_stats = tokio::time::interval(LOG_STATS_INTERVAL);
+    let mut reported_dropped = 0;
+    // Sample the runtime metrics in the background
+    let stats_task = tokio::spawn(report_stats(session.clone(), response_tx.clone()));
 
     loop {
         tokio::select! {
             // Handle incoming commands
-            result = read_message(reader, &mut line) => {
+            result = read_message(reader) => {
                 match result? {
                     None => break, // Socket closed
                     Some(MainToRuntime::Stop) => {
+                        let _ = send_logs(writer, std::mem::take(&mut log_batch)).await;
                         send_message(writer, &RuntimeToMain::Stopped).await?;
                         break;
                     }
-                    Some(MainToRuntime::Start(_)) => {
-                        // Ignore duplicate start commands
+                    Some(MainToRuntime::Start(_) | MainToRuntime::Hello { .. }) => {
+                        // Ignore duplicate start and handshake commands
                     }
                     Some(MainToRuntime::GetConfig) => {
                         let config = get_config(runtime);
                         send_message(writer, &RuntimeToMain::Config(Box::new(config))).await?;
                     }
+                    Some(MainToRuntime::UpdateConfig(entries)) => {
+                        let response = match update_config(runtime, &entries) {
+                            Ok(()) => RuntimeToMain::ConfigUpdated(Box::new(get_config(runtime))),
+                            Err(e) => RuntimeToMain::ConfigUpdateError(e),
+                        };
+                        send_message(writer, &response).await?;
+                    }
+                    Some(MainToRuntime::MeasureLatency {
+                        id,
+                        target,
+                        samples,
+                        timeout_ms,
+                        payload_size,
+                    }) => {
+                        let session = session.clone();
+                        let response_tx = response_tx.clone();
+                        tokio::spawn(async move {
+                            let timeout = Duration::from_millis(timeout_ms);
+                            let result =
+                                ping(&session, &target, samples, timeout, payload_size).await;
+                            let _ = response_tx.send(RuntimeToMain::LatencyResult { id, result });
+                        });
+                    }
+                    Some(MainToRuntime::Query { id, selector, timeout_ms }) => {
+                        let session = session.clone();
+                        let response_tx = response_tx.clone();
+                        tokio::spawn(async move {
+                            let result =
+                                query(&session, &selector, Duration::from_millis(timeout_ms)).await;
+                            let _ = response_tx.send(RuntimeToMain::QueryResult { id, result });
+                        });
+                    }
+                    Some(MainToRuntime::DeclareTestEntity { id, entity }) => {
+                        let result = declare_test_entity(session, id, entity, &response_tx)
+                            .await
+                            .map(|handle| {
+                                test_entities.insert(id, handle);
+                            });
+                        send_message(writer, &RuntimeToMain::TestEntityDeclared { id, result })
+                            .await?;
+                    }
+                    Some(MainToRuntime::UndeclareTestEntity(id)) => {
+                        test_entities.remove(&id);
+                    }
+                    Some(MainToRuntime::GetHealth) => {
+                        let info = session.info();
+                        let peers = info.peers_zid().await.count();
+                        let routers = info.routers_zid().await.count();
+                        send_message(writer, &RuntimeToMain::Health { peers, routers }).await?;
+                    }
+                    Some(MainToRuntime::Ping(n)) => {
+                        last_ping = Instant::now();
+                        send_message(writer, &RuntimeToMain::Pong(n)).await?;
+                    }
+                    Some(MainToRuntime::Connect { id, zid, locators }) => {
+                        let runtime = runtime.clone();
+                        let response_tx = response_tx.clone();
+                        tokio::spawn(async move {
+                            let result = connect(&runtime, &zid, &locators).await;
+                            let _ = response_tx.send(RuntimeToMain::Connected { id, result });
+                        });
+                    }
+                    Some(MainToRuntime::PublishFile { id, keyexpr, path, encoding }) => {
+                        let session = session.clone();
+                        let response_tx = response_tx.clone();
+                        tokio::spawn(async move {
+                            let result = publish_file(&session, &keyexpr, &path, encoding).await;
+                            let _ = response_tx.send(RuntimeToMain::Published { id, result });
+                        });
+                    }
+                    Some(MainToRuntime::Scout { id, timeout_ms }) => {
+                        let runtime = runtime.clone();
+                        let session = session.clone();
+                        let response_tx = response_tx.clone();
+                        tokio::spawn(async move {
+                            let timeout = Duration::from_millis(timeout_ms);
+                            let result = scout(&runtime, &session, timeout).await;
+                            let _ = response_tx.send(RuntimeToMain::Scouted { id, result });
+                        });
+                    }
+                }
+            }
+            _ = ping_check.tick() => {
+                if last_ping.elapsed() > PING_INTERVAL * MISSED_PINGS_LIMIT {
+                    tracing::warn!("Main process stopped pinging, shutting down");
+                    break;
                 }
             }
-            // Forward log entries to main process
-            Some(entry) = log_rx.recv() => {
-                // Ignore send errors (main process may have closed)
-                let _ = send_message(writer, &RuntimeToMain::Log(entry)).await;
+            // Forward responses of background tasks to main process
+            Some(response) = response_rx.recv() => {
+                send_message(writer, &response).await?;
+            }
+            // Batch log entries for the main process
+            entries = log_queue.recv(LOG_BATCH_SIZE - log_batch.len()) => {
+                if log_batch.is_empty() {
+                    log_flush
+                        .as_mut()
+                        .reset(tokio::time::Instant::now() + LOG_BATCH_INTERVAL);
+                }
+                log_batch.extend(entries);
+                if log_batch.len() >= LOG_BATCH_SIZE {
+                    // Ignore send errors (main process may have closed)
+                    let _ = send_logs(writer, std::mem::take(&mut log_batch)).await;
+                }
+            }
+            () = &mut log_flush, if !log_batch.is_empty() => {
+                let _ = send_logs(writer, std::mem::take(&mut log_batch)).await;
+            }
+            _ = log_stats.tick() => {
+                let dropped_count = log_queue.dropped();
+                if dropped_count != reported_dropped {
+                    reported_dropped = dropped_count;
+                    send_message(writer, &RuntimeToMain::LogStats { dropped_count }).await?;
+                }
             }
         }
     }
+    stats_task.abort();
+    let _ = send_logs(writer, log_batch).await;
 
     Ok(())
 }
@@ -214,24 +1077,21 @@ async fn run_event_loop(
 // ============================================================================
 
 /// Flush remaining logs and send final error message
-async fn send_final_error(
-    writer: &mut OwnedWriteHalf,
-    log_rx: &mut mpsc::UnboundedReceiver<LogEntry>,
-    error_msg: String,
-) {
-    // Drain any remaining logs in the channel
-    while let Ok(entry) = log_rx.try_recv() {
-        let _ = send_message(writer, &RuntimeToMain::Log(entry)).await;
-    }
-
-    // Send final error log
-    let final_log = LogEntry {
-        timestamp: Utc::now(),
-        level: zenoh_sandbox_lib::ts::log::LogEntryLevel::ERROR,
-        target: "zenoh_runtime".to_string(),
-        message: error_msg,
-    };
-    let _ = send_message(writer, &RuntimeToMain::Log(final_log)).await;
+async fn send_final_error(writer: &mut IpcWriteHalf, log_queue: &LogQueue, error_msg: String) {
+    // Drain any remaining logs in the queue, then add the final error log
+    let mut entries = log_queue.try_recv(usize::MAX);
+    entries.push(LogEntry::new(
+        zenoh_sandbox_lib::ts::log::LogEntryLevel::ERROR,
+        "zenoh_runtime",
+        error_msg,
+    ));
+    for batch in entries.chunks(LOG_BATCH_SIZE) {
+        let _ = send_logs(writer, batch.to_vec()).await;
+    }
+    let dropped_count = log_queue.dropped();
+    if dropped_count > 0 {
+        let _ = send_message(writer, &RuntimeToMain::LogStats { dropped_count }).await;
+    }
 
     // Small delay to ensure message is sent
     tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
@@ -241,49 +1101,81 @@ async fn send_final_error(
 async fn run_main() -> Result<(), Box<dyn std::error::Error>> {
     // Parse command line arguments
     let args: Vec<String> = std::env::args().collect();
+    if args.get(1).is_some_and(|arg| arg == "--version") {
+        println!("{}", env!("CARGO_PKG_VERSION"));
+        return Ok(());
+    }
     if args.len() != 2 {
-        eprintln!("Usage: {} <socket_path>", args[0]);
+        eprintln!("Usage: {} <ipc_address> | --version", args[0]);
         std::process::exit(1);
     }
-    let socket_path = &args[1];
+    let ipc_address = &args[1];
 
-    // Connect to UDS socket and split into reader/writer
-    let socket = UnixStream::connect(socket_path).await?;
-    let (reader, writer) = socket.into_split();
-    let mut reader = BufReader::new(reader);
+    // Connect to the main process and split into reader/writer
+    let (reader, writer) = ipc::connect(ipc_address).await?;
+    let mut reader = FrameReader::new(reader, *MAX_MESSAGE_SIZE);
     let mut writer = writer;
 
     // Set up log capture channel
-    let (log_tx, mut log_rx) = mpsc::unbounded_channel::<LogEntry>();
-    setup_logging(log_tx);
+    let log_queue = Arc::new(LogQueue::new());
+    let _log_filter_watcher = setup_logging(log_queue.clone());
+
+    // Introduce ourselves and check that the main process speaks the same protocol
+    let hello = RuntimeToMain::Hello {
+        protocol_version: PROTOCOL_VERSION,
+        runtime_version: env!("CARGO_PKG_VERSION").to_string(),
+        codecs: SUPPORTED_CODECS.to_vec(),
+    };
+    send_message(&mut writer, &hello).await?;
+    match read_message(&mut reader).await? {
+        Some(MainToRuntime::Hello {
+            protocol_version,
+            codec,
+            ..
+        }) if protocol_version == PROTOCOL_VERSION => {
+            let _ = CODEC.set(codec);
+        }
+        Some(MainToRuntime::Hello {
+            protocol_version,
+            sandbox_version,
+            ..
+        }) => {
+            return Err(format!(
+                "Sandbox {sandbox_version} speaks protocol version {protocol_version}, \
+                 expected version {PROTOCOL_VERSION}"
+            )
+            .into());
+        }
+        _ => return Ok(()), // Socket closed or unexpected message
+    }
 
     // Wait for Start command
-    let mut line = String::new();
-    let Some(MainToRuntime::Start(config)) = read_message(&mut reader, &mut line).await? else {
+    let Some(MainToRuntime::Start(config)) = read_message(&mut reader).await? else {
         return Ok(()); // Socket closed or unexpected message
     };
 
     // Start the runtime
     match start_runtime(*config).await {
-        Ok((zid, runtime)) => {
+        Ok((zid, runtime, session)) => {
             // Runtime started successfully
             send_message(&mut writer, &RuntimeToMain::Started(zid.to_string())).await?;
 
             // Run event loop
-            match run_event_loop(&mut reader, &mut writer, &mut log_rx, &runtime).await {
+            match run_event_loop(&mut reader, &mut writer, &log_queue, &runtime, &session).await {
                 Ok(()) => {
                     // Clean shutdown - flush remaining logs
-                    send_final_error(&mut writer, &mut log_rx, "Runtime stopped".to_string()).await;
+                    send_final_error(&mut writer, &log_queue, "Runtime stopped".to_string()).await;
                 }
                 Err(e) => {
                     // Event loop error - flush logs and send error
-                    send_final_error(&mut writer, &mut log_rx, format!("Runtime error: {}", e)).await;
+                    send_final_error(&mut writer, &log_queue, format!("Runtime error: {}", e))
+                        .await;
                 }
             }
         }
         Err(e) => {
             // Runtime start error - flush logs and send error
-            send_final_error(&mut writer, &mut log_rx, format!("Failed to start: {}", e)).await;
+            send_final_error(&mut writer, &log_queue, format!("Failed to start: {}", e)).await;
             send_message(&mut writer, &RuntimeToMain::StartError(e)).await?;
         }
     }
@@ -0,0 +1,148 @@
+use std::{future::Future, sync::Arc};
+
+use tauri::{AppHandle, Manager};
+use tokio::{sync::Semaphore, task::JoinSet};
+
+use crate::{
+    RuntimeId, ZenohRuntimes,
+    error::SandboxError,
+    ts::runtime::{BulkOperationReport, RuntimeOperationOutcome},
+};
+
+// ============================================================================
+// Constants
+// ============================================================================
+
+/// Number of runtimes processed concurrently when no limit is given
+pub const DEFAULT_MAX_PARALLEL: usize = 8;
+
+// ============================================================================
+// Bounded concurrency
+// ============================================================================
+
+/// Run `operation` for every runtime with at most `max_parallel` operations in flight.
+/// The operation returns an optional ZenohId on success.
+/// Failures of some runtimes don't prevent the others from being processed.
+pub(crate) async fn run_bounded<F, Fut>(
+    app: &AppHandle,
+    runtime_ids: Vec<RuntimeId>,
+    max_parallel: Option<usize>,
+    operation: F,
+) -> BulkOperationReport
+where
+    F: Fn(AppHandle, RuntimeId) -> Fut,
+    Fut: Future<Output = Result<Option<String>, String>> + Send + 'static,
+{
+    let semaphore = Arc::new(Semaphore::new(
+        max_parallel.unwrap_or(DEFAULT_MAX_PARALLEL).max(1),
+    ));
+    let mut tasks = JoinSet::new();
+
+    for runtime_id in runtime_ids {
+        let semaphore = semaphore.clone();
+        let operation = operation(app.clone(), runtime_id);
+        tasks.spawn(async move {
+            let result = match semaphore.acquire_owned().await {
+                Ok(_permit) => operation.await,
+                Err(_) => Err("Operation cancelled".to_string()),
+            };
+            (runtime_id, result)
+        });
+    }
+
+    let mut outcomes = Vec::new();
+    while let Some(joined) = tasks.join_next().await {
+        match joined {
+            Ok((runtime_id, result)) => outcomes.push(RuntimeOperationOutcome {
+                runtime_id,
+                zenoh_id: result.as_ref().ok().cloned().flatten(),
+                error: result.err(),
+            }),
+            Err(e) => tracing::error!("Bulk operation task failed: {}", e),
+        }
+    }
+    outcomes.sort_by_key(|outcome| outcome.runtime_id);
+
+    BulkOperationReport::from_outcomes(outcomes)
+}
+
+// ============================================================================
+// Tauri commands
+// ============================================================================
+
+/// Start several declared runtimes concurrently, spawning and handshaking
+/// at most `max_parallel` of them at the same time.
+/// Returns the outcome for each runtime, including partial failures.
+#[tauri::command]
+#[tracing::instrument(skip(app))]
+pub async fn start_runtimes(
+    runtime_ids: Vec<RuntimeId>,
+    max_parallel: Option<usize>,
+    app: AppHandle,
+) -> Result<BulkOperationReport, SandboxError> {
+    Ok(run_bounded(&app, runtime_ids, max_parallel, start_operation).await)
+}
+
+/// Stop several runtimes concurrently, releasing their ports like `zenoh_runtime_stop`,
+/// with at most `max_parallel` of them stopping at the same time.
+/// Returns the outcome for each runtime, including partial failures.
+#[tauri::command]
+#[tracing::instrument(skip(app))]
+pub async fn stop_runtimes(
+    runtime_ids: Vec<RuntimeId>,
+    max_parallel: Option<usize>,
+    app: AppHandle,
+) -> Result<BulkOperationReport, SandboxError> {
+    Ok(run_bounded(&app, runtime_ids, max_parallel, stop_operation).await)
+}
+
+/// Stop every runtime with a runtime process, see `stop_runtimes`.
+/// Declared runtimes are kept, so they can be started again.
+#[tauri::command]
+#[tracing::instrument(skip(app))]
+pub async fn stop_all_runtimes(
+    max_parallel: Option<usize>,
+    app: AppHandle,
+) -> Result<BulkOperationReport, SandboxError> {
+    let runtime_ids = app.state::<ZenohRuntimes>().active_runtime_ids().await;
+    Ok(run_bounded(&app, runtime_ids, max_parallel, stop_operation).await)
+}
+
+/// Start one runtime within a bulk operation
+pub(crate) async fn start_operation(
+    app: AppHandle,
+    runtime_id: RuntimeId,
+) -> Result<Option<String>, String> {
+    let zid = app
+        .state::<ZenohRuntimes>()
+        .launch(&app, runtime_id)
+        .await?;
+    Ok(Some(zid.to_string()))
+}
+
+/// Stop one runtime within a bulk operation
+pub(crate) async fn stop_operation(
+    app: AppHandle,
+    runtime_id: RuntimeId,
+) -> Result<Option<String>, String> {
+    app.state::<ZenohRuntimes>()
+        .shutdown(runtime_id, true)
+        .await?;
+    Ok(None)
+}
+
+impl ZenohRuntimes {
+    /// RuntimeIds of the runtimes with a runtime process, sorted
+    async fn active_runtime_ids(&self) -> Vec<RuntimeId> {
+        let mut runtime_ids: Vec<RuntimeId> = self
+            .runtimes
+            .read()
+            .await
+            .iter()
+            .filter(|(_, runtime_process)| runtime_process.state.is_active())
+            .map(|(runtime_id, _)| *runtime_id)
+            .collect();
+        runtime_ids.sort();
+        runtime_ids
+    }
+}
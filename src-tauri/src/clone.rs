@@ -0,0 +1,141 @@
+use std::collections::HashMap;
+
+use serde_json::Value as JsonValue;
+use tauri::State;
+
+use crate::{
+    RuntimeId, ZenohRuntimes,
+    config_history::ConfigHistory,
+    endpoints::{listen_endpoints_mut, split_endpoint_port},
+    error::SandboxError,
+    ts::{
+        config::{ZenohConfigJson, json_set_path},
+        report::TopologyLink,
+    },
+};
+
+// ============================================================================
+// Listen endpoints
+// ============================================================================
+
+/// Give new ports to the listen endpoints of a config with a fixed port, for all modes
+/// when they are given per mode. Endpoints sharing a port get the same new port.
+/// Returns the config and the allocated ports.
+async fn reallocate_listen_ports(
+    runtimes: &ZenohRuntimes,
+    config: &ZenohConfigJson,
+) -> Result<(JsonValue, Vec<u16>), String> {
+    let mut json = config.as_json().clone();
+    let mut new_ports: HashMap<u16, u16> = HashMap::new();
+    for endpoint in listen_endpoints_mut(&mut json) {
+        let Some((head, port, tail)) = endpoint.as_str().and_then(split_endpoint_port) else {
+            continue;
+        };
+        // Ports allocated when the runtime starts are left as they are
+        let Some(port) = port.parse::<u16>().ok().filter(|port| *port != 0) else {
+            continue;
+        };
+        let new_port = match new_ports.get(&port) {
+            Some(new_port) => *new_port,
+            None => match runtimes.allocate_port().await {
+                Ok(new_port) => *new_ports.entry(port).or_insert(new_port),
+                Err(e) => {
+                    for new_port in new_ports.values() {
+                        runtimes.release_port(*new_port).await;
+                    }
+                    return Err(e);
+                }
+            },
+        };
+        *endpoint = JsonValue::String(format!("{head}{new_port}{tail}"));
+    }
+    Ok((json, new_ports.into_values().collect()))
+}
+
+// ============================================================================
+// Tauri commands
+// ============================================================================
+
+/// Declare a new runtime with the sandbox config and options of an existing one,
+/// to scale out identical peers. The listen endpoints with a fixed port and the
+/// websocket port get newly allocated ports, the links of the runtime are copied.
+/// The ports of the listen endpoints stay reserved until the new runtime stops, so that
+/// other clones get different ones.
+/// Returns the RuntimeId of the new runtime.
+#[tauri::command]
+#[tracing::instrument(skip(runtimes_state))]
+pub async fn clone_runtime(
+    runtime_id: RuntimeId,
+    runtimes_state: State<'_, ZenohRuntimes>,
+) -> Result<RuntimeId, SandboxError> {
+    let (config, mut options) = {
+        let runtimes = runtimes_state.runtimes.read().await;
+        let runtime_process = runtimes
+            .get(&runtime_id)
+            .ok_or(SandboxError::RuntimeNotFound(runtime_id))?;
+        (
+            runtime_process.sandbox_config.clone(),
+            runtime_process.options.clone(),
+        )
+    };
+
+    // Names refer to a single runtime
+    options.name = None;
+    let (json, reserved_ports) = reallocate_listen_ports(&runtimes_state, &config).await?;
+    let declared = match ZenohConfigJson::from_json(json) {
+        Ok(config) => {
+            runtimes_state
+                .declare_unpublished(config, options, None)
+                .await
+        }
+        Err(e) => Err(e),
+    };
+    let (clone_id, websocket_port) = match declared {
+        Ok(declared) => declared,
+        Err(e) => {
+            for port in reserved_ports {
+                runtimes_state.release_port(port).await;
+            }
+            return Err(e.into());
+        }
+    };
+
+    // Point the remote_api plugin of the declared config to the allocated port
+    {
+        let mut runtimes = runtimes_state.runtimes.write().await;
+        let runtime_process = runtimes
+            .get_mut(&clone_id)
+            .ok_or(SandboxError::RuntimeNotFound(clone_id))?;
+        let mut json = runtime_process.sandbox_config.as_json().clone();
+        json_set_path(
+            &mut json,
+            "plugins/remote_api/websocket_port",
+            JsonValue::String(websocket_port.to_string()),
+        );
+        let config = ZenohConfigJson::from_json(json)?;
+        runtime_process.config_history = ConfigHistory::new(&config);
+        runtime_process.reserved_ports = reserved_ports;
+        runtime_process.sandbox_config = config;
+    }
+    // Published once the declared config has its websocket port
+    runtimes_state.publish_declared(clone_id).await;
+
+    let mut links = runtimes_state.links.write().await;
+    let cloned_links: Vec<TopologyLink> = links
+        .iter()
+        .filter(|link| link.from == runtime_id)
+        .map(|link| TopologyLink {
+            from: clone_id,
+            to: link.to,
+        })
+        .collect();
+    links.extend(cloned_links);
+    tracing::info!(
+        "Runtime {} cloned from {} on port {}",
+        clone_id,
+        runtime_id,
+        websocket_port
+    );
+
+    Ok(clone_id)
+}
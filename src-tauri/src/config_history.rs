@@ -0,0 +1,154 @@
+use std::collections::VecDeque;
+
+use chrono::Utc;
+use tauri::State;
+
+use crate::{
+    RuntimeId, RuntimeProcess, ZenohRuntimes,
+    error::SandboxError,
+    logs::LogStorage,
+    timeline::Timeline,
+    ts::{
+        config::{ConfigRevision, ZenohConfigJson},
+        timeline::ConfigChangeSource,
+    },
+};
+
+// ============================================================================
+// Config history
+// ============================================================================
+
+/// Maximum number of revisions kept per runtime, the oldest are forgotten first
+const MAX_REVISIONS: usize = 100;
+
+/// Revisions of the config of a runtime, oldest first
+pub(crate) struct ConfigHistory {
+    revisions: VecDeque<ConfigRevision>,
+    next_revision: u64,
+}
+
+impl ConfigHistory {
+    /// History starting with the config a runtime is declared with
+    pub(crate) fn new(config: &ZenohConfigJson) -> Self {
+        let mut history = Self {
+            revisions: VecDeque::new(),
+            next_revision: 0,
+        };
+        history.push(None, config.clone());
+        history
+    }
+
+    /// Append a revision, unless `config` is the same as the latest one
+    pub(crate) fn push(&mut self, source: Option<ConfigChangeSource>, config: ZenohConfigJson) {
+        if self
+            .revisions
+            .back()
+            .is_some_and(|latest| latest.config.etag() == config.etag())
+        {
+            return;
+        }
+        if self.revisions.len() >= MAX_REVISIONS {
+            self.revisions.pop_front();
+        }
+        self.revisions.push_back(ConfigRevision {
+            revision: self.next_revision,
+            timestamp: Utc::now(),
+            source,
+            config,
+        });
+        self.next_revision += 1;
+    }
+}
+
+impl RuntimeProcess {
+    /// Replace the declared config, recording it in the history. Returns the old config.
+    /// The JSON5 text of the old config no longer applies and is dropped.
+    pub(crate) fn replace_config(
+        &mut self,
+        config: ZenohConfigJson,
+        source: ConfigChangeSource,
+    ) -> ZenohConfigJson {
+        self.config_source = None;
+        self.config_history.push(Some(source), config.clone());
+        std::mem::replace(&mut self.sandbox_config, config)
+    }
+}
+
+impl ZenohRuntimes {
+    /// Record the running config of a runtime after a live update
+    pub(crate) async fn record_live_config(&self, runtime_id: RuntimeId, config: ZenohConfigJson) {
+        if let Some(runtime_process) = self.runtimes.write().await.get_mut(&runtime_id) {
+            runtime_process
+                .config_history
+                .push(Some(ConfigChangeSource::LiveUpdate), config);
+        }
+    }
+}
+
+// ============================================================================
+// Tauri commands
+// ============================================================================
+
+/// Get the config revisions of a runtime, oldest first.
+/// The first revision is the config the runtime was declared with, until `MAX_REVISIONS`
+/// later revisions push it out.
+#[tauri::command]
+#[tracing::instrument(skip(state))]
+pub async fn get_config_history(
+    runtime_id: RuntimeId,
+    state: State<'_, ZenohRuntimes>,
+) -> Result<Vec<ConfigRevision>, SandboxError> {
+    let runtimes = state.runtimes.read().await;
+    let runtime_process = runtimes
+        .get(&runtime_id)
+        .ok_or(SandboxError::RuntimeNotFound(runtime_id))?;
+
+    Ok(runtime_process
+        .config_history
+        .revisions
+        .iter()
+        .cloned()
+        .collect())
+}
+
+/// Make the config of a revision the declared config of a runtime, recorded as a new revision.
+/// The change is annotated in the timeline and, if `annotate_log` is set, in the runtime's
+/// log stream. The config is used the next time the runtime is started.
+/// Returns the diff between the old and restored config.
+#[tauri::command]
+#[tracing::instrument(skip(runtimes_state, logs_state, timeline_state))]
+pub async fn revert_config(
+    runtime_id: RuntimeId,
+    revision: u64,
+    annotate_log: Option<bool>,
+    runtimes_state: State<'_, ZenohRuntimes>,
+    logs_state: State<'_, LogStorage>,
+    timeline_state: State<'_, Timeline>,
+) -> Result<serde_json::Value, SandboxError> {
+    let (old_config, config) = {
+        let mut runtimes = runtimes_state.runtimes.write().await;
+        let runtime_process = runtimes
+            .get_mut(&runtime_id)
+            .ok_or(SandboxError::RuntimeNotFound(runtime_id))?;
+        let config = runtime_process
+            .config_history
+            .revisions
+            .iter()
+            .find(|entry| entry.revision == revision)
+            .map(|entry| entry.config.clone())
+            .ok_or_else(|| format!("Revision {} of runtime {} not found", revision, runtime_id))?;
+        (
+            runtime_process.replace_config(config.clone(), ConfigChangeSource::Revert),
+            config,
+        )
+    };
+
+    let logs = annotate_log.unwrap_or(false).then(|| logs_state.inner());
+    Ok(timeline_state.annotate_config_change(
+        runtime_id,
+        ConfigChangeSource::Revert,
+        &old_config,
+        &config,
+        logs,
+    ))
+}
@@ -0,0 +1,84 @@
+use serde_json::{Map, Value as JsonValue, json};
+use zenoh::config::WhatAmI;
+
+use crate::error::SandboxError;
+
+// ============================================================================
+// Config schema
+// ============================================================================
+//
+// zenoh::Config has no schema of its own, so it is derived from its serialized default:
+// each field gets the JSON type of its default value. Fields defaulting to null may
+// hold any value. Enum values are only known for the fields listed in `known_enum`.
+
+/// Values of the fields taking one of a fixed set of strings, by path
+fn known_enum(path: &str) -> Option<Vec<JsonValue>> {
+    match path {
+        "mode" => Some(
+            [WhatAmI::Router, WhatAmI::Peer, WhatAmI::Client]
+                .iter()
+                .map(|what_am_i| json!(what_am_i.to_string()))
+                .collect(),
+        ),
+        "transport/link/tx/queue/allocation/mode" => Some(vec![json!("init"), json!("lazy")]),
+        _ => None,
+    }
+}
+
+/// JSON Schema of a value, with `default` as its default
+fn value_schema(path: &str, default: &JsonValue) -> JsonValue {
+    let mut schema = match default {
+        JsonValue::Object(fields) => {
+            let properties: Map<String, JsonValue> = fields
+                .iter()
+                .map(|(key, value)| {
+                    let field_path = if path.is_empty() {
+                        key.clone()
+                    } else {
+                        format!("{path}/{key}")
+                    };
+                    (key.clone(), value_schema(&field_path, value))
+                })
+                .collect();
+            json!({ "type": "object", "properties": properties })
+        }
+        // Elements are described by the first one, if any
+        JsonValue::Array(elements) => match elements.first() {
+            Some(element) => json!({ "type": "array", "items": value_schema(path, element) }),
+            None => json!({ "type": "array" }),
+        },
+        JsonValue::String(_) => json!({ "type": "string" }),
+        JsonValue::Number(number) if number.is_u64() => json!({ "type": "integer", "minimum": 0 }),
+        JsonValue::Number(number) if number.is_i64() => json!({ "type": "integer" }),
+        JsonValue::Number(_) => json!({ "type": "number" }),
+        JsonValue::Bool(_) => json!({ "type": "boolean" }),
+        JsonValue::Null => json!({}),
+    };
+    if let Some(values) = known_enum(path) {
+        schema["enum"] = JsonValue::Array(values);
+    }
+    schema["default"] = default.clone();
+    schema
+}
+
+/// JSON Schema of the zenoh config, derived from the default config
+pub fn config_schema() -> Result<JsonValue, String> {
+    let default = serde_json::to_value(zenoh::Config::default())
+        .map_err(|e| format!("Failed to serialize default config: {}", e))?;
+    let mut schema = value_schema("", &default);
+    schema["$schema"] = json!("https://json-schema.org/draft/2020-12/schema");
+    schema["title"] = json!("Zenoh config");
+    Ok(schema)
+}
+
+// ============================================================================
+// Tauri commands
+// ============================================================================
+
+/// Get a JSON Schema of the zenoh config, for structured config editors.
+/// Every field has its type and default value, and the allowed values of known enums.
+#[tauri::command]
+#[tracing::instrument]
+pub async fn get_config_schema() -> Result<JsonValue, SandboxError> {
+    Ok(config_schema()?)
+}
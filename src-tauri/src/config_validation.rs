@@ -0,0 +1,96 @@
+use serde_json::Value as JsonValue;
+
+use crate::error::SandboxError;
+use crate::ts::config::{ConfigValidationError, json_set_path};
+
+// ============================================================================
+// Field-level config validation
+// ============================================================================
+//
+// zenoh reports the first error of a config without saying where it is. To locate
+// the offending fields, each field of the config is set alone on top of the default
+// config and validated, descending into the objects that fail.
+
+fn error(path: &str, message: String) -> ConfigValidationError {
+    ConfigValidationError {
+        path: path.to_string(),
+        message,
+    }
+}
+
+/// Validate a JSON config the way `validate_config` does
+fn check(config: &JsonValue) -> Result<(), String> {
+    zenoh::Config::from_json5(&config.to_string())
+        .map(|_| ())
+        .map_err(|e| e.to_string())
+}
+
+/// Collect the errors of `value` once set at `path` in `base`, reporting the deepest
+/// offending fields. A failing object whose fields are all valid alone is reported itself.
+fn field_errors(
+    base: &JsonValue,
+    path: &str,
+    value: &JsonValue,
+    errors: &mut Vec<ConfigValidationError>,
+) {
+    let mut candidate = base.clone();
+    json_set_path(&mut candidate, path, value.clone());
+    let Err(message) = check(&candidate) else {
+        return;
+    };
+
+    let found = errors.len();
+    if let JsonValue::Object(fields) = value {
+        for (key, field) in fields {
+            field_errors(base, &format!("{path}/{key}"), field, errors);
+        }
+    }
+    if errors.len() == found {
+        errors.push(error(path, message));
+    }
+}
+
+/// Validate a JSON5 config, returning the errors with the paths of the offending fields.
+/// Returns no errors if the config is valid.
+pub fn validate_fields(content: &str) -> Vec<ConfigValidationError> {
+    let json: JsonValue = match json5::from_str(content) {
+        Ok(json) => json,
+        Err(e) => return vec![error("", format!("Invalid JSON5: {}", e))],
+    };
+    let config_error = match zenoh::Config::from_json5(content) {
+        Ok(_) => return Vec::new(),
+        Err(e) => e.to_string(),
+    };
+    let JsonValue::Object(sections) = &json else {
+        return vec![error("", "Config must be a JSON object".to_string())];
+    };
+    let base = match serde_json::to_value(zenoh::Config::default()) {
+        Ok(base) => base,
+        Err(_) => return vec![error("", config_error)],
+    };
+
+    let mut errors = Vec::new();
+    for (key, value) in sections {
+        field_errors(&base, key, value, &mut errors);
+    }
+    // The sections are only invalid together
+    if errors.is_empty() {
+        errors.push(error("", config_error));
+    }
+    errors
+}
+
+// ============================================================================
+// Tauri commands
+// ============================================================================
+
+/// Validate a JSON5 config like `validate_config`, but report every offending field
+/// with its path, e.g. `scouting/multicast/ttl`, so that editors can highlight them.
+/// Returns an empty list if the config is valid.
+#[tauri::command]
+#[tracing::instrument(skip_all)]
+pub async fn validate_config_fields(
+    content: String,
+) -> Result<Vec<ConfigValidationError>, SandboxError> {
+    Ok(validate_fields(&content))
+}
@@ -0,0 +1,122 @@
+use std::collections::{BTreeMap, VecDeque};
+
+// ============================================================================
+// Constants
+// ============================================================================
+
+/// Number of snapshots kept to compute deltas against the acknowledged one
+const SNAPSHOT_HISTORY_LEN: usize = 32;
+
+// ============================================================================
+// Delta encoding
+// ============================================================================
+//
+// Periodic snapshots sent to the frontend as events (topology, stats) are delta encoded:
+// every changed snapshot gets a sequence number, and the event carries the changes since
+// the last snapshot acknowledged by the frontend. Since the frontend may not have
+// acknowledged the snapshots it already applied, a delta includes every entry changed
+// in any snapshot since the acknowledged one, so that it can be applied on top of any of them.
+// When the acknowledged snapshot is unknown, the full snapshot is sent instead.
+
+/// Changes of a keyed collection
+pub(crate) struct KeyedDelta<K, V> {
+    /// Entries added or changed, with their current value
+    pub upserted: Vec<V>,
+    /// Keys of the removed entries
+    pub removed: Vec<K>,
+}
+
+/// Changes turning any of the `previous` collections into `current`.
+/// Without previous collections, every entry of `current` is upserted.
+pub(crate) fn keyed_delta<K: Ord + Clone, V: Clone + PartialEq>(
+    previous: &[&BTreeMap<K, V>],
+    current: &BTreeMap<K, V>,
+) -> KeyedDelta<K, V> {
+    let upserted = current
+        .iter()
+        .filter(|(key, value)| {
+            previous
+                .iter()
+                .any(|collection| collection.get(*key) != Some(*value))
+                || previous.is_empty()
+        })
+        .map(|(_, value)| value.clone())
+        .collect();
+    let mut removed: Vec<K> = previous
+        .iter()
+        .flat_map(|collection| collection.keys())
+        .filter(|key| !current.contains_key(*key))
+        .cloned()
+        .collect();
+    removed.sort();
+    removed.dedup();
+    KeyedDelta { upserted, removed }
+}
+
+/// Sequenced snapshots sent to the frontend, and the last one it acknowledged
+pub(crate) struct SnapshotHistory<S> {
+    next_seq: u64,
+    /// Recent snapshots with their sequence number, oldest first
+    snapshots: VecDeque<(u64, S)>,
+    acked: Option<u64>,
+}
+
+impl<S> Default for SnapshotHistory<S> {
+    fn default() -> Self {
+        Self {
+            next_seq: 1,
+            snapshots: VecDeque::new(),
+            acked: None,
+        }
+    }
+}
+
+impl<S> SnapshotHistory<S> {
+    /// Record a new snapshot. Returns its sequence number.
+    pub(crate) fn push(&mut self, snapshot: S) -> u64 {
+        let seq = self.next_seq;
+        self.next_seq += 1;
+        self.snapshots.push_back((seq, snapshot));
+        if self.snapshots.len() > SNAPSHOT_HISTORY_LEN {
+            self.snapshots.pop_front();
+        }
+        seq
+    }
+
+    /// Record that the frontend applied the snapshot `seq`
+    pub(crate) fn ack(&mut self, seq: u64) -> Result<(), String> {
+        if !self
+            .snapshots
+            .iter()
+            .any(|(snapshot_seq, _)| *snapshot_seq == seq)
+        {
+            return Err(format!("Unknown snapshot {}, resync required", seq));
+        }
+        // Acknowledgements may arrive out of order
+        if self.acked.is_none_or(|acked| acked < seq) {
+            self.acked = Some(seq);
+        }
+        Ok(())
+    }
+
+    /// Forget the acknowledged snapshot, so that the next delta is a full snapshot
+    pub(crate) fn reset_ack(&mut self) {
+        self.acked = None;
+    }
+
+    /// Sequence number of the acknowledged snapshot, with it and the snapshots after it
+    /// excluding the latest one. None if nothing was acknowledged, or if the acknowledged
+    /// snapshot is no longer kept and the frontend needs a full snapshot.
+    pub(crate) fn since_ack(&self) -> Option<(u64, Vec<&S>)> {
+        let acked = self.acked?;
+        let position = self.snapshots.iter().position(|(seq, _)| *seq == acked)?;
+        let snapshots = self
+            .snapshots
+            .iter()
+            .take(self.snapshots.len() - 1)
+            .skip(position)
+            .map(|(_, snapshot)| snapshot)
+            .collect();
+        Some((acked, snapshots))
+    }
+}
@@ -0,0 +1,251 @@
+use std::time::Duration;
+
+use tauri::{AppHandle, Manager, State};
+use zenoh::key_expr::{KeyExpr, keyexpr};
+
+use crate::{
+    RuntimeId, ZenohRuntimes,
+    error::SandboxError,
+    logs::LogStorage,
+    protocol::TestEntity,
+    testing::{self, Testing},
+    timeline::Timeline,
+    ts::{
+        config::{
+            DownsamplingItem, DownsamplingMessage, InterceptorFlow, downsampling_items,
+            with_downsampling,
+        },
+        testing::{DownsamplingReport, TestEntityKind},
+        timeline::ConfigChangeSource,
+    },
+};
+
+// ============================================================================
+// Constants
+// ============================================================================
+
+/// Time the publisher runs when none is given
+const DEFAULT_VERIFY_DURATION: Duration = Duration::from_secs(5);
+
+/// Longest time the publisher runs
+const MAX_VERIFY_DURATION: Duration = Duration::from_secs(60);
+
+/// Highest publication rate, the period of the publisher being in milliseconds
+const MAX_PUBLISH_RATE_HZ: f64 = 1000.0;
+
+/// Time for the subscriber declaration to reach the publisher's runtime before publishing
+const SUBSCRIBER_SETTLE_DELAY: Duration = Duration::from_millis(500);
+
+/// Time for the samples in flight to arrive once the publisher is undeclared
+const DRAIN_DELAY: Duration = Duration::from_millis(200);
+
+// ============================================================================
+// Downsampling rules
+// ============================================================================
+
+impl ZenohRuntimes {
+    /// Lowest frequency of the downsampling rules of the declared config of a runtime
+    /// applying to puts in direction `flow` on key expressions intersecting `key_expr`.
+    /// Interfaces and link protocols are not taken into account.
+    async fn downsampling_rate(
+        &self,
+        runtime_id: RuntimeId,
+        flow: InterceptorFlow,
+        key_expr: &keyexpr,
+    ) -> Result<Option<f64>, SandboxError> {
+        let config = self
+            .runtimes
+            .read()
+            .await
+            .get(&runtime_id)
+            .ok_or(SandboxError::RuntimeNotFound(runtime_id))?
+            .sandbox_config
+            .clone();
+        let items = downsampling_items(&config).map_err(SandboxError::ConfigInvalid)?;
+        Ok(items
+            .iter()
+            .filter(|item| item.applies_to(DownsamplingMessage::Put, flow))
+            .flat_map(|item| &item.rules)
+            .filter(|rule| {
+                KeyExpr::try_from(rule.key_expr.as_str())
+                    .is_ok_and(|rule_key_expr| rule_key_expr.intersects(key_expr))
+            })
+            .map(|rule| rule.freq)
+            .reduce(f64::min))
+    }
+}
+
+// ============================================================================
+// Tauri commands
+// ============================================================================
+
+/// Get the `downsampling` items of the declared config of a runtime
+#[tauri::command]
+#[tracing::instrument(skip(state))]
+pub async fn get_runtime_downsampling(
+    runtime_id: RuntimeId,
+    state: State<'_, ZenohRuntimes>,
+) -> Result<Vec<DownsamplingItem>, SandboxError> {
+    let runtimes = state.runtimes.read().await;
+    let runtime_process = runtimes
+        .get(&runtime_id)
+        .ok_or(SandboxError::RuntimeNotFound(runtime_id))?;
+    downsampling_items(&runtime_process.sandbox_config).map_err(SandboxError::ConfigInvalid)
+}
+
+/// Replace the `downsampling` items of the declared config of a runtime, once validated.
+/// The change is annotated in the timeline and the runtime's log stream, and takes effect
+/// the next time the runtime is started.
+/// Returns the diff between the old and new config.
+#[tauri::command]
+#[tracing::instrument(skip(app))]
+pub async fn set_runtime_downsampling(
+    runtime_id: RuntimeId,
+    items: Vec<DownsamplingItem>,
+    app: AppHandle,
+) -> Result<serde_json::Value, SandboxError> {
+    let runtimes = app.state::<ZenohRuntimes>();
+    let (old_config, new_config) = {
+        let mut runtime_processes = runtimes.runtimes.write().await;
+        let runtime_process = runtime_processes
+            .get_mut(&runtime_id)
+            .ok_or(SandboxError::RuntimeNotFound(runtime_id))?;
+        let new_config = with_downsampling(&runtime_process.sandbox_config, &items)
+            .map_err(SandboxError::ConfigInvalid)?;
+        (
+            runtime_process.replace_config(new_config.clone(), ConfigChangeSource::Patch),
+            new_config,
+        )
+    };
+    Ok(app.state::<Timeline>().annotate_config_change(
+        runtime_id,
+        ConfigChangeSource::Patch,
+        &old_config,
+        &new_config,
+        Some(app.state::<LogStorage>().inner()),
+    ))
+}
+
+/// Put on `key_expr` at `rate_hz` from a running runtime during `duration_ms`, and count
+/// the samples received by a subscriber on another running runtime, to check the
+/// downsampling rules of their configs. The test publisher and subscriber are undeclared
+/// once done.
+#[tauri::command]
+#[tracing::instrument(skip(runtimes, testing))]
+pub async fn verify_downsampling(
+    publisher_runtime_id: RuntimeId,
+    subscriber_runtime_id: RuntimeId,
+    key_expr: String,
+    rate_hz: f64,
+    duration_ms: Option<u64>,
+    runtimes: State<'_, ZenohRuntimes>,
+    testing: State<'_, Testing>,
+) -> Result<DownsamplingReport, SandboxError> {
+    if publisher_runtime_id == subscriber_runtime_id {
+        return Err("Downsampling applies to transports: \
+             the publisher and subscriber must be on different runtimes"
+            .into());
+    }
+    if !rate_hz.is_finite() || rate_hz <= 0.0 || rate_hz > MAX_PUBLISH_RATE_HZ {
+        return Err(format!(
+            "Invalid rate {} Hz: expected a rate above 0 and up to {} Hz",
+            rate_hz, MAX_PUBLISH_RATE_HZ
+        )
+        .into());
+    }
+    let parsed_key_expr = KeyExpr::try_from(key_expr.as_str())
+        .map_err(|e| format!("Invalid key expression '{}': {}", key_expr, e))?;
+    let period_ms = ((1000.0 / rate_hz).round() as u64).max(1);
+    let published_rate_hz = 1000.0 / period_ms as f64;
+    let duration = duration_ms
+        .map(Duration::from_millis)
+        .unwrap_or(DEFAULT_VERIFY_DURATION)
+        .min(MAX_VERIFY_DURATION);
+
+    let egress_rate = runtimes
+        .downsampling_rate(
+            publisher_runtime_id,
+            InterceptorFlow::Egress,
+            &parsed_key_expr,
+        )
+        .await?;
+    let ingress_rate = runtimes
+        .downsampling_rate(
+            subscriber_runtime_id,
+            InterceptorFlow::Ingress,
+            &parsed_key_expr,
+        )
+        .await?;
+    let expected_rate_hz = egress_rate
+        .into_iter()
+        .chain(ingress_rate)
+        .reduce(f64::min)
+        .map(|rate| rate.min(published_rate_hz));
+
+    let subscriber = testing::declare(
+        &runtimes,
+        &testing,
+        subscriber_runtime_id,
+        key_expr.clone(),
+        TestEntityKind::Subscriber,
+        TestEntity::Subscriber {
+            keyexpr: key_expr.clone(),
+        },
+    )
+    .await?;
+    tokio::time::sleep(SUBSCRIBER_SETTLE_DELAY).await;
+    let publisher = testing::declare(
+        &runtimes,
+        &testing,
+        publisher_runtime_id,
+        key_expr.clone(),
+        TestEntityKind::PeriodicPublisher {
+            payload_template: testing::SEQ_PLACEHOLDER.to_string(),
+            period_ms,
+            jitter_ms: 0,
+            burst: 1,
+        },
+        TestEntity::PeriodicPublisher {
+            keyexpr: key_expr.clone(),
+            payload_template: testing::SEQ_PLACEHOLDER.to_string(),
+            period_ms,
+            jitter_ms: 0,
+            burst: 1,
+        },
+    )
+    .await;
+    let publisher = match publisher {
+        Ok(publisher) => publisher,
+        Err(e) => {
+            let _ = testing::undeclare(&runtimes, &testing, subscriber.id).await;
+            return Err(e);
+        }
+    };
+
+    tokio::time::sleep(duration).await;
+    let stopped = testing::undeclare(&runtimes, &testing, publisher.id).await;
+    tokio::time::sleep(DRAIN_DELAY).await;
+    let received = testing.received(subscriber.id);
+    testing::undeclare(&runtimes, &testing, subscriber.id).await?;
+    stopped?;
+
+    let observed_rate_hz = received as f64 / duration.as_secs_f64();
+    tracing::info!(
+        "Downsampling of {} from runtime {} to {}: {:.1} Hz published, {:.1} Hz observed",
+        key_expr,
+        publisher_runtime_id,
+        subscriber_runtime_id,
+        published_rate_hz,
+        observed_rate_hz
+    );
+    Ok(DownsamplingReport {
+        publisher_runtime_id,
+        subscriber_runtime_id,
+        key_expr,
+        published_rate_hz,
+        expected_rate_hz,
+        received,
+        duration_ms: duration.as_millis() as u64,
+        observed_rate_hz,
+    })
+}
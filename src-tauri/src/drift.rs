@@ -0,0 +1,130 @@
+use serde_json::Value as JsonValue;
+use tauri::State;
+
+use crate::{
+    RuntimeId, ZenohRuntimes, config_to_json,
+    error::SandboxError,
+    ts::config::{ConfigDrift, ConfigDriftField, ZenohConfigJson, json_diff, json_diff_paths},
+};
+
+// ============================================================================
+// Sandbox changes
+// ============================================================================
+//
+// The sandbox starts runtimes with more than their declared config: the adminspace and
+// plugins it talks to the runtime through, the plugins of the runtime options, and the
+// ports allocated to listen endpoints. Both configs are compared with the zenoh defaults
+// filled in, so that only these changes and the live updates show up.
+
+/// Config paths the sandbox sets when starting a runtime, with the reason.
+/// A path also covers the fields below it.
+const SANDBOX_CHANGES: &[(&str, &str)] = &[
+    (
+        "adminspace",
+        "Enabled by the sandbox to query the runtime's adminspace",
+    ),
+    (
+        "plugins_loading",
+        "Enabled by the sandbox to load the plugins it runs in the runtime",
+    ),
+    (
+        "plugins/remote_api",
+        "Plugin the frontend talks to the runtime through, on a port allocated by the sandbox",
+    ),
+    (
+        "plugins/rest",
+        "Plugin of the `rest` option, on a port allocated by the sandbox",
+    ),
+    (
+        "plugins/storage_manager",
+        "Plugin running the storages of the runtime options",
+    ),
+    (
+        "timestamping",
+        "Enabled by the sandbox for the storages of the runtime options",
+    ),
+    (
+        "listen/endpoints",
+        "Ports allocated by the sandbox or the system as the runtime started",
+    ),
+    (
+        "transport/shared_memory/enabled",
+        "Set by the sandbox from the `shared_memory` option",
+    ),
+];
+
+/// What the sandbox changed a config field for, if it is one it sets
+fn sandbox_change_reason(path: &str) -> Option<String> {
+    SANDBOX_CHANGES
+        .iter()
+        .find(|(prefix, _)| {
+            path.strip_prefix(prefix)
+                .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+        })
+        .map(|(_, reason)| reason.to_string())
+}
+
+/// A config as JSON with the zenoh defaults filled in
+fn normalized(config: &ZenohConfigJson) -> Result<JsonValue, String> {
+    let config: zenoh::config::Config = config.clone().try_into()?;
+    Ok(config_to_json(&config)?.as_json().clone())
+}
+
+/// Value at a path in the zenoh config key notation, null if unset
+fn value_at(json: &JsonValue, path: &str) -> JsonValue {
+    json.pointer(&format!("/{}", path))
+        .cloned()
+        .unwrap_or(JsonValue::Null)
+}
+
+// ============================================================================
+// Tauri commands
+// ============================================================================
+
+/// Compare the running config of a runtime with its declared config, to see what the
+/// sandbox added or changed when starting it (adminspace, plugins, allocated ports)
+/// and what live updates changed since.
+/// The ZenohId is left out when the declared config doesn't set one, the zenoh default
+/// being a random one.
+#[tauri::command]
+#[tracing::instrument(skip(state))]
+pub async fn zenoh_runtime_config_drift(
+    runtime_id: RuntimeId,
+    state: State<'_, ZenohRuntimes>,
+) -> Result<ConfigDrift, SandboxError> {
+    let declared = state
+        .runtimes
+        .read()
+        .await
+        .get(&runtime_id)
+        .ok_or(SandboxError::RuntimeNotFound(runtime_id))?
+        .sandbox_config
+        .clone();
+    let live = state.live_config(runtime_id).await?;
+
+    let mut effective = config_to_json(&live)?.as_json().clone();
+    let mut declared_json = normalized(&declared).map_err(SandboxError::ConfigInvalid)?;
+    if declared.as_json().get("id").is_none() {
+        for json in [&mut declared_json, &mut effective] {
+            if let Some(object) = json.as_object_mut() {
+                object.remove("id");
+            }
+        }
+    }
+
+    let diff = json_diff(&declared_json, &effective);
+    let fields = json_diff_paths(&diff)
+        .into_iter()
+        .map(|path| ConfigDriftField {
+            declared: value_at(&declared_json, &path),
+            effective: value_at(&effective, &path),
+            reason: sandbox_change_reason(&path),
+            path,
+        })
+        .collect();
+    Ok(ConfigDrift {
+        runtime_id,
+        diff,
+        fields,
+    })
+}
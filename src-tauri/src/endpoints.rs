@@ -0,0 +1,347 @@
+use std::collections::{HashMap, HashSet};
+
+use serde_json::Value as JsonValue;
+use tauri::State;
+
+use crate::{
+    RuntimeId, RuntimePorts, RuntimeProcess, ZenohRuntimes,
+    error::SandboxError,
+    ts::{
+        config::ZenohConfigJson,
+        runtime::{PortConflict, PortConflictReason},
+    },
+};
+
+// ============================================================================
+// Listen endpoints
+// ============================================================================
+
+/// Port of a TCP listen endpoint allocated by the sandbox when the runtime starts,
+/// like port 0
+pub const AUTO_PORT: &str = "${AUTO}";
+
+/// Endpoints of `listen/endpoints` of a config, for all modes when they are given per mode
+pub(crate) fn listen_endpoints_mut(json: &mut JsonValue) -> Vec<&mut JsonValue> {
+    let lists: Vec<&mut JsonValue> = match json.pointer_mut("/listen/endpoints") {
+        // Endpoints per mode
+        Some(JsonValue::Object(modes)) => modes.values_mut().collect(),
+        Some(list) => vec![list],
+        None => Vec::new(),
+    };
+    lists
+        .into_iter()
+        .filter_map(|list| list.as_array_mut())
+        .flatten()
+        .collect()
+}
+
+/// Port of an endpoint like `tcp/0.0.0.0:7447?prio=1-7#iface=lo`, with the text before
+/// and after it. None if the endpoint has no port.
+pub(crate) fn split_endpoint_port(endpoint: &str) -> Option<(&str, &str, &str)> {
+    endpoint.split_once('/')?;
+    let address_end = endpoint.find(['?', '#']).unwrap_or(endpoint.len());
+    let port_start = endpoint[..address_end].rfind(':')? + 1;
+    Some((
+        &endpoint[..port_start],
+        &endpoint[port_start..address_end],
+        &endpoint[address_end..],
+    ))
+}
+
+/// Endpoint with the port allocated by the sandbox, if it is a TCP endpoint
+/// with port 0 or `${AUTO}`
+fn with_auto_port(endpoint: &str, port: u16) -> Option<String> {
+    if !endpoint.starts_with("tcp/") {
+        return None;
+    }
+    match split_endpoint_port(endpoint)? {
+        (head, "0" | AUTO_PORT, tail) => Some(format!("{head}{port}{tail}")),
+        _ => None,
+    }
+}
+
+impl ZenohRuntimes {
+    /// Give the TCP listen endpoints of `config` with port 0 or `${AUTO}` a port allocated
+    /// by the sandbox, in endpoint order. The ports allocated for the previous start of
+    /// the runtime are reused, until they are released when it stops.
+    /// Returns the config to start the runtime process with.
+    pub(crate) async fn allocate_listen_ports(
+        &self,
+        runtime_id: RuntimeId,
+        config: ZenohConfigJson,
+    ) -> Result<ZenohConfigJson, String> {
+        let mut json = config.as_json().clone();
+        let mut endpoints: Vec<&mut JsonValue> = listen_endpoints_mut(&mut json)
+            .into_iter()
+            .filter(|endpoint| {
+                endpoint
+                    .as_str()
+                    .and_then(|endpoint| with_auto_port(endpoint, 0))
+                    .is_some()
+            })
+            .collect();
+
+        let mut ports = {
+            let mut runtimes = self.runtimes.write().await;
+            let runtime_process = runtimes
+                .get_mut(&runtime_id)
+                .ok_or(SandboxError::RuntimeNotFound(runtime_id))?;
+            std::mem::take(&mut runtime_process.listen_ports)
+        };
+        let mut allocated = Ok(());
+        while ports.len() < endpoints.len() {
+            match self.allocate_port().await {
+                Ok(port) => ports.push(port),
+                Err(e) => {
+                    allocated = Err(e);
+                    break;
+                }
+            }
+        }
+        if allocated.is_ok() {
+            for port in ports.split_off(endpoints.len()) {
+                self.release_port(port).await;
+            }
+        }
+        if let Some(runtime_process) = self.runtimes.write().await.get_mut(&runtime_id) {
+            runtime_process.listen_ports = ports.clone();
+        }
+        allocated?;
+
+        if endpoints.is_empty() {
+            return Ok(config);
+        }
+        for (endpoint, port) in endpoints.iter_mut().zip(&ports) {
+            if let Some(allocated) = endpoint
+                .as_str()
+                .and_then(|endpoint| with_auto_port(endpoint, *port))
+            {
+                **endpoint = JsonValue::String(allocated);
+            }
+        }
+        tracing::debug!(runtime_id, "Allocated listen ports {:?}", ports);
+        ZenohConfigJson::from_json(json)
+    }
+
+    /// Release the ports allocated or reserved for the listen endpoints of a runtime
+    pub(crate) async fn release_listen_ports(&self, runtime_id: RuntimeId) {
+        let ports = self
+            .runtimes
+            .write()
+            .await
+            .get_mut(&runtime_id)
+            .map(|runtime_process| {
+                let mut ports = std::mem::take(&mut runtime_process.listen_ports);
+                ports.append(&mut runtime_process.reserved_ports);
+                ports
+            })
+            .unwrap_or_default();
+        for port in ports {
+            self.release_port(port).await;
+        }
+    }
+}
+
+// ============================================================================
+// Port conflicts
+// ============================================================================
+
+/// Transport protocol a runtime listens on a port with
+#[derive(Debug, Clone, Copy)]
+enum Transport {
+    Tcp,
+    Udp,
+}
+
+impl Transport {
+    /// Whether no other process listens on `port` with this protocol
+    fn is_port_free(self, port: u16) -> bool {
+        let address = (std::net::Ipv4Addr::UNSPECIFIED, port);
+        match self {
+            Transport::Tcp => std::net::TcpListener::bind(address).is_ok(),
+            Transport::Udp => std::net::UdpSocket::bind(address).is_ok(),
+        }
+    }
+}
+
+/// Port a runtime listens on, with the config entry using it
+struct ConfigPort {
+    port: u16,
+    transport: Transport,
+    usage: String,
+}
+
+/// Fixed port and transport protocol of a listen endpoint
+fn endpoint_port(endpoint: &str) -> Option<(u16, Transport)> {
+    let (protocol, _) = endpoint.split_once('/')?;
+    let transport = match protocol {
+        "tcp" | "tls" | "ws" => Transport::Tcp,
+        "udp" | "quic" => Transport::Udp,
+        _ => return None,
+    };
+    let (_, port, _) = split_endpoint_port(endpoint)?;
+    let port = port.parse().ok().filter(|port| *port != 0)?;
+    Some((port, transport))
+}
+
+/// Endpoints of `listen/endpoints` of a config, see `listen_endpoints_mut`
+fn listen_endpoints(json: &JsonValue) -> Vec<&str> {
+    let lists: Vec<&JsonValue> = match json.pointer("/listen/endpoints") {
+        // Endpoints per mode
+        Some(JsonValue::Object(modes)) => modes.values().collect(),
+        Some(list) => vec![list],
+        None => Vec::new(),
+    };
+    lists
+        .into_iter()
+        .filter_map(|list| list.as_array())
+        .flatten()
+        .filter_map(|endpoint| endpoint.as_str())
+        .collect()
+}
+
+/// Ports a runtime listens on: its fixed listen endpoints and the ports of its plugins
+fn config_ports(config: &ZenohConfigJson, ports: RuntimePorts) -> Vec<ConfigPort> {
+    let mut config_ports: Vec<ConfigPort> = listen_endpoints(config.as_json())
+        .into_iter()
+        .filter_map(|endpoint| {
+            let (port, transport) = endpoint_port(endpoint)?;
+            Some(ConfigPort {
+                port,
+                transport,
+                usage: endpoint.to_string(),
+            })
+        })
+        .collect();
+    config_ports.push(ConfigPort {
+        port: ports.websocket,
+        transport: Transport::Tcp,
+        usage: "plugins/remote_api".to_string(),
+    });
+    if let Some(rest_port) = ports.rest {
+        config_ports.push(ConfigPort {
+            port: rest_port,
+            transport: Transport::Tcp,
+            usage: "plugins/rest".to_string(),
+        });
+    }
+    config_ports
+}
+
+/// Ports allocated to a runtime by the sandbox
+pub(crate) fn allocated_ports(runtime_process: &RuntimeProcess) -> impl Iterator<Item = u16> + '_ {
+    std::iter::once(runtime_process.allocated_port)
+        .chain(runtime_process.rest_port)
+        .chain(runtime_process.listen_ports.iter().copied())
+        .chain(runtime_process.reserved_ports.iter().copied())
+}
+
+/// Error message of a start prevented by port conflicts
+pub(crate) fn port_conflicts_error(conflicts: &[PortConflict]) -> String {
+    let conflicts: Vec<String> = conflicts
+        .iter()
+        .map(|conflict| {
+            let reason = match (conflict.reason, conflict.runtime_id) {
+                (PortConflictReason::Runtime, Some(runtime_id)) => {
+                    format!("used by runtime {}", runtime_id)
+                }
+                (PortConflictReason::Runtime, None) => "used by another runtime".to_string(),
+                (PortConflictReason::Reserved, _) => "reserved by the sandbox".to_string(),
+                (PortConflictReason::InUse, _) => "in use by another process".to_string(),
+            };
+            format!("port {} of {} is {}", conflict.port, conflict.usage, reason)
+        })
+        .collect();
+    format!("Port conflicts: {}", conflicts.join(", "))
+}
+
+impl ZenohRuntimes {
+    /// Ports of `config` that runtime `runtime_id` can't listen on, because another runtime
+    /// of the sandbox uses them, the sandbox reserved them, or another process listens on them
+    pub(crate) async fn port_conflicts(
+        &self,
+        runtime_id: RuntimeId,
+        config: &ZenohConfigJson,
+        ports: RuntimePorts,
+    ) -> Vec<PortConflict> {
+        let (users, reserved) = {
+            let runtimes = self.runtimes.read().await;
+            let tracker = self.port_tracker.read().await;
+            let mut users: HashMap<u16, RuntimeId> = HashMap::new();
+            for (other_id, runtime_process) in runtimes.iter().filter(|(id, _)| **id != runtime_id)
+            {
+                // Ports released when the runtime stopped are no longer its own
+                users.extend(
+                    allocated_ports(runtime_process)
+                        .filter(|port| tracker.contains(port))
+                        .map(|port| (port, *other_id)),
+                );
+                if runtime_process.state.is_active() {
+                    users.extend(
+                        listen_endpoints(runtime_process.sandbox_config.as_json())
+                            .into_iter()
+                            .filter_map(endpoint_port)
+                            .map(|(port, _)| (port, *other_id)),
+                    );
+                }
+            }
+            let own: HashSet<u16> = runtimes
+                .get(&runtime_id)
+                .map(|runtime_process| allocated_ports(runtime_process).collect())
+                .unwrap_or_default();
+            let reserved: HashSet<u16> = tracker.difference(&own).copied().collect();
+            (users, reserved)
+        };
+
+        config_ports(config, ports)
+            .into_iter()
+            .filter_map(|config_port| {
+                let user = users.get(&config_port.port).copied();
+                let reason = if user.is_some() {
+                    PortConflictReason::Runtime
+                } else if reserved.contains(&config_port.port) {
+                    PortConflictReason::Reserved
+                } else if !config_port.transport.is_port_free(config_port.port) {
+                    PortConflictReason::InUse
+                } else {
+                    return None;
+                };
+                Some(PortConflict {
+                    port: config_port.port,
+                    usage: config_port.usage,
+                    reason,
+                    runtime_id: user,
+                })
+            })
+            .collect()
+    }
+}
+
+// ============================================================================
+// Tauri commands
+// ============================================================================
+
+/// Check the ports of the declared config of a runtime before starting it.
+/// Ports allocated as the runtime starts are not checked.
+/// Returns the ports the runtime can't listen on.
+#[tauri::command]
+#[tracing::instrument(skip(state))]
+pub async fn check_port_conflicts(
+    runtime_id: RuntimeId,
+    state: State<'_, ZenohRuntimes>,
+) -> Result<Vec<PortConflict>, SandboxError> {
+    let (config, ports) = {
+        let runtimes = state.runtimes.read().await;
+        let runtime_process = runtimes
+            .get(&runtime_id)
+            .ok_or(SandboxError::RuntimeNotFound(runtime_id))?;
+        (
+            runtime_process.sandbox_config.clone(),
+            RuntimePorts {
+                websocket: runtime_process.allocated_port,
+                rest: runtime_process.rest_port,
+            },
+        )
+    };
+    Ok(state.port_conflicts(runtime_id, &config, ports).await)
+}
@@ -0,0 +1,345 @@
+use std::{
+    net::{Ipv4Addr, TcpListener, UdpSocket},
+    path::Path,
+    time::Duration,
+};
+
+use tauri::{AppHandle, Manager};
+
+use crate::{
+    PortAllocation, ZenohRuntimes,
+    error::SandboxError,
+    ipc::{IpcListener, PlatformListener},
+    runtime_binary_path,
+    ts::environment::{CheckStatus, EnvironmentCheck, EnvironmentReport},
+};
+
+// ============================================================================
+// Constants
+// ============================================================================
+
+/// Time given to the runtime binary to print its version
+const VERSION_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Number of ports checked from the start of the port range
+const CHECKED_PORTS: u16 = 32;
+
+/// Free disk space below which runtimes may fail to write their logs
+const MIN_FREE_DISK: u64 = 100 * 1024 * 1024;
+
+/// Free disk space below which a warning is reported
+const LOW_FREE_DISK: u64 = 1024 * 1024 * 1024;
+
+/// Default zenoh multicast scouting group
+const SCOUTING_MULTICAST_GROUP: Ipv4Addr = Ipv4Addr::new(224, 0, 0, 224);
+
+// ============================================================================
+// Environment checks
+// ============================================================================
+
+impl EnvironmentCheck {
+    fn pass(name: &str, message: String) -> Self {
+        Self {
+            name: name.to_string(),
+            status: CheckStatus::Pass,
+            message,
+            remediation: None,
+        }
+    }
+
+    fn warn(name: &str, message: String, remediation: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            status: CheckStatus::Warn,
+            message,
+            remediation: Some(remediation.to_string()),
+        }
+    }
+
+    fn fail(name: &str, message: String, remediation: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            status: CheckStatus::Fail,
+            message,
+            remediation: Some(remediation.to_string()),
+        }
+    }
+}
+
+/// Check that the runtime binary exists and has the version of the sandbox
+async fn check_runtime_binary() -> EnvironmentCheck {
+    const NAME: &str = "runtime_binary";
+    const BUILD_HINT: &str = "Build the runtime binary with 'cargo build --bins' \
+        and keep it next to the sandbox executable";
+
+    let runtime_binary = match runtime_binary_path() {
+        Ok(runtime_binary) => runtime_binary,
+        Err(e) => return EnvironmentCheck::fail(NAME, e, BUILD_HINT),
+    };
+    if !runtime_binary.exists() {
+        return EnvironmentCheck::fail(
+            NAME,
+            format!("Runtime binary not found at {}", runtime_binary.display()),
+            BUILD_HINT,
+        );
+    }
+
+    let output = tokio::time::timeout(
+        VERSION_TIMEOUT,
+        tokio::process::Command::new(&runtime_binary)
+            .arg("--version")
+            .kill_on_drop(true)
+            .output(),
+    )
+    .await;
+    let version = match output {
+        Ok(Ok(output)) if output.status.success() => {
+            String::from_utf8_lossy(&output.stdout).trim().to_string()
+        }
+        Ok(Ok(output)) => {
+            return EnvironmentCheck::fail(
+                NAME,
+                format!(
+                    "Runtime binary {} failed to report its version ({}): {}",
+                    runtime_binary.display(),
+                    output.status,
+                    String::from_utf8_lossy(&output.stderr).trim()
+                ),
+                BUILD_HINT,
+            );
+        }
+        Ok(Err(e)) => {
+            return EnvironmentCheck::fail(
+                NAME,
+                format!("Failed to run {}: {}", runtime_binary.display(), e),
+                "Check that the runtime binary is executable by the current user",
+            );
+        }
+        Err(_) => {
+            return EnvironmentCheck::fail(
+                NAME,
+                format!(
+                    "Runtime binary {} didn't report its version within {:?}",
+                    runtime_binary.display(),
+                    VERSION_TIMEOUT
+                ),
+                BUILD_HINT,
+            );
+        }
+    };
+
+    let sandbox_version = env!("CARGO_PKG_VERSION");
+    if version != sandbox_version {
+        return EnvironmentCheck::warn(
+            NAME,
+            format!(
+                "Runtime binary {} has version {}, the sandbox has version {}",
+                runtime_binary.display(),
+                version,
+                sandbox_version
+            ),
+            "Rebuild the runtime binary together with the sandbox with 'cargo build --bins'",
+        );
+    }
+    EnvironmentCheck::pass(
+        NAME,
+        format!(
+            "Runtime binary {} has version {}",
+            runtime_binary.display(),
+            version
+        ),
+    )
+}
+
+/// Check that an IPC endpoint can be created in the socket directory
+fn check_ipc_transport(socket_dir: &Path) -> EnvironmentCheck {
+    const NAME: &str = "ipc_transport";
+    let transport = if cfg!(unix) {
+        "Unix domain socket"
+    } else {
+        "loopback TCP socket"
+    };
+    match PlatformListener::bind(socket_dir) {
+        Ok(listener) => EnvironmentCheck::pass(
+            NAME,
+            format!("Created a {} at {}", transport, listener.address()),
+        ),
+        Err(e) => EnvironmentCheck::fail(
+            NAME,
+            format!(
+                "Failed to create a {} in {}: {}",
+                transport,
+                socket_dir.display(),
+                e
+            ),
+            "Move the socket directory to a local filesystem supporting sockets \
+            with a short path, using relocate_storage",
+        ),
+    }
+}
+
+/// Check that a file can be created in a directory
+fn check_writable_dir(name: &str, dir: &Path) -> EnvironmentCheck {
+    let probe = dir.join(format!(".probe-{:x}", rand::random::<u32>()));
+    let result = std::fs::create_dir_all(dir).and_then(|_| std::fs::write(&probe, b"probe"));
+    let _ = std::fs::remove_file(&probe);
+    match result {
+        Ok(()) => EnvironmentCheck::pass(name, format!("{} is writable", dir.display())),
+        Err(e) => EnvironmentCheck::fail(
+            name,
+            format!("{} is not writable: {}", dir.display(), e),
+            "Fix the permissions of the directory or move it with relocate_storage",
+        ),
+    }
+}
+
+/// Free space available to the current user on the filesystem of `dir`
+#[cfg(unix)]
+fn free_disk_space(dir: &Path) -> Result<u64, String> {
+    use std::{ffi::CString, os::unix::ffi::OsStrExt};
+
+    let path = CString::new(dir.as_os_str().as_bytes())
+        .map_err(|_| format!("Invalid path {}", dir.display()))?;
+    let mut stat: libc::statvfs = unsafe { std::mem::zeroed() };
+    // SAFETY: `path` is a NUL terminated string and `stat` is valid for writes
+    if unsafe { libc::statvfs(path.as_ptr(), &mut stat) } != 0 {
+        return Err(std::io::Error::last_os_error().to_string());
+    }
+    Ok(stat.f_bavail as u64 * stat.f_frsize as u64)
+}
+
+#[cfg(not(unix))]
+fn free_disk_space(_dir: &Path) -> Result<u64, String> {
+    Err("not supported on this platform".to_string())
+}
+
+/// Check the free disk space of the log directory
+fn check_free_disk(log_dir: &Path) -> EnvironmentCheck {
+    const NAME: &str = "free_disk";
+    const HINT: &str = "Free some disk space, or move the log directory \
+        to another filesystem with relocate_storage";
+    let free = match free_disk_space(log_dir) {
+        Ok(free) => free,
+        Err(e) => {
+            return EnvironmentCheck::warn(
+                NAME,
+                format!(
+                    "Failed to get the free space of {}: {}",
+                    log_dir.display(),
+                    e
+                ),
+                "Make sure the log directory has enough free space",
+            );
+        }
+    };
+    let message = format!(
+        "{} MiB free for {}",
+        free / (1024 * 1024),
+        log_dir.display()
+    );
+    if free < MIN_FREE_DISK {
+        EnvironmentCheck::fail(NAME, message, HINT)
+    } else if free < LOW_FREE_DISK {
+        EnvironmentCheck::warn(NAME, message, HINT)
+    } else {
+        EnvironmentCheck::pass(NAME, message)
+    }
+}
+
+/// Check that the first ports allocated to runtimes are free
+fn check_port_range(ports: &PortAllocation, allocated: &[u16]) -> EnvironmentCheck {
+    const NAME: &str = "port_range";
+    let PortAllocation::Range { first, last } = *ports else {
+        return EnvironmentCheck::pass(NAME, "Ports are assigned by the OS".to_string());
+    };
+    let last = last.min(first.saturating_add(CHECKED_PORTS - 1));
+    let busy: Vec<String> = (first..=last)
+        .filter(|port| !allocated.contains(port))
+        .filter(|port| TcpListener::bind((Ipv4Addr::UNSPECIFIED, *port)).is_err())
+        .map(|port| port.to_string())
+        .collect();
+    if busy.is_empty() {
+        return EnvironmentCheck::pass(NAME, format!("Ports {}-{} are available", first, last));
+    }
+    EnvironmentCheck::warn(
+        NAME,
+        format!(
+            "Ports used by other processes in {}-{}: {}",
+            first,
+            last,
+            busy.join(", ")
+        ),
+        "These ports are skipped when allocating ports to runtimes. If the range runs short, \
+        stop the processes using them or set ZENOH_SANDBOX_PORTS to another range or to 'os'",
+    )
+}
+
+/// Check that the zenoh scouting multicast group can be joined
+fn check_multicast() -> EnvironmentCheck {
+    const NAME: &str = "multicast";
+    let result = UdpSocket::bind((Ipv4Addr::UNSPECIFIED, 0)).and_then(|socket| {
+        socket.join_multicast_v4(&SCOUTING_MULTICAST_GROUP, &Ipv4Addr::UNSPECIFIED)
+    });
+    match result {
+        Ok(()) => EnvironmentCheck::pass(
+            NAME,
+            format!("Joined the scouting group {}", SCOUTING_MULTICAST_GROUP),
+        ),
+        Err(e) => EnvironmentCheck::warn(
+            NAME,
+            format!(
+                "Failed to join the scouting group {}: {}",
+                SCOUTING_MULTICAST_GROUP, e
+            ),
+            "Runtimes won't discover each other by multicast scouting. \
+            Enable multicast on a network interface, or connect runtimes explicitly",
+        ),
+    }
+}
+
+// ============================================================================
+// Tauri commands
+// ============================================================================
+
+/// Check the environment the sandbox runs in: runtime binary, IPC transport,
+/// writable directories, free disk space, port range and multicast.
+/// Returns each check with a remediation hint, to show on first launch.
+#[tauri::command]
+#[tracing::instrument(skip(app))]
+pub async fn environment_report(app: AppHandle) -> Result<EnvironmentReport, SandboxError> {
+    let runtimes = app.state::<ZenohRuntimes>();
+    let dirs = runtimes.dirs.read().clone();
+    let ports = runtimes.ports.clone();
+    let allocated: Vec<u16> = runtimes.port_tracker.read().await.iter().copied().collect();
+
+    let mut checks = vec![check_runtime_binary().await];
+    let blocking_checks = tokio::task::spawn_blocking(move || {
+        vec![
+            check_ipc_transport(&dirs.socket_dir),
+            check_writable_dir("socket_dir", &dirs.socket_dir),
+            check_writable_dir("log_dir", &dirs.log_dir),
+            check_free_disk(&dirs.log_dir),
+            check_port_range(&ports, &allocated),
+            check_multicast(),
+        ]
+    })
+    .await
+    .map_err(|e| format!("Environment checks failed: {}", e))?;
+    checks.extend(blocking_checks);
+
+    for check in &checks {
+        if check.status != CheckStatus::Pass {
+            tracing::warn!("Environment check {}: {}", check.name, check.message);
+        }
+    }
+
+    Ok(EnvironmentReport {
+        sandbox_version: env!("CARGO_PKG_VERSION").to_string(),
+        status: checks
+            .iter()
+            .map(|check| check.status)
+            .max()
+            .unwrap_or(CheckStatus::Pass),
+        checks,
+    })
+}
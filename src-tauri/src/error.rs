@@ -0,0 +1,90 @@
+use serde::Serialize;
+
+use crate::{
+    RuntimeId,
+    ts::error::{CommandError, SandboxErrorKind},
+};
+
+// ============================================================================
+// Sandbox errors
+// ============================================================================
+//
+// Commands return `SandboxError`, serialized as a `CommandError` so that the frontend can
+// branch on its kind. Helpers whose errors have no particular kind keep returning a String,
+// converted to `SandboxError::Other` by `?`.
+
+/// Error of a command or of an operation on a runtime
+#[derive(Debug, Clone, thiserror::Error, Serialize)]
+#[serde(into = "CommandError")]
+pub enum SandboxError {
+    /// A configuration was rejected
+    #[error("{0}")]
+    ConfigInvalid(String),
+    #[error("Runtime {0} not found")]
+    RuntimeNotFound(RuntimeId),
+    #[error("Runtime {0} is not running")]
+    RuntimeNotStarted(RuntimeId),
+    /// The runtime process could not be started
+    #[error("{0}")]
+    SpawnFailed(String),
+    /// A response or a connection did not arrive in time
+    #[error("{0}")]
+    Timeout(String),
+    /// Communication with a runtime process failed
+    #[error("{0}")]
+    IpcError(String),
+    #[error("{0}")]
+    Other(String),
+}
+
+impl SandboxError {
+    pub fn kind(&self) -> SandboxErrorKind {
+        match self {
+            SandboxError::ConfigInvalid(_) => SandboxErrorKind::ConfigInvalid,
+            SandboxError::RuntimeNotFound(_) => SandboxErrorKind::RuntimeNotFound,
+            SandboxError::RuntimeNotStarted(_) => SandboxErrorKind::RuntimeNotStarted,
+            SandboxError::SpawnFailed(_) => SandboxErrorKind::SpawnFailed,
+            SandboxError::Timeout(_) => SandboxErrorKind::Timeout,
+            SandboxError::IpcError(_) => SandboxErrorKind::IpcError,
+            SandboxError::Other(_) => SandboxErrorKind::Other,
+        }
+    }
+
+    /// Runtime the error is about, if any
+    pub fn runtime_id(&self) -> Option<RuntimeId> {
+        match self {
+            SandboxError::RuntimeNotFound(runtime_id)
+            | SandboxError::RuntimeNotStarted(runtime_id) => Some(*runtime_id),
+            _ => None,
+        }
+    }
+}
+
+impl From<String> for SandboxError {
+    fn from(message: String) -> Self {
+        SandboxError::Other(message)
+    }
+}
+
+impl From<&str> for SandboxError {
+    fn from(message: &str) -> Self {
+        SandboxError::Other(message.to_string())
+    }
+}
+
+/// Lets helpers returning a String error use `?` on sandbox errors
+impl From<SandboxError> for String {
+    fn from(error: SandboxError) -> Self {
+        error.to_string()
+    }
+}
+
+impl From<SandboxError> for CommandError {
+    fn from(error: SandboxError) -> Self {
+        CommandError {
+            kind: error.kind(),
+            message: error.to_string(),
+            runtime_id: error.runtime_id(),
+        }
+    }
+}
@@ -0,0 +1,100 @@
+use tauri::{AppHandle, Emitter, Manager};
+use tokio::sync::broadcast;
+
+use crate::ts::runtime::{
+    RuntimeConfigChangedEvent, RuntimeCrashedEvent, RuntimeDeclaredEvent, RuntimeStartedEvent,
+    RuntimeStoppedEvent,
+};
+
+// ============================================================================
+// Constants
+// ============================================================================
+
+/// Number of events kept for the frontend forwarder when it falls behind
+const EVENT_CAPACITY: usize = 1024;
+
+// ============================================================================
+// Runtime lifecycle events
+// ============================================================================
+//
+// The runtime manager and the timeline publish lifecycle changes on a single channel,
+// forwarded to the frontend as Tauri events, so that it doesn't have to poll the runtime
+// list to notice them.
+
+/// Lifecycle change of a runtime, emitted to the frontend as the event of its name
+#[derive(Debug, Clone)]
+pub enum RuntimeEvent {
+    Declared(RuntimeDeclaredEvent),
+    Started(RuntimeStartedEvent),
+    Stopped(RuntimeStoppedEvent),
+    Crashed(RuntimeCrashedEvent),
+    ConfigChanged(RuntimeConfigChangedEvent),
+}
+
+impl RuntimeEvent {
+    /// Name of the Tauri event
+    pub fn name(&self) -> &'static str {
+        match self {
+            RuntimeEvent::Declared(_) => "runtime-declared",
+            RuntimeEvent::Started(_) => "runtime-started",
+            RuntimeEvent::Stopped(_) => "runtime-stopped",
+            RuntimeEvent::Crashed(_) => "runtime-crashed",
+            RuntimeEvent::ConfigChanged(_) => "runtime-config-changed",
+        }
+    }
+
+    /// Emit the payload of the event to the frontend
+    fn emit(&self, app: &AppHandle) -> tauri::Result<()> {
+        match self {
+            RuntimeEvent::Declared(payload) => app.emit(self.name(), payload),
+            RuntimeEvent::Started(payload) => app.emit(self.name(), payload),
+            RuntimeEvent::Stopped(payload) => app.emit(self.name(), payload),
+            RuntimeEvent::Crashed(payload) => app.emit(self.name(), payload),
+            RuntimeEvent::ConfigChanged(payload) => app.emit(self.name(), payload),
+        }
+    }
+}
+
+/// Channel of the runtime lifecycle events, shared by its publishers
+#[derive(Clone)]
+pub struct RuntimeEvents {
+    tx: broadcast::Sender<RuntimeEvent>,
+}
+
+impl Default for RuntimeEvents {
+    fn default() -> Self {
+        Self {
+            tx: broadcast::channel(EVENT_CAPACITY).0,
+        }
+    }
+}
+
+impl RuntimeEvents {
+    /// Publish an event. Events published while nothing listens are dropped.
+    pub fn publish(&self, event: RuntimeEvent) {
+        tracing::debug!("Runtime event {}: {:?}", event.name(), event);
+        let _ = self.tx.send(event);
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<RuntimeEvent> {
+        self.tx.subscribe()
+    }
+}
+
+/// Forward the runtime lifecycle events to the frontend until the app exits
+pub async fn forward_runtime_events(app: AppHandle) {
+    let mut events = app.state::<RuntimeEvents>().subscribe();
+    loop {
+        match events.recv().await {
+            Ok(event) => {
+                if let Err(e) = event.emit(&app) {
+                    tracing::warn!("Failed to emit {}: {}", event.name(), e);
+                }
+            }
+            Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                tracing::warn!("{} runtime events dropped, the frontend lagged", skipped);
+            }
+            Err(broadcast::error::RecvError::Closed) => break,
+        }
+    }
+}
@@ -0,0 +1,149 @@
+use tauri::{AppHandle, Manager, State};
+
+use crate::{
+    RuntimeId, ZenohRuntimes,
+    bulk::{run_bounded, start_operation, stop_operation},
+    error::SandboxError,
+    ts::runtime::{BulkOperationReport, RuntimeGroup},
+};
+
+// ============================================================================
+// Groups
+// ============================================================================
+
+impl ZenohRuntimes {
+    /// Runtimes of a group, sorted
+    async fn group_runtime_ids(&self, name: &str) -> Result<Vec<RuntimeId>, SandboxError> {
+        self.groups
+            .read()
+            .await
+            .get(name)
+            .map(|members| members.iter().copied().collect())
+            .ok_or_else(|| SandboxError::Other(format!("No group named '{}'", name)))
+    }
+}
+
+// ============================================================================
+// Tauri commands
+// ============================================================================
+
+/// Create an empty group of runtimes, to organize large topologies
+/// (e.g. "backbone" routers and "edge" peers) and start or stop them together
+#[tauri::command]
+#[tracing::instrument(skip(state))]
+pub async fn create_group(
+    name: String,
+    state: State<'_, ZenohRuntimes>,
+) -> Result<RuntimeGroup, SandboxError> {
+    let name = name.trim().to_string();
+    if name.is_empty() {
+        return Err("Group name must not be empty".into());
+    }
+    let mut groups = state.groups.write().await;
+    if groups.contains_key(&name) {
+        return Err(format!("Group '{}' already exists", name).into());
+    }
+    groups.insert(name.clone(), Default::default());
+    tracing::info!("Group '{}' created", name);
+    Ok(RuntimeGroup {
+        name,
+        runtime_ids: Vec::new(),
+    })
+}
+
+/// Delete a group. Its runtimes are kept, without a group.
+#[tauri::command]
+#[tracing::instrument(skip(state))]
+pub async fn delete_group(
+    name: String,
+    state: State<'_, ZenohRuntimes>,
+) -> Result<(), SandboxError> {
+    state
+        .groups
+        .write()
+        .await
+        .remove(&name)
+        .ok_or_else(|| SandboxError::Other(format!("No group named '{}'", name)))?;
+    tracing::info!("Group '{}' deleted", name);
+    Ok(())
+}
+
+/// Move a runtime to a group, out of the group it was in, or out of any group
+/// when `group` is None
+#[tauri::command]
+#[tracing::instrument(skip(state))]
+pub async fn assign_runtime_to_group(
+    runtime_id: RuntimeId,
+    group: Option<String>,
+    state: State<'_, ZenohRuntimes>,
+) -> Result<(), SandboxError> {
+    if !state.runtimes.read().await.contains_key(&runtime_id) {
+        return Err(SandboxError::RuntimeNotFound(runtime_id));
+    }
+    let mut groups = state.groups.write().await;
+    if let Some(group) = &group
+        && !groups.contains_key(group)
+    {
+        return Err(format!("No group named '{}'", group).into());
+    }
+    for members in groups.values_mut() {
+        members.remove(&runtime_id);
+    }
+    if let Some(group) = group {
+        if let Some(members) = groups.get_mut(&group) {
+            members.insert(runtime_id);
+        }
+        tracing::info!("Runtime {} assigned to group '{}'", runtime_id, group);
+    }
+    Ok(())
+}
+
+/// List the groups with their runtimes, ordered by name
+#[tauri::command]
+#[tracing::instrument(skip(state))]
+pub async fn list_groups(
+    state: State<'_, ZenohRuntimes>,
+) -> Result<Vec<RuntimeGroup>, SandboxError> {
+    Ok(state
+        .groups
+        .read()
+        .await
+        .iter()
+        .map(|(name, members)| RuntimeGroup {
+            name: name.clone(),
+            runtime_ids: members.iter().copied().collect(),
+        })
+        .collect())
+}
+
+/// Start the runtimes of a group concurrently, see `start_runtimes`.
+/// Returns the outcome for each runtime, including partial failures.
+#[tauri::command]
+#[tracing::instrument(skip(app))]
+pub async fn start_group(
+    name: String,
+    max_parallel: Option<usize>,
+    app: AppHandle,
+) -> Result<BulkOperationReport, SandboxError> {
+    let runtime_ids = app
+        .state::<ZenohRuntimes>()
+        .group_runtime_ids(&name)
+        .await?;
+    Ok(run_bounded(&app, runtime_ids, max_parallel, start_operation).await)
+}
+
+/// Stop the runtimes of a group concurrently, see `stop_runtimes`.
+/// Returns the outcome for each runtime, including partial failures.
+#[tauri::command]
+#[tracing::instrument(skip(app))]
+pub async fn stop_group(
+    name: String,
+    max_parallel: Option<usize>,
+    app: AppHandle,
+) -> Result<BulkOperationReport, SandboxError> {
+    let runtime_ids = app
+        .state::<ZenohRuntimes>()
+        .group_runtime_ids(&name)
+        .await?;
+    Ok(run_bounded(&app, runtime_ids, max_parallel, stop_operation).await)
+}
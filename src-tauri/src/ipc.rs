@@ -0,0 +1,209 @@
+use std::{io, path::Path};
+
+use tokio::io::{AsyncRead, AsyncWrite};
+
+// ============================================================================
+// IPC transport between main process and runtime processes
+// ============================================================================
+//
+// The main process listens on an endpoint and passes its address to the runtime
+// process on the command line. The runtime connects back and both sides exchange
+// protocol messages over the resulting stream.
+//
+// The transport is selected at compile time:
+// - Unix: a Unix domain socket in the sandbox socket directory
+// - Other platforms: a loopback TCP socket. Since any local process may connect to it,
+//   the runtime must first send a random token, passed through `IPC_TOKEN_ENV`.
+
+/// Environment variable used to pass the connection token to the runtime process
+pub const IPC_TOKEN_ENV: &str = "ZENOH_SANDBOX_IPC_TOKEN";
+
+/// Reading half of an IPC connection
+pub type IpcReadHalf = Box<dyn AsyncRead + Send + Unpin>;
+
+/// Writing half of an IPC connection
+pub type IpcWriteHalf = Box<dyn AsyncWrite + Send + Unpin>;
+
+/// Listening side of the IPC transport, owned by the main process
+pub trait IpcListener: Sized {
+    /// Create a new endpoint. `dir` may be used to store endpoint files.
+    fn bind(dir: &Path) -> io::Result<Self>;
+
+    /// Address to pass to the runtime process
+    fn address(&self) -> String;
+
+    /// Environment variables to pass to the runtime process
+    fn env(&self) -> Vec<(&'static str, String)>;
+
+    /// Wait for the runtime process to connect
+    fn accept(&self) -> impl Future<Output = io::Result<(IpcReadHalf, IpcWriteHalf)>> + Send;
+}
+
+/// Listener of the current platform
+#[cfg(unix)]
+pub type PlatformListener = unix::UdsListener;
+
+/// Listener of the current platform
+#[cfg(not(unix))]
+pub type PlatformListener = tcp::TcpTokenListener;
+
+/// Connect to the main process at `address`, as passed by `IpcListener::address`
+pub async fn connect(address: &str) -> io::Result<(IpcReadHalf, IpcWriteHalf)> {
+    #[cfg(unix)]
+    return unix::connect(address).await;
+    #[cfg(not(unix))]
+    return tcp::connect(address).await;
+}
+
+// ============================================================================
+// Unix domain sockets
+// ============================================================================
+
+#[cfg(unix)]
+mod unix {
+    use std::{io, path::Path, path::PathBuf};
+
+    use tokio::net::{UnixListener, UnixStream};
+
+    use super::{IpcListener, IpcReadHalf, IpcWriteHalf};
+
+    /// Unix domain socket listener, removing its socket file when dropped
+    pub struct UdsListener {
+        path: PathBuf,
+        listener: UnixListener,
+    }
+
+    impl IpcListener for UdsListener {
+        fn bind(dir: &Path) -> io::Result<Self> {
+            // Use a short random name to stay within the SUN_LEN limit
+            let random_id: u32 = rand::random();
+            let path = dir.join(format!("z{:x}.sock", random_id));
+            let listener = UnixListener::bind(&path)?;
+            Ok(Self { path, listener })
+        }
+
+        fn address(&self) -> String {
+            self.path.to_string_lossy().to_string()
+        }
+
+        fn env(&self) -> Vec<(&'static str, String)> {
+            Vec::new()
+        }
+
+        async fn accept(&self) -> io::Result<(IpcReadHalf, IpcWriteHalf)> {
+            let (stream, _) = self.listener.accept().await?;
+            let (reader, writer) = stream.into_split();
+            Ok((Box::new(reader), Box::new(writer)))
+        }
+    }
+
+    impl UdsListener {
+        /// Give the socket to another user, so that a runtime process running as it can connect
+        pub fn give_to(&self, uid: u32, gid: u32) -> io::Result<()> {
+            std::os::unix::fs::chown(&self.path, Some(uid), Some(gid))
+        }
+    }
+
+    impl Drop for UdsListener {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_file(&self.path);
+        }
+    }
+
+    pub async fn connect(address: &str) -> io::Result<(IpcReadHalf, IpcWriteHalf)> {
+        let (reader, writer) = UnixStream::connect(address).await?.into_split();
+        Ok((Box::new(reader), Box::new(writer)))
+    }
+}
+
+// ============================================================================
+// Loopback TCP with token
+// ============================================================================
+
+#[cfg(not(unix))]
+mod tcp {
+    use std::{io, path::Path, time::Duration};
+
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::{TcpListener, TcpStream},
+    };
+
+    use super::{IPC_TOKEN_ENV, IpcListener, IpcReadHalf, IpcWriteHalf};
+
+    /// Length of the hex encoded connection token
+    const TOKEN_LEN: usize = 32;
+
+    /// Time for a connection to present its token, so that a silent one doesn't block
+    /// the runtime process from connecting
+    const TOKEN_READ_TIMEOUT: Duration = Duration::from_secs(2);
+
+    /// Compare tokens in constant time, not revealing how much of a token is right
+    fn tokens_match(received: &[u8], expected: &[u8]) -> bool {
+        received.len() == expected.len()
+            && received
+                .iter()
+                .zip(expected)
+                .fold(0u8, |diff, (a, b)| diff | (a ^ b))
+                == 0
+    }
+
+    /// Loopback TCP listener accepting only connections presenting its token
+    pub struct TcpTokenListener {
+        token: String,
+        listener: TcpListener,
+    }
+
+    impl IpcListener for TcpTokenListener {
+        fn bind(_dir: &Path) -> io::Result<Self> {
+            let listener = std::net::TcpListener::bind(("127.0.0.1", 0))?;
+            listener.set_nonblocking(true)?;
+            Ok(Self {
+                token: format!("{:032x}", rand::random::<u128>()),
+                listener: TcpListener::from_std(listener)?,
+            })
+        }
+
+        fn address(&self) -> String {
+            self.listener
+                .local_addr()
+                .map(|addr| addr.to_string())
+                .unwrap_or_default()
+        }
+
+        fn env(&self) -> Vec<(&'static str, String)> {
+            vec![(IPC_TOKEN_ENV, self.token.clone())]
+        }
+
+        async fn accept(&self) -> io::Result<(IpcReadHalf, IpcWriteHalf)> {
+            // Drop connections until one presents the expected token
+            loop {
+                let (mut stream, peer) = self.listener.accept().await?;
+                let mut token = [0u8; TOKEN_LEN];
+                let read =
+                    tokio::time::timeout(TOKEN_READ_TIMEOUT, stream.read_exact(&mut token)).await;
+                match read {
+                    Ok(Ok(_)) if tokens_match(&token, self.token.as_bytes()) => {
+                        let (reader, writer) = stream.into_split();
+                        return Ok((Box::new(reader), Box::new(writer)));
+                    }
+                    _ => tracing::warn!("Rejected IPC connection from {} with invalid token", peer),
+                }
+            }
+        }
+    }
+
+    pub async fn connect(address: &str) -> io::Result<(IpcReadHalf, IpcWriteHalf)> {
+        let token = std::env::var(IPC_TOKEN_ENV).map_err(|_| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("{IPC_TOKEN_ENV} is not set"),
+            )
+        })?;
+        let mut stream = TcpStream::connect(address).await?;
+        stream.set_nodelay(true)?;
+        stream.write_all(token.as_bytes()).await?;
+        let (reader, writer) = stream.into_split();
+        Ok((Box::new(reader), Box::new(writer)))
+    }
+}
@@ -0,0 +1,196 @@
+use std::{
+    collections::HashMap,
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+use chrono::Utc;
+use parking_lot::RwLock as ParkingLotRwLock;
+use tauri::{AppHandle, Manager};
+
+use crate::{
+    RuntimeId, ZenohRuntimes,
+    error::SandboxError,
+    timeline::Timeline,
+    ts::{journal::JournalEntry, timeline::TimelineEvent},
+};
+
+// ============================================================================
+// Experiment journal
+// ============================================================================
+
+/// Sandbox-wide journal of observations made during experiments.
+/// It is saved with the declared runtimes by `export_session`.
+#[derive(Default)]
+pub struct Journal {
+    /// Entries, oldest first
+    entries: ParkingLotRwLock<Vec<JournalEntry>>,
+    /// Identifier of the next entry
+    next_id: AtomicU64,
+}
+
+impl Journal {
+    /// Add an entry, timestamped now
+    fn add(
+        &self,
+        text: String,
+        runtime_id: Option<RuntimeId>,
+        events: Vec<TimelineEvent>,
+    ) -> JournalEntry {
+        let entry = JournalEntry {
+            id: self.next_id.fetch_add(1, Ordering::Relaxed),
+            timestamp: Utc::now(),
+            text,
+            runtime_id,
+            events,
+        };
+        self.entries.write().push(entry.clone());
+        entry
+    }
+
+    /// Remove an entry. Returns false if it doesn't exist.
+    fn remove(&self, id: u64) -> bool {
+        let mut entries = self.entries.write();
+        let len = entries.len();
+        entries.retain(|entry| entry.id != id);
+        entries.len() != len
+    }
+
+    /// All entries, oldest first
+    pub(crate) fn entries(&self) -> Vec<JournalEntry> {
+        self.entries.read().clone()
+    }
+
+    /// Append entries of an imported session, giving them new identifiers
+    /// and remapping their RuntimeIds. Entries about unknown runtimes are kept
+    /// without runtime.
+    pub(crate) fn import(
+        &self,
+        imported: Vec<JournalEntry>,
+        new_ids: &HashMap<RuntimeId, RuntimeId>,
+    ) {
+        let remap =
+            |runtime_id: Option<RuntimeId>| runtime_id.and_then(|id| new_ids.get(&id).copied());
+        let mut entries = self.entries.write();
+        for mut entry in imported {
+            entry.id = self.next_id.fetch_add(1, Ordering::Relaxed);
+            entry.runtime_id = remap(entry.runtime_id);
+            for event in &mut entry.events {
+                event.runtime_id = remap(event.runtime_id);
+            }
+            entries.push(entry);
+        }
+    }
+
+    /// Remove all entries
+    pub(crate) fn clear(&self) {
+        self.entries.write().clear();
+    }
+}
+
+impl ZenohRuntimes {
+    /// Notes of a runtime
+    pub(crate) async fn notes(&self, runtime_id: RuntimeId) -> Result<String, SandboxError> {
+        self.runtimes
+            .read()
+            .await
+            .get(&runtime_id)
+            .map(|runtime_process| runtime_process.notes.clone())
+            .ok_or(SandboxError::RuntimeNotFound(runtime_id))
+    }
+
+    /// Replace the notes of a runtime
+    pub(crate) async fn set_notes(
+        &self,
+        runtime_id: RuntimeId,
+        notes: String,
+    ) -> Result<(), SandboxError> {
+        let mut runtimes = self.runtimes.write().await;
+        let runtime_process = runtimes
+            .get_mut(&runtime_id)
+            .ok_or(SandboxError::RuntimeNotFound(runtime_id))?;
+        runtime_process.notes = notes;
+        Ok(())
+    }
+}
+
+// ============================================================================
+// Tauri commands
+// ============================================================================
+
+/// Replace the Markdown notes of a runtime. They are saved with the runtime by `export_session`.
+#[tauri::command]
+#[tracing::instrument(skip(markdown, app))]
+pub async fn set_runtime_notes(
+    runtime_id: RuntimeId,
+    markdown: String,
+    app: AppHandle,
+) -> Result<(), SandboxError> {
+    app.state::<ZenohRuntimes>()
+        .set_notes(runtime_id, markdown)
+        .await
+}
+
+/// Get the Markdown notes of a runtime
+#[tauri::command]
+#[tracing::instrument(skip(app))]
+pub async fn get_runtime_notes(
+    runtime_id: RuntimeId,
+    app: AppHandle,
+) -> Result<String, SandboxError> {
+    app.state::<ZenohRuntimes>().notes(runtime_id).await
+}
+
+/// Add an entry to the experiment journal, optionally about a runtime
+/// and referring to events of the timeline, identified by their id.
+#[tauri::command]
+#[tracing::instrument(skip(text, app))]
+pub async fn add_journal_entry(
+    text: String,
+    runtime_id: Option<RuntimeId>,
+    timeline_events: Vec<u64>,
+    app: AppHandle,
+) -> Result<JournalEntry, SandboxError> {
+    if let Some(runtime_id) = runtime_id
+        && !app
+            .state::<ZenohRuntimes>()
+            .runtimes
+            .read()
+            .await
+            .contains_key(&runtime_id)
+    {
+        return Err(SandboxError::RuntimeNotFound(runtime_id));
+    }
+    let mut events = app.state::<Timeline>().get_events(&timeline_events);
+    if let Some(missing) = timeline_events
+        .iter()
+        .find(|id| !events.iter().any(|event| event.id == **id))
+    {
+        return Err(format!("Timeline event {} not found", missing).into());
+    }
+    events.reverse();
+    Ok(app.state::<Journal>().add(text, runtime_id, events))
+}
+
+/// Get the entries of the experiment journal, oldest first, optionally only those about a runtime
+#[tauri::command]
+#[tracing::instrument(skip(app))]
+pub async fn get_journal(
+    runtime_id: Option<RuntimeId>,
+    app: AppHandle,
+) -> Result<Vec<JournalEntry>, SandboxError> {
+    let mut entries = app.state::<Journal>().entries();
+    if runtime_id.is_some() {
+        entries.retain(|entry| entry.runtime_id == runtime_id);
+    }
+    Ok(entries)
+}
+
+/// Remove an entry from the experiment journal
+#[tauri::command]
+#[tracing::instrument(skip(app))]
+pub async fn remove_journal_entry(id: u64, app: AppHandle) -> Result<(), SandboxError> {
+    if !app.state::<Journal>().remove(id) {
+        return Err(format!("Journal entry {} not found", id).into());
+    }
+    Ok(())
+}
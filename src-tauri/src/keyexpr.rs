@@ -0,0 +1,52 @@
+use zenoh::key_expr::{KeyExpr, OwnedKeyExpr};
+
+use crate::error::SandboxError;
+use crate::ts::keyexpr::KeyExprValidation;
+
+// ============================================================================
+// Key expressions
+// ============================================================================
+
+/// Parse a key expression, failing with a message naming it
+fn parse(expr: &str) -> Result<KeyExpr<'_>, String> {
+    KeyExpr::try_from(expr).map_err(|e| format!("Invalid key expression {}: {}", expr, e))
+}
+
+// ============================================================================
+// Tauri commands
+// ============================================================================
+
+/// Check whether `expr` is a valid key expression. Non-canonical expressions such as
+/// `a/**/**` are not valid, but their canonical form is returned.
+#[tauri::command]
+#[tracing::instrument]
+pub async fn validate_keyexpr(expr: String) -> Result<KeyExprValidation, SandboxError> {
+    Ok(match parse(&expr) {
+        Ok(keyexpr) => KeyExprValidation {
+            valid: true,
+            canonical: Some(keyexpr.to_string()),
+            error: None,
+        },
+        Err(error) => KeyExprValidation {
+            valid: false,
+            canonical: OwnedKeyExpr::autocanonize(expr)
+                .ok()
+                .map(|keyexpr| keyexpr.to_string()),
+            error: Some(error),
+        },
+    })
+}
+
+/// Check whether some key matches both `a` and `b`, e.g. `a/*` and `*/b`
+#[tauri::command]
+#[tracing::instrument]
+pub async fn keyexpr_intersects(a: String, b: String) -> Result<bool, SandboxError> {
+    Ok(parse(&a)?.intersects(&parse(&b)?))
+}
+
+/// Check whether every key matching `b` also matches `a`, e.g. `a/**` includes `a/b/*`
+#[tauri::command]
+#[tracing::instrument]
+pub async fn keyexpr_includes(a: String, b: String) -> Result<bool, SandboxError> {
+    Ok(parse(&a)?.includes(&parse(&b)?))
+}
@@ -0,0 +1,158 @@
+use std::{sync::Arc, time::Duration};
+
+use chrono::Utc;
+use tauri::{AppHandle, Manager};
+use tokio::{sync::Semaphore, task::JoinSet};
+
+use crate::{
+    RuntimeId, ZenohRuntimes,
+    bulk::DEFAULT_MAX_PARALLEL,
+    error::SandboxError,
+    reports::{OperationResults, operation_summary},
+    ts::{
+        latency::{LatencyCell, LatencyTestResult, MeshLatencyMatrix, percentile},
+        report::OperationKind,
+    },
+};
+
+// ============================================================================
+// Constants
+// ============================================================================
+
+/// Number of queries per runtime pair when no count is given
+pub const DEFAULT_LATENCY_SAMPLES: u32 = 10;
+
+/// Maximum time to wait for each echo reply
+const ECHO_TIMEOUT: Duration = Duration::from_secs(2);
+
+// ============================================================================
+// Tauri commands
+// ============================================================================
+
+/// Measure the round-trip time between every ordered pair of running runtimes.
+/// Each runtime queries the echo queryable of the others `samples` times,
+/// with at most `max_parallel` pairs measured at the same time.
+/// Returns an N×N matrix indexed by the running runtimes.
+/// The result is recorded so that it can be exported with `export_report`.
+#[tauri::command]
+#[tracing::instrument(skip(app))]
+pub async fn run_mesh_latency_sweep(
+    samples: Option<u32>,
+    max_parallel: Option<usize>,
+    app: AppHandle,
+) -> Result<MeshLatencyMatrix, SandboxError> {
+    let samples = samples.unwrap_or(DEFAULT_LATENCY_SAMPLES).max(1);
+    let results = app.state::<OperationResults>();
+    let operation_id = results.next_id();
+    let started_at = Utc::now();
+    let running = app.state::<ZenohRuntimes>().running_zenoh_ids().await;
+    let n = running.len();
+
+    let semaphore = Arc::new(Semaphore::new(
+        max_parallel.unwrap_or(DEFAULT_MAX_PARALLEL).max(1),
+    ));
+    let mut tasks = JoinSet::new();
+    for (i, &(runtime_id, _)) in running.iter().enumerate() {
+        for (j, &(_, target)) in running.iter().enumerate() {
+            if i == j {
+                continue;
+            }
+            let app = app.clone();
+            let semaphore = semaphore.clone();
+            tasks.spawn(async move {
+                let result = match semaphore.acquire_owned().await {
+                    Ok(_permit) => app
+                        .state::<ZenohRuntimes>()
+                        .ping(runtime_id, target, samples, ECHO_TIMEOUT, 0)
+                        .await
+                        .map_err(|e| e.to_string()),
+                    Err(_) => Err("Operation cancelled".to_string()),
+                };
+                (i, j, LatencyCell::from_result(result))
+            });
+        }
+    }
+
+    let mut cells: Vec<Vec<Option<LatencyCell>>> = vec![vec![None; n]; n];
+    while let Some(joined) = tasks.join_next().await {
+        match joined {
+            Ok((i, j, cell)) => cells[i][j] = Some(cell),
+            Err(e) => tracing::error!("Latency measurement task failed: {}", e),
+        }
+    }
+
+    let runtime_ids: Vec<_> = running.iter().map(|(runtime_id, _)| *runtime_id).collect();
+    let matrix = MeshLatencyMatrix {
+        operation_id,
+        started_at,
+        completed_at: Utc::now(),
+        runtime_ids: runtime_ids.clone(),
+        zenoh_ids: running.iter().map(|(_, zid)| zid.to_string()).collect(),
+        cells,
+    };
+    let summary = operation_summary(
+        operation_id,
+        OperationKind::LatencySweep,
+        started_at,
+        runtime_ids,
+    );
+    results
+        .record(&app.state::<ZenohRuntimes>(), summary, &matrix)
+        .await?;
+    Ok(matrix)
+}
+
+/// Measure the round-trip time from `runtime_a` to `runtime_b` with `samples` queries
+/// to the echo queryable of `runtime_b`, each carrying `payload_size` bytes echoed back.
+/// Returns the min, median, p95, p99 and max round-trip times, so that the effect of
+/// the network or config on latency can be compared across runs.
+/// The result is recorded so that it can be exported with `export_report`.
+#[tauri::command]
+#[tracing::instrument(skip(app))]
+pub async fn run_latency_test(
+    runtime_a: RuntimeId,
+    runtime_b: RuntimeId,
+    samples: Option<u32>,
+    payload_size: Option<usize>,
+    app: AppHandle,
+) -> Result<LatencyTestResult, SandboxError> {
+    let samples = samples.unwrap_or(DEFAULT_LATENCY_SAMPLES).max(1);
+    let payload_size = payload_size.unwrap_or(0);
+    let runtimes = app.state::<ZenohRuntimes>();
+    let results = app.state::<OperationResults>();
+    let operation_id = results.next_id();
+    let started_at = Utc::now();
+
+    runtimes.running_zenoh_id(runtime_a).await?;
+    let target = runtimes.running_zenoh_id(runtime_b).await?;
+    let mut rtts = runtimes
+        .ping(runtime_a, target, samples, ECHO_TIMEOUT, payload_size)
+        .await?;
+    rtts.sort_unstable();
+    let (Some(&min_us), Some(&max_us)) = (rtts.first(), rtts.last()) else {
+        return Err("No samples".into());
+    };
+
+    let result = LatencyTestResult {
+        operation_id,
+        started_at,
+        completed_at: Utc::now(),
+        runtime_a,
+        runtime_b,
+        samples,
+        payload_size,
+        min_us,
+        median_us: rtts[rtts.len() / 2],
+        p95_us: percentile(&rtts, 95),
+        p99_us: percentile(&rtts, 99),
+        max_us,
+    };
+    let summary = operation_summary(
+        operation_id,
+        OperationKind::LatencyTest,
+        started_at,
+        vec![runtime_a, runtime_b],
+    );
+    results.record(&runtimes, summary, &result).await?;
+    Ok(result)
+}
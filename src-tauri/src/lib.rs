@@ -1,20 +1,27 @@
 use std::{
-    collections::{HashMap, HashSet},
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet},
     fs::OpenOptions,
     path::PathBuf,
-    process::Stdio,
+    process::{ExitStatus, Stdio},
     str::FromStr,
+    sync::{
+        Arc,
+        atomic::{AtomicU32, AtomicU64, Ordering},
+    },
+    time::{Duration, Instant},
 };
 
-use protocol::{MainToRuntime, RuntimeToMain};
-use tauri::State;
+use ipc::{IpcReadHalf, IpcWriteHalf};
+use protocol::{Codec, Frame, FrameReader, MainToRuntime, QueryReply, RuntimeToMain, TestEntity};
+use tauri::{AppHandle, Emitter, Manager, State};
 use tokio::{
-    io::{AsyncBufReadExt, AsyncWriteExt, BufReader},
-    net::UnixListener,
     process::Child,
     sync::{RwLock, mpsc, oneshot},
     task::JoinHandle,
 };
+use parking_lot::{Mutex as ParkingLotMutex, RwLock as ParkingLotRwLock};
+use sysinfo::{Pid, ProcessRefreshKind, ProcessesToUpdate, System};
+use tracing::Instrument;
 use zenoh::config::Config;
 use zenoh::session::ZenohId;
 
@@ -22,13 +29,84 @@ use zenoh::session::ZenohId;
 // Modules
 // ============================================================================
 
+pub mod acl;
+pub mod auth;
+pub mod bulk;
+pub mod clone;
+pub mod config_history;
+pub mod config_schema;
+pub mod config_validation;
+mod delta;
+pub mod downsampling;
+pub mod drift;
+pub mod endpoints;
+pub mod environment;
+pub mod error;
+pub mod events;
+pub mod groups;
+pub mod ipc;
+pub mod journal;
+pub mod keyexpr;
+pub mod latency;
+pub mod links;
+pub mod limits;
+mod log_archive;
 pub mod logs;
+pub mod orphans;
+pub mod peers;
+pub mod presets;
+pub mod privileges;
 pub mod protocol;
+pub mod reports;
+mod restart;
+pub mod sandbox_tracing;
+pub mod scenarios;
+pub mod scouting;
+pub mod session;
+pub mod shm;
+pub mod storages;
+pub mod testing;
+pub mod timeline;
+pub mod tls;
+pub mod topology;
+pub mod transform;
 pub mod ts;
-
-use logs::{LogEntry, LogStorage};
-
-use crate::ts::{config::{ZenohConfigEdit, ZenohConfigJson}, log::LogEntryLevel};
+pub mod templating;
+pub mod tuning;
+mod watchdog;
+pub mod zenoh_admin;
+
+use config_history::ConfigHistory;
+use error::SandboxError;
+use events::{RuntimeEvent, RuntimeEvents};
+use ipc::{IpcListener, PlatformListener};
+use journal::Journal;
+use logs::{LogEntry, LogFilter, LogStorage};
+use orphans::Orphans;
+use presets::ConfigPresets;
+use reports::OperationResults;
+use sandbox_tracing::SandboxTracing;
+use scenarios::Scenarios;
+use session::SessionAutosave;
+use testing::Testing;
+use timeline::Timeline;
+use topology::Topology;
+
+use crate::ts::{
+    config::{ConfigCommitResult, ConfigEditSession, ZenohConfigEdit, ZenohConfigJson},
+    log::{LogEntryLevel, LogPage, LogSearch, LogStats},
+    report::TopologyLink,
+    runtime::{
+        PeerDivergence, ProcessMetrics, ProcessPrivileges, RemoteApiInfo, RuntimeCrashedEvent,
+        RuntimeDeclaredEvent, RuntimeInfo, RuntimeOptions, RuntimeSelector, RuntimeStartedEvent,
+        RuntimeState, RuntimeStats, RuntimeStatus, RuntimeStoppedEvent, RuntimeSummary,
+        SandboxMetrics, WatchdogStatus,
+    },
+    scouting::DiscoveredPeer,
+    storage::{StorageLocations, StorageRelocation},
+    testing::LivelinessEvent,
+    timeline::{ConfigChangeSource, TimelineEvent, TimelineEventKind},
+};
 
 // ============================================================================
 // State management for Zenoh runtimes
@@ -37,10 +115,70 @@ use crate::ts::{config::{ZenohConfigEdit, ZenohConfigJson}, log::LogEntryLevel};
 /// Runtime ID type - used as primary identifier for runtimes
 pub type RuntimeId = u32;
 
+/// Reserved RuntimeId under which the sandbox's own logs are stored
+pub const SANDBOX_RUNTIME_ID: RuntimeId = RuntimeId::MAX;
+
+/// First port allocated to the remote_api plugin of runtimes, unless configured otherwise
+pub const FIRST_ALLOCATED_PORT: u16 = 10000;
+
+/// Environment variable selecting the port allocation: `os`, or a range such as `10000-10999`
+pub const PORT_ALLOCATION_ENV: &str = "ZENOH_SANDBOX_PORTS";
+
+/// Number of ports requested from the OS before giving up on finding one not already allocated
+const OS_PORT_ATTEMPTS: usize = 16;
+
+/// Number of ports of the range picked at once to be probed
+const PORT_PROBE_BATCH: usize = 16;
+
+/// Time for the remote_api port of a runtime to accept a connection when probed
+const REMOTE_API_PROBE_TIMEOUT: Duration = Duration::from_millis(500);
+
 /// Request type for communication with the runtime background task
 enum RuntimeRequest {
     /// Request to get the config, with a oneshot channel for the response
     GetConfig(oneshot::Sender<Config>),
+    /// Request to apply (key, JSON5 value) pairs to the running config
+    UpdateConfig(Vec<(String, String)>, oneshot::Sender<Result<Config, String>>),
+    /// Request to measure round-trip times (in microseconds) to another runtime
+    MeasureLatency {
+        target: ZenohId,
+        samples: u32,
+        timeout: std::time::Duration,
+        payload_size: usize,
+        response_tx: oneshot::Sender<Result<Vec<u64>, String>>,
+    },
+    /// Request to declare a test entity with the given id
+    DeclareTestEntity {
+        id: u64,
+        entity: TestEntity,
+        response_tx: oneshot::Sender<Result<(), String>>,
+    },
+    /// Request to undeclare a test entity, without response
+    UndeclareTestEntity(u64),
+    /// Request to run a `get` on a selector and collect all replies
+    Query {
+        selector: String,
+        timeout: std::time::Duration,
+        response_tx: oneshot::Sender<Result<Vec<QueryReply>, String>>,
+    },
+    /// Request to open a transport to another runtime
+    Connect {
+        target: ZenohId,
+        locators: Vec<String>,
+        response_tx: oneshot::Sender<Result<(), String>>,
+    },
+    /// Request to put the bytes of a file, with the number of bytes published as response
+    PublishFile {
+        keyexpr: String,
+        path: PathBuf,
+        encoding: Option<String>,
+        response_tx: oneshot::Sender<Result<usize, String>>,
+    },
+    /// Request to scout for peers and routers during `timeout`
+    Scout {
+        timeout: std::time::Duration,
+        response_tx: oneshot::Sender<Result<Vec<DiscoveredPeer>, String>>,
+    },
     /// Request to stop the runtime
     Stop(oneshot::Sender<()>),
 }
@@ -50,6 +188,51 @@ enum RuntimeRequest {
 struct DeclareRuntimeResponse {
     runtime_id: RuntimeId,
     ws_port: u16,
+    /// Port of the REST plugin, if enabled
+    rest_port: Option<u16>,
+}
+
+/// Ports allocated to the plugins of a runtime
+#[derive(Debug, Clone, Copy)]
+struct RuntimePorts {
+    /// Port of the remote_api plugin
+    websocket: u16,
+    /// Port of the REST plugin, if enabled
+    rest: Option<u16>,
+}
+
+/// Runtime process being started, killed and unregistered from the orphan records
+/// if the startup fails on any path
+struct StartingProcess {
+    child: Option<Child>,
+    pid_file: Option<PathBuf>,
+}
+
+impl StartingProcess {
+    fn new(child: Child, pid_file: Option<PathBuf>) -> Self {
+        Self {
+            child: Some(child),
+            pid_file,
+        }
+    }
+
+    /// The process once started, no longer cleaned up
+    fn started(mut self) -> (Child, Option<PathBuf>) {
+        let child = self.child.take().expect("child kept until started");
+        (child, self.pid_file.take())
+    }
+}
+
+impl Drop for StartingProcess {
+    fn drop(&mut self) {
+        // The process is reaped by tokio once the child is dropped
+        if let Some(child) = &mut self.child {
+            let _ = child.start_kill();
+        }
+        if let Some(pid_file) = &self.pid_file {
+            orphans::unregister(pid_file);
+        }
+    }
 }
 
 /// Information about a running runtime process
@@ -58,14 +241,79 @@ struct RuntimeProcess {
     zenoh_id: Option<ZenohId>,
     /// The original sandbox configuration
     sandbox_config: ZenohConfigJson,
-    /// The child process handle
-    child: Option<Child>,
+    /// JSON5 text of the declared configuration, with its comments, if it was given.
+    /// Cleared when the declared configuration changes without a new text.
+    config_source: Option<String>,
+    /// Revisions of the declared and live configuration
+    config_history: ConfigHistory,
+    /// Declared configuration the last runtime process started with
+    last_run_config: Option<ZenohConfigJson>,
+    /// PID of the runtime process
+    pid: Option<u32>,
+    /// Task waiting for the runtime process to exit
+    exit_watcher: Option<JoinHandle<()>>,
+    /// Channel asking the exit watcher to kill the runtime process
+    kill_tx: Option<oneshot::Sender<()>>,
+    /// Exit code of the last runtime process
+    exit_code: Option<i32>,
     /// Task handle for log receiving and request handling
     receiver_task: Option<JoinHandle<()>>,
     /// Channel to send requests to the receiver task
     request_tx: Option<mpsc::Sender<RuntimeRequest>>,
     /// The allocated port for remote_api
     allocated_port: u16,
+    /// The allocated port for the REST plugin, if enabled in the options
+    rest_port: Option<u16>,
+    /// Options given when the runtime was declared
+    options: RuntimeOptions,
+    /// User and umask validated from the options
+    privileges: ProcessPrivileges,
+    /// Current lifecycle state
+    state: RuntimeState,
+    /// When the runtime process was started
+    started_at: Option<Instant>,
+    /// Last observed difference between expected and actual peers
+    peer_divergence: Option<PeerDivergence>,
+    /// Messages from the runtime process truncated or dropped for exceeding the maximum size
+    truncated_messages: Arc<AtomicU64>,
+    /// Log entries dropped by the runtime process, as last reported by it
+    dropped_logs: Arc<AtomicU64>,
+    /// Latest metrics reported by the runtime process
+    stats: Arc<ParkingLotMutex<Option<RuntimeStats>>>,
+    /// Free-form notes about the runtime, in Markdown
+    notes: String,
+    /// When the last message from the runtime process was received
+    last_message_at: Arc<ParkingLotMutex<Instant>>,
+    /// Number of consecutive keepalive pings left unanswered by the runtime process
+    missed_pings: Arc<AtomicU32>,
+    /// Signals observed by the watchdog
+    watchdog: WatchdogStatus,
+    /// Record of the runtime process, removed when it exits
+    pid_file: Option<PathBuf>,
+    /// Number of consecutive automatic restarts after unexpected exits
+    restart_attempts: u32,
+    /// Ports allocated to the TCP listen endpoints declared with port 0 or `${AUTO}`
+    listen_ports: Vec<u16>,
+    /// Ports reserved for the fixed listen endpoints of a cloned runtime
+    reserved_ports: Vec<u16>,
+    /// Task checking the memory of the runtime process against the limit of the options
+    memory_monitor: Option<JoinHandle<()>>,
+    /// File receiving the stdout of the last runtime process
+    stdout_log: Option<PathBuf>,
+    /// File receiving the stderr of the last runtime process
+    stderr_log: Option<PathBuf>,
+    /// IPC endpoint the last runtime process connected to
+    socket_path: Option<String>,
+}
+
+impl RuntimeProcess {
+    /// Release the ports allocated or reserved for the runtime, once it is removed.
+    /// Its process must have exited, so that the ports are no longer bound.
+    fn release_ports(&self, port_tracker: &mut HashSet<u16>) {
+        for port in endpoints::allocated_ports(self) {
+            port_tracker.remove(&port);
+        }
+    }
 }
 
 /// Holds all active Zenoh runtime processes
@@ -75,14 +323,103 @@ pub struct ZenohRuntimes {
     next_runtime_id: RwLock<RuntimeId>,
     /// Port tracker for ensuring unique port assignments
     port_tracker: RwLock<HashSet<u16>>,
-    /// Directory for UDS sockets
+    /// How ports are allocated
+    ports: PortAllocation,
+    /// Directories for files of runtime processes, switchable with `relocate_storage`
+    dirs: ParkingLotRwLock<StorageDirs>,
+    /// Links wired with `connect_runtimes`, from the connecting runtime to its target
+    links: RwLock<BTreeSet<TopologyLink>>,
+    /// Groups created with `create_group`, by name, with their runtimes
+    groups: RwLock<BTreeMap<String, BTreeSet<RuntimeId>>>,
+    /// Channel of the lifecycle events of the runtimes
+    events: RuntimeEvents,
+}
+
+/// How ports are allocated to the remote_api plugin of runtimes.
+/// In both modes, ports already in use on the system are skipped.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PortAllocation {
+    /// Lowest free port of the range, bounds included
+    Range { first: u16, last: u16 },
+    /// Free port assigned by the OS
+    Os,
+}
+
+impl Default for PortAllocation {
+    fn default() -> Self {
+        Self::Range {
+            first: FIRST_ALLOCATED_PORT,
+            last: u16::MAX,
+        }
+    }
+}
+
+impl FromStr for PortAllocation {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.trim().eq_ignore_ascii_case("os") {
+            return Ok(Self::Os);
+        }
+        let invalid = || {
+            format!(
+                "Invalid port allocation {:?}: expected 'os' or a range such as '10000-10999'",
+                s
+            )
+        };
+        let (first, last) = s.trim().split_once('-').ok_or_else(invalid)?;
+        let first: u16 = first.trim().parse().map_err(|_| invalid())?;
+        let last: u16 = last.trim().parse().map_err(|_| invalid())?;
+        if first == 0 || first > last {
+            return Err(invalid());
+        }
+        Ok(Self::Range { first, last })
+    }
+}
+
+/// Whether a port can be listened on by a runtime
+fn is_port_free(port: u16) -> bool {
+    std::net::TcpListener::bind((std::net::Ipv4Addr::UNSPECIFIED, port)).is_ok()
+}
+
+/// First port of `candidates` that can be listened on, probed on a blocking thread
+async fn first_free_port(candidates: Vec<u16>) -> Option<u16> {
+    tokio::task::spawn_blocking(move || candidates.into_iter().find(|port| is_port_free(*port)))
+        .await
+        .ok()
+        .flatten()
+}
+
+/// Free port assigned by the OS, requested on a blocking thread
+async fn os_assigned_port() -> Option<u16> {
+    tokio::task::spawn_blocking(|| {
+        let listener = std::net::TcpListener::bind((std::net::Ipv4Addr::UNSPECIFIED, 0)).ok()?;
+        Some(listener.local_addr().ok()?.port())
+    })
+    .await
+    .ok()
+    .flatten()
+}
+
+/// Check whether a local port accepts a connection within `timeout`
+async fn is_port_listening(port: u16, timeout: Duration) -> bool {
+    matches!(
+        tokio::time::timeout(timeout, tokio::net::TcpStream::connect(("127.0.0.1", port))).await,
+        Ok(Ok(_))
+    )
+}
+
+/// Directories for files of runtime processes
+#[derive(Clone)]
+struct StorageDirs {
+    /// Directory for IPC endpoints (UDS sockets on Unix)
     socket_dir: PathBuf,
     /// Directory for runtime logs
     log_dir: PathBuf,
 }
 
 impl ZenohRuntimes {
-    pub fn new(socket_dir: PathBuf, log_dir: PathBuf) -> Self {
+    pub fn new(socket_dir: PathBuf, log_dir: PathBuf, ports: PortAllocation) -> Self {
         // Ensure socket directory exists
         std::fs::create_dir_all(&socket_dir).ok();
 
@@ -93,11 +430,23 @@ impl ZenohRuntimes {
             runtimes: RwLock::new(HashMap::new()),
             next_runtime_id: RwLock::new(0),
             port_tracker: RwLock::new(HashSet::new()),
-            socket_dir,
-            log_dir,
+            ports,
+            dirs: ParkingLotRwLock::new(StorageDirs {
+                socket_dir,
+                log_dir,
+            }),
+            links: RwLock::new(BTreeSet::new()),
+            groups: RwLock::new(BTreeMap::new()),
+            events: RuntimeEvents::default(),
         }
     }
 
+    /// Publish the lifecycle events of the runtimes on `events`
+    pub fn with_events(mut self, events: RuntimeEvents) -> Self {
+        self.events = events;
+        self
+    }
+
     /// Allocate a new runtime ID
     pub async fn allocate_runtime_id(&self) -> RuntimeId {
         let mut next_id = self.next_runtime_id.write().await;
@@ -106,16 +455,60 @@ impl ZenohRuntimes {
         id
     }
 
-    /// Allocate a free port
-    /// Returns the next available port starting from 10000
-    pub async fn allocate_port(&self) -> u16 {
-        let mut tracker = self.port_tracker.write().await;
-        let mut port = 10000;
-        while tracker.contains(&port) {
-            port += 1;
+    /// Allocate a port that is neither allocated to another runtime nor in use on the system.
+    /// Candidates are picked under the port tracker lock but probed outside it, so that
+    /// allocations don't wait for each other's probes. A probed port is only allocated if no
+    /// other allocation took it in the meantime.
+    pub async fn allocate_port(&self) -> Result<u16, String> {
+        match self.ports {
+            PortAllocation::Range { first, last } => {
+                let mut next = Some(first);
+                while let Some(from) = next.filter(|from| *from <= last) {
+                    let candidates: Vec<u16> = {
+                        let tracker = self.port_tracker.read().await;
+                        (from..=last)
+                            .filter(|port| !tracker.contains(port))
+                            .take(PORT_PROBE_BATCH)
+                            .collect()
+                    };
+                    let Some(&last_candidate) = candidates.last() else {
+                        break;
+                    };
+                    next = match first_free_port(candidates).await {
+                        Some(port) => {
+                            if self.port_tracker.write().await.insert(port) {
+                                return Ok(port);
+                            }
+                            port.checked_add(1)
+                        }
+                        None => last_candidate.checked_add(1),
+                    };
+                }
+                Err(format!("No free port in range {}-{}", first, last))
+            }
+            PortAllocation::Os => {
+                for _ in 0..OS_PORT_ATTEMPTS {
+                    if let Some(port) = os_assigned_port().await
+                        && self.port_tracker.write().await.insert(port)
+                    {
+                        return Ok(port);
+                    }
+                }
+                Err("Failed to get a free port from the OS".to_string())
+            }
         }
-        tracker.insert(port);
-        port
+    }
+
+    /// Allocate `preferred` if it is free, otherwise another port
+    pub async fn allocate_preferred_port(&self, preferred: u16) -> Result<u16, String> {
+        let tracked = self.port_tracker.read().await.contains(&preferred);
+        if !tracked
+            && first_free_port(vec![preferred]).await.is_some()
+            && self.port_tracker.write().await.insert(preferred)
+        {
+            return Ok(preferred);
+        }
+        self.allocate_port().await
     }
 
     /// Release a port back to the pool
@@ -123,6 +516,15 @@ impl ZenohRuntimes {
         let mut tracker = self.port_tracker.write().await;
         tracker.remove(&port);
     }
+
+    /// Set the lifecycle state of a runtime
+    async fn set_state(&self, runtime_id: RuntimeId, state: RuntimeState) {
+        let mut runtimes = self.runtimes.write().await;
+        if let Some(runtime_process) = runtimes.get_mut(&runtime_id) {
+            tracing::debug!(runtime_id, "Runtime state: {:?} -> {:?}", runtime_process.state, state);
+            runtime_process.state = state;
+        }
+    }
 }
 
 impl Default for ZenohRuntimes {
@@ -134,158 +536,26 @@ impl Default for ZenohRuntimes {
             .map(PathBuf::from)
             .unwrap_or_else(|_| std::env::temp_dir().join("zenoh_sandbox/logs"));
 
-        Self::new(socket_dir, log_dir)
+        // Use environment variable if set, otherwise default to the range from FIRST_ALLOCATED_PORT
+        let ports = match std::env::var(PORT_ALLOCATION_ENV) {
+            Ok(value) => value.parse().unwrap_or_else(|e| {
+                tracing::warn!("{}, using the default port range", e);
+                PortAllocation::default()
+            }),
+            Err(_) => PortAllocation::default(),
+        };
+
+        Self::new(socket_dir, log_dir, ports)
     }
 }
 
 // ============================================================================
-// Tauri commands
+// Runtime lifecycle
 // ============================================================================
 
-/// Create a new validated Zenoh config from edit content
-#[tauri::command]
-async fn create_zenoh_config(
-    edit: ZenohConfigEdit,
-) -> Result<(ZenohConfigEdit, ZenohConfigJson), String> {
-    let config = edit.to_config()?;
-
-    let config_json = serde_json::to_value(&config)
-        .map_err(|e| format!("Failed to serialize config: {}", e))?;
-    let validated = ZenohConfigJson::from_json(config_json)?;
-
-    Ok((edit, validated))
-}
-
-/// Get the default configuration as JSON string
-#[tauri::command]
-async fn get_default_config_json() -> Result<String, String> {
-    let config = zenoh::config::Config::default();
-
-    serde_json::to_string_pretty(&config)
-        .map_err(|e| format!("Failed to serialize default config: {}", e))
-}
-
-/// Validate JSON string as zenoh config and return validated JSON
-#[tauri::command]
-async fn validate_config(content: String) -> Result<ZenohConfigJson, String> {
-    let config = zenoh::Config::from_json5(&content).map_err(|e| {
-        format!("Invalid JSON5 config: {}", e)
-    })?;
-    let config_json = serde_json::to_value(&config)
-        .map_err(|e| format!("Failed to serialize config: {}", e))?;
-    ZenohConfigJson::from_json(config_json)
-}
-
-/// Compute the difference between two JSON configurations.
-/// Returns a JSON object containing only fields that differ from base.
-/// Deleted fields are represented as null.
-#[tauri::command]
-async fn compute_config_diff(
-    base: ZenohConfigJson,
-    modified: ZenohConfigJson,
-) -> Result<serde_json::Value, String> {
-    let diff = ts::config::json_diff(base.as_json(), modified.as_json());
-    Ok(diff)
-}
-
-/// Declare a new runtime with the given config, allocating resources but not starting it yet.
-/// Returns the RuntimeId that can be used to start the runtime.
-#[tauri::command]
-async fn declare_runtime(
-    config: ZenohConfigJson,
-    runtimes_state: State<'_, ZenohRuntimes>,
-) -> Result<DeclareRuntimeResponse, String> {
-    // Allocate runtime ID
-    let runtime_id = runtimes_state.allocate_runtime_id().await;
-
-    // Allocate port
-    let port = runtimes_state.allocate_port().await;
-
-    // Create runtime entry with uninitialized fields
-    let runtime_process = RuntimeProcess {
-        zenoh_id: None,
-        sandbox_config: config,
-        child: None,
-        receiver_task: None,
-        request_tx: None,
-        allocated_port: port,
-    };
-
-    // Store in state
-    let mut runtimes = runtimes_state.runtimes.write().await;
-    runtimes.insert(runtime_id, runtime_process);
-
-    Ok(DeclareRuntimeResponse {
-        runtime_id,
-        ws_port: port,
-    })
-}
-
-/// Start a previously declared runtime.
-/// Returns the ZenohId string.
-#[tauri::command]
-async fn start_runtime(
-    runtime_id: RuntimeId,
-    runtimes_state: State<'_, ZenohRuntimes>,
-    logs_state: State<'_, LogStorage>,
-) -> Result<String, String> {
-    // Get the runtime process and config
-    let (config, port) = {
-        let runtimes = runtimes_state.runtimes.read().await;
-        let runtime_process = runtimes
-            .get(&runtime_id)
-            .ok_or_else(|| format!("Runtime {} not found", runtime_id))?;
-        (runtime_process.sandbox_config.clone(), runtime_process.allocated_port)
-    };
-
-    eprintln!(
-        "🔵 start_runtime called for runtime_id={} with config: port={:?}",
-        runtime_id,
-        config.get_websocket_port()
-    );
-
-    // Convert ZenohConfigJson to zenoh::Config
-    let mut zenoh_config: zenoh::config::Config = config.try_into()?;
-
-    // Apply runtime-specific config modifications (not visible to GUI)
-    // Enable adminspace
-    zenoh_config
-        .adminspace
-        .set_enabled(true)
-        .map_err(|e| format!("Failed to enable adminspace: {e}"))?;
-
-    // Enable plugins loading
-    zenoh_config
-        .plugins_loading
-        .set_enabled(true)
-        .map_err(|e| format!("Failed to enable plugins loading: {e}"))?;
-
-    // Add remote_api plugin configuration
-    zenoh_config
-        .insert_json5("plugins/remote_api", "{}")
-        .map_err(|e| format!("Failed to add remote_api plugin config: {e}"))?;
-
-    // Set websocket_port for remote_api
-    zenoh_config
-        .insert_json5(
-            "plugins/remote_api/websocket_port",
-            &format!(r#""{}""#, port),
-        )
-        .map_err(|e| format!("Failed to set websocket_port: {e}"))?;
-
-    // Create a unique socket path with short name to avoid SUN_LEN limit
-    // Use a short random suffix instead of full UUID
-    let random_id: u32 = rand::random();
-    let socket_path = runtimes_state
-        .socket_dir
-        .join(format!("z{:x}.sock", random_id));
-
-    // Create UDS listener
-    let listener = UnixListener::bind(&socket_path)
-        .map_err(|e| format!("Failed to create UDS listener: {}", e))?;
-
-    // Get the path to the runtime binary
-    let runtime_binary = std::env::current_exe()
+/// Path of the runtime binary, next to the sandbox executable
+pub(crate) fn runtime_binary_path() -> Result<PathBuf, String> {
+    Ok(std::env::current_exe()
         .map_err(|e| format!("Failed to get current exe path: {}", e))?
         .parent()
         .ok_or_else(|| "Failed to get parent directory".to_string())?
@@ -293,375 +563,2326 @@ async fn start_runtime(
             "zenoh_runtime.exe"
         } else {
             "zenoh_runtime"
-        });
+        }))
+}
 
-    // Check if binary exists
-    if !runtime_binary.exists() {
-        return Err(format!(
-            "Runtime binary not found at: {}. Did you run 'cargo build --bins'?",
-            runtime_binary.display()
-        ));
-    }
+/// Time given to the runtime processes to stop when the app exits, before killing them
+const STOP_ALL_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(5);
 
-    eprintln!("Starting runtime binary: {}", runtime_binary.display());
+/// Time for a runtime process to connect to the sandbox once spawned, unless set in its options
+const DEFAULT_START_TIMEOUT: Duration = Duration::from_secs(10);
 
-    // Use the log_dir from state (already created in ZenohRuntimes::new)
-    let log_dir = &runtimes_state.log_dir;
+/// Time for a runtime process to acknowledge the Stop request and exit before it is killed,
+/// unless set in its options
+const DEFAULT_STOP_TIMEOUT: Duration = Duration::from_secs(7);
 
-    // Create log files for stdout and stderr
-    let log_prefix = format!("z{:x}", random_id);
-    let stdout_log = log_dir.join(format!("{}-stdout.log", log_prefix));
-    let stderr_log = log_dir.join(format!("{}-stderr.log", log_prefix));
+/// Time for a connected runtime process to send its `Hello`
+const HANDSHAKE_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(5);
 
-    let stdout_file = OpenOptions::new()
-        .create(true)
-        .append(true)
-        .open(&stdout_log)
-        .map_err(|e| {
-            format!(
-                "Failed to create stdout log file {}: {}",
-                stdout_log.display(),
-                e
-            )
-        })?;
+/// Time for the exit watcher to notice a process exit before a closed IPC connection
+/// is considered a disconnection
+const DISCONNECT_GRACE_PERIOD: std::time::Duration = std::time::Duration::from_secs(1);
 
-    let stderr_file = OpenOptions::new()
-        .create(true)
-        .append(true)
-        .open(&stderr_log)
-        .map_err(|e| {
-            format!(
-                "Failed to create stderr log file {}: {}",
-                stderr_log.display(),
-                e
-            )
-        })?;
-
-    // Spawn the runtime process
-    let mut child = tokio::process::Command::new(&runtime_binary)
-        .arg(socket_path.to_string_lossy().to_string())
-        .stdout(Stdio::from(stdout_file))
-        .stderr(Stdio::from(stderr_file))
-        .spawn()
-        .map_err(|e| {
-            format!(
-                "Failed to spawn runtime process: {} (path: {})",
-                e,
-                runtime_binary.display()
-            )
-        })?;
+/// Check the environment variables and `RUST_LOG` directive of runtime options
+fn validate_env(options: &RuntimeOptions) -> Result<(), String> {
+    for name in options.env.keys() {
+        if name.is_empty() || name.contains(['=', '\0']) {
+            return Err(format!("Invalid environment variable name '{}'", name));
+        }
+    }
+    if let Some(rust_log) = &options.rust_log {
+        tracing_subscriber::EnvFilter::try_new(rust_log)
+            .map_err(|e| format!("Invalid RUST_LOG directive '{}': {}", rust_log, e))?;
+    }
+    Ok(())
+}
 
-    eprintln!("Runtime process spawned with PID: {:?}", child.id());
-    eprintln!(
-        "Logs:\n{}\n{}\n",
-        stdout_log.display(),
-        stderr_log.display()
-    );
+impl ZenohRuntimes {
+    /// Declare a new runtime, allocating its RuntimeId and port but not starting it.
+    /// The `preferred_port` is used if it is free.
+    /// Returns the RuntimeId and the allocated port.
+    async fn declare(
+        &self,
+        config: ZenohConfigJson,
+        options: RuntimeOptions,
+        preferred_port: Option<u16>,
+    ) -> Result<(RuntimeId, u16), String> {
+        let declared = self
+            .declare_unpublished(config, options, preferred_port)
+            .await?;
+        self.publish_declared(declared.0).await;
+        Ok(declared)
+    }
 
-    // Accept connection from the runtime process
-    eprintln!("Waiting for runtime to connect...");
-    let (socket, _) = tokio::time::timeout(std::time::Duration::from_secs(10), listener.accept())
-        .await
-        .map_err(|_| {
-            drop(child.kill());
-            "Timeout waiting for runtime to connect (10s). Check stderr output.".to_string()
-        })?
-        .map_err(|e| {
-            drop(child.kill());
-            format!("Failed to accept connection: {}", e)
-        })?;
-
-    eprintln!("Runtime connected successfully");
-
-    // Send Start message with zenoh::Config
-    eprintln!("📤 Sending start message to runtime...");
-    let start_msg = MainToRuntime::Start(Box::new(zenoh_config.clone()));
-    let msg_json = serde_json::to_string(&start_msg)
-        .map_err(|e| format!("Failed to serialize start message: {}", e))?;
-
-    let (reader, mut writer) = socket.into_split();
-    writer
-        .write_all(format!("{}\n", msg_json).as_bytes())
-        .await
-        .map_err(|e| format!("Failed to send start message: {}", e))?;
-    writer
-        .flush()
-        .await
-        .map_err(|e| format!("Failed to flush socket: {}", e))?;
-    eprintln!("📤 Start message sent");
-
-    // Receive Started response (may receive Log messages first)
-    eprintln!("📥 Waiting for runtime response...");
-    let mut reader = BufReader::new(reader);
-    let mut line = String::new();
-
-    let logs_storage = logs_state.inner().clone();
-    let zid = loop {
-        line.clear();
-        reader
-            .read_line(&mut line)
+    /// Declare a new runtime like `declare`, without publishing the `runtime-declared`
+    /// event, for callers that still adjust the declared config
+    async fn declare_unpublished(
+        &self,
+        config: ZenohConfigJson,
+        options: RuntimeOptions,
+        preferred_port: Option<u16>,
+    ) -> Result<(RuntimeId, u16), String> {
+        let privileges = privileges::resolve(&options)?;
+        storages::validate(&options.storages)?;
+        limits::validate(&options)?;
+        validate_env(&options)?;
+
+        // Allocate port
+        let port = match preferred_port {
+            Some(port) => self.allocate_preferred_port(port).await?,
+            None => self.allocate_port().await?,
+        };
+
+        let rest_port = if options.rest {
+            match self.allocate_port().await {
+                Ok(rest_port) => Some(rest_port),
+                Err(e) => {
+                    self.release_port(port).await;
+                    return Err(e);
+                }
+            }
+        } else {
+            None
+        };
+
+        // Allocate runtime ID
+        let runtime_id = self.allocate_runtime_id().await;
+
+        // Create runtime entry with uninitialized fields
+        let runtime_process = RuntimeProcess {
+            zenoh_id: None,
+            config_source: None,
+            config_history: ConfigHistory::new(&config),
+            sandbox_config: config,
+            last_run_config: None,
+            pid: None,
+            exit_watcher: None,
+            kill_tx: None,
+            exit_code: None,
+            receiver_task: None,
+            request_tx: None,
+            allocated_port: port,
+            rest_port,
+            options,
+            privileges,
+            state: RuntimeState::Declared,
+            started_at: None,
+            peer_divergence: None,
+            truncated_messages: Arc::default(),
+            dropped_logs: Arc::default(),
+            stats: Arc::default(),
+            notes: String::new(),
+            last_message_at: Arc::new(ParkingLotMutex::new(Instant::now())),
+            missed_pings: Arc::new(AtomicU32::new(0)),
+            watchdog: WatchdogStatus::default(),
+            pid_file: None,
+            restart_attempts: 0,
+            listen_ports: Vec::new(),
+            reserved_ports: Vec::new(),
+            memory_monitor: None,
+            stdout_log: None,
+            stderr_log: None,
+            socket_path: None,
+        };
+
+        // Store in state
+        self.runtimes
+            .write()
             .await
-            .map_err(|e| format!("Failed to read response: {}", e))?;
-        eprintln!("📥 Got response: {}", line.trim());
+            .insert(runtime_id, runtime_process);
 
-        let response: RuntimeToMain =
-            serde_json::from_str(&line).map_err(|e| format!("Failed to parse response: {}", e))?;
+        Ok((runtime_id, port))
+    }
 
-        match response {
-            RuntimeToMain::Started(zid_str) => {
-                let parsed_zid = ZenohId::from_str(&zid_str).map_err(|e| format!("Invalid ZenohId: {}", e))?;
-                eprintln!("✅ Parsed ZenohId: {}", parsed_zid);
-                break parsed_zid;
-            }
-            RuntimeToMain::StartError(err) => {
-                // Kill the child process
-                let _ = child.kill().await;
-                return Err(err);
+    /// Publish the `runtime-declared` event of a runtime, with its declared config
+    async fn publish_declared(&self, runtime_id: RuntimeId) {
+        let Some(mode) = self
+            .runtimes
+            .read()
+            .await
+            .get(&runtime_id)
+            .map(|runtime_process| runtime_process.sandbox_config.mode())
+        else {
+            return;
+        };
+        self.events
+            .publish(RuntimeEvent::Declared(RuntimeDeclaredEvent {
+                runtime_id,
+                mode,
+            }));
+    }
+
+    /// Keep the JSON5 text of the declared config of a runtime, once checked to describe it
+    async fn set_config_source(
+        &self,
+        runtime_id: RuntimeId,
+        source: Option<String>,
+    ) -> Result<(), String> {
+        let mut runtimes = self.runtimes.write().await;
+        let runtime_process = runtimes
+            .get_mut(&runtime_id)
+            .ok_or(SandboxError::RuntimeNotFound(runtime_id))?;
+        runtime_process.config_source =
+            ts::config::check_config_source(source, &runtime_process.sandbox_config)?;
+        Ok(())
+    }
+
+    /// Spawn the runtime process of a declared runtime and wait until it reports its ZenohId.
+    async fn launch(
+        &self,
+        app: &AppHandle,
+        runtime_id: RuntimeId,
+    ) -> Result<ZenohId, SandboxError> {
+        // Get the runtime process and config, moving it to the Starting state
+        let (config, ports, options, privileges) = {
+            let mut runtimes = self.runtimes.write().await;
+            let runtime_process = runtimes
+                .get_mut(&runtime_id)
+                .ok_or(SandboxError::RuntimeNotFound(runtime_id))?;
+            if runtime_process.state.is_active() {
+                return Err(SandboxError::Other(format!(
+                    "Runtime {} is already {:?}",
+                    runtime_id, runtime_process.state
+                )));
             }
-            RuntimeToMain::Log(entry) => {
-                // Handle logs during startup - store them
-                eprintln!("📝 Received log during startup: {:?}", entry.message);
-                logs_storage.add_log(runtime_id, entry);
-                // Continue waiting for Started message
+            runtime_process.state = RuntimeState::Starting;
+            (
+                runtime_process.sandbox_config.clone(),
+                RuntimePorts {
+                    websocket: runtime_process.allocated_port,
+                    rest: runtime_process.rest_port,
+                },
+                runtime_process.options.clone(),
+                runtime_process.privileges.clone(),
+            )
+        };
+
+        let result = match self.start_config(app, runtime_id, config, ports).await {
+            Ok(config) => {
+                self.spawn_process(app, runtime_id, config, ports, options, privileges).await
             }
-            _ => {
-                let _ = child.kill().await;
-                return Err("Unexpected response from runtime".to_string());
+            Err(e) => Err(e),
+        };
+        match &result {
+            Ok(zid) => {
+                self.annotate_restart_config(app, runtime_id).await;
+                self.events
+                    .publish(RuntimeEvent::Started(RuntimeStartedEvent {
+                        runtime_id,
+                        zenoh_id: zid.to_string(),
+                    }));
             }
+            Err(_) => self.set_state(runtime_id, RuntimeState::Stopped).await,
         }
-    };
+        result
+    }
 
-    // Spawn log receiver task (reader continues to receive logs)
-    // This task also handles config requests
-    eprintln!("🔧 Setting up receiver task...");
-    let logs_storage_clone = logs_storage.clone();
-    let runtime_id_clone = runtime_id;
-
-    // Create channel for sending requests to the receiver task
-    let (request_tx, mut request_rx) = mpsc::channel::<RuntimeRequest>(16);
-
-    eprintln!("🚀 Spawning receiver task...");
-    let receiver_task = tokio::spawn(async move {
-        let mut line = String::new();
-        // Track pending config request
-        let mut pending_config_request: Option<oneshot::Sender<Config>> = None;
-
-        loop {
-            tokio::select! {
-                // Handle incoming messages from runtime
-                read_result = reader.read_line(&mut line) => {
-                    match read_result {
-                        Ok(0) => break, // Socket closed
-                        Ok(_) => {
-                            if let Ok(msg) = serde_json::from_str::<RuntimeToMain>(&line) {
-                                match msg {
-                                    RuntimeToMain::Log(entry) => {
-                                        logs_storage_clone.add_log(runtime_id_clone, entry);
-                                    }
-                                    RuntimeToMain::Config(config) => {
-                                        // Send response to pending request
-                                        if let Some(tx) = pending_config_request.take() {
-                                            let _ = tx.send(*config);
-                                        }
-                                    }
-                                    _ => {}
-                                }
-                            }
-                            line.clear();
-                        }
-                        Err(_) => break,
-                    }
-                }
-                // Handle requests from main thread
-                Some(request) = request_rx.recv() => {
-                    match request {
-                        RuntimeRequest::GetConfig(response_tx) => {
-                            // Send GetConfig request to runtime
-                            let msg = MainToRuntime::GetConfig;
-                            if let Ok(json) = serde_json::to_string(&msg)
-                                && writer.write_all(format!("{json}\n").as_bytes()).await.is_ok()
-                            {
-                                let _ = writer.flush().await;
-                                pending_config_request = Some(response_tx);
-                            }
-                        }
-                        RuntimeRequest::Stop(response_tx) => {
-                            // Send Stop request to runtime
-                            let msg = MainToRuntime::Stop;
-                            if let Ok(json) = serde_json::to_string(&msg) {
-                                let _ = writer.write_all(format!("{json}\n").as_bytes()).await;
-                                let _ = writer.flush().await;
-                            }
-                            let _ = response_tx.send(());
-                            break;
-                        }
-                    }
-                }
-            }
+    /// Record the declared config a runtime process started with, and annotate the change
+    /// in the timeline if the runtime was restarted with a config differing from its last run
+    async fn annotate_restart_config(&self, app: &AppHandle, runtime_id: RuntimeId) {
+        let change = {
+            let mut runtimes = self.runtimes.write().await;
+            let Some(runtime_process) = runtimes.get_mut(&runtime_id) else {
+                return;
+            };
+            let config = runtime_process.sandbox_config.clone();
+            runtime_process
+                .last_run_config
+                .replace(config.clone())
+                .filter(|last_run_config| last_run_config.etag() != config.etag())
+                .map(|last_run_config| (last_run_config, config))
+        };
+        if let Some((last_run_config, config)) = change {
+            app.state::<Timeline>().annotate_config_change(
+                runtime_id,
+                ConfigChangeSource::Restart,
+                &last_run_config,
+                &config,
+                Some(app.state::<LogStorage>().inner()),
+            );
         }
-    });
+    }
 
-    // Update the runtime process with the started runtime details
-    eprintln!("🔷 About to acquire write lock for runtime_id: {}", runtime_id);
-    {
-        let mut runtimes = runtimes_state.runtimes.write().await;
-        eprintln!("🔷 Write lock acquired for runtime_id: {}", runtime_id);
-        if let Some(runtime_process) = runtimes.get_mut(&runtime_id) {
-            runtime_process.zenoh_id = Some(zid);
-            runtime_process.child = Some(child);
-            runtime_process.receiver_task = Some(receiver_task);
-            runtime_process.request_tx = Some(request_tx);
-        } else {
-            return Err(format!("Runtime {} disappeared during startup", runtime_id));
+    /// Config to start a runtime process with: the declared config, rendered from its
+    /// template if any, with allocated listen ports and checked for port conflicts
+    async fn start_config(
+        &self,
+        app: &AppHandle,
+        runtime_id: RuntimeId,
+        config: ZenohConfigJson,
+        ports: RuntimePorts,
+    ) -> Result<ZenohConfigJson, SandboxError> {
+        // A config template is rendered now that the runtimes it refers to may be running
+        let config = self
+            .apply_config_template(app, runtime_id)
+            .await
+            .map_err(SandboxError::ConfigInvalid)?
+            .unwrap_or(config);
+        let config = self.allocate_listen_ports(runtime_id, config).await?;
+        let conflicts = self.port_conflicts(runtime_id, &config, ports).await;
+        if !conflicts.is_empty() {
+            return Err(SandboxError::SpawnFailed(endpoints::port_conflicts_error(
+                &conflicts,
+            )));
         }
-        eprintln!("🔷 Updated runtime for runtime_id: {}", runtime_id);
+        Ok(config)
     }
-    eprintln!("🔷 Write lock released for runtime_id: {}", runtime_id);
-
-    // Clean up socket file
-    let _ = tokio::fs::remove_file(&socket_path).await;
 
-    eprintln!("🟢 start_runtime returning success: {} on port {}", zid, port);
-    Ok(zid.to_string())
-}
+    /// Spawn the runtime process, send it the Start message and set up the receiver task
+    async fn spawn_process(
+        &self,
+        app: &AppHandle,
+        runtime_id: RuntimeId,
+        config: ZenohConfigJson,
+        ports: RuntimePorts,
+        options: RuntimeOptions,
+        privileges: ProcessPrivileges,
+    ) -> Result<ZenohId, SandboxError> {
+        let port = ports.websocket;
+        let logs_storage = app.state::<LogStorage>().inner().clone();
+        let testing = app.state::<Testing>().inner().clone();
+
+        tracing::debug!(
+            "start_runtime called with config: port={:?}",
+            config.get_websocket_port()
+        );
+
+        let shared_memory_configured = config
+            .as_json()
+            .pointer("/transport/shared_memory/enabled")
+            .is_some();
+
+        // Convert ZenohConfigJson to zenoh::Config
+        let mut zenoh_config: zenoh::config::Config =
+            config.try_into().map_err(SandboxError::ConfigInvalid)?;
+
+        // Apply runtime-specific config modifications (not visible to GUI)
+        // Enable adminspace
+        zenoh_config
+            .adminspace
+            .set_enabled(true)
+            .map_err(|e| format!("Failed to enable adminspace: {e}"))?;
+
+        // Enable plugins loading
+        zenoh_config
+            .plugins_loading
+            .set_enabled(true)
+            .map_err(|e| format!("Failed to enable plugins loading: {e}"))?;
+
+        // Add remote_api plugin configuration
+        zenoh_config
+            .insert_json5("plugins/remote_api", "{}")
+            .map_err(|e| format!("Failed to add remote_api plugin config: {e}"))?;
+
+        // Set websocket_port for remote_api
+        zenoh_config
+            .insert_json5(
+                "plugins/remote_api/websocket_port",
+                &format!(r#""{}""#, port),
+            )
+            .map_err(|e| format!("Failed to set websocket_port: {e}"))?;
+
+        // Add rest plugin configuration, on its own port
+        if let Some(rest_port) = ports.rest {
+            zenoh_config
+                .insert_json5(
+                    "plugins/rest",
+                    &format!(r#"{{ http_port: "{}" }}"#, rest_port),
+                )
+                .map_err(|e| format!("Failed to add rest plugin config: {e}"))?;
+        }
 
-/// stop (close) a Zenoh runtime by its RuntimeId.
-#[tauri::command]
-async fn zenoh_runtime_stop(
-    runtime_id: RuntimeId,
-    runtimes_state: State<'_, ZenohRuntimes>,
-    _logs_state: State<'_, LogStorage>,
-) -> Result<(), String> {
-    // Get and update the runtime process
-    let (child_opt, receiver_task_opt, request_tx_opt, port) = {
-        let mut runtimes = runtimes_state.runtimes.write().await;
-        let runtime_process = runtimes
-            .get_mut(&runtime_id)
-            .ok_or_else(|| format!("Runtime {} not found", runtime_id))?;
+        // Add storage_manager plugin configuration, storages need timestamped samples
+        if let Some(storage_manager) = storages::plugin_config(&options.storages) {
+            zenoh_config
+                .insert_json5("plugins/storage_manager", &storage_manager)
+                .map_err(|e| format!("Failed to add storage_manager plugin config: {e}"))?;
+            zenoh_config
+                .insert_json5("timestamping/enabled", "true")
+                .map_err(|e| format!("Failed to enable timestamping: {e}"))?;
+        }
 
-        // Extract the running components and clear them
-        let child = runtime_process.child.take();
-        let receiver_task = runtime_process.receiver_task.take();
-        let request_tx = runtime_process.request_tx.take();
-        let port = runtime_process.allocated_port;
+        // Enable shared memory when asked, the zenoh default being to enable it
+        if options.shared_memory || !shared_memory_configured {
+            zenoh_config
+                .insert_json5(
+                    "transport/shared_memory/enabled",
+                    &options.shared_memory.to_string(),
+                )
+                .map_err(|e| format!("Failed to set shared memory: {e}"))?;
+        }
 
-        (child, receiver_task, request_tx, port)
-    };
+        // Directories are relocatable, use the same ones for the whole startup
+        let dirs = self.dirs.read().clone();
+
+        // Create IPC listener for the runtime process to connect to
+        let listener = PlatformListener::bind(&dirs.socket_dir)
+            .map_err(|e| format!("Failed to create IPC listener: {}", e))?;
+        #[cfg(unix)]
+        if let (Some(uid), Some(gid)) = (privileges.uid, privileges.gid) {
+            listener
+                .give_to(uid, gid)
+                .map_err(|e| format!("Failed to give IPC socket to uid {}: {}", uid, e))?;
+        }
 
-    // Send Stop request through the channel if available
-    if let Some(request_tx) = request_tx_opt {
-        let (response_tx, response_rx) = oneshot::channel();
-        let _ = request_tx.send(RuntimeRequest::Stop(response_tx)).await;
-        // Wait for the stop to be sent (with timeout)
-        let _ = tokio::time::timeout(std::time::Duration::from_secs(2), response_rx).await;
-    }
+        // Get the path to the runtime binary
+        let runtime_binary = runtime_binary_path().map_err(SandboxError::SpawnFailed)?;
 
-    // Wait for the child process to exit
-    if let Some(mut child) = child_opt {
-        let _ = tokio::time::timeout(
-            std::time::Duration::from_secs(5),
-            child.wait(),
-        )
-        .await;
-        // Kill the child process if it's still running
-        let _ = child.kill().await;
-    }
+        // Check if binary exists
+        if !runtime_binary.exists() {
+            return Err(SandboxError::SpawnFailed(format!(
+                "Runtime binary not found at: {}. Did you run 'cargo build --bins'?",
+                runtime_binary.display()
+            )));
+        }
 
-    // Abort the receiver task
-    if let Some(receiver_task) = receiver_task_opt {
-        receiver_task.abort();
-    }
+        tracing::info!("Starting runtime binary: {}", runtime_binary.display());
+
+        // Use the log_dir from state (already created in ZenohRuntimes::new or relocate_storage)
+        let log_dir = &dirs.log_dir;
+
+        // Create log files for stdout and stderr
+        let random_id: u32 = rand::random();
+        let log_prefix = format!("z{:x}", random_id);
+        let stdout_log = log_dir.join(format!("{}-stdout.log", log_prefix));
+        let stderr_log = log_dir.join(format!("{}-stderr.log", log_prefix));
+
+        let stdout_file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&stdout_log)
+            .map_err(|e| {
+                format!(
+                    "Failed to create stdout log file {}: {}",
+                    stdout_log.display(),
+                    e
+                )
+            })?;
+
+        let stderr_file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&stderr_log)
+            .map_err(|e| {
+                format!(
+                    "Failed to create stderr log file {}: {}",
+                    stderr_log.display(),
+                    e
+                )
+            })?;
+        if let Some(runtime_process) = self.runtimes.write().await.get_mut(&runtime_id) {
+            runtime_process.stdout_log = Some(stdout_log.clone());
+            runtime_process.stderr_log = Some(stderr_log.clone());
+            runtime_process.socket_path = Some(listener.address());
+        }
 
-    // Release the allocated port
-    runtimes_state.release_port(port).await;
+        // Spawn the runtime process
+        let mut command = tokio::process::Command::new(&runtime_binary);
+        // The runtime falls back to its default filter without RUST_LOG, rather than
+        // inheriting the sandbox's
+        command.env_remove("RUST_LOG").envs(&options.env);
+        if let Some(rust_log) = &options.rust_log {
+            command.env("RUST_LOG", rust_log);
+        }
+        command
+            .arg(listener.address())
+            .envs(listener.env())
+            .stdout(Stdio::from(stdout_file))
+            .stderr(Stdio::from(stderr_file));
+
+        // Let the runtime watch its log filter file, if any
+        if let Some(log_filter_file) = &options.log_filter_file {
+            let log_filter_file = std::path::absolute(log_filter_file)
+                .map_err(|e| format!("Invalid log filter file {}: {}", log_filter_file, e))?;
+            command.env(protocol::LOG_FILTER_FILE_ENV, log_filter_file);
+        }
+        let max_message_size = protocol::max_message_size(options.max_message_size);
+        command.env(protocol::MAX_MESSAGE_SIZE_ENV, max_message_size.to_string());
+
+        privileges::apply(&mut command, &privileges);
+        limits::apply(&mut command, &options);
+
+        let start_timeout = options
+            .start_timeout_ms
+            .map_or(DEFAULT_START_TIMEOUT, Duration::from_millis);
+        let connect_retries = options.connect_retries.unwrap_or(0);
+        let mut attempt = 0;
+        let (process, reader, mut writer) = loop {
+            let child = command.spawn().map_err(|e| {
+                SandboxError::SpawnFailed(format!(
+                    "Failed to spawn runtime process: {} (path: {})",
+                    e,
+                    runtime_binary.display()
+                ))
+            })?;
+
+            tracing::info!("Runtime process spawned with PID: {:?}", child.id());
+
+            // Record the process, so that it can be found if the sandbox crashes
+            let pid_file = child.id().and_then(|pid| {
+                orphans::register(&dirs.socket_dir, pid, runtime_id, port)
+                    .inspect_err(|e| tracing::warn!("{}", e))
+                    .ok()
+            });
+            let process = StartingProcess::new(child, pid_file);
+            tracing::debug!(
+                "Logs:\n{}\n{}\n",
+                stdout_log.display(),
+                stderr_log.display()
+            );
+
+            // Accept connection from the runtime process
+            tracing::debug!("Waiting for runtime to connect...");
+            match tokio::time::timeout(start_timeout, listener.accept()).await {
+                Ok(Ok((reader, writer))) => break (process, reader, writer),
+                Ok(Err(e)) => {
+                    return Err(SandboxError::IpcError(format!(
+                        "Failed to accept connection: {}",
+                        e
+                    )));
+                }
+                Err(_) => {
+                    drop(process);
+                    if attempt == connect_retries {
+                        return Err(SandboxError::Timeout(format!(
+                            "Timeout waiting for runtime to connect ({} ms, {} attempts). Check stderr output.",
+                            start_timeout.as_millis(),
+                            attempt + 1
+                        )));
+                    }
+                    attempt += 1;
+                    logs_storage.add_sandbox_log(
+                        runtime_id,
+                        LogEntryLevel::WARN,
+                        format!(
+                            "Runtime process did not connect within {} ms, retrying ({}/{})",
+                            start_timeout.as_millis(),
+                            attempt,
+                            connect_retries
+                        ),
+                    );
+                }
+            }
+        };
 
-    // Don't clear logs - keep them available for stopped runtime
-    // Don't remove from state - keep runtime entry for UI
+        tracing::debug!("Runtime connected successfully");
+        let mut reader = FrameReader::new(reader, max_message_size);
 
-    Ok(())
-}
+        // Check that the runtime binary speaks the same protocol before sending it anything else
+        let codec = handshake(&mut reader, &mut writer)
+            .await
+            .map_err(SandboxError::IpcError)?;
 
-/// List all runtime IDs.
-#[tauri::command]
-async fn zenoh_runtime_list(state: State<'_, ZenohRuntimes>) -> Result<Vec<RuntimeId>, String> {
-    let runtimes = state.runtimes.read().await;
-    let runtime_ids: Vec<RuntimeId> = runtimes.keys().copied().collect();
-    Ok(runtime_ids)
-}
+        // Send Start message with zenoh::Config
+        tracing::debug!("Sending start message to runtime...");
+        let start_msg = MainToRuntime::Start(Box::new(zenoh_config.clone()));
+        protocol::write_message(&mut writer, codec, &start_msg)
+            .await
+            .map_err(|e| {
+                SandboxError::IpcError(format!("Failed to send start message: {}", e))
+            })?;
+        tracing::debug!("Start message sent");
+
+        // Receive Started response (may receive Log messages first)
+        tracing::debug!("Waiting for runtime response...");
+        let truncated_messages = Arc::new(AtomicU64::new(0));
+        let dropped_logs = Arc::new(AtomicU64::new(0));
+
+        let zid = loop {
+            let frame = match reader
+                .next_frame()
+                .await
+                .map_err(|e| {
+                    SandboxError::IpcError(format!("Failed to read response: {}", e))
+                })?
+            {
+                Some(Frame::Message(frame)) => frame,
+                Some(Frame::Oversized(size)) => {
+                    truncated_messages.fetch_add(1, Ordering::Relaxed);
+                    tracing::warn!("Dropped startup message of {} bytes from runtime", size);
+                    continue;
+                }
+                None => {
+                    return Err(SandboxError::IpcError(
+                        "Connection closed by the runtime process during startup".to_string(),
+                    ));
+                }
+            };
+            let response: RuntimeToMain = codec.decode(&frame).map_err(|e| {
+                SandboxError::IpcError(format!("Failed to parse response: {}", e))
+            })?;
+            tracing::trace!("Got response: {}", response.kind());
+
+            match response {
+                RuntimeToMain::Started(zid_str) => {
+                    let parsed_zid = ZenohId::from_str(&zid_str).map_err(|e| format!("Invalid ZenohId: {}", e))?;
+                    tracing::debug!("Parsed ZenohId: {}", parsed_zid);
+                    break parsed_zid;
+                }
+                RuntimeToMain::StartError(err) => {
+                    return Err(SandboxError::SpawnFailed(err));
+                }
+                RuntimeToMain::Log(entry) => {
+                    // Handle logs during startup - store them
+                    tracing::trace!("Received log during startup: {:?}", entry.message);
+                    logs_storage.add_log(runtime_id, entry);
+                    // Continue waiting for Started message
+                }
+                RuntimeToMain::LogBatch(entries) => {
+                    logs_storage.add_logs(runtime_id, entries);
+                }
+                RuntimeToMain::LogStats { dropped_count } => {
+                    let previous = dropped_logs.swap(dropped_count, Ordering::Relaxed);
+                    logs_storage.skip_seq(runtime_id, dropped_count.saturating_sub(previous));
+                }
+                RuntimeToMain::Truncated { .. } => {
+                    truncated_messages.fetch_add(1, Ordering::Relaxed);
+                }
+                _ => {
+                    return Err(SandboxError::IpcError(
+                        "Unexpected response from runtime".to_string(),
+                    ));
+                }
+            }
+        };
+
+        // Spawn log receiver task (reader continues to receive logs)
+        // This task also handles config requests
+        let logs_storage_clone = logs_storage.clone();
+        let runtime_id_clone = runtime_id;
+
+        // Create channel for sending requests to the receiver task
+        let (request_tx, mut request_rx) = mpsc::channel::<RuntimeRequest>(16);
+
+        // Heartbeat summaries injected into the log stream, if enabled
+        let mut heartbeat = options
+            .heartbeat_interval_secs
+            .filter(|secs| *secs > 0)
+            .map(|secs| {
+                let period = std::time::Duration::from_secs(secs);
+                tokio::time::interval_at(tokio::time::Instant::now() + period, period)
+            });
+
+        tracing::debug!("Spawning receiver task...");
+        let app_clone = app.clone();
+        let truncated_messages_clone = truncated_messages.clone();
+        let dropped_logs_clone = dropped_logs.clone();
+        let stats = Arc::new(ParkingLotMutex::new(None));
+        let stats_clone = stats.clone();
+        let last_message_at = Arc::new(ParkingLotMutex::new(Instant::now()));
+        let last_message_at_clone = last_message_at.clone();
+        let missed_pings = Arc::new(AtomicU32::new(0));
+        let missed_pings_clone = missed_pings.clone();
+        let receiver_task = tokio::spawn(async move {
+            // Set when the connection breaks, as opposed to the runtime being stopped
+            let mut disconnect_error: Option<String> = None;
+            // Track pending config request
+            let mut pending_config_request: Option<oneshot::Sender<Config>> = None;
+            // Track pending config update request
+            let mut pending_update_request: Option<oneshot::Sender<Result<Config, String>>> = None;
+            // Track pending ping, query and connect requests by id, several may be in flight
+            let mut pending_latency_requests: HashMap<u64, oneshot::Sender<Result<Vec<u64>, String>>> =
+                HashMap::new();
+            let mut pending_query_requests: HashMap<
+                u64,
+                oneshot::Sender<Result<Vec<QueryReply>, String>>,
+            > = HashMap::new();
+            let mut next_request_id: u64 = 0;
+            let mut pending_connect_requests: HashMap<u64, oneshot::Sender<Result<(), String>>> =
+                HashMap::new();
+            let mut pending_publish_requests: HashMap<u64, oneshot::Sender<Result<usize, String>>> =
+                HashMap::new();
+            let mut pending_scout_requests: HashMap<
+                u64,
+                oneshot::Sender<Result<Vec<DiscoveredPeer>, String>>,
+            > = HashMap::new();
+            // Track pending test entity declarations by entity id
+            let mut pending_declare_requests: HashMap<u64, oneshot::Sender<Result<(), String>>> =
+                HashMap::new();
+            // Messages received since the last heartbeat, and the rate of the last heartbeat
+            let mut messages_since_heartbeat: u64 = 0;
+            let mut last_heartbeat = Instant::now();
+            let mut heartbeat_rate: Option<f64> = None;
+            // Keepalive: number of the last ping sent and whether it was answered
+            let mut ping_interval = tokio::time::interval_at(
+                tokio::time::Instant::now() + protocol::PING_INTERVAL,
+                protocol::PING_INTERVAL,
+            );
+            let mut last_ping: u64 = 0;
+            let mut ping_answered = true;
+
+            loop {
+                tokio::select! {
+                    // Handle incoming messages from runtime
+                    read_result = reader.next_frame() => {
+                        if let Ok(Some(_)) = read_result {
+                            *last_message_at_clone.lock() = Instant::now();
+                        }
+                        match read_result {
+                            Ok(None) => {
+                                // Socket closed
+                                disconnect_error =
+                                    Some("connection closed by the runtime process".to_string());
+                                break;
+                            }
+                            Ok(Some(Frame::Oversized(size))) => {
+                                // The runtime process keeps its messages within the limit,
+                                // so this only happens if it misbehaves
+                                truncated_messages_clone.fetch_add(1, Ordering::Relaxed);
+                                logs_storage_clone.add_sandbox_log(
+                                    runtime_id_clone,
+                                    LogEntryLevel::WARN,
+                                    format!(
+                                        "Dropped IPC message of {} bytes exceeding the maximum of {} bytes",
+                                        size, max_message_size
+                                    ),
+                                );
+                            }
+                            Ok(Some(Frame::Message(frame))) => {
+                                if let Ok(msg) = codec.decode::<RuntimeToMain>(&frame) {
+                                    messages_since_heartbeat += 1;
+                                    match msg {
+                                        RuntimeToMain::Log(entry) => {
+                                            logs_storage_clone.add_log(runtime_id_clone, entry);
+                                        }
+                                        RuntimeToMain::LogBatch(entries) => {
+                                            logs_storage_clone.add_logs(runtime_id_clone, entries);
+                                        }
+                                        RuntimeToMain::LogStats { dropped_count } => {
+                                            let previous =
+                                                dropped_logs_clone.swap(dropped_count, Ordering::Relaxed);
+                                            if dropped_count > previous {
+                                                logs_storage_clone
+                                                    .skip_seq(runtime_id_clone, dropped_count - previous);
+                                                logs_storage_clone.add_sandbox_log(
+                                                    runtime_id_clone,
+                                                    LogEntryLevel::WARN,
+                                                    format!(
+                                                        "Runtime dropped {} log entries produced faster than they could be forwarded, {} in total",
+                                                        dropped_count - previous,
+                                                        dropped_count
+                                                    ),
+                                                );
+                                            }
+                                        }
+                                        RuntimeToMain::Stats(runtime_stats) => {
+                                            *stats_clone.lock() = Some(runtime_stats);
+                                        }
+                                        RuntimeToMain::Config(config) => {
+                                            // Send response to pending request
+                                            if let Some(tx) = pending_config_request.take() {
+                                                let _ = tx.send(*config);
+                                            }
+                                        }
+                                        RuntimeToMain::ConfigUpdated(config) => {
+                                            if let Some(tx) = pending_update_request.take() {
+                                                let _ = tx.send(Ok(*config));
+                                            }
+                                        }
+                                        RuntimeToMain::ConfigUpdateError(err) => {
+                                            if let Some(tx) = pending_update_request.take() {
+                                                let _ = tx.send(Err(err));
+                                            }
+                                        }
+                                        RuntimeToMain::LatencyResult { id, result } => {
+                                            if let Some(tx) = pending_latency_requests.remove(&id) {
+                                                let _ = tx.send(result);
+                                            }
+                                        }
+                                        RuntimeToMain::QueryResult { id, result } => {
+                                            if let Some(tx) = pending_query_requests.remove(&id) {
+                                                let _ = tx.send(result);
+                                            }
+                                        }
+                                        RuntimeToMain::TestEntityDeclared { id, result } => {
+                                            if let Some(tx) = pending_declare_requests.remove(&id) {
+                                                let _ = tx.send(result);
+                                            }
+                                        }
+                                        RuntimeToMain::Connected { id, result } => {
+                                            if let Some(tx) = pending_connect_requests.remove(&id) {
+                                                let _ = tx.send(result);
+                                            }
+                                        }
+                                        RuntimeToMain::Published { id, result } => {
+                                            if let Some(tx) = pending_publish_requests.remove(&id) {
+                                                let _ = tx.send(result);
+                                            }
+                                        }
+                                        RuntimeToMain::Scouted { id, result } => {
+                                            if let Some(tx) = pending_scout_requests.remove(&id) {
+                                                let _ = tx.send(result);
+                                            }
+                                        }
+                                        RuntimeToMain::TestSample {
+                                            id,
+                                            sample,
+                                            payload,
+                                        } => {
+                                            testing.add_sample(id, sample, payload);
+                                        }
+                                        RuntimeToMain::LivelinessChange { id, key_expr, alive } => {
+                                            let event = LivelinessEvent {
+                                                subscriber_id: id,
+                                                runtime_id: runtime_id_clone,
+                                                received_at: chrono::Utc::now(),
+                                                key_expr,
+                                                alive,
+                                            };
+                                            testing.add_liveliness_event(event.clone());
+                                            let _ = app_clone.emit("liveliness-event", event);
+                                        }
+                                        RuntimeToMain::Truncated { kind, size } => {
+                                            truncated_messages_clone.fetch_add(1, Ordering::Relaxed);
+                                            if kind == "Config" {
+                                                // Dropping the sender fails the pending request
+                                                pending_config_request = None;
+                                            }
+                                            if kind != "Log" {
+                                                logs_storage_clone.add_sandbox_log(
+                                                    runtime_id_clone,
+                                                    LogEntryLevel::WARN,
+                                                    format!(
+                                                        "Runtime dropped a {} message of {} bytes exceeding the maximum of {} bytes",
+                                                        kind, size, max_message_size
+                                                    ),
+                                                );
+                                            }
+                                        }
+                                        RuntimeToMain::Pong(n) if n == last_ping => {
+                                            ping_answered = true;
+                                            missed_pings_clone.store(0, Ordering::Relaxed);
+                                        }
+                                        RuntimeToMain::Health { peers, routers } => {
+                                            if let Some(rate) = heartbeat_rate.take() {
+                                                logs_storage_clone.add_sandbox_log(
+                                                    runtime_id_clone,
+                                                    LogEntryLevel::INFO,
+                                                    format!(
+                                                        "Runtime alive, {} peers, {} routers, {:.1} msg/s",
+                                                        peers, routers, rate
+                                                    ),
+                                                );
+                                            }
+                                        }
+                                        _ => {}
+                                    }
+                                }
+                            }
+                            Err(e) => {
+                                disconnect_error = Some(e.to_string());
+                                break;
+                            }
+                        }
+                    }
+                    // Ask the runtime for its health, logged when the answer arrives
+                    _ = next_heartbeat(&mut heartbeat) => {
+                        let elapsed = last_heartbeat.elapsed().as_secs_f64();
+                        heartbeat_rate = Some(messages_since_heartbeat as f64 / elapsed);
+                        messages_since_heartbeat = 0;
+                        last_heartbeat = Instant::now();
+                        let msg = MainToRuntime::GetHealth;
+                        let _ = protocol::write_message(&mut writer, codec, &msg).await;
+                    }
+                    // Ping the runtime, counting the pings left unanswered
+                    _ = ping_interval.tick() => {
+                        if !ping_answered {
+                            missed_pings_clone.fetch_add(1, Ordering::Relaxed);
+                        }
+                        last_ping += 1;
+                        ping_answered = false;
+                        let msg = MainToRuntime::Ping(last_ping);
+                        let _ = protocol::write_message(&mut writer, codec, &msg).await;
+                    }
+                    // Handle requests from main thread
+                    Some(request) = request_rx.recv() => {
+                        match request {
+                            RuntimeRequest::GetConfig(response_tx) => {
+                                // Send GetConfig request to runtime
+                                let msg = MainToRuntime::GetConfig;
+                                if protocol::write_message(&mut writer, codec, &msg).await.is_ok() {
+                                    pending_config_request = Some(response_tx);
+                                }
+                            }
+                            RuntimeRequest::UpdateConfig(entries, response_tx) => {
+                                // Send UpdateConfig request to runtime
+                                let msg = MainToRuntime::UpdateConfig(entries);
+                                if protocol::write_message(&mut writer, codec, &msg).await.is_ok() {
+                                    pending_update_request = Some(response_tx);
+                                }
+                            }
+                            RuntimeRequest::MeasureLatency { target, samples, timeout, payload_size, response_tx } => {
+                                // Send MeasureLatency request to runtime
+                                let id = next_request_id;
+                                next_request_id += 1;
+                                let msg = MainToRuntime::MeasureLatency {
+                                    id,
+                                    target: target.to_string(),
+                                    samples,
+                                    timeout_ms: timeout.as_millis() as u64,
+                                    payload_size,
+                                };
+                                if protocol::write_message(&mut writer, codec, &msg).await.is_ok() {
+                                    pending_latency_requests.retain(|_, tx| !tx.is_closed());
+                                    pending_latency_requests.insert(id, response_tx);
+                                }
+                            }
+                            RuntimeRequest::DeclareTestEntity { id, entity, response_tx } => {
+                                // Send DeclareTestEntity request to runtime
+                                let msg = MainToRuntime::DeclareTestEntity { id, entity };
+                                if protocol::write_message(&mut writer, codec, &msg).await.is_ok() {
+                                    pending_declare_requests.insert(id, response_tx);
+                                }
+                            }
+                            RuntimeRequest::UndeclareTestEntity(id) => {
+                                let msg = MainToRuntime::UndeclareTestEntity(id);
+                                let _ = protocol::write_message(&mut writer, codec, &msg).await;
+                            }
+                            RuntimeRequest::Query { selector, timeout, response_tx } => {
+                                // Send Query request to runtime
+                                let id = next_request_id;
+                                next_request_id += 1;
+                                let msg = MainToRuntime::Query {
+                                    id,
+                                    selector,
+                                    timeout_ms: timeout.as_millis() as u64,
+                                };
+                                if protocol::write_message(&mut writer, codec, &msg).await.is_ok() {
+                                    pending_query_requests.retain(|_, tx| !tx.is_closed());
+                                    pending_query_requests.insert(id, response_tx);
+                                }
+                            }
+                            RuntimeRequest::Connect { target, locators, response_tx } => {
+                                // Send Connect request to runtime
+                                let id = next_request_id;
+                                next_request_id += 1;
+                                let msg = MainToRuntime::Connect {
+                                    id,
+                                    zid: target.to_string(),
+                                    locators,
+                                };
+                                if protocol::write_message(&mut writer, codec, &msg).await.is_ok() {
+                                    pending_connect_requests.retain(|_, tx| !tx.is_closed());
+                                    pending_connect_requests.insert(id, response_tx);
+                                }
+                            }
+                            RuntimeRequest::PublishFile { keyexpr, path, encoding, response_tx } => {
+                                // Send PublishFile request to runtime
+                                let id = next_request_id;
+                                next_request_id += 1;
+                                let msg = MainToRuntime::PublishFile {
+                                    id,
+                                    keyexpr,
+                                    path: path.to_string_lossy().to_string(),
+                                    encoding,
+                                };
+                                if protocol::write_message(&mut writer, codec, &msg).await.is_ok() {
+                                    pending_publish_requests.retain(|_, tx| !tx.is_closed());
+                                    pending_publish_requests.insert(id, response_tx);
+                                }
+                            }
+                            RuntimeRequest::Scout { timeout, response_tx } => {
+                                // Send Scout request to runtime
+                                let id = next_request_id;
+                                next_request_id += 1;
+                                let msg = MainToRuntime::Scout {
+                                    id,
+                                    timeout_ms: timeout.as_millis() as u64,
+                                };
+                                if protocol::write_message(&mut writer, codec, &msg).await.is_ok() {
+                                    pending_scout_requests.retain(|_, tx| !tx.is_closed());
+                                    pending_scout_requests.insert(id, response_tx);
+                                }
+                            }
+                            RuntimeRequest::Stop(response_tx) => {
+                                // Send Stop request to runtime
+                                let msg = MainToRuntime::Stop;
+                                let _ = protocol::write_message(&mut writer, codec, &msg).await;
+                                let _ = response_tx.send(());
+                                break;
+                            }
+                        }
+                    }
+                }
+            }
+            if let Some(error) = disconnect_error {
+                app_clone
+                    .state::<ZenohRuntimes>()
+                    .handle_disconnect(&app_clone, runtime_id_clone, error)
+                    .await;
+            }
+        }.instrument(tracing::debug_span!("receiver_task", runtime_id)));
+
+        // Update the runtime process with the started runtime details
+        {
+            let mut runtimes = self.runtimes.write().await;
+            if let Some(runtime_process) = runtimes.get_mut(&runtime_id) {
+                // Spawned while holding the lock, so that an immediate exit
+                // is only observed once the runtime is marked as Running
+                let (kill_tx, kill_rx) = oneshot::channel();
+                // Until here, returning early kills the process as it is dropped
+                let (child, pid_file) = process.started();
+                runtime_process.zenoh_id = Some(zid);
+                runtime_process.pid = child.id();
+                let memory_monitor = child.id().and_then(|pid| {
+                    limits::spawn_memory_monitor(app.clone(), runtime_id, pid, &options)
+                });
+                if let Some(previous) =
+                    std::mem::replace(&mut runtime_process.memory_monitor, memory_monitor)
+                {
+                    previous.abort();
+                }
+                runtime_process.exit_code = None;
+                runtime_process.exit_watcher =
+                    Some(spawn_exit_watcher(app.clone(), runtime_id, child, kill_rx));
+                runtime_process.kill_tx = Some(kill_tx);
+                runtime_process.receiver_task = Some(receiver_task);
+                runtime_process.request_tx = Some(request_tx);
+                runtime_process.state = RuntimeState::Running;
+                runtime_process.started_at = Some(Instant::now());
+                runtime_process.truncated_messages = truncated_messages;
+                runtime_process.dropped_logs = dropped_logs;
+                runtime_process.stats = stats;
+                runtime_process.last_message_at = last_message_at;
+                runtime_process.missed_pings = missed_pings;
+                runtime_process.watchdog = WatchdogStatus::default();
+                runtime_process.pid_file = pid_file;
+            } else {
+                return Err(SandboxError::RuntimeNotFound(runtime_id));
+            }
+        }
+
+        // Clean up the IPC endpoint, the connection stays open
+        drop(listener);
+
+        tracing::info!("Runtime started: {} on port {}", zid, port);
+        Ok(zid)
+    }
+
+    /// Stop the runtime process, keeping the runtime entry and its logs.
+    /// The allocated port is released only if `release_port` is set.
+    async fn shutdown(
+        &self,
+        runtime_id: RuntimeId,
+        release_port: bool,
+    ) -> Result<(), SandboxError> {
+        // Get and update the runtime process
+        let (exit_watcher_opt, kill_tx_opt, receiver_task_opt, request_tx_opt, ports, stop_timeout) = {
+            let mut runtimes = self.runtimes.write().await;
+            let runtime_process = runtimes
+                .get_mut(&runtime_id)
+                .ok_or(SandboxError::RuntimeNotFound(runtime_id))?;
+
+            // Extract the running components and clear them
+            let exit_watcher = runtime_process.exit_watcher.take();
+            let kill_tx = runtime_process.kill_tx.take();
+            let receiver_task = runtime_process.receiver_task.take();
+            let request_tx = runtime_process.request_tx.take();
+            if let Some(memory_monitor) = runtime_process.memory_monitor.take() {
+                memory_monitor.abort();
+            }
+            let ports = RuntimePorts {
+                websocket: runtime_process.allocated_port,
+                rest: runtime_process.rest_port,
+            };
+            let stop_timeout = runtime_process
+                .options
+                .stop_timeout_ms
+                .map_or(DEFAULT_STOP_TIMEOUT, Duration::from_millis);
+            runtime_process.state = RuntimeState::Stopping;
+            runtime_process.started_at = None;
+            runtime_process.peer_divergence = None;
+            runtime_process.watchdog = WatchdogStatus::default();
+            runtime_process.restart_attempts = 0;
+
+            (
+                exit_watcher,
+                kill_tx,
+                receiver_task,
+                request_tx,
+                ports,
+                stop_timeout,
+            )
+        };
+
+        // The Stop request and the exit share the stop timeout
+        let deadline = tokio::time::Instant::now() + stop_timeout;
+
+        // Send Stop request through the channel if available
+        if let Some(request_tx) = request_tx_opt {
+            let (response_tx, response_rx) = oneshot::channel();
+            let _ = request_tx.send(RuntimeRequest::Stop(response_tx)).await;
+            // Wait for the stop to be sent (with timeout)
+            let _ = tokio::time::timeout_at(deadline, response_rx).await;
+        }
+
+        // Wait for the child process to exit
+        if let Some(mut exit_watcher) = exit_watcher_opt
+            && tokio::time::timeout_at(deadline, &mut exit_watcher)
+                .await
+                .is_err()
+        {
+            // Kill the child process if it's still running
+            if let Some(kill_tx) = kill_tx_opt {
+                let _ = kill_tx.send(());
+            }
+            let _ = exit_watcher.await;
+        }
+
+        // Abort the receiver task
+        if let Some(receiver_task) = receiver_task_opt {
+            receiver_task.abort();
+        }
+
+        // Release the allocated port unless the runtime is going to be restarted
+        if release_port {
+            self.release_port(ports.websocket).await;
+            if let Some(rest_port) = ports.rest {
+                self.release_port(rest_port).await;
+            }
+            self.release_listen_ports(runtime_id).await;
+        }
+
+        self.set_state(runtime_id, RuntimeState::Stopped).await;
+        self.events
+            .publish(RuntimeEvent::Stopped(RuntimeStoppedEvent { runtime_id }));
+
+        Ok(())
+    }
+
+    /// Stop every active runtime at once, as the app exits: send Stop to all runtime
+    /// processes, wait up to `timeout` for them to exit, kill the remaining ones,
+    /// then remove leftover socket files. Returns the number of runtimes stopped.
+    pub async fn stop_all(&self, timeout: std::time::Duration) -> usize {
+        let mut stopping: Vec<_> = {
+            let mut runtimes = self.runtimes.write().await;
+            runtimes
+                .iter_mut()
+                .filter(|(_, runtime_process)| runtime_process.state.is_active())
+                .map(|(runtime_id, runtime_process)| {
+                    runtime_process.state = RuntimeState::Stopping;
+                    runtime_process.started_at = None;
+                    runtime_process.peer_divergence = None;
+                    runtime_process.watchdog = WatchdogStatus::default();
+                    (
+                        *runtime_id,
+                        runtime_process.request_tx.take(),
+                        runtime_process.exit_watcher.take(),
+                        runtime_process.kill_tx.take(),
+                        runtime_process.receiver_task.take(),
+                    )
+                })
+                .collect()
+        };
+        tracing::info!("Stopping {} runtimes", stopping.len());
+
+        // Ask all runtime processes to stop before waiting for any of them
+        for (_, request_tx, ..) in &stopping {
+            if let Some(request_tx) = request_tx {
+                let (response_tx, _) = oneshot::channel();
+                let _ = request_tx.try_send(RuntimeRequest::Stop(response_tx));
+            }
+        }
+
+        // The processes exit concurrently, wait for all of them until the same deadline
+        let deadline = tokio::time::Instant::now() + timeout;
+        for (runtime_id, _, exit_watcher, kill_tx, receiver_task) in stopping.iter_mut() {
+            if let Some(exit_watcher) = exit_watcher
+                && tokio::time::timeout_at(deadline, &mut *exit_watcher)
+                    .await
+                    .is_err()
+            {
+                tracing::warn!(
+                    runtime_id = *runtime_id,
+                    "Runtime process didn't stop, killing it"
+                );
+                if let Some(kill_tx) = kill_tx.take() {
+                    let _ = kill_tx.send(());
+                }
+                let _ = exit_watcher.await;
+            }
+            if let Some(receiver_task) = receiver_task {
+                receiver_task.abort();
+            }
+            self.set_state(*runtime_id, RuntimeState::Stopped).await;
+            self.events
+                .publish(RuntimeEvent::Stopped(RuntimeStoppedEvent {
+                    runtime_id: *runtime_id,
+                }));
+        }
+
+        let removed = self.remove_socket_files().await;
+        if removed > 0 {
+            tracing::info!("Removed {} leftover socket files", removed);
+        }
+        stopping.len()
+    }
+
+    /// Handle the loss of the IPC connection to a running runtime process.
+    /// Unless the process exits meanwhile, the runtime is marked as Disconnected
+    /// and the error is reported in its logs and in the timeline.
+    async fn handle_disconnect(&self, app: &AppHandle, runtime_id: RuntimeId, error: String) {
+        // An exiting process also closes the connection, let the exit watcher handle it first
+        tokio::time::sleep(DISCONNECT_GRACE_PERIOD).await;
+        {
+            let mut runtimes = self.runtimes.write().await;
+            let Some(runtime_process) = runtimes.get_mut(&runtime_id) else {
+                return;
+            };
+            if !matches!(
+                runtime_process.state,
+                RuntimeState::Running | RuntimeState::Unresponsive
+            ) {
+                return;
+            }
+            runtime_process.state = RuntimeState::Disconnected;
+            runtime_process.request_tx = None;
+            runtime_process.peer_divergence = None;
+        }
+
+        tracing::error!(runtime_id, "IPC connection to runtime process lost: {}", error);
+        let summary = format!("IPC connection lost: {}", error);
+        app.state::<LogStorage>().add_sandbox_log(
+            runtime_id,
+            LogEntryLevel::ERROR,
+            format!(
+                "{}. The runtime process may still be running, stop or restart it",
+                summary
+            ),
+        );
+        app.state::<Timeline>().add_event(
+            Some(runtime_id),
+            summary,
+            TimelineEventKind::IpcFailure { error },
+        );
+    }
+
+    /// Handle the exit of a runtime process.
+    /// If the runtime was not being stopped, it is marked as Crashed, an ERROR entry
+    /// is added to its logs, a `runtime-crashed` event is emitted and the runtime is
+    /// restarted if its restart policy says so.
+    async fn handle_exit(&self, app: &AppHandle, runtime_id: RuntimeId, status: Option<ExitStatus>) {
+        let exit_code = status.and_then(|status| status.code());
+        let crashed = {
+            let mut runtimes = self.runtimes.write().await;
+            let Some(runtime_process) = runtimes.get_mut(&runtime_id) else {
+                return;
+            };
+            runtime_process.pid = None;
+            runtime_process.exit_code = exit_code;
+            if let Some(pid_file) = runtime_process.pid_file.take() {
+                orphans::unregister(&pid_file);
+            }
+            if matches!(
+                runtime_process.state,
+                RuntimeState::Running | RuntimeState::Disconnected | RuntimeState::Unresponsive
+            ) {
+                runtime_process.state = RuntimeState::Crashed;
+                if runtime_process
+                    .started_at
+                    .is_some_and(|started_at| started_at.elapsed() >= restart::STABLE_RUN_PERIOD)
+                {
+                    runtime_process.restart_attempts = 0;
+                }
+                runtime_process.started_at = None;
+                runtime_process.peer_divergence = None;
+                runtime_process.request_tx = None;
+                runtime_process.kill_tx = None;
+                runtime_process.exit_watcher = None;
+                true
+            } else {
+                false
+            }
+        };
+
+        if !crashed {
+            return;
+        }
+
+        let exit_status = status;
+        let status = status
+            .map(|status| status.to_string())
+            .unwrap_or_else(|| "unknown status".to_string());
+        tracing::error!(runtime_id, "Runtime process exited unexpectedly: {}", status);
+        app.state::<LogStorage>().add_sandbox_log(
+            runtime_id,
+            LogEntryLevel::ERROR,
+            format!("Runtime process exited unexpectedly ({})", status),
+        );
+        self.events
+            .publish(RuntimeEvent::Crashed(RuntimeCrashedEvent {
+                runtime_id,
+                exit_code,
+                status,
+            }));
+        tokio::spawn(restart::restart_after_exit(app.clone(), runtime_id, exit_status));
+    }
+}
+
+// ============================================================================
+// Runtime snapshots
+// ============================================================================
+
+impl ZenohRuntimes {
+    /// Remove all runtimes without a runtime process, releasing their ports and links.
+    /// RuntimeIds are allocated from 0 again once no runtime is left.
+    /// Returns the removed RuntimeIds, sorted.
+    pub(crate) async fn remove_inactive(&self) -> Vec<RuntimeId> {
+        let mut runtimes = self.runtimes.write().await;
+        let mut removed: Vec<RuntimeId> = runtimes
+            .iter()
+            .filter(|(_, runtime_process)| !runtime_process.state.is_active())
+            .map(|(runtime_id, _)| *runtime_id)
+            .collect();
+        removed.sort();
+
+        let mut port_tracker = self.port_tracker.write().await;
+        for runtime_id in &removed {
+            if let Some(runtime_process) = runtimes.remove(runtime_id) {
+                runtime_process.release_ports(&mut port_tracker);
+            }
+        }
+        self.links
+            .write()
+            .await
+            .retain(|link| runtimes.contains_key(&link.from) && runtimes.contains_key(&link.to));
+        for members in self.groups.write().await.values_mut() {
+            members.retain(|runtime_id| runtimes.contains_key(runtime_id));
+        }
+        if runtimes.is_empty() {
+            *self.next_runtime_id.write().await = 0;
+        }
+        removed
+    }
+
+    /// RuntimeIds of the runtimes matching `selector`, sorted
+    async fn select(&self, selector: &RuntimeSelector) -> Vec<RuntimeId> {
+        let runtimes = self.runtimes.read().await;
+        let mut runtime_ids: Vec<RuntimeId> = runtimes
+            .iter()
+            .filter(|(runtime_id, runtime_process)| {
+                let mode = runtime_process.sandbox_config.mode();
+                selector.matches(**runtime_id, &mode, runtime_process.state)
+            })
+            .map(|(runtime_id, _)| *runtime_id)
+            .collect();
+        runtime_ids.sort();
+        runtime_ids
+    }
+
+    /// Status of every runtime, sorted by RuntimeId
+    pub(crate) async fn statuses(&self) -> Vec<RuntimeStatus> {
+        let runtimes = self.runtimes.read().await;
+        let mut statuses: Vec<RuntimeStatus> = runtimes
+            .iter()
+            .map(|(runtime_id, runtime_process)| RuntimeStatus {
+                runtime_id: *runtime_id,
+                state: runtime_process.state,
+                zenoh_id: runtime_process.zenoh_id.map(|zid| zid.to_string()),
+                pid: runtime_process.pid,
+                port: runtime_process.allocated_port,
+                rest_port: runtime_process.rest_port,
+                uptime_secs: runtime_process
+                    .started_at
+                    .map(|started_at| started_at.elapsed().as_secs()),
+                exit_code: runtime_process.exit_code,
+                peer_divergence: runtime_process.peer_divergence.clone(),
+                privileges: runtime_process.privileges.clone(),
+                truncated_messages: runtime_process.truncated_messages.load(Ordering::Relaxed),
+                dropped_logs: runtime_process.dropped_logs.load(Ordering::Relaxed),
+                watchdog: runtime_process.watchdog.clone(),
+            })
+            .collect();
+        statuses.sort_by_key(|status| status.runtime_id);
+        statuses
+    }
+
+    /// Declared configuration of the given runtimes, skipping unknown ones
+    async fn declared_configs(
+        &self,
+        runtime_ids: &[RuntimeId],
+    ) -> BTreeMap<RuntimeId, ZenohConfigJson> {
+        let runtimes = self.runtimes.read().await;
+        runtime_ids
+            .iter()
+            .filter_map(|runtime_id| {
+                let runtime_process = runtimes.get(runtime_id)?;
+                Some((*runtime_id, runtime_process.sandbox_config.clone()))
+            })
+            .collect()
+    }
+}
+
+// ============================================================================
+// Requests to running runtimes
+// ============================================================================
+
+impl ZenohRuntimes {
+    /// RuntimeIds and ZenohIds of the running runtimes, sorted by RuntimeId
+    async fn running_zenoh_ids(&self) -> Vec<(RuntimeId, ZenohId)> {
+        let runtimes = self.runtimes.read().await;
+        let mut running: Vec<(RuntimeId, ZenohId)> = runtimes
+            .iter()
+            .filter(|(_, runtime_process)| runtime_process.state == RuntimeState::Running)
+            .filter_map(|(runtime_id, runtime_process)| {
+                runtime_process.zenoh_id.map(|zid| (*runtime_id, zid))
+            })
+            .collect();
+        running.sort_by_key(|(runtime_id, _)| *runtime_id);
+        running
+    }
+
+    /// ZenohId of a running runtime
+    async fn running_zenoh_id(&self, runtime_id: RuntimeId) -> Result<ZenohId, SandboxError> {
+        let runtimes = self.runtimes.read().await;
+        let runtime_process = runtimes
+            .get(&runtime_id)
+            .ok_or(SandboxError::RuntimeNotFound(runtime_id))?;
+        match (runtime_process.state, runtime_process.zenoh_id) {
+            (RuntimeState::Running, Some(zid)) => Ok(zid),
+            _ => Err(SandboxError::RuntimeNotStarted(runtime_id)),
+        }
+    }
+}
+
+/// Timeout for a running runtime to answer a request
+const REQUEST_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(5);
+
+/// Timeout for a running runtime to read and publish a file, which may be large
+const PUBLISH_FILE_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(60);
+
+impl ZenohRuntimes {
+    /// Send a request to the receiver task of a running runtime and wait for the response.
+    /// `what` names the request in error messages.
+    async fn request<T>(
+        &self,
+        runtime_id: RuntimeId,
+        what: &str,
+        make_request: impl FnOnce(oneshot::Sender<T>) -> RuntimeRequest,
+    ) -> Result<T, SandboxError> {
+        self.request_with_timeout(runtime_id, what, REQUEST_TIMEOUT, make_request)
+            .await
+    }
+
+    /// Same as `request`, for requests that may take longer than `REQUEST_TIMEOUT`
+    async fn request_with_timeout<T>(
+        &self,
+        runtime_id: RuntimeId,
+        what: &str,
+        timeout: std::time::Duration,
+        make_request: impl FnOnce(oneshot::Sender<T>) -> RuntimeRequest,
+    ) -> Result<T, SandboxError> {
+        // Get the request channel
+        let request_tx = {
+            let runtimes = self.runtimes.read().await;
+            let runtime_process = runtimes
+                .get(&runtime_id)
+                .ok_or(SandboxError::RuntimeNotFound(runtime_id))?;
+            runtime_process
+                .request_tx
+                .clone()
+                .ok_or(SandboxError::RuntimeNotStarted(runtime_id))?
+        };
+
+        // Send request and wait for response
+        let (response_tx, response_rx) = oneshot::channel();
+        request_tx
+            .send(make_request(response_tx))
+            .await
+            .map_err(|_| SandboxError::IpcError(format!("Failed to send {} request", what)))?;
+
+        tokio::time::timeout(timeout, response_rx)
+            .await
+            .map_err(|_| SandboxError::Timeout(format!("Timeout waiting for {} response", what)))?
+            .map_err(|_| SandboxError::IpcError(format!("The {} request was cancelled", what)))
+    }
+
+    /// Get the current configuration of a running runtime
+    async fn live_config(&self, runtime_id: RuntimeId) -> Result<Config, SandboxError> {
+        self.request(runtime_id, "config", RuntimeRequest::GetConfig)
+            .await
+    }
+
+    /// Apply (key, JSON5 value) pairs to the configuration of a running runtime.
+    /// Returns the resulting configuration.
+    async fn update_live_config(
+        &self,
+        runtime_id: RuntimeId,
+        entries: Vec<(String, String)>,
+    ) -> Result<Config, SandboxError> {
+        self.request(runtime_id, "config update", |response_tx| {
+            RuntimeRequest::UpdateConfig(entries, response_tx)
+        })
+        .await?
+        .map_err(SandboxError::ConfigInvalid)
+    }
+
+    /// Measure `samples` round-trip times (in microseconds) from a running runtime
+    /// to the runtime with ZenohId `target`, each query waiting at most `timeout`.
+    async fn ping(
+        &self,
+        runtime_id: RuntimeId,
+        target: ZenohId,
+        samples: u32,
+        timeout: std::time::Duration,
+        payload_size: usize,
+    ) -> Result<Vec<u64>, SandboxError> {
+        // Queries are sequential, leave some margin for the IPC round trip
+        let total_timeout = timeout * samples + REQUEST_TIMEOUT;
+        self.request_with_timeout(runtime_id, "ping", total_timeout, |response_tx| {
+            RuntimeRequest::MeasureLatency {
+                target,
+                samples,
+                timeout,
+                payload_size,
+                response_tx,
+            }
+        })
+        .await?
+        .map_err(SandboxError::from)
+    }
+
+    /// Declare a test publisher or subscriber on the session of a running runtime
+    async fn declare_test_entity(
+        &self,
+        runtime_id: RuntimeId,
+        id: u64,
+        entity: TestEntity,
+    ) -> Result<(), SandboxError> {
+        self.request(runtime_id, "test entity declaration", |response_tx| {
+            RuntimeRequest::DeclareTestEntity {
+                id,
+                entity,
+                response_tx,
+            }
+        })
+        .await?
+        .map_err(SandboxError::from)
+    }
+
+    /// Undeclare a test entity from the session of a running runtime
+    async fn undeclare_test_entity(
+        &self,
+        runtime_id: RuntimeId,
+        id: u64,
+    ) -> Result<(), SandboxError> {
+        let runtimes = self.runtimes.read().await;
+        let request_tx = runtimes
+            .get(&runtime_id)
+            .and_then(|runtime_process| runtime_process.request_tx.clone())
+            .ok_or(SandboxError::RuntimeNotStarted(runtime_id))?;
+        request_tx
+            .send(RuntimeRequest::UndeclareTestEntity(id))
+            .await
+            .map_err(|_| {
+                SandboxError::IpcError("Failed to send test entity undeclaration".to_string())
+            })
+    }
+
+    /// Run a `get` on `selector` from the session of a running runtime,
+    /// collecting the replies received within `timeout`.
+    async fn query(
+        &self,
+        runtime_id: RuntimeId,
+        selector: String,
+        timeout: std::time::Duration,
+    ) -> Result<Vec<QueryReply>, SandboxError> {
+        self.request_with_timeout(runtime_id, "query", timeout + REQUEST_TIMEOUT, |response_tx| {
+            RuntimeRequest::Query {
+                selector,
+                timeout,
+                response_tx,
+            }
+        })
+        .await?
+        .map_err(SandboxError::from)
+    }
+
+    /// Open a transport from a running runtime to the runtime `target` through any of `locators`
+    async fn connect(
+        &self,
+        runtime_id: RuntimeId,
+        target: ZenohId,
+        locators: Vec<String>,
+    ) -> Result<(), SandboxError> {
+        self.request_with_timeout(runtime_id, "connect", 2 * REQUEST_TIMEOUT, |response_tx| {
+            RuntimeRequest::Connect {
+                target,
+                locators,
+                response_tx,
+            }
+        })
+        .await?
+        .map_err(SandboxError::from)
+    }
+
+    /// Put the bytes of the file at `path` from the session of a running runtime.
+    /// Returns the number of bytes published.
+    async fn publish_file(
+        &self,
+        runtime_id: RuntimeId,
+        keyexpr: String,
+        path: PathBuf,
+        encoding: Option<String>,
+    ) -> Result<usize, SandboxError> {
+        self.request_with_timeout(
+            runtime_id,
+            "publish file",
+            PUBLISH_FILE_TIMEOUT,
+            |response_tx| RuntimeRequest::PublishFile {
+                keyexpr,
+                path,
+                encoding,
+                response_tx,
+            },
+        )
+        .await?
+        .map_err(SandboxError::from)
+    }
+}
+
+/// Convert a zenoh::Config into validated sandbox config JSON
+fn config_to_json(config: &Config) -> Result<ZenohConfigJson, String> {
+    let config_json = serde_json::to_value(config)
+        .map_err(|e| format!("Failed to serialize config: {}", e))?;
+    ZenohConfigJson::from_json(config_json)
+}
+
+/// Exchange `Hello` messages with a runtime process that just connected,
+/// failing if it speaks another protocol version. Returns the codec of the following messages.
+async fn handshake(
+    reader: &mut FrameReader<IpcReadHalf>,
+    writer: &mut IpcWriteHalf,
+) -> Result<Codec, String> {
+    // Runtime binaries from before the handshake wait for Start without saying anything
+    let frame = tokio::time::timeout(HANDSHAKE_TIMEOUT, reader.next_frame())
+        .await
+        .map_err(|_| {
+            "Timeout waiting for the runtime handshake. The runtime binary may be outdated, \
+             rebuild it with 'cargo build --bins'"
+                .to_string()
+        })?
+        .map_err(|e| format!("Failed to read handshake: {}", e))?;
+    let Some(Frame::Message(frame)) = frame else {
+        return Err("Connection closed by the runtime process during handshake".to_string());
+    };
+    let Ok(RuntimeToMain::Hello {
+        protocol_version,
+        runtime_version,
+        codecs,
+    }) = Codec::Json.decode(&frame)
+    else {
+        return Err("Unexpected handshake from the runtime process".to_string());
+    };
+    if protocol_version != protocol::PROTOCOL_VERSION {
+        return Err(format!(
+            "Runtime binary {} speaks protocol version {}, the sandbox expects version {}. \
+             Rebuild it with 'cargo build --bins'",
+            runtime_version,
+            protocol_version,
+            protocol::PROTOCOL_VERSION
+        ));
+    }
+    let codec = Codec::negotiate(&codecs);
+    tracing::debug!(
+        "Handshake with runtime binary {} done, using codec {:?}",
+        runtime_version,
+        codec
+    );
+
+    let hello = MainToRuntime::Hello {
+        protocol_version: protocol::PROTOCOL_VERSION,
+        sandbox_version: env!("CARGO_PKG_VERSION").to_string(),
+        codec,
+    };
+    protocol::write_message(writer, Codec::Json, &hello)
+        .await
+        .map_err(|e| format!("Failed to send handshake: {}", e))?;
+    Ok(codec)
+}
+
+/// Wait for the next heartbeat tick, forever if heartbeats are disabled
+async fn next_heartbeat(heartbeat: &mut Option<tokio::time::Interval>) {
+    match heartbeat {
+        Some(interval) => {
+            interval.tick().await;
+        }
+        None => std::future::pending().await,
+    }
+}
+
+/// Spawn a task owning the runtime process: it waits for the process to exit
+/// (or kills it when asked through `kill_rx`) and reports the exit to `ZenohRuntimes`.
+fn spawn_exit_watcher(
+    app: AppHandle,
+    runtime_id: RuntimeId,
+    mut child: Child,
+    kill_rx: oneshot::Receiver<()>,
+) -> JoinHandle<()> {
+    tokio::spawn(
+        async move {
+            let status = tokio::select! {
+                status = child.wait() => status,
+                _ = kill_rx => {
+                    let _ = child.kill().await;
+                    child.wait().await
+                }
+            };
+            app.state::<ZenohRuntimes>()
+                .handle_exit(&app, runtime_id, status.ok())
+                .await;
+            app.state::<Testing>().forget_runtime(runtime_id);
+        }
+        .instrument(tracing::debug_span!("exit_watcher", runtime_id)),
+    )
+}
+
+// ============================================================================
+// Sandbox metrics
+// ============================================================================
+
+/// Interval between two samples of the resource usage of the runtime processes
+const SANDBOX_METRICS_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Latest resource usage of the runtime processes, sampled by `sample_sandbox_metrics`
+#[derive(Default)]
+pub struct ProcessMonitor {
+    latest: ParkingLotRwLock<SandboxMetrics>,
+}
+
+/// Sample the CPU, memory, open files and threads of the running runtime processes,
+/// for the lifetime of the app
+async fn sample_sandbox_metrics(app: AppHandle) {
+    let mut system = System::new();
+    let mut sampled_pids: Vec<Pid> = Vec::new();
+    let mut interval = tokio::time::interval(SANDBOX_METRICS_INTERVAL);
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+    loop {
+        interval.tick().await;
+        let processes: Vec<(RuntimeId, Option<String>, Pid)> = {
+            let runtimes = app.state::<ZenohRuntimes>();
+            let runtimes = runtimes.runtimes.read().await;
+            runtimes
+                .iter()
+                .filter(|(_, runtime_process)| runtime_process.state.is_active())
+                .filter_map(|(runtime_id, runtime_process)| {
+                    let pid = Pid::from_u32(runtime_process.pid?);
+                    Some((*runtime_id, runtime_process.options.name.clone(), pid))
+                })
+                .collect()
+        };
+
+        // Refresh the processes of the previous sample too, so that exited ones are forgotten
+        sampled_pids.extend(processes.iter().map(|(_, _, pid)| *pid));
+        sampled_pids.sort_unstable();
+        sampled_pids.dedup();
+        system.refresh_processes_specifics(
+            ProcessesToUpdate::Some(&sampled_pids),
+            true,
+            ProcessRefreshKind::nothing()
+                .with_memory()
+                .with_cpu()
+                .with_tasks(),
+        );
+        sampled_pids = processes.iter().map(|(_, _, pid)| *pid).collect();
+
+        let mut metrics: Vec<ProcessMetrics> = processes
+            .into_iter()
+            .filter_map(|(runtime_id, name, pid)| {
+                let process = system.process(pid)?;
+                Some(ProcessMetrics {
+                    runtime_id,
+                    name,
+                    pid: pid.as_u32(),
+                    cpu_percent: process.cpu_usage(),
+                    memory_bytes: process.memory(),
+                    open_files: process.open_files(),
+                    threads: process.tasks().map(|tasks| tasks.len()),
+                })
+            })
+            .collect();
+        metrics.sort_by_key(|metrics| metrics.runtime_id);
+        *app.state::<ProcessMonitor>().latest.write() = SandboxMetrics {
+            sampled_at: Some(chrono::Utc::now()),
+            total_cpu_percent: metrics.iter().map(|metrics| metrics.cpu_percent).sum(),
+            total_memory_bytes: metrics.iter().map(|metrics| metrics.memory_bytes).sum(),
+            runtimes: metrics,
+        };
+    }
+}
+
+// ============================================================================
+// Tauri commands
+// ============================================================================
+
+/// Create a new validated Zenoh config from edit content
+#[tauri::command]
+#[tracing::instrument(skip_all)]
+async fn create_zenoh_config(
+    edit: ZenohConfigEdit,
+) -> Result<(ZenohConfigEdit, ZenohConfigJson), SandboxError> {
+    let config = edit.to_config().map_err(SandboxError::ConfigInvalid)?;
+
+    let config_json = serde_json::to_value(&config)
+        .map_err(|e| format!("Failed to serialize config: {}", e))?;
+    let validated = ZenohConfigJson::from_json(config_json).map_err(SandboxError::ConfigInvalid)?;
+
+    Ok((edit, validated))
+}
+
+/// Get the default configuration as JSON string
+#[tauri::command]
+#[tracing::instrument]
+async fn get_default_config_json() -> Result<String, SandboxError> {
+    let config = zenoh::config::Config::default();
+
+    let json = serde_json::to_string_pretty(&config)
+        .map_err(|e| format!("Failed to serialize default config: {}", e))?;
+    Ok(json)
+}
+
+/// Validate JSON string as zenoh config and return validated JSON
+#[tauri::command]
+#[tracing::instrument(skip_all)]
+async fn validate_config(content: String) -> Result<ZenohConfigJson, SandboxError> {
+    let config = zenoh::Config::from_json5(&content).map_err(|e| {
+        SandboxError::ConfigInvalid(format!("Invalid JSON5 config: {}", e))
+    })?;
+    let config_json = serde_json::to_value(&config)
+        .map_err(|e| format!("Failed to serialize config: {}", e))?;
+    ZenohConfigJson::from_json(config_json).map_err(SandboxError::ConfigInvalid)
+}
+
+/// Compute the difference between two JSON configurations.
+/// Returns a JSON object containing only fields that differ from base.
+/// Deleted fields are represented as null.
+#[tauri::command]
+#[tracing::instrument(skip_all)]
+async fn compute_config_diff(
+    base: ZenohConfigJson,
+    modified: ZenohConfigJson,
+) -> Result<serde_json::Value, SandboxError> {
+    let diff = ts::config::json_diff(base.as_json(), modified.as_json());
+    Ok(diff)
+}
+
+/// Apply a diff produced by `compute_config_diff` to a base configuration.
+/// Null values in the diff delete fields. Returns the validated resulting config.
+#[tauri::command]
+#[tracing::instrument(skip_all)]
+async fn apply_config_diff(
+    base: ZenohConfigJson,
+    diff: serde_json::Value,
+) -> Result<ZenohConfigJson, SandboxError> {
+    ts::config::apply_config_diff(&base, &diff).map_err(SandboxError::ConfigInvalid)
+}
+
+/// Declare a new runtime with the given config, allocating resources but not starting it yet.
+/// The JSON5 `source` of the config, if given, is kept with its comments.
+/// Returns the RuntimeId that can be used to start the runtime.
+#[tauri::command]
+#[tracing::instrument(skip_all)]
+async fn declare_runtime(
+    config: ZenohConfigJson,
+    options: Option<RuntimeOptions>,
+    source: Option<String>,
+    runtimes_state: State<'_, ZenohRuntimes>,
+) -> Result<DeclareRuntimeResponse, SandboxError> {
+    let source = ts::config::check_config_source(source, &config)?;
+    let (runtime_id, port) = runtimes_state
+        .declare(config, options.unwrap_or_default(), None)
+        .await?;
+    runtimes_state.set_config_source(runtime_id, source).await?;
+    let rest_port = runtimes_state
+        .runtimes
+        .read()
+        .await
+        .get(&runtime_id)
+        .and_then(|runtime_process| runtime_process.rest_port);
+
+    Ok(DeclareRuntimeResponse {
+        runtime_id,
+        ws_port: port,
+        rest_port,
+    })
+}
+
+/// Start a previously declared runtime.
+/// Returns the ZenohId string.
+#[tauri::command]
+#[tracing::instrument(skip(app, runtimes_state))]
+async fn start_runtime(
+    runtime_id: RuntimeId,
+    app: AppHandle,
+    runtimes_state: State<'_, ZenohRuntimes>,
+) -> Result<String, SandboxError> {
+    let zid = runtimes_state.launch(&app, runtime_id).await?;
+    Ok(zid.to_string())
+}
+
+/// stop (close) a Zenoh runtime by its RuntimeId.
+#[tauri::command]
+#[tracing::instrument(skip(runtimes_state, _logs_state))]
+async fn zenoh_runtime_stop(
+    runtime_id: RuntimeId,
+    runtimes_state: State<'_, ZenohRuntimes>,
+    _logs_state: State<'_, LogStorage>,
+) -> Result<(), SandboxError> {
+    runtimes_state.shutdown(runtime_id, true).await?;
+
+    // Don't clear logs - keep them available for stopped runtime
+    // Don't remove from state - keep runtime entry for UI
+
+    Ok(())
+}
+
+/// Restart a runtime, reusing its RuntimeId, allocated port and sandbox config.
+/// Logs are kept, so the log stream continues across the restart.
+/// Returns the ZenohId string of the new process.
+#[tauri::command]
+#[tracing::instrument(skip(app, runtimes_state))]
+async fn restart_runtime(
+    runtime_id: RuntimeId,
+    app: AppHandle,
+    runtimes_state: State<'_, ZenohRuntimes>,
+) -> Result<String, SandboxError> {
+    runtimes_state.shutdown(runtime_id, false).await?;
+    let zid = runtimes_state.launch(&app, runtime_id).await?;
+    Ok(zid.to_string())
+}
+
+/// List all runtime IDs.
+#[tauri::command]
+#[tracing::instrument(skip_all)]
+async fn zenoh_runtime_list(
+    state: State<'_, ZenohRuntimes>,
+) -> Result<Vec<RuntimeId>, SandboxError> {
+    let runtimes = state.runtimes.read().await;
+    let runtime_ids: Vec<RuntimeId> = runtimes.keys().copied().collect();
+    Ok(runtime_ids)
+}
+
+/// Get a summary of every runtime: name, state, ZenohId, port, mode, uptime and
+/// number of stored log entries per level, sorted by RuntimeId.
+#[tauri::command]
+#[tracing::instrument(skip_all)]
+async fn zenoh_runtime_list_detailed(
+    state: State<'_, ZenohRuntimes>,
+    logs_state: State<'_, LogStorage>,
+    scenarios_state: State<'_, Scenarios>,
+) -> Result<Vec<RuntimeSummary>, SandboxError> {
+    let names = scenarios_state.runtime_names().await;
+    let runtimes = state.runtimes.read().await;
+    let mut summaries: Vec<RuntimeSummary> = runtimes
+        .iter()
+        .map(|(runtime_id, runtime_process)| RuntimeSummary {
+            id: *runtime_id,
+            name: runtime_process
+                .options
+                .name
+                .clone()
+                .or_else(|| names.get(runtime_id).cloned()),
+            state: runtime_process.state,
+            zid: runtime_process.zenoh_id.map(|zid| zid.to_string()),
+            port: runtime_process.allocated_port,
+            mode: runtime_process.sandbox_config.mode(),
+            uptime_secs: runtime_process
+                .started_at
+                .map(|started_at| started_at.elapsed().as_secs()),
+            log_counts_by_level: logs_state.counts_by_level(*runtime_id),
+        })
+        .collect();
+    summaries.sort_by_key(|summary| summary.id);
+    Ok(summaries)
+}
+
+/// Get the status of every runtime: state, ZenohId, PID, port and uptime.
+#[tauri::command]
+#[tracing::instrument(skip_all)]
+async fn zenoh_runtime_status(
+    state: State<'_, ZenohRuntimes>,
+) -> Result<Vec<RuntimeStatus>, SandboxError> {
+    Ok(state.statuses().await)
+}
+
+/// Get the PID of the process of a runtime, the files receiving its stdout and stderr
+/// and its IPC endpoint. The files are kept after the process exits.
+#[tauri::command]
+#[tracing::instrument(skip(state))]
+async fn zenoh_runtime_info(
+    runtime_id: RuntimeId,
+    state: State<'_, ZenohRuntimes>,
+) -> Result<RuntimeInfo, SandboxError> {
+    let runtimes = state.runtimes.read().await;
+    let runtime_process = runtimes
+        .get(&runtime_id)
+        .ok_or(SandboxError::RuntimeNotFound(runtime_id))?;
+    let display =
+        |path: &Option<PathBuf>| path.as_ref().map(|path| path.to_string_lossy().to_string());
+    Ok(RuntimeInfo {
+        runtime_id,
+        pid: runtime_process.pid,
+        stdout_log: display(&runtime_process.stdout_log),
+        stderr_log: display(&runtime_process.stderr_log),
+        socket_path: runtime_process.socket_path.clone(),
+    })
+}
+
+/// Get the latest metrics of a runtime: transports, sessions and routing entries, and the
+/// memory and CPU usage of its process. Sampled every `STATS_INTERVAL` while running,
+/// None until the first sample arrives.
+#[tauri::command]
+#[tracing::instrument(skip(state))]
+async fn zenoh_runtime_stats(
+    runtime_id: RuntimeId,
+    state: State<'_, ZenohRuntimes>,
+) -> Result<Option<RuntimeStats>, SandboxError> {
+    let runtimes = state.runtimes.read().await;
+    let runtime_process = runtimes
+        .get(&runtime_id)
+        .ok_or(SandboxError::RuntimeNotFound(runtime_id))?;
+    if !runtime_process.state.is_active() {
+        return Ok(None);
+    }
+    let stats = runtime_process.stats.lock().clone();
+    Ok(stats)
+}
+
+/// Get the CPU, memory, open files and threads of every running runtime process, with
+/// the totals for the sandbox, to find which runtime uses the most resources.
+/// Sampled every `SANDBOX_METRICS_INTERVAL`.
+#[tauri::command]
+#[tracing::instrument(skip_all)]
+async fn get_sandbox_metrics(
+    monitor: State<'_, ProcessMonitor>,
+) -> Result<SandboxMetrics, SandboxError> {
+    Ok(monitor.latest.read().clone())
+}
+
+/// Get how to connect to the remote_api plugin of a runtime: its WebSocket URL, the
+/// locator to open a zenoh-ts session with, and whether the port accepts connections.
+#[tauri::command]
+#[tracing::instrument(skip(state))]
+async fn zenoh_runtime_remote_api_info(
+    runtime_id: RuntimeId,
+    state: State<'_, ZenohRuntimes>,
+) -> Result<RemoteApiInfo, SandboxError> {
+    let port = state
+        .runtimes
+        .read()
+        .await
+        .get(&runtime_id)
+        .map(|runtime_process| runtime_process.allocated_port)
+        .ok_or(SandboxError::RuntimeNotFound(runtime_id))?;
+
+    Ok(RemoteApiInfo {
+        runtime_id,
+        port,
+        url: format!("ws://127.0.0.1:{}", port),
+        locator: format!("ws/127.0.0.1:{}", port),
+        listening: is_port_listening(port, REMOTE_API_PROBE_TIMEOUT).await,
+    })
+}
 
 /// Get the initial configuration used to start a runtime by its RuntimeId.
-/// Returns the sandbox::ZenohConfigJson.
+/// Returns the declared ZenohConfigJson.
 #[tauri::command]
+#[tracing::instrument(skip(state))]
 async fn zenoh_runtime_config(
     runtime_id: RuntimeId,
     state: State<'_, ZenohRuntimes>,
-) -> Result<ZenohConfigJson, String> {
+) -> Result<ZenohConfigJson, SandboxError> {
     // Get the config from state
     let runtimes = state.runtimes.read().await;
     let runtime_process = runtimes
         .get(&runtime_id)
-        .ok_or_else(|| format!("Runtime {} not found", runtime_id))?;
+        .ok_or(SandboxError::RuntimeNotFound(runtime_id))?;
 
     Ok(runtime_process.sandbox_config.clone())
 }
 
-/// Get the current Zenoh configuration from a running runtime.
-/// This returns the actual zenoh::Config.
+/// Replace the declared configuration of a runtime, with the JSON5 `source` of the new
+/// config if given.
+/// The change is annotated in the timeline and, if `annotate_log` is set, in the runtime's log stream.
+/// The new config is used the next time the runtime is started.
+/// Returns the diff between the old and new config.
 #[tauri::command]
-async fn zenoh_runtime_config_json(
+#[tracing::instrument(skip(config, source, runtimes_state, logs_state, timeline_state))]
+async fn zenoh_runtime_set_config(
+    runtime_id: RuntimeId,
+    config: ZenohConfigJson,
+    source: Option<String>,
+    annotate_log: Option<bool>,
+    runtimes_state: State<'_, ZenohRuntimes>,
+    logs_state: State<'_, LogStorage>,
+    timeline_state: State<'_, Timeline>,
+) -> Result<serde_json::Value, SandboxError> {
+    let source = ts::config::check_config_source(source, &config)?;
+    let old_config = {
+        let mut runtimes = runtimes_state.runtimes.write().await;
+        let runtime_process = runtimes
+            .get_mut(&runtime_id)
+            .ok_or(SandboxError::RuntimeNotFound(runtime_id))?;
+        let old_config = runtime_process.replace_config(config.clone(), ConfigChangeSource::Patch);
+        runtime_process.config_source = source;
+        old_config
+    };
+
+    let logs = annotate_log.unwrap_or(false).then(|| logs_state.inner());
+    Ok(timeline_state.annotate_config_change(
+        runtime_id,
+        ConfigChangeSource::Patch,
+        &old_config,
+        &config,
+        logs,
+    ))
+}
+
+/// Begin editing the declared configuration of a runtime.
+/// Returns the current config with its etag, which must be passed to `commit_config_edit`.
+#[tauri::command]
+#[tracing::instrument(skip(state))]
+async fn begin_config_edit(
     runtime_id: RuntimeId,
     state: State<'_, ZenohRuntimes>,
-) -> Result<Config, String> {
-    // Get the request channel
-    let request_tx = {
-        let runtimes = state.runtimes.read().await;
+) -> Result<ConfigEditSession, SandboxError> {
+    let runtimes = state.runtimes.read().await;
+    let runtime_process = runtimes
+        .get(&runtime_id)
+        .ok_or(SandboxError::RuntimeNotFound(runtime_id))?;
+
+    Ok(ConfigEditSession {
+        config: runtime_process.sandbox_config.clone(),
+        etag: runtime_process.sandbox_config.etag(),
+        source: runtime_process.config_source.clone(),
+    })
+}
+
+/// Commit an edit started with `begin_config_edit`, with the JSON5 `source` of the new
+/// config if given.
+/// If the declared config was modified in the meantime (its etag differs), nothing is written
+/// and a conflict with the current config is returned instead.
+#[tauri::command]
+#[tracing::instrument(skip(config, source, runtimes_state, app))]
+async fn commit_config_edit(
+    runtime_id: RuntimeId,
+    config: ZenohConfigJson,
+    etag: String,
+    source: Option<String>,
+    annotate_log: Option<bool>,
+    runtimes_state: State<'_, ZenohRuntimes>,
+    app: AppHandle,
+) -> Result<ConfigCommitResult, SandboxError> {
+    let source = ts::config::check_config_source(source, &config)?;
+    let old_config = {
+        let mut runtimes = runtimes_state.runtimes.write().await;
         let runtime_process = runtimes
-            .get(&runtime_id)
-            .ok_or_else(|| format!("Runtime {} not found", runtime_id))?;
-        runtime_process.request_tx.clone()
-            .ok_or_else(|| "Runtime not started yet".to_string())?
+            .get_mut(&runtime_id)
+            .ok_or(SandboxError::RuntimeNotFound(runtime_id))?;
+
+        let current_etag = runtime_process.sandbox_config.etag();
+        if current_etag != etag {
+            tracing::debug!("Config edit conflict: expected {}, found {}", etag, current_etag);
+            let current = runtime_process.sandbox_config.clone();
+            let diff = ts::config::json_diff(current.as_json(), config.as_json());
+            return Ok(ConfigCommitResult::Conflict {
+                current,
+                etag: current_etag,
+                diff,
+            });
+        }
+        let old_config = runtime_process.replace_config(config.clone(), ConfigChangeSource::Patch);
+        runtime_process.config_source = source;
+        old_config
     };
 
-    // Send request and wait for response
-    let (response_tx, response_rx) = oneshot::channel();
-    request_tx
-        .send(RuntimeRequest::GetConfig(response_tx))
-        .await
-        .map_err(|_| "Failed to send config request".to_string())?;
+    let logs_state = app.state::<LogStorage>();
+    let logs = annotate_log.unwrap_or(false).then(|| logs_state.inner());
+    let diff = app.state::<Timeline>().annotate_config_change(
+        runtime_id,
+        ConfigChangeSource::Patch,
+        &old_config,
+        &config,
+        logs,
+    );
+    Ok(ConfigCommitResult::Committed {
+        etag: config.etag(),
+        diff,
+    })
+}
 
-    // Wait for response with timeout
-    let config = tokio::time::timeout(std::time::Duration::from_secs(5), response_rx)
-        .await
-        .map_err(|_| "Timeout waiting for config response".to_string())?
-        .map_err(|_| "Config request was cancelled".to_string())?;
+/// Get the current Zenoh configuration from a running runtime.
+/// This returns the actual zenoh::Config, with the ports allocated to its listen endpoints.
+#[tauri::command]
+#[tracing::instrument(skip(state))]
+async fn zenoh_runtime_config_json(
+    runtime_id: RuntimeId,
+    state: State<'_, ZenohRuntimes>,
+) -> Result<Config, SandboxError> {
+    state.live_config(runtime_id).await
+}
 
-    Ok(config)
+/// Apply a JSON patch to the configuration of a running runtime, without restarting it.
+/// Nested objects in the patch address nested config keys, e.g.
+/// `{ "connect": { "endpoints": ["tcp/127.0.0.1:7447"] } }`.
+/// The declared config is left untouched. The change is annotated in the timeline and,
+/// if `annotate_log` is set, in the runtime's log stream.
+/// Returns the resulting running configuration.
+#[tauri::command]
+#[tracing::instrument(skip(patch, runtimes_state, logs_state, timeline_state))]
+async fn zenoh_runtime_update_config(
+    runtime_id: RuntimeId,
+    patch: serde_json::Value,
+    annotate_log: Option<bool>,
+    runtimes_state: State<'_, ZenohRuntimes>,
+    logs_state: State<'_, LogStorage>,
+    timeline_state: State<'_, Timeline>,
+) -> Result<Config, SandboxError> {
+    let entries = ts::config::json_patch_entries(&patch)?;
+
+    let old_config = runtimes_state.live_config(runtime_id).await?;
+    let new_config = runtimes_state
+        .update_live_config(runtime_id, entries)
+        .await?;
+
+    let new_config_json = config_to_json(&new_config)?;
+    runtimes_state
+        .record_live_config(runtime_id, new_config_json.clone())
+        .await;
+
+    let logs = annotate_log.unwrap_or(false).then(|| logs_state.inner());
+    timeline_state.annotate_config_change(
+        runtime_id,
+        ConfigChangeSource::LiveUpdate,
+        &config_to_json(&old_config)?,
+        &new_config_json,
+        logs,
+    );
+
+    Ok(new_config)
 }
 
-/// Get a page of logs from a specific runtime.
-/// Page 0 returns the most recent logs.
+/// Get a page of logs from a specific runtime, most recent first.
+/// Entries can be filtered by level, message search (substring or regex), target prefix
+/// and target patterns: `zenoh_transport::*` matches a module and its submodules,
+/// `zenoh*` the targets starting with `zenoh`, other patterns match exactly.
+/// Without cursor, the page holds the most recent logs. `before_seq` pages towards older
+/// entries and `after_seq` towards newer ones, without skipping or repeating entries
+/// when new ones arrive between calls.
 #[tauri::command]
+#[tracing::instrument(skip(state))]
+#[allow(clippy::too_many_arguments)]
 async fn zenoh_runtime_log(
     runtime_id: RuntimeId,
+    level: Option<LogEntryLevel>,
+    search: Option<LogSearch>,
+    target: Option<String>,
+    targets: Option<Vec<String>>,
+    before_seq: Option<u64>,
+    after_seq: Option<u64>,
+    state: State<'_, LogStorage>,
+) -> Result<LogPage, SandboxError> {
+    let filter = LogFilter::new(level, search, target)?.with_targets(targets);
+    Ok(state.get_page_by_cursor(runtime_id, &filter, before_seq, after_seq))
+}
+
+/// Get the distinct targets of the logs of a runtime, sorted, to suggest target filters
+#[tauri::command]
+#[tracing::instrument(skip(state))]
+async fn get_log_targets(
+    runtime_id: RuntimeId,
+    state: State<'_, LogStorage>,
+) -> Result<Vec<String>, SandboxError> {
+    Ok(state.targets(runtime_id))
+}
+
+/// Get the number of stored logs of a runtime, in total and per level,
+/// and the range of their sequence numbers
+#[tauri::command]
+#[tracing::instrument(skip(state))]
+async fn get_log_stats(
+    runtime_id: RuntimeId,
+    state: State<'_, LogStorage>,
+) -> Result<LogStats, SandboxError> {
+    Ok(state.stats(runtime_id))
+}
+
+/// Get a page of the sandbox timeline, optionally restricted to one runtime.
+/// Page 0 returns the most recent events.
+#[tauri::command]
+#[tracing::instrument(skip(state))]
+async fn get_timeline(
+    runtime_id: Option<RuntimeId>,
+    page: usize,
+    state: State<'_, Timeline>,
+) -> Result<Vec<TimelineEvent>, SandboxError> {
+    Ok(state.get_page(runtime_id, page))
+}
+
+/// Get a page of the sandbox backend's own logs.
+/// Page 0 returns the most recent logs.
+#[tauri::command]
+async fn sandbox_internal_logs(
     level: Option<LogEntryLevel>,
     page: usize,
     state: State<'_, LogStorage>,
-) -> Result<Vec<LogEntry>, String> {
-    Ok(state.get_page(runtime_id, level, page))
+) -> Result<Vec<LogEntry>, SandboxError> {
+    Ok(state.get_page(SANDBOX_RUNTIME_ID, &LogFilter::level(level), page))
+}
+
+/// Get the filter directive applied to the sandbox backend's own logs
+#[tauri::command]
+async fn get_sandbox_log_filter(
+    state: State<'_, SandboxTracing>,
+) -> Result<String, SandboxError> {
+    Ok(state.filter())
+}
+
+/// Set the filter directive applied to the sandbox backend's own logs,
+/// using the `EnvFilter` syntax (e.g. `zenoh_sandbox_lib=trace`)
+#[tauri::command]
+#[tracing::instrument(skip(state))]
+async fn set_sandbox_log_filter(
+    filter: String,
+    state: State<'_, SandboxTracing>,
+) -> Result<(), SandboxError> {
+    Ok(state.set_filter(&filter)?)
 }
 
 /// Cleanup logs and remove a stopped runtime.
 /// This should be called when removing a stopped runtime from the UI.
+/// Active runtimes are refused, removing them would leave their process record behind
+/// and release ports their process still binds.
 #[tauri::command]
+#[tracing::instrument(skip(runtimes_state, logs_state))]
 async fn zenoh_runtime_cleanup(
     runtime_id: RuntimeId,
     runtimes_state: State<'_, ZenohRuntimes>,
     logs_state: State<'_, LogStorage>,
-) -> Result<(), String> {
+) -> Result<(), SandboxError> {
     // Remove from runtime state
     {
         let mut runtimes = runtimes_state.runtimes.write().await;
-        runtimes.remove(&runtime_id);
+        if let Some(runtime_process) = runtimes.get(&runtime_id)
+            && runtime_process.state.is_active()
+        {
+            return Err(SandboxError::Other(format!(
+                "Runtime {} is {:?}, stop it before removing it",
+                runtime_id, runtime_process.state
+            )));
+        }
+        // The runtime is inactive, its process exited and no longer binds its ports
+        if let Some(runtime_process) = runtimes.remove(&runtime_id) {
+            runtime_process.release_ports(&mut *runtimes_state.port_tracker.write().await);
+        }
+    }
+    runtimes_state
+        .links
+        .write()
+        .await
+        .retain(|link| link.from != runtime_id && link.to != runtime_id);
+    for members in runtimes_state.groups.write().await.values_mut() {
+        members.remove(&runtime_id);
     }
 
     // Clear logs for this runtime
@@ -670,6 +2891,117 @@ async fn zenoh_runtime_cleanup(
     Ok(())
 }
 
+/// Get the directories used for files of runtime processes
+#[tauri::command]
+#[tracing::instrument(skip_all)]
+async fn get_storage_locations(
+    runtimes_state: State<'_, ZenohRuntimes>,
+) -> Result<StorageLocations, SandboxError> {
+    let dirs = runtimes_state.dirs.read().clone();
+    Ok(StorageLocations {
+        log_dir: dirs.log_dir.to_string_lossy().to_string(),
+        socket_dir: dirs.socket_dir.to_string_lossy().to_string(),
+    })
+}
+
+/// Switch the log and/or socket directory used by runtimes started from now on.
+/// If `migrate` is set, existing log files are moved to the new log directory.
+/// Running runtimes keep writing to their log file, which follows the move
+/// only if both directories are on the same filesystem.
+#[tauri::command]
+#[tracing::instrument(skip(runtimes_state))]
+async fn relocate_storage(
+    new_log_dir: Option<String>,
+    new_socket_dir: Option<String>,
+    migrate: bool,
+    runtimes_state: State<'_, ZenohRuntimes>,
+) -> Result<StorageRelocation, SandboxError> {
+    let absolute = |dir: String| {
+        std::path::absolute(&dir).map_err(|e| format!("Invalid directory {}: {}", dir, e))
+    };
+    let new_log_dir = new_log_dir.map(absolute).transpose()?;
+    let new_socket_dir = new_socket_dir.map(absolute).transpose()?;
+
+    // Socket paths must fit in sockaddr_un, leave room for the socket file name
+    #[cfg(unix)]
+    if let Some(socket_dir) = &new_socket_dir
+        && socket_dir.as_os_str().len() > 90
+    {
+        return Err(format!(
+            "Socket directory path is too long for Unix sockets: {}",
+            socket_dir.display()
+        )
+        .into());
+    }
+
+    // Create the new directories before switching to them
+    for dir in new_log_dir.iter().chain(new_socket_dir.iter()) {
+        tokio::fs::create_dir_all(dir)
+            .await
+            .map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+    }
+
+    let old_dirs = {
+        let mut dirs = runtimes_state.dirs.write();
+        let old_dirs = dirs.clone();
+        if let Some(log_dir) = &new_log_dir {
+            dirs.log_dir = log_dir.clone();
+        }
+        if let Some(socket_dir) = &new_socket_dir {
+            dirs.socket_dir = socket_dir.clone();
+        }
+        old_dirs
+    };
+    tracing::info!(
+        "Storage relocated: logs {} -> {:?}, sockets {} -> {:?}",
+        old_dirs.log_dir.display(),
+        new_log_dir,
+        old_dirs.socket_dir.display(),
+        new_socket_dir
+    );
+
+    let mut migrated_files = Vec::new();
+    let mut failed_files = Vec::new();
+    if migrate
+        && let Some(log_dir) = &new_log_dir
+        && *log_dir != old_dirs.log_dir
+    {
+        let mut entries = tokio::fs::read_dir(&old_dirs.log_dir)
+            .await
+            .map_err(|e| format!("Failed to read {}: {}", old_dirs.log_dir.display(), e))?;
+        while let Ok(Some(entry)) = entries.next_entry().await {
+            let path = entry.path();
+            if path.extension().is_none_or(|extension| extension != "log") {
+                continue;
+            }
+            let target = log_dir.join(entry.file_name());
+            match move_file(&path, &target).await {
+                Ok(()) => migrated_files.push(target.to_string_lossy().to_string()),
+                Err(e) => failed_files.push(format!("{}: {}", path.display(), e)),
+            }
+        }
+    }
+
+    let dirs = runtimes_state.dirs.read().clone();
+    Ok(StorageRelocation {
+        locations: StorageLocations {
+            log_dir: dirs.log_dir.to_string_lossy().to_string(),
+            socket_dir: dirs.socket_dir.to_string_lossy().to_string(),
+        },
+        migrated_files,
+        failed_files,
+    })
+}
+
+/// Move a file, copying it when source and target are on different filesystems
+async fn move_file(source: &std::path::Path, target: &std::path::Path) -> std::io::Result<()> {
+    if tokio::fs::rename(source, target).await.is_ok() {
+        return Ok(());
+    }
+    tokio::fs::copy(source, target).await?;
+    tokio::fs::remove_file(source).await
+}
+
 // ============================================================================
 // Tauri application entry point
 // ============================================================================
@@ -679,27 +3011,211 @@ pub fn run() {
     // Initialize log storage
     let log_storage = LogStorage::default();
 
+    // Initialize tracing of the sandbox backend itself
+    let sandbox_tracing = SandboxTracing::init(log_storage.clone());
+
+    // Initialize the channel of runtime lifecycle events
+    let events = RuntimeEvents::default();
+
     // Initialize runtime manager
-    let runtimes = ZenohRuntimes::default();
+    let runtimes = ZenohRuntimes::default().with_events(events.clone());
+
+    // Persist logs to the log directory if requested
+    if std::env::var(logs::PERSIST_LOGS_ENV).is_ok_and(|value| value == "1" || value == "true") {
+        let log_dir = runtimes.dirs.read().log_dir.clone();
+        if let Err(e) = log_storage.enable_persistence(log_dir.clone(), None) {
+            tracing::error!("Failed to enable log persistence in {}: {}", log_dir.display(), e);
+        }
+    }
+
+    // Initialize event timeline
+    let timeline = Timeline::default().with_events(events.clone());
 
     tauri::Builder::default()
         .plugin(tauri_plugin_opener::init())
         .manage(runtimes)
         .manage(log_storage)
+        .manage(timeline)
+        .manage(events)
+        .manage(sandbox_tracing)
+        .manage(Testing::default())
+        .manage(OperationResults::default())
+        .manage(Scenarios::default())
+        .manage(Topology::default())
+        .manage(Journal::default())
+        .manage(Orphans::default())
+        .manage(ProcessMonitor::default())
+        .setup(|app| {
+            let data_dir = app.path().app_data_dir()?;
+            app.manage(ConfigPresets::load(
+                data_dir.join(presets::PRESETS_FILE_NAME),
+            ));
+            app.manage(SessionAutosave::new(
+                data_dir.join(session::AUTOSAVE_FILE_NAME),
+            ));
+            app.manage(tls::TlsStore::new(data_dir.join(tls::TLS_DIR_NAME)));
+            app.manage(auth::UsrPwdStore::new(data_dir.join(auth::AUTH_DIR_NAME)));
+            tauri::async_runtime::spawn(events::forward_runtime_events(app.handle().clone()));
+            tauri::async_runtime::spawn(SessionAutosave::run(app.handle().clone()));
+            tauri::async_runtime::spawn(peers::monitor_expected_peers(app.handle().clone()));
+            tauri::async_runtime::spawn(topology::monitor_topology(app.handle().clone()));
+            tauri::async_runtime::spawn(watchdog::monitor_runtimes(app.handle().clone()));
+            tauri::async_runtime::spawn(logs::replay_persisted_logs(app.handle().clone()));
+            tauri::async_runtime::spawn(orphans::scan_on_startup(app.handle().clone()));
+            tauri::async_runtime::spawn(sample_sandbox_metrics(app.handle().clone()));
+            Ok(())
+        })
         .invoke_handler(tauri::generate_handler![
             validate_config,
+            config_validation::validate_config_fields,
+            acl::validate_acl_config,
+            acl::build_acl_config,
             get_default_config_json,
             compute_config_diff,
+            apply_config_diff,
+            config_schema::get_config_schema,
+            tuning::tune_for_low_latency,
+            tuning::tune_for_high_throughput,
+            tuning::tune_for_lossy_network,
+            tuning::apply_transport_profile,
+            config_history::get_config_history,
+            config_history::revert_config,
             create_zenoh_config,
             declare_runtime,
             start_runtime,
             zenoh_runtime_stop,
+            restart_runtime,
+            clone::clone_runtime,
+            endpoints::check_port_conflicts,
+            bulk::start_runtimes,
+            bulk::stop_runtimes,
+            bulk::stop_all_runtimes,
+            groups::create_group,
+            groups::delete_group,
+            groups::assign_runtime_to_group,
+            groups::list_groups,
+            groups::start_group,
+            groups::stop_group,
+            keyexpr::validate_keyexpr,
+            keyexpr::keyexpr_intersects,
+            keyexpr::keyexpr_includes,
+            latency::run_mesh_latency_sweep,
+            latency::run_latency_test,
+            zenoh_admin::admin_query,
+            zenoh_admin::admin_list_routers,
+            zenoh_admin::admin_list_sessions,
+            zenoh_admin::admin_get_routes,
+            peers::set_expected_peers,
+            peers::topology_drift,
+            environment::environment_report,
+            journal::set_runtime_notes,
+            journal::get_runtime_notes,
+            journal::add_journal_entry,
+            journal::get_journal,
+            journal::remove_journal_entry,
+            orphans::list_orphans,
+            orphans::cleanup_orphans,
+            topology::get_topology,
+            topology::ack_topology,
+            topology::resync_topology,
+            topology::get_interest_map,
+            links::connect_runtimes,
+            links::list_runtime_links,
+            scouting::get_discovered_peers,
+            scouting::get_runtime_scouting,
+            scouting::set_runtime_scouting,
+            downsampling::get_runtime_downsampling,
+            downsampling::set_runtime_downsampling,
+            downsampling::verify_downsampling,
+            shm::verify_shared_memory,
+            tls::configure_tls,
+            auth::configure_usrpwd,
+            auth::check_usrpwd_links,
+            transform::apply_config_transform,
+            testing::declare_test_publisher,
+            testing::declare_liveliness_token,
+            testing::subscribe_liveliness,
+            testing::get_liveliness_events,
+            testing::declare_test_subscriber,
+            testing::declare_sequence_publisher,
+            testing::start_periodic_publisher,
+            testing::stop_periodic_publisher,
+            testing::declare_sequence_checker,
+            testing::get_sequence_report,
+            testing::declare_test_queryable,
+            testing::run_test_get,
+            testing::publish_file,
+            testing::undeclare_test_entity,
+            testing::list_test_entities,
+            testing::list_active_keyexprs,
+            testing::get_test_samples,
+            testing::decode_sample,
+            testing::get_subscriber_stats,
+            testing::start_recording,
+            testing::stop_recording,
+            testing::replay_recording,
+            reports::list_operations,
+            reports::export_report,
+            presets::save_config_preset,
+            presets::list_config_presets,
+            presets::load_config_preset,
+            presets::delete_config_preset,
+            scenarios::load_scenario,
+            scenarios::start_scenario,
+            scenarios::stop_scenario,
+            session::export_session,
+            session::import_session,
+            session::end_session,
             zenoh_runtime_list,
+            zenoh_runtime_list_detailed,
+            zenoh_runtime_status,
+            zenoh_runtime_info,
+            zenoh_runtime_remote_api_info,
+            zenoh_runtime_stats,
+            get_sandbox_metrics,
             zenoh_runtime_config,
             zenoh_runtime_config_json,
+            drift::zenoh_runtime_config_drift,
+            zenoh_runtime_update_config,
+            zenoh_runtime_set_config,
+            begin_config_edit,
+            commit_config_edit,
             zenoh_runtime_log,
+            get_log_stats,
+            get_log_targets,
+            logs::get_merged_logs,
+            logs::get_log_timeline,
+            logs::zenoh_runtime_log_export,
+            logs::zenoh_runtime_raw_logs,
+            log_archive::export_all_logs,
+            logs::get_log_persistence,
+            logs::set_log_persistence,
+            get_timeline,
+            sandbox_internal_logs,
+            get_sandbox_log_filter,
+            set_sandbox_log_filter,
             zenoh_runtime_cleanup,
+            get_storage_locations,
+            relocate_storage,
         ])
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .build(tauri::generate_context!())
+        .expect("error while running tauri application")
+        .run(|app, event| {
+            // Don't leave runtime processes behind
+            if let tauri::RunEvent::Exit = event {
+                // Save the runtimes before stopping them, to record which were running
+                if let Some(autosave) = app.try_state::<SessionAutosave>() {
+                    tauri::async_runtime::block_on(autosave.save(app));
+                }
+                let stopped = tauri::async_runtime::block_on(
+                    app.state::<ZenohRuntimes>().stop_all(STOP_ALL_TIMEOUT),
+                );
+                tracing::info!("Stopped {} runtimes on exit", stopped);
+                let adopted =
+                    tauri::async_runtime::block_on(app.state::<Orphans>().stop_adopted());
+                if adopted > 0 {
+                    tracing::info!("Stopped {} adopted orphan processes on exit", adopted);
+                }
+            }
+        });
 }
@@ -0,0 +1,264 @@
+use std::{ops::RangeInclusive, time::Duration};
+
+use sysinfo::{Pid, ProcessRefreshKind, ProcessesToUpdate, System};
+use tauri::{AppHandle, Emitter, Manager};
+use tokio::task::JoinHandle;
+use tracing::Instrument;
+
+use crate::{
+    RuntimeId, ZenohRuntimes,
+    error::SandboxError,
+    logs::LogStorage,
+    ts::{
+        log::LogEntryLevel,
+        runtime::{MemoryLimitAction, RuntimeMemoryLimitEvent, RuntimeOptions},
+    },
+};
+
+// ============================================================================
+// Constants
+// ============================================================================
+
+/// Niceness values accepted by `setpriority`, from the highest priority to the lowest
+const NICE_RANGE: RangeInclusive<i32> = -20..=19;
+
+/// Interval between two reads of the resident set size of a runtime process
+/// with a memory limit
+const MEMORY_CHECK_INTERVAL: Duration = Duration::from_secs(2);
+
+// ============================================================================
+// Scheduling of runtime processes
+// ============================================================================
+
+/// Validate the niceness, CPU affinity and memory limit requested in the runtime options
+pub fn validate(options: &RuntimeOptions) -> Result<(), String> {
+    if let Some(nice) = options.nice {
+        if !cfg!(unix) {
+            return Err("Setting the niceness is only supported on Unix".to_string());
+        }
+        if !NICE_RANGE.contains(&nice) {
+            return Err(format!(
+                "Invalid niceness {}: expected a value from {} to {}",
+                nice,
+                NICE_RANGE.start(),
+                NICE_RANGE.end()
+            ));
+        }
+    }
+    if let Some(cpus) = &options.cpu_affinity {
+        validate_affinity(cpus)?;
+    }
+    if options.memory_limit_mb == Some(0) {
+        return Err("Memory limit must be at least 1 MiB".to_string());
+    }
+    Ok(())
+}
+
+#[cfg(target_os = "linux")]
+fn validate_affinity(cpus: &[usize]) -> Result<(), String> {
+    if cpus.is_empty() {
+        return Err("CPU affinity must list at least one CPU".to_string());
+    }
+    match cpus.iter().find(|cpu| **cpu >= libc::CPU_SETSIZE as usize) {
+        Some(cpu) => Err(format!("Invalid CPU {} in CPU affinity", cpu)),
+        None => Ok(()),
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+fn validate_affinity(_cpus: &[usize]) -> Result<(), String> {
+    Err("CPU affinity is only supported on Linux".to_string())
+}
+
+/// Restrict the runtime process to the given CPUs
+#[cfg(target_os = "linux")]
+fn set_affinity(cpus: &[usize]) -> std::io::Result<()> {
+    // SAFETY: cpu_set_t is a plain bit set, valid when zeroed
+    let mut set: libc::cpu_set_t = unsafe { std::mem::zeroed() };
+    for cpu in cpus {
+        // SAFETY: cpus were checked to be below CPU_SETSIZE
+        unsafe { libc::CPU_SET(*cpu, &mut set) };
+    }
+    // SAFETY: `set` is a valid cpu_set_t of the given size
+    if unsafe { libc::sched_setaffinity(0, std::mem::size_of::<libc::cpu_set_t>(), &set) } != 0 {
+        return Err(std::io::Error::last_os_error());
+    }
+    Ok(())
+}
+
+/// Configure the command to run with the niceness and CPU affinity of the options
+#[cfg(unix)]
+pub fn apply(command: &mut tokio::process::Command, options: &RuntimeOptions) {
+    let nice = options.nice;
+    let cpus = options.cpu_affinity.clone();
+    if nice.is_none() && cpus.is_none() {
+        return;
+    }
+    // SAFETY: setpriority and sched_setaffinity are async-signal-safe,
+    // and the CPUs were collected before forking
+    unsafe {
+        command.pre_exec(move || {
+            if let Some(nice) = nice
+                && libc::setpriority(libc::PRIO_PROCESS, 0, nice) != 0
+            {
+                return Err(std::io::Error::last_os_error());
+            }
+            #[cfg(target_os = "linux")]
+            if let Some(cpus) = &cpus {
+                set_affinity(cpus)?;
+            }
+            Ok(())
+        });
+    }
+}
+
+/// Configure the command to run with the niceness and CPU affinity of the options
+#[cfg(not(unix))]
+pub fn apply(_command: &mut tokio::process::Command, _options: &RuntimeOptions) {}
+
+// ============================================================================
+// Memory limit
+// ============================================================================
+//
+// The resident set size of a runtime process with a memory limit is read periodically by
+// the main process, so that the limit holds even when the runtime process stops answering.
+// Exceeding the limit is reported once, until the process is back under it, and the action
+// of the options is taken.
+
+impl ZenohRuntimes {
+    /// Kill the runtime process, which is then handled as a crash
+    async fn kill_process(&self, runtime_id: RuntimeId) -> Result<(), SandboxError> {
+        let kill_tx = self
+            .runtimes
+            .write()
+            .await
+            .get_mut(&runtime_id)
+            .ok_or(SandboxError::RuntimeNotFound(runtime_id))?
+            .kill_tx
+            .take()
+            .ok_or(SandboxError::RuntimeNotStarted(runtime_id))?;
+        kill_tx
+            .send(())
+            .map_err(|_| SandboxError::RuntimeNotStarted(runtime_id))
+    }
+}
+
+/// Spawn the task watching the memory of the runtime process `pid`,
+/// if the options set a memory limit
+pub(crate) fn spawn_memory_monitor(
+    app: AppHandle,
+    runtime_id: RuntimeId,
+    pid: u32,
+    options: &RuntimeOptions,
+) -> Option<JoinHandle<()>> {
+    let limit_bytes = options.memory_limit_mb? * 1024 * 1024;
+    let action = options.memory_limit_action.unwrap_or_default();
+    Some(tokio::spawn(
+        monitor_memory(app, runtime_id, pid, limit_bytes, action)
+            .instrument(tracing::debug_span!("memory_monitor", runtime_id)),
+    ))
+}
+
+/// Check the resident set size of a runtime process until it exits or is killed
+/// for exceeding the limit
+async fn monitor_memory(
+    app: AppHandle,
+    runtime_id: RuntimeId,
+    pid: u32,
+    limit_bytes: u64,
+    action: MemoryLimitAction,
+) {
+    let pid = Pid::from_u32(pid);
+    let mut system = System::new();
+    let mut interval = tokio::time::interval(MEMORY_CHECK_INTERVAL);
+    let mut exceeded = false;
+    loop {
+        interval.tick().await;
+        system.refresh_processes_specifics(
+            ProcessesToUpdate::Some(&[pid]),
+            true,
+            ProcessRefreshKind::nothing().with_memory(),
+        );
+        let Some(rss_bytes) = system.process(pid).map(|process| process.memory()) else {
+            // The runtime process exited
+            return;
+        };
+        if rss_bytes <= limit_bytes {
+            exceeded = false;
+            continue;
+        }
+        if exceeded {
+            continue;
+        }
+        exceeded = true;
+        if action == MemoryLimitAction::Alert {
+            report_memory_limit(&app, runtime_id, rss_bytes, limit_bytes, action);
+            continue;
+        }
+        // Stopping the runtime aborts this task
+        let app = app.clone();
+        tauri::async_runtime::spawn(async move {
+            report_memory_limit(&app, runtime_id, rss_bytes, limit_bytes, action);
+            handle_memory_limit(&app, runtime_id, action).await;
+        });
+        return;
+    }
+}
+
+/// Report a runtime process exceeding its memory limit
+fn report_memory_limit(
+    app: &AppHandle,
+    runtime_id: RuntimeId,
+    rss_bytes: u64,
+    limit_bytes: u64,
+    action: MemoryLimitAction,
+) {
+    let message = format!(
+        "Runtime process exceeds its memory limit: {} MiB resident, limit {} MiB",
+        rss_bytes / (1024 * 1024),
+        limit_bytes / (1024 * 1024)
+    );
+    tracing::error!(runtime_id, "{}", message);
+    app.state::<LogStorage>()
+        .add_sandbox_log(runtime_id, LogEntryLevel::ERROR, message);
+    let _ = app.emit(
+        "runtime-memory-limit",
+        RuntimeMemoryLimitEvent {
+            runtime_id,
+            rss_bytes,
+            limit_bytes,
+            action,
+        },
+    );
+}
+
+/// Kill or restart a runtime whose process exceeds its memory limit
+async fn handle_memory_limit(app: &AppHandle, runtime_id: RuntimeId, action: MemoryLimitAction) {
+    let runtimes = app.state::<ZenohRuntimes>();
+    let (result, done) = match action {
+        MemoryLimitAction::Alert => return,
+        MemoryLimitAction::Kill => (runtimes.kill_process(runtime_id).await, "Killed"),
+        MemoryLimitAction::Restart => {
+            let result = match runtimes.shutdown(runtime_id, false).await {
+                Ok(()) => runtimes.launch(app, runtime_id).await.map(|_| ()),
+                Err(e) => Err(e),
+            };
+            (result, "Restarted")
+        }
+    };
+    let (level, message) = match result {
+        Ok(()) => (
+            LogEntryLevel::WARN,
+            format!("{} the runtime process exceeding its memory limit", done),
+        ),
+        Err(e) => (
+            LogEntryLevel::ERROR,
+            format!(
+                "Failed to act on the runtime process exceeding its memory limit: {}",
+                e
+            ),
+        ),
+    };
+    app.state::<LogStorage>()
+        .add_sandbox_log(runtime_id, level, message);
+}
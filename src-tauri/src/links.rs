@@ -0,0 +1,202 @@
+use tauri::{AppHandle, Manager, State};
+
+use crate::{
+    RuntimeId, ZenohRuntimes, config_to_json,
+    error::SandboxError,
+    logs::LogStorage,
+    timeline::Timeline,
+    ts::{
+        config::ZenohConfigJson,
+        report::{RuntimeConnection, TopologyLink},
+        timeline::ConfigChangeSource,
+    },
+    zenoh_admin,
+};
+
+// ============================================================================
+// Endpoints
+// ============================================================================
+
+/// Endpoints of an endpoint list of a config (`listen/endpoints` or `connect/endpoints`),
+/// for all modes when they are given per mode
+fn config_endpoints(config: &zenoh::Config, key: &str) -> Result<Vec<String>, String> {
+    let json = config
+        .get_json(key)
+        .map_err(|e| format!("Failed to read {}: {}", key, e))?;
+    let value: serde_json::Value =
+        serde_json::from_str(&json).map_err(|e| format!("Failed to parse {}: {}", key, e))?;
+    let lists = match &value {
+        // Endpoints per mode
+        serde_json::Value::Object(modes) => modes.values().collect(),
+        list => vec![list],
+    };
+    let mut endpoints = Vec::new();
+    for endpoint in lists
+        .into_iter()
+        .filter_map(|list| list.as_array())
+        .flatten()
+        .filter_map(|endpoint| endpoint.as_str())
+    {
+        if !endpoints.iter().any(|known| known == endpoint) {
+            endpoints.push(endpoint.to_string());
+        }
+    }
+    Ok(endpoints)
+}
+
+/// Turn a listen endpoint into one the other runtimes of the host can connect to,
+/// replacing unspecified addresses by loopback. Endpoints without a fixed port are skipped.
+fn loopback_endpoint(endpoint: &str) -> Option<String> {
+    let (protocol, address) = endpoint.split_once('/')?;
+    // Drop the endpoint metadata and config
+    let address = address.split(['?', '#']).next()?;
+    let (host, port) = address.rsplit_once(':')?;
+    if port.parse::<u16>().ok()? == 0 {
+        return None;
+    }
+    let host = match host {
+        "0.0.0.0" => "127.0.0.1",
+        "[::]" => "[::1]",
+        host => host,
+    };
+    Some(format!("{protocol}/{host}:{port}"))
+}
+
+/// Endpoints other runtimes can connect to: the live locators of a running runtime,
+/// otherwise the listen endpoints with a fixed port of its declared config
+pub(crate) async fn reachable_endpoints(
+    runtimes: &ZenohRuntimes,
+    runtime_id: RuntimeId,
+) -> Result<Vec<String>, String> {
+    if runtimes.running_zenoh_id(runtime_id).await.is_ok() {
+        return Ok(zenoh_admin::local_locators(runtimes, runtime_id).await?);
+    }
+
+    let config = {
+        let runtime_processes = runtimes.runtimes.read().await;
+        runtime_processes
+            .get(&runtime_id)
+            .ok_or(SandboxError::RuntimeNotFound(runtime_id))?
+            .sandbox_config
+            .clone()
+    };
+    let zenoh_config: zenoh::Config = config.try_into()?;
+    let endpoints: Vec<String> = config_endpoints(&zenoh_config, "listen/endpoints")?
+        .iter()
+        .filter_map(|endpoint| loopback_endpoint(endpoint))
+        .collect();
+    if endpoints.is_empty() {
+        return Err(format!(
+            "Runtime {} is not running and has no listen endpoint with a fixed port",
+            runtime_id
+        ));
+    }
+    Ok(endpoints)
+}
+
+/// Add endpoints to the connect endpoints of a config, skipping those already present
+pub(crate) fn with_connect_endpoints(
+    config: &ZenohConfigJson,
+    endpoints: &[String],
+) -> Result<ZenohConfigJson, String> {
+    if endpoints.is_empty() {
+        return Ok(config.clone());
+    }
+    let mut zenoh_config: zenoh::Config = config.clone().try_into()?;
+    let current = zenoh_config
+        .get_json("connect/endpoints")
+        .map_err(|e| format!("Failed to read connect endpoints: {}", e))?;
+    let mut value: serde_json::Value = serde_json::from_str(&current)
+        .map_err(|e| format!("Failed to parse connect endpoints: {}", e))?;
+    let extend = |list: &mut serde_json::Value| {
+        if let Some(list) = list.as_array_mut() {
+            for endpoint in endpoints {
+                let endpoint = serde_json::Value::String(endpoint.clone());
+                if !list.contains(&endpoint) {
+                    list.push(endpoint);
+                }
+            }
+        }
+    };
+    match &mut value {
+        // Endpoints per mode
+        serde_json::Value::Object(modes) => modes.values_mut().for_each(extend),
+        list => extend(list),
+    }
+    zenoh_config
+        .insert_json5("connect/endpoints", &value.to_string())
+        .map_err(|e| format!("Failed to set connect endpoints: {}", e))?;
+    config_to_json(&zenoh_config)
+}
+
+// ============================================================================
+// Tauri commands
+// ============================================================================
+
+/// Make runtime `from` connect to runtime `to`.
+/// The endpoints of `to` are added to the connect endpoints of the declared config of `from`,
+/// and a transport is opened right away when both runtimes are running.
+/// The link is recorded so that it can be drawn as a topology edge.
+#[tauri::command]
+#[tracing::instrument(skip(app))]
+pub async fn connect_runtimes(
+    from: RuntimeId,
+    to: RuntimeId,
+    app: AppHandle,
+) -> Result<RuntimeConnection, SandboxError> {
+    if from == to {
+        return Err(format!("Runtime {} can't connect to itself", from).into());
+    }
+    let runtimes = app.state::<ZenohRuntimes>();
+    let endpoints = reachable_endpoints(&runtimes, to).await?;
+
+    // Update the declared config, so that the connection is restored when `from` restarts
+    let (old_config, new_config) = {
+        let mut runtime_processes = runtimes.runtimes.write().await;
+        let runtime_process = runtime_processes
+            .get_mut(&from)
+            .ok_or(SandboxError::RuntimeNotFound(from))?;
+        let new_config = with_connect_endpoints(&runtime_process.sandbox_config, &endpoints)?;
+        (
+            runtime_process.replace_config(new_config.clone(), ConfigChangeSource::Patch),
+            new_config,
+        )
+    };
+    app.state::<Timeline>().annotate_config_change(
+        from,
+        ConfigChangeSource::Patch,
+        &old_config,
+        &new_config,
+        Some(app.state::<LogStorage>().inner()),
+    );
+
+    let live = match (
+        runtimes.running_zenoh_id(from).await,
+        runtimes.running_zenoh_id(to).await,
+    ) {
+        (Ok(_), Ok(target)) => {
+            runtimes.connect(from, target, endpoints.clone()).await?;
+            true
+        }
+        _ => false,
+    };
+
+    let link = TopologyLink { from, to };
+    runtimes.links.write().await.insert(link.clone());
+    tracing::info!("Runtime {} connects to {} via {:?}", from, to, endpoints);
+
+    Ok(RuntimeConnection {
+        link,
+        endpoints,
+        live,
+    })
+}
+
+/// List the links wired with `connect_runtimes`
+#[tauri::command]
+#[tracing::instrument(skip(state))]
+pub async fn list_runtime_links(
+    state: State<'_, ZenohRuntimes>,
+) -> Result<Vec<TopologyLink>, SandboxError> {
+    Ok(state.links.read().await.iter().cloned().collect())
+}
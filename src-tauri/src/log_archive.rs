@@ -0,0 +1,257 @@
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap},
+    fs::File,
+    io::{self, BufWriter, Write},
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use chrono::Utc;
+use flate2::{Compression, write::GzEncoder};
+use tauri::{AppHandle, Manager};
+use tokio::task::JoinSet;
+
+use crate::{
+    RuntimeId, SANDBOX_RUNTIME_ID, ZenohRuntimes,
+    error::SandboxError,
+    logs::{LogEntry, LogFilter, LogStorage, write_log_entry, write_log_file, write_log_header},
+    ts::log::{LogArchiveManifest, LogArchiveRuntime, LogExportFormat, LogTimeRange},
+};
+
+// ============================================================================
+// Constants
+// ============================================================================
+
+/// Name of the manifest in log archives
+const MANIFEST_FILE: &str = "manifest.json";
+
+/// Base name of the merged log file in log archives
+const MERGED_FILE: &str = "merged";
+
+// ============================================================================
+// Archive contents
+// ============================================================================
+
+fn extension(format: LogExportFormat) -> &'static str {
+    match format {
+        LogExportFormat::Jsonl => "jsonl",
+        LogExportFormat::Csv => "csv",
+        LogExportFormat::Text => "log",
+    }
+}
+
+/// Name of the log file of a runtime in archives
+fn runtime_file(runtime_id: RuntimeId, format: LogExportFormat) -> String {
+    if runtime_id == SANDBOX_RUNTIME_ID {
+        format!("sandbox.{}", extension(format))
+    } else {
+        format!("runtime-{}.{}", runtime_id, extension(format))
+    }
+}
+
+/// Merge the logs of all runtimes, each one sorted oldest first, into a single chronological file
+fn write_merged_file(
+    path: &Path,
+    format: LogExportFormat,
+    logs: &[(RuntimeId, Arc<Vec<LogEntry>>)],
+) -> io::Result<()> {
+    let mut writer = BufWriter::new(File::create(path)?);
+    write_log_header(&mut writer, format, true)?;
+
+    // Next entry of each runtime, oldest first
+    let mut heap: BinaryHeap<Reverse<_>> = logs
+        .iter()
+        .enumerate()
+        .filter_map(|(index, (_, entries))| Some(Reverse((entries.first()?.timestamp, index, 0))))
+        .collect();
+    while let Some(Reverse((_, index, position))) = heap.pop() {
+        let (runtime_id, entries) = &logs[index];
+        write_log_entry(&mut writer, format, Some(*runtime_id), &entries[position])?;
+        if let Some(next) = entries.get(position + 1) {
+            heap.push(Reverse((next.timestamp, index, position + 1)));
+        }
+    }
+    writer.flush()
+}
+
+/// Pack the files of `dir` into a gzipped tarball written at `path`
+fn write_tarball(path: &Path, dir: &Path, files: &[String]) -> io::Result<()> {
+    let encoder = GzEncoder::new(BufWriter::new(File::create(path)?), Compression::default());
+    let mut builder = tar::Builder::new(encoder);
+    for file in files {
+        builder.append_path_with_name(dir.join(file), file)?;
+    }
+    builder.into_inner()?.finish()?.flush()
+}
+
+/// Write the log files of all runtimes, the merged log file and the manifest in `dir`.
+/// Returns the names of the written files and the number of exported entries.
+pub(crate) async fn stage_logs(
+    app: &AppHandle,
+    dir: &Path,
+    format: LogExportFormat,
+    range: LogTimeRange,
+) -> Result<(Vec<String>, usize), String> {
+    tokio::fs::create_dir_all(dir)
+        .await
+        .map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+    let storage = app.state::<LogStorage>();
+    let filter = LogFilter::default().with_range(Some(range.clone()));
+    let logs: Vec<(RuntimeId, Arc<Vec<LogEntry>>)> = storage
+        .runtime_ids()
+        .into_iter()
+        .map(|runtime_id| (runtime_id, Arc::new(storage.get_all(runtime_id, &filter))))
+        .collect();
+
+    // Per-runtime files and the merged file are written concurrently
+    let mut tasks = JoinSet::new();
+    for (runtime_id, entries) in &logs {
+        let file = runtime_file(*runtime_id, format);
+        let file_path = dir.join(&file);
+        let entries = entries.clone();
+        tasks.spawn_blocking(move || {
+            write_log_file(&file_path, format, &entries).map_err(|e| format!("{}: {}", file, e))
+        });
+    }
+    let merged_file = format!("{}.{}", MERGED_FILE, extension(format));
+    {
+        let file_path = dir.join(&merged_file);
+        let file = merged_file.clone();
+        let logs = logs.clone();
+        tasks.spawn_blocking(move || {
+            write_merged_file(&file_path, format, &logs).map_err(|e| format!("{}: {}", file, e))
+        });
+    }
+    while let Some(joined) = tasks.join_next().await {
+        joined
+            .map_err(|e| format!("Log export task failed: {}", e))?
+            .map_err(|e| format!("Failed to write log file {}", e))?;
+    }
+
+    let statuses: HashMap<RuntimeId, _> = app
+        .state::<ZenohRuntimes>()
+        .statuses()
+        .await
+        .into_iter()
+        .map(|status| (status.runtime_id, status))
+        .collect();
+    let manifest = LogArchiveManifest {
+        exported_at: Utc::now(),
+        sandbox_version: env!("CARGO_PKG_VERSION").to_string(),
+        format,
+        range,
+        merged_file: merged_file.clone(),
+        runtimes: logs
+            .iter()
+            .map(|(runtime_id, entries)| LogArchiveRuntime {
+                runtime_id: *runtime_id,
+                file: runtime_file(*runtime_id, format),
+                entries: entries.len(),
+                status: statuses.get(runtime_id).cloned(),
+            })
+            .collect(),
+    };
+    let contents = serde_json::to_string_pretty(&manifest)
+        .map_err(|e| format!("Failed to serialize manifest: {}", e))?;
+    tokio::fs::write(dir.join(MANIFEST_FILE), contents)
+        .await
+        .map_err(|e| format!("Failed to write {}: {}", MANIFEST_FILE, e))?;
+
+    let mut files = vec![MANIFEST_FILE.to_string(), merged_file];
+    files.extend(manifest.runtimes.iter().map(|runtime| runtime.file.clone()));
+    Ok((files, logs.iter().map(|(_, entries)| entries.len()).sum()))
+}
+
+/// Archive being built: files are staged in a directory next to it, then packed into
+/// a temporary file renamed to the archive path once complete
+pub(crate) struct ArchiveStaging {
+    path: PathBuf,
+    dir: PathBuf,
+    tmp_path: PathBuf,
+}
+
+impl ArchiveStaging {
+    pub(crate) async fn create(path: &str) -> Result<Self, String> {
+        let path = std::path::absolute(PathBuf::from(path))
+            .map_err(|e| format!("Invalid archive path {}: {}", path, e))?;
+        let file_name = path
+            .file_name()
+            .ok_or_else(|| format!("Invalid archive path {}", path.display()))?
+            .to_string_lossy()
+            .to_string();
+        let dir = path.with_file_name(format!(".{}.parts", file_name));
+        let tmp_path = path.with_file_name(format!(".{}.tmp", file_name));
+        tokio::fs::create_dir_all(&dir)
+            .await
+            .map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+        Ok(Self {
+            path,
+            dir,
+            tmp_path,
+        })
+    }
+
+    /// Directory to stage the files in
+    pub(crate) fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Pack the staged files, given relative to the staging directory, into the archive.
+    /// Returns the absolute path of the archive.
+    pub(crate) async fn finish(self, files: Vec<String>) -> Result<PathBuf, String> {
+        let (tmp_path, dir) = (self.tmp_path.clone(), self.dir.clone());
+        let result = tokio::task::spawn_blocking(move || write_tarball(&tmp_path, &dir, &files))
+            .await
+            .map_err(|e| format!("Archive task failed: {}", e))
+            .and_then(|result| result.map_err(|e| format!("Failed to write archive: {}", e)));
+        let result = match result {
+            Ok(()) => tokio::fs::rename(&self.tmp_path, &self.path)
+                .await
+                .map_err(|e| format!("Failed to write {}: {}", self.path.display(), e)),
+            Err(e) => Err(e),
+        };
+        let path = self.path.clone();
+        self.abort().await;
+        result.map(|()| path)
+    }
+
+    /// Remove the staged files and the partial archive
+    pub(crate) async fn abort(self) {
+        if let Err(e) = tokio::fs::remove_dir_all(&self.dir).await {
+            tracing::warn!("Failed to remove {}: {}", self.dir.display(), e);
+        }
+        let _ = tokio::fs::remove_file(&self.tmp_path).await;
+    }
+}
+
+// ============================================================================
+// Tauri commands
+// ============================================================================
+
+/// Write the logs of all runtimes and of the sandbox to a `.tar.gz` archive: one file per runtime,
+/// a file merging all logs chronologically and a manifest with the runtime statuses.
+/// Files are staged on disk next to the archive rather than built in memory.
+/// Returns the absolute path of the written archive.
+#[tauri::command]
+#[tracing::instrument(skip(app))]
+pub async fn export_all_logs(
+    path: String,
+    format: LogExportFormat,
+    range: Option<LogTimeRange>,
+    app: AppHandle,
+) -> Result<String, SandboxError> {
+    let staging = ArchiveStaging::create(&path).await?;
+    let (files, entries) =
+        match stage_logs(&app, staging.dir(), format, range.unwrap_or_default()).await {
+            Ok(staged) => staged,
+            Err(e) => {
+                staging.abort().await;
+                return Err(e.into());
+            }
+        };
+    let path = staging.finish(files).await?;
+
+    tracing::info!("Exported {} log entries to {}", entries, path.display());
+    Ok(path.to_string_lossy().to_string())
+}
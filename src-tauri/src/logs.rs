@@ -1,10 +1,30 @@
-use std::{collections::HashMap, sync::Arc};
+use std::{
+    borrow::Cow,
+    cmp::Reverse,
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque, hash_map::Entry},
+    fmt::Write as _,
+    fs::{File, OpenOptions},
+    io::{self, BufRead, BufReader, BufWriter, Read, Seek, SeekFrom, Write},
+    path::{Path, PathBuf},
+    sync::{Arc, LazyLock},
+};
 
 use chrono::{DateTime, Utc};
-use parking_lot::RwLock as ParkingLotRwLock;
+use parking_lot::{Mutex as ParkingLotMutex, RwLock as ParkingLotRwLock};
 use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager, State};
+use tracing::{Level, span};
+use tracing_subscriber::{Layer, layer::Context, registry::LookupSpan};
+use ts_rs::TS;
 
-use crate::{RuntimeId, ts::log::LogEntryLevel};
+use crate::{
+    RuntimeId, SANDBOX_RUNTIME_ID, ZenohRuntimes,
+    error::SandboxError,
+    ts::log::{
+        LogEntryLevel, LogExportFormat, LogLevelCounts, LogPage, LogPersistenceStatus, LogSearch,
+        LogStats, LogTimeRange, LogTimelineBucket, MergedLogEntry, OutputStream,
+    },
+};
 
 // ============================================================================
 // Constants
@@ -16,14 +36,34 @@ pub const LOG_PAGE_SIZE: usize = 100;
 /// Maximum number of log entries to keep per runtime
 const MAX_LOG_ENTRIES: usize = 10_000;
 
+/// Target used for log entries synthesized by the sandbox itself
+pub const SANDBOX_LOG_TARGET: &str = "zenoh_sandbox";
+
+/// Environment variable enabling persistent logs at startup when set to `1` or `true`
+pub const PERSIST_LOGS_ENV: &str = "ZENOH_SANDBOX_PERSIST_LOGS";
+
+/// Size in bytes above which a persisted log file is rotated, when none is given
+const DEFAULT_PERSISTED_LOG_FILE_SIZE: u64 = 16 * 1024 * 1024;
+
+/// Maximum number of buckets returned by `get_log_timeline`
+const MAX_LOG_TIMELINE_BUCKETS: i64 = 10_000;
+
 // ============================================================================
 // Log Entry Structure
 // ============================================================================
 
 /// A single log entry
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../src/types/generated/")]
 pub struct LogEntry {
+    /// Sequence number of the entry among the entries of its runtime, assigned when stored.
+    /// Increases with each stored entry, so it can be used as a pagination cursor and to
+    /// deduplicate entries. Entries dropped by the runtime process leave a gap in the numbers.
+    #[serde(default)]
+    #[ts(type = "number")]
+    pub seq: u64,
     /// Timestamp of the log entry
+    #[ts(type = "string")]
     pub timestamp: DateTime<Utc>,
     /// Log level (e.g., "INFO", "DEBUG", "ERROR")
     pub level: LogEntryLevel,
@@ -31,19 +71,349 @@ pub struct LogEntry {
     pub target: String,
     /// The log message
     pub message: String,
+    /// Fields of the event other than the message
+    #[serde(default)]
+    pub fields: HashMap<String, String>,
+    /// Spans the event occurred in, outermost first, as `name{field=value ...}`
+    #[serde(default)]
+    pub spans: Vec<String>,
+}
+
+impl LogEntry {
+    /// Entry without fields nor spans, timestamped now
+    pub fn new(level: LogEntryLevel, target: impl Into<String>, message: String) -> Self {
+        Self {
+            seq: 0,
+            timestamp: Utc::now(),
+            level,
+            target: target.into(),
+            message,
+            fields: HashMap::new(),
+            spans: Vec::new(),
+        }
+    }
+
+    /// Entry for a tracing event, with its fields and the spans of `ctx` it occurred in
+    pub fn from_event<S>(event: &tracing::Event<'_>, ctx: &Context<'_, S>) -> Self
+    where
+        S: tracing::Subscriber + for<'a> LookupSpan<'a>,
+    {
+        let mut visitor = FieldVisitor::default();
+        event.record(&mut visitor);
+        let spans = ctx
+            .event_scope(event)
+            .map(|scope| {
+                scope
+                    .from_root()
+                    .map(|span| match span.extensions().get::<SpanFields>() {
+                        Some(SpanFields(fields)) if !fields.is_empty() => {
+                            format!("{}{{{}}}", span.name(), fields)
+                        }
+                        _ => span.name().to_string(),
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        Self {
+            seq: 0,
+            timestamp: Utc::now(),
+            level: event.metadata().level().into(),
+            target: event.metadata().target().to_string(),
+            message: visitor.message,
+            fields: visitor.fields,
+            spans,
+        }
+    }
+}
+
+// ============================================================================
+// Log Filter
+// ============================================================================
+
+/// Criteria selecting log entries, applied before pagination
+#[derive(Default)]
+pub struct LogFilter {
+    /// Minimum severity
+    level: Option<LogEntryLevel>,
+    /// Search in the message
+    search: Option<LogMatcher>,
+    /// Target prefix, e.g. `zenoh_transport`
+    target: Option<String>,
+    /// Target patterns, any of which must match, e.g. `zenoh_transport::*`
+    targets: Vec<String>,
+    /// Time range of the entries
+    range: Option<LogTimeRange>,
+}
+
+/// How the message of a log entry is searched
+enum LogMatcher {
+    /// Case-insensitive substring, stored lowercase
+    Substring(String),
+    Regex(regex::Regex),
+}
+
+impl LogFilter {
+    pub fn new(
+        level: Option<LogEntryLevel>,
+        search: Option<LogSearch>,
+        target: Option<String>,
+    ) -> Result<Self, String> {
+        let search = match search {
+            Some(search) if search.query.is_empty() => None,
+            Some(search) if search.regex => Some(LogMatcher::Regex(
+                regex::Regex::new(&search.query)
+                    .map_err(|e| format!("Invalid regex {}: {}", search.query, e))?,
+            )),
+            Some(search) => Some(LogMatcher::Substring(search.query.to_lowercase())),
+            None => None,
+        };
+        Ok(Self {
+            level,
+            search,
+            target: target.filter(|target| !target.is_empty()),
+            targets: Vec::new(),
+            range: None,
+        })
+    }
+
+    /// Filter on the severity only
+    pub fn level(level: Option<LogEntryLevel>) -> Self {
+        Self {
+            level,
+            ..Default::default()
+        }
+    }
+
+    /// Restrict the filter to a time range
+    pub fn with_range(mut self, range: Option<LogTimeRange>) -> Self {
+        self.range = range;
+        self
+    }
+
+    /// Restrict the filter to the targets matching any of `targets`.
+    /// A pattern ending with `::*` matches a module and its submodules, a pattern ending
+    /// with `*` matches the targets starting with the rest, other patterns match exactly.
+    pub fn with_targets(mut self, targets: Option<Vec<String>>) -> Self {
+        self.targets = targets.unwrap_or_default();
+        self.targets.retain(|target| !target.is_empty());
+        self
+    }
+
+    pub fn matches(&self, entry: &LogEntry) -> bool {
+        // tracing::Level ordering: TRACE > DEBUG > INFO > WARN > ERROR
+        // We want to show entries at or above the selected severity,
+        // so entry.level <= lvl (e.g., INFO entry <= INFO filter shows INFO, WARN, ERROR)
+        if let Some(lvl) = &self.level
+            && &entry.level > lvl
+        {
+            return false;
+        }
+        if let Some(target) = &self.target
+            && !entry.target.starts_with(target.as_str())
+        {
+            return false;
+        }
+        if !self.targets.is_empty()
+            && !self
+                .targets
+                .iter()
+                .any(|pattern| target_matches(pattern, &entry.target))
+        {
+            return false;
+        }
+        if let Some(range) = &self.range
+            && !range.contains(&entry.timestamp)
+        {
+            return false;
+        }
+        match &self.search {
+            Some(LogMatcher::Substring(query)) => entry.message.to_lowercase().contains(query),
+            Some(LogMatcher::Regex(regex)) => regex.is_match(&entry.message),
+            None => true,
+        }
+    }
+}
+
+/// Whether `target` matches a pattern of `LogFilter::with_targets`
+fn target_matches(pattern: &str, target: &str) -> bool {
+    match pattern.strip_suffix('*') {
+        Some(prefix) => match prefix.strip_suffix("::") {
+            Some(module) => target == module || target.starts_with(prefix),
+            None => target.starts_with(prefix),
+        },
+        None => target == pattern,
+    }
+}
+
+// ============================================================================
+// Log Persistence
+// ============================================================================
+
+/// Path of the persisted log file of a runtime, or of its rotated file
+fn persisted_log_path(dir: &Path, runtime_id: RuntimeId, rotated: bool) -> PathBuf {
+    let name = if runtime_id == SANDBOX_RUNTIME_ID {
+        "sandbox".to_string()
+    } else {
+        format!("runtime-{}", runtime_id)
+    };
+    let suffix = if rotated { ".1" } else { "" };
+    dir.join(format!("{}{}.jsonl", name, suffix))
+}
+
+/// Persisted log file open for appending
+struct PersistedLogFile {
+    file: File,
+    size: u64,
+}
+
+impl PersistedLogFile {
+    /// Open the log file of a runtime, first moving the existing one to the rotated file if `rotate`
+    fn open(dir: &Path, runtime_id: RuntimeId, rotate: bool) -> io::Result<Self> {
+        let path = persisted_log_path(dir, runtime_id, false);
+        if rotate && path.exists() {
+            std::fs::rename(&path, persisted_log_path(dir, runtime_id, true))?;
+        }
+        let file = OpenOptions::new().create(true).append(true).open(&path)?;
+        let size = file.metadata()?.len();
+        Ok(Self { file, size })
+    }
+}
+
+/// Append-only JSONL files of log entries, one per runtime.
+/// A file is rotated once it exceeds `max_file_size`, keeping only the previous one.
+struct LogPersistence {
+    dir: PathBuf,
+    max_file_size: u64,
+    files: HashMap<RuntimeId, PersistedLogFile>,
+    /// Runtimes whose persisted logs were replayed, so their files are continued.
+    /// Files of other runtimes are left by a previous session and rotated before being written.
+    continued: HashSet<RuntimeId>,
+}
+
+impl LogPersistence {
+    fn append(&mut self, runtime_id: RuntimeId, entry: &LogEntry) -> io::Result<()> {
+        let mut line = serde_json::to_vec(entry)?;
+        line.push(b'\n');
+        let len = line.len() as u64;
+
+        // The file is closed before being rotated
+        let full = self
+            .files
+            .get(&runtime_id)
+            .is_some_and(|file| file.size > 0 && file.size + len > self.max_file_size);
+        if full {
+            self.files.remove(&runtime_id);
+        }
+        let file = match self.files.entry(runtime_id) {
+            Entry::Occupied(occupied) => occupied.into_mut(),
+            Entry::Vacant(vacant) => {
+                let rotate = full || !self.continued.contains(&runtime_id);
+                vacant.insert(PersistedLogFile::open(&self.dir, runtime_id, rotate)?)
+            }
+        };
+        file.file.write_all(&line)?;
+        file.size += len;
+        Ok(())
+    }
+
+    fn remove(&mut self, runtime_id: RuntimeId) {
+        self.files.remove(&runtime_id);
+        self.continued.remove(&runtime_id);
+        for rotated in [false, true] {
+            let _ = std::fs::remove_file(persisted_log_path(&self.dir, runtime_id, rotated));
+        }
+    }
+
+    fn status(&self) -> LogPersistenceStatus {
+        LogPersistenceStatus {
+            enabled: true,
+            dir: Some(self.dir.to_string_lossy().to_string()),
+            max_file_size: self.max_file_size,
+        }
+    }
 }
 
 // ============================================================================
 // Log Storage
 // ============================================================================
 
+/// Log entries of a runtime, kept in a ring buffer of `max_entries` entries
+pub struct RuntimeLogs {
+    /// Entries, oldest first, ordered by sequence number
+    entries: VecDeque<LogEntry>,
+    /// Sequence number of the next stored entry
+    next_seq: u64,
+    /// Distinct targets of the entries stored so far, including evicted ones
+    targets: BTreeSet<String>,
+}
+
+impl RuntimeLogs {
+    fn new(max_entries: usize) -> Self {
+        Self {
+            entries: VecDeque::with_capacity(max_entries),
+            next_seq: 0,
+            targets: BTreeSet::new(),
+        }
+    }
+
+    fn add_target(&mut self, target: &str) {
+        if !self.targets.contains(target) {
+            self.targets.insert(target.to_string());
+        }
+    }
+
+    /// Number the entry and store it as the most recent one, dropping the oldest one when full
+    fn push(&mut self, mut entry: LogEntry, max_entries: usize) -> &LogEntry {
+        entry.seq = self.next_seq;
+        self.next_seq += 1;
+        self.add_target(&entry.target);
+        if self.entries.len() >= max_entries {
+            self.entries.pop_front();
+        }
+        self.entries.push_back(entry);
+        &self.entries[self.entries.len() - 1]
+    }
+
+    /// Entries matching `filter`, most recent first, starting before sequence number
+    /// `before_seq` and stopping after sequence number `after_seq` if given
+    fn recent(
+        &self,
+        filter: &LogFilter,
+        before_seq: Option<u64>,
+        after_seq: Option<u64>,
+    ) -> impl Iterator<Item = &LogEntry> {
+        let end = before_seq.map_or(self.entries.len(), |before_seq| {
+            self.entries.partition_point(|entry| entry.seq < before_seq)
+        });
+        let start = after_seq.map_or(0, |after_seq| {
+            self.entries.partition_point(|entry| entry.seq <= after_seq)
+        });
+        self.entries
+            .range(start.min(end)..end)
+            .rev()
+            .filter(|entry| filter.matches(entry))
+    }
+
+    /// Number the entries from the most recent one, keeping its number
+    /// unless the older entries would not fit below it
+    fn renumber(&mut self) {
+        let count = self.entries.len() as u64;
+        self.next_seq = self.next_seq.max(count);
+        for (entry, seq) in self.entries.iter_mut().rev().zip((0..self.next_seq).rev()) {
+            entry.seq = seq;
+        }
+    }
+}
+
 /// Stores logs from all runtimes, separated by RuntimeId
 #[derive(Clone)]
 pub struct LogStorage {
     /// Maximum number of log entries to keep per runtime
     max_entries: usize,
-    /// Map of RuntimeId to log entries (most recent first)
-    logs: Arc<ParkingLotRwLock<HashMap<RuntimeId, Vec<LogEntry>>>>,
+    /// Map of RuntimeId to log entries
+    logs: Arc<ParkingLotRwLock<HashMap<RuntimeId, RuntimeLogs>>>,
+    /// Files the entries are also written to, if persistence is enabled
+    persistence: Arc<ParkingLotMutex<Option<LogPersistence>>>,
 }
 
 impl LogStorage {
@@ -51,62 +421,360 @@ impl LogStorage {
         Self {
             max_entries,
             logs: Arc::new(ParkingLotRwLock::new(HashMap::new())),
+            persistence: Arc::new(ParkingLotMutex::new(None)),
+        }
+    }
+
+    /// Write log entries to files in `dir` in addition to memory.
+    /// Files are rotated once they exceed `max_file_size` bytes.
+    pub fn enable_persistence(&self, dir: PathBuf, max_file_size: Option<u64>) -> io::Result<()> {
+        std::fs::create_dir_all(&dir)?;
+        *self.persistence.lock() = Some(LogPersistence {
+            dir,
+            max_file_size: max_file_size.unwrap_or(DEFAULT_PERSISTED_LOG_FILE_SIZE),
+            files: HashMap::new(),
+            continued: HashSet::new(),
+        });
+        Ok(())
+    }
+
+    /// Stop writing log entries to files, which are kept
+    pub fn disable_persistence(&self) {
+        *self.persistence.lock() = None;
+    }
+
+    pub fn persistence_status(&self) -> LogPersistenceStatus {
+        self.persistence
+            .lock()
+            .as_ref()
+            .map(LogPersistence::status)
+            .unwrap_or(LogPersistenceStatus {
+                enabled: false,
+                dir: None,
+                max_file_size: DEFAULT_PERSISTED_LOG_FILE_SIZE,
+            })
+    }
+
+    /// Load the persisted logs of a runtime into memory, behind the entries already there.
+    /// Only persisted entries older than the in-memory ones are kept, so that entries
+    /// written by this session are not duplicated. The loaded entries are numbered below the
+    /// in-memory ones, which are renumbered if there is no room below them.
+    /// Returns the number of loaded entries.
+    pub fn replay(&self, runtime_id: RuntimeId) -> usize {
+        let Some(dir) = self.persistence.lock().as_mut().map(|persistence| {
+            persistence.continued.insert(runtime_id);
+            persistence.dir.clone()
+        }) else {
+            return 0;
+        };
+
+        // Oldest first, keeping only what fits in memory
+        let mut entries = VecDeque::new();
+        for rotated in [true, false] {
+            let Ok(file) = File::open(persisted_log_path(&dir, runtime_id, rotated)) else {
+                continue;
+            };
+            for line in BufReader::new(file).lines().map_while(Result::ok) {
+                if let Ok(entry) = serde_json::from_str::<LogEntry>(&line) {
+                    entries.push_back(entry);
+                    if entries.len() > self.max_entries {
+                        entries.pop_front();
+                    }
+                }
+            }
         }
+
+        let mut logs = self.logs.write();
+        let runtime_logs = self.runtime_logs(&mut logs, runtime_id);
+        let oldest = runtime_logs.entries.front().map(|entry| entry.timestamp);
+        let len = runtime_logs.entries.len();
+        let room = self.max_entries.saturating_sub(len);
+        for entry in entries
+            .into_iter()
+            .rev()
+            .filter(|entry| oldest.is_none_or(|oldest| entry.timestamp < oldest))
+            .take(room)
+        {
+            runtime_logs.add_target(&entry.target);
+            runtime_logs.entries.push_front(entry);
+        }
+        runtime_logs.renumber();
+        runtime_logs.entries.len() - len
+    }
+
+    /// Logs of a runtime, created with room for `max_entries` entries if there are none yet
+    fn runtime_logs<'a>(
+        &self,
+        logs: &'a mut HashMap<RuntimeId, RuntimeLogs>,
+        runtime_id: RuntimeId,
+    ) -> &'a mut RuntimeLogs {
+        logs.entry(runtime_id)
+            .or_insert_with(|| RuntimeLogs::new(self.max_entries))
     }
 
     /// Add a log entry for a specific runtime
     pub fn add_log(&self, runtime_id: RuntimeId, entry: LogEntry) {
-        let mut logs = self.logs.write();
-        let runtime_logs = logs.entry(runtime_id).or_default();
+        let stored = {
+            let mut logs = self.logs.write();
+            self.runtime_logs(&mut logs, runtime_id)
+                .push(entry, self.max_entries)
+                .clone()
+        };
 
-        // Insert at the beginning (most recent first)
-        runtime_logs.insert(0, entry);
+        // Errors can't be logged from here, the entry may come from the sandbox's own tracing
+        if let Some(persistence) = self.persistence.lock().as_mut() {
+            let _ = persistence.append(runtime_id, &stored);
+        }
+    }
 
-        // Keep only max_entries
-        if runtime_logs.len() > self.max_entries {
-            runtime_logs.truncate(self.max_entries);
+    /// Add entries received together, oldest first, locking the storage once
+    pub fn add_logs(&self, runtime_id: RuntimeId, entries: Vec<LogEntry>) {
+        let mut persistence = self.persistence.lock();
+        let mut logs = self.logs.write();
+        let runtime_logs = self.runtime_logs(&mut logs, runtime_id);
+        for entry in entries {
+            let stored = runtime_logs.push(entry, self.max_entries);
+            if let Some(persistence) = persistence.as_mut() {
+                let _ = persistence.append(runtime_id, stored);
+            }
         }
     }
 
-    /// Get a page of logs for a specific runtime
+    /// Add an entry synthesized by the sandbox (not produced by the runtime itself)
+    pub fn add_sandbox_log(&self, runtime_id: RuntimeId, level: LogEntryLevel, message: String) {
+        self.add_log(
+            runtime_id,
+            LogEntry::new(level, SANDBOX_LOG_TARGET, message),
+        );
+    }
+
+    /// Get a page of logs for a specific runtime, among the entries matching `filter`
     /// Page 0 returns the most recent logs
-    pub fn get_page(&self, runtime_id: RuntimeId, level: Option<LogEntryLevel>, page: usize) -> Vec<LogEntry> {
+    pub fn get_page(
+        &self,
+        runtime_id: RuntimeId,
+        filter: &LogFilter,
+        page: usize,
+    ) -> Vec<LogEntry> {
         let logs = self.logs.read();
         if let Some(runtime_logs) = logs.get(&runtime_id) {
-            let filtered_logs: Vec<LogEntry> = runtime_logs
-                .iter().filter(|&entry| {
-                    if let Some(ref lvl) = level {
-                        // tracing::Level ordering: TRACE > DEBUG > INFO > WARN > ERROR
-                        // We want to show entries at or above the selected severity,
-                        // so entry.level <= lvl (e.g., INFO entry <= INFO filter shows INFO, WARN, ERROR)
-                        &entry.level <= lvl
-                    } else {
-                        true
-                    }
-                }).cloned()
-                .collect();
+            runtime_logs
+                .recent(filter, None, None)
+                .skip(page * LOG_PAGE_SIZE)
+                .take(LOG_PAGE_SIZE)
+                .cloned()
+                .collect()
+        } else {
+            Vec::new()
+        }
+    }
+
+    /// Get up to `LOG_PAGE_SIZE` logs of a runtime matching `filter`, most recent first.
+    /// With `before_seq`, the page holds the most recent entries older than this sequence
+    /// number. With `after_seq`, it holds the oldest entries more recent than this sequence
+    /// number, so that polling for new entries doesn't skip any, even among entries sharing
+    /// a timestamp. Without cursor, the page holds the most recent entries. Entries arriving
+    /// between two calls don't shift pages.
+    pub fn get_page_by_cursor(
+        &self,
+        runtime_id: RuntimeId,
+        filter: &LogFilter,
+        before_seq: Option<u64>,
+        after_seq: Option<u64>,
+    ) -> LogPage {
+        let logs = self.logs.read();
+        let Some(runtime_logs) = logs.get(&runtime_id) else {
+            return LogPage::default();
+        };
+        let matching = || runtime_logs.recent(filter, before_seq, after_seq);
+
+        let total_matching = matching().count();
+        let entries: Vec<LogEntry> = if after_seq.is_some() {
+            let skip = total_matching.saturating_sub(LOG_PAGE_SIZE);
+            matching().skip(skip).cloned().collect()
+        } else {
+            matching().take(LOG_PAGE_SIZE).cloned().collect()
+        };
+        LogPage {
+            has_more: total_matching > entries.len(),
+            total_matching,
+            entries,
+        }
+    }
+
+    /// All logs of a runtime matching `filter`, oldest first
+    pub fn get_all(&self, runtime_id: RuntimeId, filter: &LogFilter) -> Vec<LogEntry> {
+        let logs = self.logs.read();
+        logs.get(&runtime_id)
+            .map(|runtime_logs| {
+                runtime_logs
+                    .entries
+                    .iter()
+                    .filter(|entry| filter.matches(entry))
+                    .cloned()
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Get a page of the logs of several runtimes matching `filter`, interleaved by timestamp,
+    /// most recent first. Entries with the same timestamp are ordered by runtime.
+    /// Page 0 returns the most recent logs.
+    pub fn get_merged_page(
+        &self,
+        runtime_ids: &[RuntimeId],
+        filter: &LogFilter,
+        page: usize,
+    ) -> Vec<MergedLogEntry> {
+        let logs = self.logs.read();
+        let mut streams: Vec<_> = runtime_ids
+            .iter()
+            .filter_map(|runtime_id| {
+                let runtime_logs = logs.get(runtime_id)?;
+                Some((
+                    *runtime_id,
+                    runtime_logs.recent(filter, None, None).peekable(),
+                ))
+            })
+            .collect();
 
-            let start = page * LOG_PAGE_SIZE;
-            let end = ((page + 1) * LOG_PAGE_SIZE).min(filtered_logs.len());
+        // Take the most recent head among the runtimes until the page is complete
+        let merged = std::iter::from_fn(|| {
+            let (runtime_id, stream) = streams
+                .iter_mut()
+                .filter_map(|(runtime_id, stream)| {
+                    let order = (stream.peek()?.timestamp, Reverse(*runtime_id));
+                    Some((order, (*runtime_id, stream)))
+                })
+                .max_by_key(|(order, _)| *order)?
+                .1;
+            stream.next().map(|entry| MergedLogEntry {
+                runtime_id,
+                entry: entry.clone(),
+            })
+        });
+        merged
+            .skip(page * LOG_PAGE_SIZE)
+            .take(LOG_PAGE_SIZE)
+            .collect()
+    }
 
-            if start >= filtered_logs.len() {
-                return Vec::new();
+    /// Number of stored log entries of a runtime per level
+    pub fn counts_by_level(&self, runtime_id: RuntimeId) -> LogLevelCounts {
+        let mut counts = LogLevelCounts::default();
+        if let Some(runtime_logs) = self.logs.read().get(&runtime_id) {
+            for entry in &runtime_logs.entries {
+                counts.add(entry.level);
             }
+        }
+        counts
+    }
 
-            filtered_logs[start..end].to_vec()
-        } else {
-            Vec::new()
+    /// Number of stored log entries of a runtime per level, in consecutive buckets of
+    /// `bucket_secs` seconds from the oldest entry to the most recent one.
+    /// Buckets without entries are included, so that they can be charted as they are.
+    pub fn timeline(
+        &self,
+        runtime_id: RuntimeId,
+        bucket_secs: u64,
+    ) -> Result<Vec<LogTimelineBucket>, String> {
+        let bucket_secs = i64::try_from(bucket_secs)
+            .ok()
+            .filter(|bucket_secs| *bucket_secs > 0)
+            .ok_or_else(|| format!("Invalid bucket duration {} s", bucket_secs))?;
+        let mut buckets: BTreeMap<i64, LogLevelCounts> = BTreeMap::new();
+        if let Some(runtime_logs) = self.logs.read().get(&runtime_id) {
+            for entry in &runtime_logs.entries {
+                buckets
+                    .entry(entry.timestamp.timestamp().div_euclid(bucket_secs))
+                    .or_default()
+                    .add(entry.level);
+            }
+        }
+        let (Some(first), Some(last)) = (
+            buckets.first_key_value().map(|(bucket, _)| *bucket),
+            buckets.last_key_value().map(|(bucket, _)| *bucket),
+        ) else {
+            return Ok(Vec::new());
+        };
+        if last - first >= MAX_LOG_TIMELINE_BUCKETS {
+            return Err(format!(
+                "The logs span more than {} buckets of {} s, use longer buckets",
+                MAX_LOG_TIMELINE_BUCKETS, bucket_secs
+            ));
+        }
+        Ok((first..=last)
+            .map(|bucket| LogTimelineBucket {
+                start: DateTime::from_timestamp(bucket * bucket_secs, 0).unwrap_or_default(),
+                counts: buckets.remove(&bucket).unwrap_or_default(),
+            })
+            .collect())
+    }
+
+    /// Number of stored log entries of a runtime, in total and per level,
+    /// and the range of their sequence numbers
+    pub fn stats(&self, runtime_id: RuntimeId) -> LogStats {
+        let counts = self.counts_by_level(runtime_id);
+        let logs = self.logs.read();
+        let entries = logs
+            .get(&runtime_id)
+            .map(|runtime_logs| &runtime_logs.entries);
+        LogStats {
+            runtime_id,
+            total: entries.map_or(0, VecDeque::len),
+            counts,
+            oldest_seq: entries
+                .and_then(|entries| entries.front())
+                .map(|entry| entry.seq),
+            newest_seq: entries
+                .and_then(|entries| entries.back())
+                .map(|entry| entry.seq),
         }
     }
 
-    /// Clear logs for a specific runtime
+    /// Clear logs for a specific runtime, including its persisted ones.
+    /// The numbering goes on, so that entries added later don't reuse sequence numbers.
     pub fn clear_logs(&self, runtime_id: RuntimeId) {
+        if let Some(persistence) = self.persistence.lock().as_mut() {
+            persistence.remove(runtime_id);
+        }
+        if let Some(runtime_logs) = self.logs.write().get_mut(&runtime_id) {
+            runtime_logs.entries.clear();
+            runtime_logs.targets.clear();
+        }
+    }
+
+    /// Distinct targets of the logs of a runtime, sorted
+    pub fn targets(&self, runtime_id: RuntimeId) -> Vec<String> {
+        self.logs
+            .read()
+            .get(&runtime_id)
+            .map(|runtime_logs| runtime_logs.targets.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    /// Skip `count` sequence numbers for entries of a runtime that were dropped before
+    /// reaching the storage, so that the gap shows where they would have been
+    pub fn skip_seq(&self, runtime_id: RuntimeId, count: u64) {
         let mut logs = self.logs.write();
-        logs.remove(&runtime_id);
+        self.runtime_logs(&mut logs, runtime_id).next_seq += count;
+    }
+
+    /// RuntimeIds having logs, including the sandbox
+    pub fn runtime_ids(&self) -> Vec<RuntimeId> {
+        let mut runtime_ids: Vec<RuntimeId> = self
+            .logs
+            .read()
+            .iter()
+            .filter(|(_, runtime_logs)| !runtime_logs.entries.is_empty())
+            .map(|(runtime_id, _)| *runtime_id)
+            .collect();
+        runtime_ids.sort();
+        runtime_ids
     }
 
     /// Get a reference to the internal logs for the custom layer
-    pub fn logs_ref(&self) -> Arc<ParkingLotRwLock<HashMap<RuntimeId, Vec<LogEntry>>>> {
+    pub fn logs_ref(&self) -> Arc<ParkingLotRwLock<HashMap<RuntimeId, RuntimeLogs>>> {
         self.logs.clone()
     }
 }
@@ -117,3 +785,431 @@ impl Default for LogStorage {
     }
 }
 
+// ============================================================================
+// Persistence commands
+// ============================================================================
+
+/// Load the persisted logs of the sandbox and of the declared runtimes, if persistence is enabled
+pub(crate) async fn replay_persisted_logs(app: AppHandle) {
+    let mut runtime_ids: Vec<RuntimeId> = app
+        .state::<ZenohRuntimes>()
+        .runtimes
+        .read()
+        .await
+        .keys()
+        .copied()
+        .collect();
+    runtime_ids.push(SANDBOX_RUNTIME_ID);
+
+    let storage = app.state::<LogStorage>().inner().clone();
+    let replayed = tokio::task::spawn_blocking(move || {
+        runtime_ids
+            .into_iter()
+            .map(|runtime_id| storage.replay(runtime_id))
+            .sum::<usize>()
+    })
+    .await;
+    match replayed {
+        Ok(0) => {}
+        Ok(count) => tracing::info!("Replayed {} persisted log entries", count),
+        Err(e) => tracing::error!("Log replay task failed: {}", e),
+    }
+}
+
+/// Get a page of the logs of several runtimes interleaved in one chronological stream,
+/// most recent first, each entry tagged with its runtime. Entries can be filtered by level
+/// and target patterns, see `zenoh_runtime_log`.
+/// Without `runtime_ids`, the logs of all runtimes are merged, except the sandbox's own.
+/// Page 0 returns the most recent logs.
+#[tauri::command]
+#[tracing::instrument(skip(state))]
+pub async fn get_merged_logs(
+    level: Option<LogEntryLevel>,
+    page: usize,
+    runtime_ids: Option<Vec<RuntimeId>>,
+    targets: Option<Vec<String>>,
+    state: State<'_, LogStorage>,
+) -> Result<Vec<MergedLogEntry>, SandboxError> {
+    let runtime_ids = runtime_ids.unwrap_or_else(|| {
+        let mut runtime_ids = state.runtime_ids();
+        runtime_ids.retain(|runtime_id| *runtime_id != SANDBOX_RUNTIME_ID);
+        runtime_ids
+    });
+    let filter = LogFilter::level(level).with_targets(targets);
+    Ok(state.get_merged_page(&runtime_ids, &filter, page))
+}
+
+/// Get the number of stored log entries of a runtime per level in buckets of `bucket_secs`
+/// seconds, oldest first, to chart the warning and error activity without fetching the entries
+#[tauri::command]
+#[tracing::instrument(skip(state))]
+pub async fn get_log_timeline(
+    runtime_id: RuntimeId,
+    bucket_secs: u64,
+    state: State<'_, LogStorage>,
+) -> Result<Vec<LogTimelineBucket>, SandboxError> {
+    Ok(state.timeline(runtime_id, bucket_secs)?)
+}
+
+/// Get whether log entries are persisted to disk, and where
+#[tauri::command]
+#[tracing::instrument(skip(state))]
+pub async fn get_log_persistence(
+    state: State<'_, LogStorage>,
+) -> Result<LogPersistenceStatus, SandboxError> {
+    Ok(state.persistence_status())
+}
+
+/// Enable or disable writing log entries to JSONL files in the log directory, one per runtime,
+/// rotated once they exceed `max_file_size` bytes. When enabled, the persisted logs of the
+/// sandbox and of the declared runtimes are loaded into memory.
+#[tauri::command]
+#[tracing::instrument(skip(app))]
+pub async fn set_log_persistence(
+    enabled: bool,
+    max_file_size: Option<u64>,
+    app: AppHandle,
+) -> Result<LogPersistenceStatus, SandboxError> {
+    let storage = app.state::<LogStorage>();
+    if enabled {
+        let dir = app.state::<ZenohRuntimes>().dirs.read().log_dir.clone();
+        storage
+            .enable_persistence(dir.clone(), max_file_size)
+            .map_err(|e| {
+                format!(
+                    "Failed to enable log persistence in {}: {}",
+                    dir.display(),
+                    e
+                )
+            })?;
+        replay_persisted_logs(app.clone()).await;
+    } else {
+        storage.disable_persistence();
+    }
+    Ok(storage.persistence_status())
+}
+
+// ============================================================================
+// Log Export
+// ============================================================================
+
+/// Quote a CSV field when it contains a separator, a quote or a line break
+fn csv_field(value: &str) -> Cow<'_, str> {
+    if value.contains([',', '"', '\n', '\r']) {
+        Cow::Owned(format!("\"{}\"", value.replace('"', "\"\"")))
+    } else {
+        Cow::Borrowed(value)
+    }
+}
+
+/// Log entry tagged with its runtime, as written in merged files
+#[derive(Serialize)]
+struct TaggedLogEntry<'a> {
+    runtime_id: RuntimeId,
+    #[serde(flatten)]
+    entry: &'a LogEntry,
+}
+
+/// Write the header of a log file, if the format has one.
+/// Merged files have an additional `runtime_id` column.
+pub(crate) fn write_log_header(
+    writer: &mut impl io::Write,
+    format: LogExportFormat,
+    merged: bool,
+) -> io::Result<()> {
+    match format {
+        LogExportFormat::Csv if merged => {
+            writeln!(writer, "timestamp,runtime_id,level,target,message")
+        }
+        LogExportFormat::Csv => writeln!(writer, "timestamp,level,target,message"),
+        LogExportFormat::Jsonl | LogExportFormat::Text => Ok(()),
+    }
+}
+
+/// Write a log entry in the given format, tagged with `runtime_id` in merged files
+pub(crate) fn write_log_entry(
+    writer: &mut impl io::Write,
+    format: LogExportFormat,
+    runtime_id: Option<RuntimeId>,
+    entry: &LogEntry,
+) -> io::Result<()> {
+    let timestamp = entry.timestamp.to_rfc3339();
+    let level = Level::from(&entry.level);
+    match (format, runtime_id) {
+        (LogExportFormat::Jsonl, Some(runtime_id)) => {
+            serde_json::to_writer(&mut *writer, &TaggedLogEntry { runtime_id, entry })?;
+            writeln!(writer)
+        }
+        (LogExportFormat::Jsonl, None) => {
+            serde_json::to_writer(&mut *writer, entry)?;
+            writeln!(writer)
+        }
+        (LogExportFormat::Csv, Some(runtime_id)) => writeln!(
+            writer,
+            "{},{},{},{},{}",
+            timestamp,
+            runtime_id,
+            level,
+            csv_field(&entry.target),
+            csv_field(&entry.message)
+        ),
+        (LogExportFormat::Csv, None) => writeln!(
+            writer,
+            "{},{},{},{}",
+            timestamp,
+            level,
+            csv_field(&entry.target),
+            csv_field(&entry.message)
+        ),
+        (LogExportFormat::Text, Some(runtime_id)) => writeln!(
+            writer,
+            "{} [{}] {:>5} {}: {}",
+            timestamp, runtime_id, level, entry.target, entry.message
+        ),
+        (LogExportFormat::Text, None) => writeln!(
+            writer,
+            "{} {:>5} {}: {}",
+            timestamp, level, entry.target, entry.message
+        ),
+    }
+}
+
+/// Write log entries to a file, streamed through a buffered writer
+pub(crate) fn write_log_file(
+    path: &Path,
+    format: LogExportFormat,
+    entries: &[LogEntry],
+) -> io::Result<()> {
+    let mut writer = BufWriter::new(File::create(path)?);
+    write_log_header(&mut writer, format, false)?;
+    for entry in entries {
+        write_log_entry(&mut writer, format, None, entry)?;
+    }
+    writer.flush()
+}
+
+/// Write the in-memory logs of a runtime to a file, oldest first.
+/// Only entries at or above `level` are written when it is given.
+/// Returns the absolute path of the written file.
+#[tauri::command]
+#[tracing::instrument(skip(state))]
+pub async fn zenoh_runtime_log_export(
+    runtime_id: RuntimeId,
+    format: LogExportFormat,
+    path: String,
+    level: Option<LogEntryLevel>,
+    state: State<'_, LogStorage>,
+) -> Result<String, SandboxError> {
+    let entries = state.get_all(runtime_id, &LogFilter::level(level));
+    let count = entries.len();
+
+    let path = std::path::absolute(PathBuf::from(&path))
+        .map_err(|e| format!("Invalid log export path {}: {}", path, e))?;
+    let file_path = path.clone();
+    tokio::task::spawn_blocking(move || write_log_file(&file_path, format, &entries))
+        .await
+        .map_err(|e| format!("Log export task failed: {}", e))?
+        .map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+    tracing::info!(
+        "Exported {} log entries of runtime {} to {}",
+        count,
+        runtime_id,
+        path.display()
+    );
+    Ok(path.to_string_lossy().to_string())
+}
+
+// ============================================================================
+// Raw process output
+// ============================================================================
+//
+// The stdout and stderr of runtime processes go to files of the log directory. They hold
+// what the runtime prints outside of its tracing subscriber, like panics and the output
+// before the subscriber is installed.
+
+/// Number of lines returned by `zenoh_runtime_raw_logs` by default
+const DEFAULT_RAW_LOG_LINES: usize = 200;
+
+/// Maximum number of lines returned by `zenoh_runtime_raw_logs`
+const MAX_RAW_LOG_LINES: usize = 10_000;
+
+/// Number of bytes read from the end of an output file, bounding the lines returned
+const RAW_LOG_TAIL_BYTES: u64 = 4 * 1024 * 1024;
+
+/// ANSI escape sequences: CSI sequences like colors, OSC sequences like hyperlinks,
+/// and other two-character escapes
+static ANSI_ESCAPE: LazyLock<regex::Regex> = LazyLock::new(|| {
+    regex::Regex::new(r"\x1b(\[[0-?]*[ -/]*[@-~]|\][^\x07\x1b]*(\x07|\x1b\\)|[@-Z\\-_])")
+        .expect("valid ANSI escape regex")
+});
+
+/// Text of a line of output, without ANSI escape sequences and carriage returns
+fn strip_ansi(line: &str) -> String {
+    ANSI_ESCAPE.replace_all(line, "").replace('\r', "")
+}
+
+/// Last `count` lines of an output file, without ANSI escape sequences.
+/// Only the end of large files is read.
+fn tail_output_file(path: &Path, count: usize) -> io::Result<Vec<String>> {
+    let mut file = File::open(path)?;
+    let len = file.metadata()?.len();
+    let start = len.saturating_sub(RAW_LOG_TAIL_BYTES);
+    file.seek(SeekFrom::Start(start))?;
+    let mut bytes = Vec::new();
+    file.read_to_end(&mut bytes)?;
+
+    let text = String::from_utf8_lossy(&bytes);
+    let mut lines: Vec<&str> = text.lines().collect();
+    if start > 0 && !lines.is_empty() {
+        // The first line read is likely cut
+        lines.remove(0);
+    }
+    let skip = lines.len().saturating_sub(count);
+    Ok(lines[skip..].iter().map(|line| strip_ansi(line)).collect())
+}
+
+/// Read the last lines written by the last process of a runtime to its stdout or stderr,
+/// without ANSI escape sequences. `tail_lines` defaults to 200 and is capped to 10000.
+#[tauri::command]
+#[tracing::instrument(skip(state))]
+pub async fn zenoh_runtime_raw_logs(
+    runtime_id: RuntimeId,
+    stream: OutputStream,
+    tail_lines: Option<usize>,
+    state: State<'_, ZenohRuntimes>,
+) -> Result<Vec<String>, SandboxError> {
+    let path = {
+        let runtimes = state.runtimes.read().await;
+        let runtime_process = runtimes
+            .get(&runtime_id)
+            .ok_or(SandboxError::RuntimeNotFound(runtime_id))?;
+        let path = match stream {
+            OutputStream::Stdout => &runtime_process.stdout_log,
+            OutputStream::Stderr => &runtime_process.stderr_log,
+        };
+        path.clone()
+            .ok_or(SandboxError::RuntimeNotStarted(runtime_id))?
+    };
+    let count = tail_lines
+        .unwrap_or(DEFAULT_RAW_LOG_LINES)
+        .min(MAX_RAW_LOG_LINES);
+
+    let file_path = path.clone();
+    let lines = tokio::task::spawn_blocking(move || tail_output_file(&file_path, count))
+        .await
+        .map_err(|e| format!("Output read task failed: {}", e))?
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    Ok(lines)
+}
+
+// ============================================================================
+// Tracing Integration
+// ============================================================================
+
+/// Records the message and the other fields of an event
+#[derive(Default)]
+struct FieldVisitor {
+    message: String,
+    fields: HashMap<String, String>,
+}
+
+impl FieldVisitor {
+    fn record(&mut self, field: &tracing::field::Field, value: String) {
+        if field.name() == "message" {
+            self.message = value;
+        } else {
+            self.fields.insert(field.name().to_string(), value);
+        }
+    }
+}
+
+impl tracing::field::Visit for FieldVisitor {
+    fn record_str(&mut self, field: &tracing::field::Field, value: &str) {
+        self.record(field, value.to_string());
+    }
+
+    fn record_debug(&mut self, field: &tracing::field::Field, value: &dyn std::fmt::Debug) {
+        self.record(field, format!("{:?}", value));
+    }
+}
+
+/// Fields of a span formatted as `field=value ...`, stored in the span's extensions
+struct SpanFields(String);
+
+/// Formats span fields as `field=value ...`
+struct SpanFieldsVisitor<'a>(&'a mut String);
+
+impl tracing::field::Visit for SpanFieldsVisitor<'_> {
+    fn record_str(&mut self, field: &tracing::field::Field, value: &str) {
+        self.record_debug(field, &value);
+    }
+
+    fn record_debug(&mut self, field: &tracing::field::Field, value: &dyn std::fmt::Debug) {
+        if !self.0.is_empty() {
+            self.0.push(' ');
+        }
+        let _ = write!(self.0, "{}={:?}", field.name(), value);
+    }
+}
+
+/// Store the fields of a new span, so that events inside it can report them.
+/// To be called from `Layer::on_new_span`.
+pub fn record_span_fields<S>(attrs: &span::Attributes<'_>, id: &span::Id, ctx: &Context<'_, S>)
+where
+    S: tracing::Subscriber + for<'a> LookupSpan<'a>,
+{
+    if let Some(span) = ctx.span(id) {
+        let mut fields = String::new();
+        attrs.record(&mut SpanFieldsVisitor(&mut fields));
+        span.extensions_mut().replace(SpanFields(fields));
+    }
+}
+
+/// Add fields recorded after the creation of a span.
+/// To be called from `Layer::on_record`.
+pub fn update_span_fields<S>(id: &span::Id, values: &span::Record<'_>, ctx: &Context<'_, S>)
+where
+    S: tracing::Subscriber + for<'a> LookupSpan<'a>,
+{
+    if let Some(span) = ctx.span(id) {
+        let mut extensions = span.extensions_mut();
+        match extensions.get_mut::<SpanFields>() {
+            Some(SpanFields(fields)) => values.record(&mut SpanFieldsVisitor(fields)),
+            None => {
+                let mut fields = String::new();
+                values.record(&mut SpanFieldsVisitor(&mut fields));
+                extensions.insert(SpanFields(fields));
+            }
+        }
+    }
+}
+
+/// Tracing layer storing the sandbox's own events in `LogStorage`
+/// under the `SANDBOX_RUNTIME_ID` pseudo-runtime
+pub struct LogStorageLayer {
+    storage: LogStorage,
+}
+
+impl LogStorageLayer {
+    pub fn new(storage: LogStorage) -> Self {
+        Self { storage }
+    }
+}
+
+impl<S> Layer<S> for LogStorageLayer
+where
+    S: tracing::Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, attrs: &span::Attributes<'_>, id: &span::Id, ctx: Context<'_, S>) {
+        record_span_fields(attrs, id, &ctx);
+    }
+
+    fn on_record(&self, id: &span::Id, values: &span::Record<'_>, ctx: Context<'_, S>) {
+        update_span_fields(id, values, &ctx);
+    }
+
+    fn on_event(&self, event: &tracing::Event<'_>, ctx: Context<'_, S>) {
+        self.storage
+            .add_log(SANDBOX_RUNTIME_ID, LogEntry::from_event(event, &ctx));
+    }
+}
@@ -0,0 +1,403 @@
+use std::{
+    collections::BTreeMap,
+    ffi::OsStr,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use chrono::{DateTime, Utc};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::{
+    RuntimeId, SANDBOX_RUNTIME_ID, ZenohRuntimes,
+    error::SandboxError,
+    logs::LogStorage,
+    runtime_binary_path,
+    ts::{
+        log::LogEntryLevel,
+        orphans::{OrphanAction, OrphanCleanupOutcome, OrphanProcess},
+    },
+};
+
+// ============================================================================
+// Constants
+// ============================================================================
+
+/// Prefix of the files recording runtime processes in the socket directory
+const RECORD_FILE_PREFIX: &str = "runtime-";
+
+/// Extension of the files recording runtime processes
+const RECORD_FILE_EXTENSION: &str = "json";
+
+/// Time for an orphan process to exit after SIGTERM before it is killed
+const KILL_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Interval between two checks of an exiting orphan process
+const KILL_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+// ============================================================================
+// Process records
+// ============================================================================
+//
+// Each spawned runtime process is recorded in a file of the socket directory,
+// removed once the process exits. When the sandbox crashes, the records of its
+// processes remain: on the next start, the recorded processes still running while
+// the sandbox instance that spawned them is gone are reported as orphans, and the
+// records of processes that exited are removed.
+
+/// Runtime process spawned by a sandbox instance
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct ProcessRecord {
+    pub pid: u32,
+    /// PID of the sandbox instance that spawned the process
+    pub sandbox_pid: u32,
+    pub runtime_id: RuntimeId,
+    pub port: u16,
+    pub started_at: DateTime<Utc>,
+}
+
+impl ProcessRecord {
+    fn orphan(&self, adopted: bool) -> OrphanProcess {
+        OrphanProcess {
+            pid: self.pid,
+            runtime_id: self.runtime_id,
+            port: self.port,
+            started_at: self.started_at,
+            adopted,
+        }
+    }
+}
+
+/// Path of the record of a runtime process
+fn record_path(socket_dir: &Path, pid: u32) -> PathBuf {
+    socket_dir.join(format!(
+        "{}{}.{}",
+        RECORD_FILE_PREFIX, pid, RECORD_FILE_EXTENSION
+    ))
+}
+
+/// Write the record of a runtime process spawned by this sandbox instance.
+/// Returns the path of the record.
+pub(crate) fn register(
+    socket_dir: &Path,
+    pid: u32,
+    runtime_id: RuntimeId,
+    port: u16,
+) -> Result<PathBuf, String> {
+    let record = ProcessRecord {
+        pid,
+        sandbox_pid: std::process::id(),
+        runtime_id,
+        port,
+        started_at: Utc::now(),
+    };
+    let path = record_path(socket_dir, pid);
+    write_record(&path, &record)?;
+    Ok(path)
+}
+
+fn write_record(path: &Path, record: &ProcessRecord) -> Result<(), String> {
+    let contents = serde_json::to_string(record)
+        .map_err(|e| format!("Failed to serialize process record: {}", e))?;
+    std::fs::write(path, contents).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+/// Remove the record of a runtime process that exited
+pub(crate) fn unregister(path: &Path) {
+    if let Err(e) = std::fs::remove_file(path)
+        && e.kind() != std::io::ErrorKind::NotFound
+    {
+        tracing::warn!("Failed to remove {}: {}", path.display(), e);
+    }
+}
+
+/// Records of the socket directory, with their path
+async fn read_records(socket_dir: &Path) -> Vec<(PathBuf, ProcessRecord)> {
+    let mut records = Vec::new();
+    let Ok(mut entries) = tokio::fs::read_dir(socket_dir).await else {
+        return records;
+    };
+    while let Ok(Some(entry)) = entries.next_entry().await {
+        let path = entry.path();
+        let is_record = path.extension() == Some(OsStr::new(RECORD_FILE_EXTENSION))
+            && entry
+                .file_name()
+                .to_str()
+                .is_some_and(|name| name.starts_with(RECORD_FILE_PREFIX));
+        if !is_record {
+            continue;
+        }
+        let record = tokio::fs::read_to_string(&path)
+            .await
+            .ok()
+            .and_then(|contents| serde_json::from_str::<ProcessRecord>(&contents).ok());
+        match record {
+            Some(record) => records.push((path, record)),
+            None => tracing::warn!("Ignoring invalid process record {}", path.display()),
+        }
+    }
+    records
+}
+
+// ============================================================================
+// Processes
+// ============================================================================
+
+/// Whether a process is running the given executable.
+/// Returns None if this can't be determined on this platform.
+#[cfg(unix)]
+fn is_running(pid: u32, executable: &OsStr) -> Option<bool> {
+    // SAFETY: signal 0 only checks that the process exists
+    let exists = unsafe { libc::kill(pid as libc::pid_t, 0) } == 0
+        || std::io::Error::last_os_error().raw_os_error() == Some(libc::EPERM);
+    if !exists {
+        return Some(false);
+    }
+    // PIDs are reused, check the executable where possible.
+    // A replaced executable is reported with a " (deleted)" suffix.
+    #[cfg(target_os = "linux")]
+    if let Ok(exe) = std::fs::read_link(format!("/proc/{}/exe", pid)) {
+        return Some(exe.file_name().is_some_and(|name| {
+            name.to_string_lossy()
+                .starts_with(executable.to_string_lossy().as_ref())
+        }));
+    }
+    #[cfg(not(target_os = "linux"))]
+    let _ = executable;
+    Some(true)
+}
+
+#[cfg(not(unix))]
+fn is_running(_pid: u32, _executable: &OsStr) -> Option<bool> {
+    None
+}
+
+/// Terminate a process, killing it if it doesn't exit within `KILL_TIMEOUT`
+#[cfg(unix)]
+async fn terminate(pid: u32, executable: &OsStr) -> Result<(), String> {
+    let signal = |signal| {
+        // SAFETY: kill has no memory safety preconditions
+        if unsafe { libc::kill(pid as libc::pid_t, signal) } != 0 {
+            return Err(std::io::Error::last_os_error().to_string());
+        }
+        Ok(())
+    };
+    signal(libc::SIGTERM)?;
+    let deadline = tokio::time::Instant::now() + KILL_TIMEOUT;
+    while tokio::time::Instant::now() < deadline {
+        if is_running(pid, executable) == Some(false) {
+            return Ok(());
+        }
+        tokio::time::sleep(KILL_POLL_INTERVAL).await;
+    }
+    tracing::warn!("Orphan process {} didn't exit, killing it", pid);
+    signal(libc::SIGKILL)
+}
+
+#[cfg(not(unix))]
+async fn terminate(_pid: u32, _executable: &OsStr) -> Result<(), String> {
+    Err("Terminating orphan processes is only supported on Unix".to_string())
+}
+
+/// File name of an executable path
+fn executable_name(path: Result<PathBuf, impl ToString>) -> Result<std::ffi::OsString, String> {
+    path.map_err(|e| e.to_string())?
+        .file_name()
+        .map(OsStr::to_os_string)
+        .ok_or_else(|| "Executable path has no file name".to_string())
+}
+
+// ============================================================================
+// Orphans
+// ============================================================================
+
+/// Orphan processes adopted by this sandbox instance, by PID
+#[derive(Default)]
+pub struct Orphans {
+    adopted: Mutex<BTreeMap<u32, (PathBuf, ProcessRecord)>>,
+}
+
+/// Result of a scan of the process records
+struct Scan {
+    /// Records of running processes whose sandbox instance is gone
+    orphans: Vec<(PathBuf, ProcessRecord)>,
+    /// Records of processes that exited
+    stale: Vec<PathBuf>,
+}
+
+/// Scan the process records of the socket directory
+async fn scan(app: &AppHandle) -> Result<Scan, String> {
+    let runtime_executable = executable_name(runtime_binary_path())?;
+    let sandbox_executable = executable_name(std::env::current_exe())?;
+    let socket_dir = app.state::<ZenohRuntimes>().dirs.read().socket_dir.clone();
+    let own_pid = std::process::id();
+
+    let mut scan = Scan {
+        orphans: Vec::new(),
+        stale: Vec::new(),
+    };
+    for (path, record) in read_records(&socket_dir).await {
+        match is_running(record.pid, &runtime_executable) {
+            Some(true) => {
+                // Processes of this instance or of another running instance aren't orphans
+                let owned = record.sandbox_pid == own_pid
+                    || is_running(record.sandbox_pid, &sandbox_executable) != Some(false);
+                if !owned {
+                    scan.orphans.push((path, record));
+                }
+            }
+            Some(false) => scan.stale.push(path),
+            None => {}
+        }
+    }
+    Ok(scan)
+}
+
+/// Look for orphan processes when the sandbox starts.
+/// Records of exited processes are removed, and orphans are reported in the sandbox logs
+/// and with an `orphans-found` event.
+pub(crate) async fn scan_on_startup(app: AppHandle) {
+    let scan = match scan(&app).await {
+        Ok(scan) => scan,
+        Err(e) => {
+            tracing::warn!("Failed to look for orphan runtime processes: {}", e);
+            return;
+        }
+    };
+    for path in &scan.stale {
+        unregister(path);
+    }
+    if scan.orphans.is_empty() {
+        return;
+    }
+
+    let orphans: Vec<OrphanProcess> = scan
+        .orphans
+        .iter()
+        .map(|(_, record)| record.orphan(false))
+        .collect();
+    let pids: Vec<String> = orphans
+        .iter()
+        .map(|orphan| orphan.pid.to_string())
+        .collect();
+    tracing::warn!("Found orphan runtime processes: {}", pids.join(", "));
+    app.state::<LogStorage>().add_sandbox_log(
+        SANDBOX_RUNTIME_ID,
+        LogEntryLevel::WARN,
+        format!(
+            "Runtime processes left by a previous sandbox instance are still running (PIDs {}), \
+             kill or adopt them with cleanup_orphans",
+            pids.join(", ")
+        ),
+    );
+    let _ = app.emit("orphans-found", orphans);
+}
+
+impl Orphans {
+    /// Stop the adopted orphan processes, when the sandbox exits.
+    /// Returns the number of processes stopped.
+    pub(crate) async fn stop_adopted(&self) -> usize {
+        let adopted = std::mem::take(&mut *self.adopted.lock());
+        let Ok(runtime_executable) = executable_name(runtime_binary_path()) else {
+            return 0;
+        };
+        let mut stopped = 0;
+        for (pid, (path, _)) in adopted {
+            match terminate(pid, &runtime_executable).await {
+                Ok(()) => {
+                    unregister(&path);
+                    stopped += 1;
+                }
+                Err(e) => tracing::warn!("Failed to stop adopted process {}: {}", pid, e),
+            }
+        }
+        stopped
+    }
+}
+
+// ============================================================================
+// Tauri commands
+// ============================================================================
+
+/// List the runtime processes left running by previous sandbox instances,
+/// including the ones adopted by this instance
+#[tauri::command]
+#[tracing::instrument(skip(app))]
+pub async fn list_orphans(app: AppHandle) -> Result<Vec<OrphanProcess>, SandboxError> {
+    let scan = scan(&app).await?;
+    let adopted = app.state::<Orphans>().adopted.lock().clone();
+    let mut orphans: Vec<OrphanProcess> = scan
+        .orphans
+        .iter()
+        .map(|(_, record)| record.orphan(false))
+        .chain(adopted.values().map(|(_, record)| record.orphan(true)))
+        .collect();
+    orphans.sort_by_key(|orphan| orphan.pid);
+    Ok(orphans)
+}
+
+/// Kill or adopt orphan runtime processes, all of them unless `pids` is given.
+/// Adopted processes keep running with their port reserved, and are stopped when
+/// the sandbox exits. Adopted processes can be killed later.
+/// Returns the outcome for each process.
+#[tauri::command]
+#[tracing::instrument(skip(app))]
+pub async fn cleanup_orphans(
+    action: OrphanAction,
+    pids: Option<Vec<u32>>,
+    app: AppHandle,
+) -> Result<Vec<OrphanCleanupOutcome>, SandboxError> {
+    let runtime_executable = executable_name(runtime_binary_path())?;
+    let orphans = app.state::<Orphans>();
+    let mut candidates = scan(&app).await?.orphans;
+    candidates.extend(orphans.adopted.lock().values().cloned());
+    if let Some(pids) = &pids {
+        candidates.retain(|(_, record)| pids.contains(&record.pid));
+    }
+
+    let runtimes = app.state::<ZenohRuntimes>();
+    let mut outcomes = Vec::new();
+    for (path, mut record) in candidates {
+        let result = match action {
+            OrphanAction::Kill => {
+                let result = terminate(record.pid, &runtime_executable).await;
+                if result.is_ok() {
+                    unregister(&path);
+                    let adopted = orphans.adopted.lock().remove(&record.pid);
+                    if adopted.is_some() {
+                        runtimes.release_port(record.port).await;
+                    }
+                }
+                result
+            }
+            OrphanAction::Adopt if orphans.adopted.lock().contains_key(&record.pid) => Ok(()),
+            OrphanAction::Adopt => {
+                // Owned by this instance from now on, so it is no longer reported as orphan
+                record.sandbox_pid = std::process::id();
+                let result = write_record(&path, &record);
+                if result.is_ok() {
+                    tracing::info!(
+                        "Adopted orphan process {} using port {}",
+                        record.pid,
+                        record.port
+                    );
+                    runtimes.port_tracker.write().await.insert(record.port);
+                    orphans
+                        .adopted
+                        .lock()
+                        .insert(record.pid, (path, record.clone()));
+                }
+                result
+            }
+        };
+        outcomes.push(OrphanCleanupOutcome {
+            pid: record.pid,
+            action,
+            error: result.err(),
+        });
+    }
+    outcomes.sort_by_key(|outcome| outcome.pid);
+    Ok(outcomes)
+}
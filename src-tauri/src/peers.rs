@@ -0,0 +1,246 @@
+use std::{
+    collections::{BTreeSet, HashMap},
+    time::Duration,
+};
+
+use tauri::{AppHandle, Emitter, Manager, State};
+
+use crate::{
+    RuntimeId, ZenohRuntimes,
+    error::SandboxError,
+    logs::LogStorage,
+    reports,
+    scenarios::Scenarios,
+    ts::{
+        log::LogEntryLevel,
+        report::{TopologyDrift, TopologyLink},
+        runtime::{PeerDivergence, PeerDivergenceEvent, RuntimeState},
+    },
+    zenoh_admin,
+};
+
+// ============================================================================
+// Constants
+// ============================================================================
+
+/// Interval between two comparisons of expected and live peers
+const PEER_CHECK_INTERVAL: Duration = Duration::from_secs(5);
+
+// ============================================================================
+// Expected peers monitoring
+// ============================================================================
+
+impl ZenohRuntimes {
+    /// Running runtimes with declared expected peers, and the RuntimeId of every running ZenohId
+    async fn expected_peers_snapshot(
+        &self,
+    ) -> (Vec<(RuntimeId, Vec<RuntimeId>)>, HashMap<String, RuntimeId>) {
+        let runtimes = self.runtimes.read().await;
+        let mut expected = Vec::new();
+        let mut by_zid = HashMap::new();
+        for (runtime_id, runtime_process) in runtimes.iter() {
+            if runtime_process.state != RuntimeState::Running {
+                continue;
+            }
+            if let Some(zid) = runtime_process.zenoh_id {
+                by_zid.insert(zid.to_string(), *runtime_id);
+            }
+            if let Some(peers) = &runtime_process.options.expected_peers {
+                expected.push((*runtime_id, peers.clone()));
+            }
+        }
+        (expected, by_zid)
+    }
+
+    /// Record the divergence of a runtime. Returns true if it changed.
+    async fn set_peer_divergence(
+        &self,
+        runtime_id: RuntimeId,
+        divergence: Option<PeerDivergence>,
+    ) -> bool {
+        let mut runtimes = self.runtimes.write().await;
+        match runtimes.get_mut(&runtime_id) {
+            Some(runtime_process)
+                if runtime_process.state == RuntimeState::Running
+                    && runtime_process.peer_divergence != divergence =>
+            {
+                runtime_process.peer_divergence = divergence;
+                true
+            }
+            _ => false,
+        }
+    }
+}
+
+/// Compare expected peers with the runtimes having a transport session
+fn compare_peers(
+    expected: &[RuntimeId],
+    connected: &BTreeSet<RuntimeId>,
+) -> Option<PeerDivergence> {
+    let expected: BTreeSet<RuntimeId> = expected.iter().copied().collect();
+    let missing: Vec<RuntimeId> = expected.difference(connected).copied().collect();
+    let unexpected: Vec<RuntimeId> = connected.difference(&expected).copied().collect();
+    if missing.is_empty() && unexpected.is_empty() {
+        None
+    } else {
+        Some(PeerDivergence {
+            missing,
+            unexpected,
+        })
+    }
+}
+
+/// Compare the expected peers of every running runtime with its live transport sessions,
+/// reporting changes of divergence through logs and `runtime-peer-divergence` events
+async fn check_expected_peers(app: &AppHandle) {
+    let runtimes = app.state::<ZenohRuntimes>();
+    let (expected, by_zid) = runtimes.expected_peers_snapshot().await;
+
+    for (runtime_id, expected_peers) in expected {
+        let sessions = match zenoh_admin::local_sessions(&runtimes, runtime_id).await {
+            Ok(sessions) => sessions,
+            Err(e) => {
+                tracing::debug!("Failed to get sessions of runtime {}: {}", runtime_id, e);
+                continue;
+            }
+        };
+        let connected: BTreeSet<RuntimeId> = sessions
+            .iter()
+            .filter_map(|session| by_zid.get(&session.peer).copied())
+            .collect();
+        let divergence = compare_peers(&expected_peers, &connected);
+
+        if !runtimes
+            .set_peer_divergence(runtime_id, divergence.clone())
+            .await
+        {
+            continue;
+        }
+
+        let (level, message) = match &divergence {
+            Some(divergence) => (
+                LogEntryLevel::WARN,
+                format!(
+                    "Peers diverge from expected: missing {:?}, unexpected {:?}",
+                    divergence.missing, divergence.unexpected
+                ),
+            ),
+            None => (
+                LogEntryLevel::INFO,
+                "Connected to all expected peers".to_string(),
+            ),
+        };
+        tracing::info!("Runtime {}: {}", runtime_id, message);
+        app.state::<LogStorage>()
+            .add_sandbox_log(runtime_id, level, message);
+        let _ = app.emit(
+            "runtime-peer-divergence",
+            PeerDivergenceEvent {
+                runtime_id,
+                divergence,
+            },
+        );
+    }
+}
+
+/// Periodically check the expected peers of running runtimes, for the lifetime of the app
+pub(crate) async fn monitor_expected_peers(app: AppHandle) {
+    let mut interval = tokio::time::interval(PEER_CHECK_INTERVAL);
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+    loop {
+        interval.tick().await;
+        check_expected_peers(&app).await;
+    }
+}
+
+// ============================================================================
+// Topology drift
+// ============================================================================
+
+impl ZenohRuntimes {
+    /// Links declared through the expected peers of all runtimes, running or not
+    async fn expected_links(&self) -> BTreeSet<TopologyLink> {
+        let runtimes = self.runtimes.read().await;
+        runtimes
+            .iter()
+            .flat_map(|(runtime_id, runtime_process)| {
+                runtime_process
+                    .options
+                    .expected_peers
+                    .iter()
+                    .flatten()
+                    .map(|peer| TopologyLink::undirected(*runtime_id, *peer))
+            })
+            .collect()
+    }
+}
+
+// ============================================================================
+// Tauri commands
+// ============================================================================
+
+/// Compare the intended topology, from expected peers, links wired with `connect_runtimes`
+/// and scenario connect edges, with the live sessions between runtimes
+#[tauri::command]
+#[tracing::instrument(skip(app))]
+pub async fn topology_drift(app: AppHandle) -> Result<TopologyDrift, SandboxError> {
+    let runtimes = app.state::<ZenohRuntimes>();
+    let mut intended = runtimes.expected_links().await;
+    intended.extend(
+        runtimes
+            .links
+            .read()
+            .await
+            .iter()
+            .map(|link| TopologyLink::undirected(link.from, link.to)),
+    );
+    intended.extend(app.state::<Scenarios>().intended_links().await);
+    let nodes: BTreeSet<RuntimeId> = intended
+        .iter()
+        .flat_map(|link| [link.from, link.to])
+        .collect();
+
+    let snapshot = reports::topology_snapshot(&runtimes).await;
+    let live: BTreeSet<TopologyLink> = snapshot
+        .links
+        .iter()
+        .map(|link| TopologyLink::undirected(link.from, link.to))
+        .collect();
+    let running: BTreeSet<RuntimeId> = snapshot
+        .runtimes
+        .iter()
+        .filter(|status| status.state == RuntimeState::Running)
+        .map(|status| status.runtime_id)
+        .collect();
+
+    Ok(TopologyDrift {
+        timestamp: snapshot.timestamp,
+        missing_links: intended.difference(&live).cloned().collect(),
+        unexpected_links: live
+            .difference(&intended)
+            .filter(|link| nodes.contains(&link.from) && nodes.contains(&link.to))
+            .cloned()
+            .collect(),
+        down_nodes: nodes.difference(&running).copied().collect(),
+    })
+}
+
+/// Declare the runtimes a runtime is expected to be connected to.
+/// `None` disables the check for this runtime.
+#[tauri::command]
+#[tracing::instrument(skip(state))]
+pub async fn set_expected_peers(
+    runtime_id: RuntimeId,
+    expected_peers: Option<Vec<RuntimeId>>,
+    state: State<'_, ZenohRuntimes>,
+) -> Result<(), SandboxError> {
+    let mut runtimes = state.runtimes.write().await;
+    let runtime_process = runtimes
+        .get_mut(&runtime_id)
+        .ok_or(SandboxError::RuntimeNotFound(runtime_id))?;
+    if expected_peers.is_none() {
+        runtime_process.peer_divergence = None;
+    }
+    runtime_process.options.expected_peers = expected_peers;
+    Ok(())
+}
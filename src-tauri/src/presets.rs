@@ -0,0 +1,168 @@
+use std::{collections::BTreeMap, path::PathBuf};
+
+use chrono::Utc;
+use tauri::State;
+use tokio::sync::Mutex;
+
+use crate::{
+    error::SandboxError,
+    ts::{
+        config::{ZenohConfigJson, check_config_source},
+        preset::{ConfigPreset, ConfigPresetSummary},
+    },
+};
+
+// ============================================================================
+// Constants
+// ============================================================================
+
+/// Name of the preset library file in the app data directory
+pub const PRESETS_FILE_NAME: &str = "config_presets.json";
+
+// ============================================================================
+// Preset library
+// ============================================================================
+
+/// Named configurations persisted to a JSON file
+pub struct ConfigPresets {
+    /// File the library is persisted to
+    path: PathBuf,
+    /// Presets by name
+    presets: Mutex<BTreeMap<String, ConfigPreset>>,
+}
+
+impl ConfigPresets {
+    /// Load the library from `path`, starting empty if the file doesn't exist or is invalid
+    pub fn load(path: PathBuf) -> Self {
+        let presets = match std::fs::read_to_string(&path) {
+            Ok(contents) => serde_json::from_str::<Vec<ConfigPreset>>(&contents)
+                .map(|presets| {
+                    presets
+                        .into_iter()
+                        .map(|preset| (preset.name.clone(), preset))
+                        .collect()
+                })
+                .unwrap_or_else(|e| {
+                    tracing::error!("Ignoring invalid preset file {}: {}", path.display(), e);
+                    BTreeMap::new()
+                }),
+            Err(_) => BTreeMap::new(),
+        };
+        Self {
+            path,
+            presets: Mutex::new(presets),
+        }
+    }
+
+    /// Write the library to its file, replacing it atomically
+    async fn persist(&self, presets: &BTreeMap<String, ConfigPreset>) -> Result<(), String> {
+        if let Some(dir) = self.path.parent() {
+            tokio::fs::create_dir_all(dir)
+                .await
+                .map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+        }
+        let contents = serde_json::to_string_pretty(&presets.values().collect::<Vec<_>>())
+            .map_err(|e| format!("Failed to serialize presets: {}", e))?;
+        let tmp_path = self.path.with_extension("json.tmp");
+        tokio::fs::write(&tmp_path, contents)
+            .await
+            .map_err(|e| format!("Failed to write {}: {}", tmp_path.display(), e))?;
+        tokio::fs::rename(&tmp_path, &self.path)
+            .await
+            .map_err(|e| format!("Failed to write {}: {}", self.path.display(), e))
+    }
+}
+
+// ============================================================================
+// Tauri commands
+// ============================================================================
+
+/// Save a config under `name`, with the JSON5 `source` it was created from if given.
+/// An existing preset with the same name is replaced only if `overwrite` is set.
+#[tauri::command]
+#[tracing::instrument(skip(config, source, presets))]
+pub async fn save_config_preset(
+    name: String,
+    config: ZenohConfigJson,
+    source: Option<String>,
+    description: Option<String>,
+    overwrite: Option<bool>,
+    presets: State<'_, ConfigPresets>,
+) -> Result<ConfigPreset, SandboxError> {
+    let name = name.trim().to_string();
+    if name.is_empty() {
+        return Err("Preset name cannot be empty".into());
+    }
+    let source = check_config_source(source, &config)?;
+
+    let mut library = presets.presets.lock().await;
+    let now = Utc::now();
+    let created_at = match library.get(&name) {
+        Some(_) if !overwrite.unwrap_or(false) => {
+            return Err(format!("Preset {} already exists", name).into());
+        }
+        Some(existing) => existing.created_at,
+        None => now,
+    };
+    let preset = ConfigPreset {
+        name: name.clone(),
+        description,
+        created_at,
+        updated_at: now,
+        config,
+        source,
+    };
+
+    let previous = library.insert(name.clone(), preset.clone());
+    if let Err(e) = presets.persist(&library).await {
+        // Keep the library consistent with the file
+        match previous {
+            Some(previous) => library.insert(name, previous),
+            None => library.remove(&name),
+        };
+        return Err(e.into());
+    }
+    Ok(preset)
+}
+
+/// List the saved presets, ordered by name
+#[tauri::command]
+#[tracing::instrument(skip(presets))]
+pub async fn list_config_presets(
+    presets: State<'_, ConfigPresets>,
+) -> Result<Vec<ConfigPresetSummary>, SandboxError> {
+    let library = presets.presets.lock().await;
+    Ok(library.values().map(ConfigPresetSummary::from).collect())
+}
+
+/// Get a saved preset with its config
+#[tauri::command]
+#[tracing::instrument(skip(presets))]
+pub async fn load_config_preset(
+    name: String,
+    presets: State<'_, ConfigPresets>,
+) -> Result<ConfigPreset, SandboxError> {
+    let library = presets.presets.lock().await;
+    library
+        .get(&name)
+        .cloned()
+        .ok_or_else(|| format!("Preset {} not found", name).into())
+}
+
+/// Delete a saved preset
+#[tauri::command]
+#[tracing::instrument(skip(presets))]
+pub async fn delete_config_preset(
+    name: String,
+    presets: State<'_, ConfigPresets>,
+) -> Result<(), SandboxError> {
+    let mut library = presets.presets.lock().await;
+    let removed = library
+        .remove(&name)
+        .ok_or_else(|| format!("Preset {} not found", name))?;
+    if let Err(e) = presets.persist(&library).await {
+        library.insert(name, removed);
+        return Err(e.into());
+    }
+    Ok(())
+}
@@ -0,0 +1,136 @@
+use crate::ts::runtime::{ProcessPrivileges, RuntimeOptions};
+
+// ============================================================================
+// Privileges of runtime processes
+// ============================================================================
+
+/// Validate the user and umask requested in the runtime options
+pub fn resolve(options: &RuntimeOptions) -> Result<ProcessPrivileges, String> {
+    if options.user.is_none() && options.umask.is_none() {
+        return Ok(ProcessPrivileges::default());
+    }
+    resolve_requested(options)
+}
+
+/// Parse an octal umask such as `077` or `0o027`
+#[cfg(unix)]
+fn parse_umask(umask: &str) -> Result<u32, String> {
+    let digits = umask.trim().trim_start_matches("0o");
+    u32::from_str_radix(digits, 8)
+        .ok()
+        .filter(|mask| *mask <= 0o777)
+        .ok_or_else(|| {
+            format!(
+                "Invalid umask {:?}: expected an octal value up to 777",
+                umask
+            )
+        })
+}
+
+#[cfg(unix)]
+fn resolve_requested(options: &RuntimeOptions) -> Result<ProcessPrivileges, String> {
+    let umask = options
+        .umask
+        .as_deref()
+        .map(|umask| parse_umask(umask).map(|mask| format!("{:03o}", mask)))
+        .transpose()?;
+
+    let Some(user) = options.user.as_deref() else {
+        return Ok(ProcessPrivileges {
+            umask,
+            ..Default::default()
+        });
+    };
+
+    let (name, uid, gid) = lookup_user(user)?;
+    // SAFETY: geteuid has no preconditions
+    let euid = unsafe { libc::geteuid() };
+    if euid != 0 && euid != uid {
+        return Err(format!(
+            "Cannot run runtime as user {}: the sandbox must run as root to switch users",
+            name
+        ));
+    }
+
+    Ok(ProcessPrivileges {
+        user: Some(name),
+        uid: Some(uid),
+        gid: Some(gid),
+        umask,
+    })
+}
+
+#[cfg(not(unix))]
+fn resolve_requested(_options: &RuntimeOptions) -> Result<ProcessPrivileges, String> {
+    Err("Running runtimes as another user or with a umask is only supported on Unix".to_string())
+}
+
+/// Find a user by name or numeric uid, returning its name, uid and primary gid
+#[cfg(unix)]
+fn lookup_user(user: &str) -> Result<(String, u32, u32), String> {
+    use std::ffi::{CStr, CString};
+
+    let mut passwd: libc::passwd = unsafe { std::mem::zeroed() };
+    let mut buffer = vec![0 as libc::c_char; 4096];
+    let mut result: *mut libc::passwd = std::ptr::null_mut();
+
+    // SAFETY: all pointers are valid for the duration of the call and
+    // `buffer.len()` is the size of the buffer
+    let status = match user.parse::<u32>() {
+        Ok(uid) => unsafe {
+            libc::getpwuid_r(
+                uid,
+                &mut passwd,
+                buffer.as_mut_ptr(),
+                buffer.len(),
+                &mut result,
+            )
+        },
+        Err(_) => {
+            let name = CString::new(user).map_err(|_| format!("Invalid user name {:?}", user))?;
+            unsafe {
+                libc::getpwnam_r(
+                    name.as_ptr(),
+                    &mut passwd,
+                    buffer.as_mut_ptr(),
+                    buffer.len(),
+                    &mut result,
+                )
+            }
+        }
+    };
+    if status != 0 || result.is_null() {
+        return Err(format!("User {} not found", user));
+    }
+
+    // SAFETY: on success pw_name points to a NUL terminated string within `buffer`
+    let name = unsafe { CStr::from_ptr(passwd.pw_name) }
+        .to_string_lossy()
+        .to_string();
+    Ok((name, passwd.pw_uid, passwd.pw_gid))
+}
+
+/// Configure the command to run with the given privileges
+#[cfg(unix)]
+pub fn apply(command: &mut tokio::process::Command, privileges: &ProcessPrivileges) {
+    if let (Some(uid), Some(gid)) = (privileges.uid, privileges.gid) {
+        command.uid(uid).gid(gid);
+    }
+    if let Some(umask) = privileges
+        .umask
+        .as_deref()
+        .and_then(|umask| parse_umask(umask).ok())
+    {
+        // SAFETY: umask is async-signal-safe and cannot fail
+        unsafe {
+            command.pre_exec(move || {
+                libc::umask(umask as libc::mode_t);
+                Ok(())
+            });
+        }
+    }
+}
+
+/// Configure the command to run with the given privileges
+#[cfg(not(unix))]
+pub fn apply(_command: &mut tokio::process::Command, _privileges: &ProcessPrivileges) {}
@@ -1,34 +1,557 @@
-use serde::{Deserialize, Serialize};
+use std::{io, time::Duration};
+
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncWrite, AsyncWriteExt, BufReader};
 use zenoh::config::Config;
 
-use crate::logs::LogEntry;
+use crate::{
+    logs::LogEntry,
+    ts::{runtime::RuntimeStats, scouting::DiscoveredPeer, testing::TestSample},
+};
 
 // ============================================================================
 // Messages between main process and runtime process
 // ============================================================================
 
+/// Version of the IPC protocol, bumped on incompatible changes of the messages or framing
+pub const PROTOCOL_VERSION: u32 = 1;
+
 /// Messages sent from main process to runtime process
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum MainToRuntime {
+    /// Answer to the `Hello` of the runtime process, before `Start`
+    Hello {
+        protocol_version: u32,
+        sandbox_version: String,
+        /// Codec of the following messages, chosen among the codecs offered by the runtime
+        #[serde(default)]
+        codec: Codec,
+    },
     /// Start the runtime with the given zenoh::Config
     Start(Box<Config>),
     /// Stop the runtime gracefully
     Stop,
     /// Request the current Zenoh configuration
     GetConfig,
+    /// Apply a patch to the running configuration, as (key, JSON5 value) pairs.
+    /// A full `Config` can't express which keys the patch sets, since unset fields hold
+    /// their defaults, and the runtime can only update keys through its config notifier.
+    UpdateConfig(Vec<(String, String)>),
+    /// Measure round-trip times to the echo queryable of the runtime with ZenohId `target`
+    MeasureLatency {
+        id: u64,
+        target: String,
+        samples: u32,
+        timeout_ms: u64,
+        /// Size in bytes of the payload of each query, echoed back in the reply
+        #[serde(default)]
+        payload_size: usize,
+    },
+    /// Run a `get` on `selector` and collect all replies
+    Query {
+        id: u64,
+        selector: String,
+        timeout_ms: u64,
+    },
+    /// Declare a test publisher or subscriber, identified by `id`
+    DeclareTestEntity { id: u64, entity: TestEntity },
+    /// Undeclare the test entity with the given id
+    UndeclareTestEntity(u64),
+    /// Request a summary of the runtime's health
+    GetHealth,
+    /// Open a transport to the runtime `zid` through any of `locators`
+    Connect {
+        id: u64,
+        zid: String,
+        locators: Vec<String>,
+    },
+    /// Keepalive sent every `PING_INTERVAL`, answered with a `Pong` carrying the same number
+    Ping(u64),
+    /// Read the file at `path` and put its bytes on `keyexpr`. The runtime process reads the
+    /// file, so that its size isn't limited by the maximum message size.
+    PublishFile {
+        id: u64,
+        keyexpr: String,
+        path: String,
+        encoding: Option<String>,
+    },
+    /// Scout for peers and routers during `timeout_ms` with the scouting settings
+    /// of the running config
+    Scout { id: u64, timeout_ms: u64 },
 }
 
 /// Messages sent from runtime process to main process
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum RuntimeToMain {
+    /// First message of the runtime process once connected
+    Hello {
+        protocol_version: u32,
+        runtime_version: String,
+        /// Codecs supported by the runtime process, by order of preference
+        #[serde(default)]
+        codecs: Vec<Codec>,
+    },
     /// Runtime started successfully with this ZenohId
     Started(String),
     /// Runtime failed to start
     StartError(String),
     /// A log entry from the runtime
     Log(LogEntry),
+    /// Log entries from the runtime, oldest first, coalesced to reduce the IPC overhead
+    LogBatch(Vec<LogEntry>),
+    /// Number of log entries dropped by the runtime since it started, because they were
+    /// produced faster than they could be sent. Sent when it changes.
+    LogStats { dropped_count: u64 },
     /// Runtime stopped
     Stopped,
     /// Response with the current Zenoh configuration
     Config(Box<Config>),
+    /// Configuration patch applied, with the resulting configuration
+    ConfigUpdated(Box<Config>),
+    /// Configuration patch could not be applied
+    ConfigUpdateError(String),
+    /// Round-trip times in microseconds for the `MeasureLatency` request with the same id
+    LatencyResult {
+        id: u64,
+        result: Result<Vec<u64>, String>,
+    },
+    /// Replies received for the `Query` request with the same id
+    QueryResult {
+        id: u64,
+        result: Result<Vec<QueryReply>, String>,
+    },
+    /// Result of the `DeclareTestEntity` request with the same id
+    TestEntityDeclared { id: u64, result: Result<(), String> },
+    /// Summary of the runtime's health: numbers of connected peers and routers
+    Health { peers: usize, routers: usize },
+    /// Sample received by the test subscriber with the given id,
+    /// or query received by the test queryable with the given id, with its raw payload
+    TestSample {
+        id: u64,
+        sample: TestSample,
+        payload: Vec<u8>,
+    },
+    /// Result of the `Connect` request with the same id
+    Connected { id: u64, result: Result<(), String> },
+    /// Number of bytes published for the `PublishFile` request with the same id
+    Published {
+        id: u64,
+        result: Result<usize, String>,
+    },
+    /// A message of the given kind, `size` bytes once serialized, exceeded the maximum
+    /// message size. Log entries are sent truncated before this notice, other messages are dropped.
+    Truncated { kind: String, size: usize },
+    /// Answer to the `Ping` with the same number
+    Pong(u64),
+    /// Nodes found by the `Scout` request with the same id, and the connected ones
+    Scouted {
+        id: u64,
+        result: Result<Vec<DiscoveredPeer>, String>,
+    },
+    /// Metrics of the runtime, sent every `STATS_INTERVAL`
+    Stats(RuntimeStats),
+    /// A liveliness token matching the liveliness subscriber with the given id
+    /// appeared or disappeared
+    LivelinessChange {
+        id: u64,
+        key_expr: String,
+        alive: bool,
+    },
+}
+
+impl RuntimeToMain {
+    /// Name of the message variant
+    pub fn kind(&self) -> &'static str {
+        match self {
+            RuntimeToMain::Hello { .. } => "Hello",
+            RuntimeToMain::Started(_) => "Started",
+            RuntimeToMain::StartError(_) => "StartError",
+            RuntimeToMain::Log(_) => "Log",
+            RuntimeToMain::LogBatch(_) => "LogBatch",
+            RuntimeToMain::LogStats { .. } => "LogStats",
+            RuntimeToMain::Stopped => "Stopped",
+            RuntimeToMain::Config(_) => "Config",
+            RuntimeToMain::ConfigUpdated(_) => "ConfigUpdated",
+            RuntimeToMain::ConfigUpdateError(_) => "ConfigUpdateError",
+            RuntimeToMain::LatencyResult { .. } => "LatencyResult",
+            RuntimeToMain::QueryResult { .. } => "QueryResult",
+            RuntimeToMain::TestEntityDeclared { .. } => "TestEntityDeclared",
+            RuntimeToMain::Health { .. } => "Health",
+            RuntimeToMain::TestSample { .. } => "TestSample",
+            RuntimeToMain::Connected { .. } => "Connected",
+            RuntimeToMain::Published { .. } => "Published",
+            RuntimeToMain::Truncated { .. } => "Truncated",
+            RuntimeToMain::Pong(_) => "Pong",
+            RuntimeToMain::Scouted { .. } => "Scouted",
+            RuntimeToMain::Stats(_) => "Stats",
+            RuntimeToMain::LivelinessChange { .. } => "LivelinessChange",
+        }
+    }
+}
+
+/// Test entity declared on the runtime's session
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum TestEntity {
+    /// Publish `payload` on `keyexpr` every `period_ms` milliseconds
+    Publisher {
+        keyexpr: String,
+        payload: Vec<u8>,
+        period_ms: u64,
+    },
+    /// Every `period_ms` milliseconds, publish on each of `keyexpr/0` to `keyexpr/<keys - 1>`
+    /// the next sequence number of the key, see `testing::sequence_payload`
+    SequencePublisher {
+        keyexpr: String,
+        keys: u32,
+        period_ms: u64,
+        payload_size: usize,
+        /// Block instead of dropping samples on congestion
+        block: bool,
+    },
+    /// Publish `burst` samples on `keyexpr` every `period_ms` milliseconds, give or take
+    /// up to `jitter_ms`, rendering `payload_template` for each sample,
+    /// see `testing::render_payload_template`
+    PeriodicPublisher {
+        keyexpr: String,
+        payload_template: String,
+        period_ms: u64,
+        jitter_ms: u64,
+        burst: u32,
+    },
+    /// Republish the samples of the recording at `path` with their relative timing,
+    /// `speed` times faster, see `testing::parse_recording`
+    Replay { path: String, speed: f64 },
+    /// Forward samples received on `keyexpr` to the main process
+    Subscriber { keyexpr: String },
+    /// Reply `reply_payload` to queries on `keyexpr`, forwarding each query to the main process
+    Queryable {
+        keyexpr: String,
+        reply_payload: Vec<u8>,
+    },
+    /// Keep a liveliness token alive on `keyexpr`
+    LivelinessToken { keyexpr: String },
+    /// Forward the liveliness changes of the tokens matching `keyexpr` to the main process,
+    /// starting with the tokens already alive if `history` is set
+    LivelinessSubscriber { keyexpr: String, history: bool },
+}
+
+/// A reply received by a query of the runtime's session
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QueryReply {
+    /// Key expression of the reply, or of the query for error replies
+    pub key_expr: String,
+    /// Encoding of the payload
+    pub encoding: String,
+    /// Raw payload
+    pub payload: Vec<u8>,
+    /// Whether the replier answered with an error
+    pub is_error: bool,
+}
+
+// ============================================================================
+// Key expressions declared by runtime processes
+// ============================================================================
+
+/// Prefix of the echo queryables declared by every runtime process
+pub const ECHO_KEYEXPR_PREFIX: &str = "zenoh_sandbox/echo";
+
+/// Key expression of the echo queryable of the runtime with the given ZenohId
+pub fn echo_keyexpr(zid: &str) -> String {
+    format!("{ECHO_KEYEXPR_PREFIX}/{zid}")
+}
+
+// ============================================================================
+// Keepalive
+// ============================================================================
+
+/// Interval between two pings of the main process to a runtime process
+pub const PING_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Number of consecutive pings that may go unanswered, or missing on the runtime side,
+/// before the other side is considered gone
+pub const MISSED_PINGS_LIMIT: u32 = 3;
+
+// ============================================================================
+// Log batching
+// ============================================================================
+
+/// Maximum time a log entry of the runtime process waits for its batch to be sent
+pub const LOG_BATCH_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Number of log entries sent at once by the runtime process
+pub const LOG_BATCH_SIZE: usize = 256;
+
+/// Interval between two checks of the number of log entries dropped by the runtime process
+pub const LOG_STATS_INTERVAL: Duration = Duration::from_secs(1);
+
+// ============================================================================
+// Runtime statistics
+// ============================================================================
+
+/// Interval between two samples of the runtime metrics sent by the runtime process
+pub const STATS_INTERVAL: Duration = Duration::from_secs(2);
+
+// ============================================================================
+// Environment passed from main process to runtime process
+// ============================================================================
+
+/// Environment variable holding the path of the log filter file to watch
+pub const LOG_FILTER_FILE_ENV: &str = "ZENOH_SANDBOX_LOG_FILTER_FILE";
+
+/// Environment variable holding the maximum size of IPC messages in bytes
+pub const MAX_MESSAGE_SIZE_ENV: &str = "ZENOH_SANDBOX_MAX_MESSAGE_SIZE";
+
+// ============================================================================
+// Message size limit
+// ============================================================================
+
+/// Maximum serialized size of an IPC message when none is configured
+pub const DEFAULT_MAX_MESSAGE_SIZE: usize = 1024 * 1024;
+
+/// Lower bound of the maximum message size, leaving room for truncated log entries
+pub const MIN_MAX_MESSAGE_SIZE: usize = 4096;
+
+/// Appended to the message of truncated log entries
+pub const TRUNCATION_MARKER: &str = " [truncated]";
+
+/// Effective maximum message size for a configured value
+pub fn max_message_size(configured: Option<usize>) -> usize {
+    configured
+        .unwrap_or(DEFAULT_MAX_MESSAGE_SIZE)
+        .max(MIN_MAX_MESSAGE_SIZE)
+}
+
+/// Serialize a message of the runtime process, keeping it within `max_size` bytes.
+/// Oversized log entries are truncated and marked with `TRUNCATION_MARKER`, oversized replies
+/// to requests are turned into errors, and other oversized messages are replaced by a
+/// `Truncated` notice. Returns the serialized message and, if it is a truncated log entry,
+/// the notice to send after it.
+pub fn encode_runtime_message(
+    msg: &RuntimeToMain,
+    codec: Codec,
+    max_size: usize,
+) -> io::Result<(Vec<u8>, Option<RuntimeToMain>)> {
+    let encoded = codec.encode(msg)?;
+    if encoded.len() <= max_size {
+        return Ok((encoded, None));
+    }
+    let notice = RuntimeToMain::Truncated {
+        kind: msg.kind().to_string(),
+        size: encoded.len(),
+    };
+    let error = format!(
+        "{} message of {} bytes exceeds the maximum IPC message size of {} bytes",
+        msg.kind(),
+        encoded.len(),
+        max_size
+    );
+
+    let replacement = match msg {
+        RuntimeToMain::Log(entry) => {
+            // Escaping may make the serialized message longer than the raw one, so cut
+            // until it fits
+            let mut entry = entry.clone();
+            let mut excess = encoded.len() - max_size;
+            loop {
+                let mut cut = entry
+                    .message
+                    .len()
+                    .saturating_sub(excess + TRUNCATION_MARKER.len());
+                while !entry.message.is_char_boundary(cut) {
+                    cut -= 1;
+                }
+                entry.message.truncate(cut);
+                entry.message.push_str(TRUNCATION_MARKER);
+                let encoded = codec.encode(&RuntimeToMain::Log(entry.clone()))?;
+                if encoded.len() <= max_size {
+                    return Ok((encoded, Some(notice)));
+                }
+                if cut == 0 {
+                    break notice;
+                }
+                excess = encoded.len() - max_size;
+            }
+        }
+        RuntimeToMain::ConfigUpdated(_) => RuntimeToMain::ConfigUpdateError(error),
+        RuntimeToMain::QueryResult { id, .. } => RuntimeToMain::QueryResult {
+            id: *id,
+            result: Err(error),
+        },
+        _ => notice,
+    };
+    Ok((codec.encode(&replacement)?, None))
+}
+
+// ============================================================================
+// Codecs
+// ============================================================================
+//
+// `Hello` messages are always JSON, so that runtime binaries of any version are understood.
+// The runtime offers its codecs in its `Hello` and the main process picks the one
+// used for all the following messages in its answer.
+
+/// Serialization format of IPC messages
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Codec {
+    /// Readable, used by runtime binaries that don't offer any other codec
+    #[default]
+    Json,
+    /// Compact binary format, cheaper to produce for high rates of log messages
+    Cbor,
+}
+
+/// Codecs supported by this build, by order of preference
+pub const SUPPORTED_CODECS: &[Codec] = &[Codec::Cbor, Codec::Json];
+
+impl Codec {
+    /// Preferred supported codec among `offered`, JSON if none is supported
+    pub fn negotiate(offered: &[Codec]) -> Codec {
+        SUPPORTED_CODECS
+            .iter()
+            .copied()
+            .find(|codec| offered.contains(codec))
+            .unwrap_or_default()
+    }
+
+    pub fn encode<M: Serialize>(self, msg: &M) -> io::Result<Vec<u8>> {
+        match self {
+            Codec::Json => Ok(serde_json::to_vec(msg)?),
+            Codec::Cbor => {
+                let mut buf = Vec::new();
+                ciborium::into_writer(msg, &mut buf)
+                    .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?;
+                Ok(buf)
+            }
+        }
+    }
+
+    pub fn decode<M: DeserializeOwned>(self, bytes: &[u8]) -> io::Result<M> {
+        match self {
+            Codec::Json => Ok(serde_json::from_slice(bytes)?),
+            Codec::Cbor => ciborium::from_reader(bytes)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string())),
+        }
+    }
+}
+
+// ============================================================================
+// Framing
+// ============================================================================
+//
+// Each message is sent as a frame: its length as a big-endian u32, then the
+// serialized message. Unlike newline-delimited messages, frames can carry any bytes
+// and their size is known before reading them.
+
+/// Size of the length prefix of frames
+const FRAME_HEADER_LEN: usize = 4;
+
+/// Write a message as a frame and flush the writer
+pub async fn write_frame<W: AsyncWrite + Unpin>(writer: &mut W, message: &[u8]) -> io::Result<()> {
+    let len = u32::try_from(message.len()).map_err(|_| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!(
+                "Message of {} bytes is too large for a frame",
+                message.len()
+            ),
+        )
+    })?;
+    writer.write_all(&len.to_be_bytes()).await?;
+    writer.write_all(message).await?;
+    writer.flush().await
+}
+
+/// Serialize a message with `codec` and write it as a frame
+pub async fn write_message<W: AsyncWrite + Unpin, M: Serialize>(
+    writer: &mut W,
+    codec: Codec,
+    msg: &M,
+) -> io::Result<()> {
+    write_frame(writer, &codec.encode(msg)?).await
+}
+
+/// Frame read by `FrameReader`
+pub enum Frame {
+    /// Complete message
+    Message(Vec<u8>),
+    /// Message exceeding the maximum message size, discarded, with its size in bytes
+    Oversized(usize),
+}
+
+/// Reader of framed messages. Messages longer than the maximum message size are
+/// discarded while being read, so that they are never buffered whole.
+/// Cancel safe: a partially read frame is kept until the next call.
+pub struct FrameReader<R> {
+    reader: BufReader<R>,
+    /// Length prefix of the next frame, as read so far
+    header: [u8; FRAME_HEADER_LEN],
+    header_read: usize,
+    /// Length of the frame being read, once its prefix is complete
+    len: Option<usize>,
+    /// Bytes of the frame read so far
+    read: usize,
+    body: Vec<u8>,
+    max_size: usize,
+}
+
+impl<R: AsyncRead + Unpin> FrameReader<R> {
+    pub fn new(reader: R, max_size: usize) -> Self {
+        Self {
+            reader: BufReader::new(reader),
+            header: [0; FRAME_HEADER_LEN],
+            header_read: 0,
+            len: None,
+            read: 0,
+            body: Vec::new(),
+            max_size,
+        }
+    }
+
+    /// Read the next frame. Returns None when the stream is closed between two frames.
+    pub async fn next_frame(&mut self) -> io::Result<Option<Frame>> {
+        loop {
+            if let Some(len) = self.len
+                && self.read == len
+            {
+                self.len = None;
+                self.read = 0;
+                if len > self.max_size {
+                    return Ok(Some(Frame::Oversized(len)));
+                }
+                return Ok(Some(Frame::Message(std::mem::take(&mut self.body))));
+            }
+
+            let available = self.reader.fill_buf().await?;
+            if available.is_empty() {
+                if self.len.is_some() || self.header_read > 0 {
+                    return Err(io::Error::new(
+                        io::ErrorKind::UnexpectedEof,
+                        "Stream closed in the middle of a frame",
+                    ));
+                }
+                return Ok(None);
+            }
+            match self.len {
+                None => {
+                    let count = (FRAME_HEADER_LEN - self.header_read).min(available.len());
+                    self.header[self.header_read..self.header_read + count]
+                        .copy_from_slice(&available[..count]);
+                    self.reader.consume(count);
+                    self.header_read += count;
+                    if self.header_read == FRAME_HEADER_LEN {
+                        self.header_read = 0;
+                        self.len = Some(u32::from_be_bytes(self.header) as usize);
+                    }
+                }
+                Some(len) => {
+                    let count = (len - self.read).min(available.len());
+                    if len <= self.max_size {
+                        self.body.extend_from_slice(&available[..count]);
+                    }
+                    self.reader.consume(count);
+                    self.read += count;
+                }
+            }
+        }
+    }
 }
@@ -0,0 +1,279 @@
+use std::{
+    collections::{BTreeSet, VecDeque},
+    path::PathBuf,
+    sync::{
+        Arc,
+        atomic::{AtomicU64, Ordering},
+    },
+};
+
+use chrono::{DateTime, Utc};
+use parking_lot::RwLock as ParkingLotRwLock;
+use serde::Serialize;
+use tauri::State;
+
+use crate::{
+    RuntimeId, ZenohRuntimes,
+    error::SandboxError,
+    ts::{
+        report::{
+            OperationKind, OperationRecord, OperationSummary, Report, ReportFormat, TopologyLink,
+            TopologySnapshot,
+        },
+        runtime::RuntimeState,
+    },
+    zenoh_admin,
+};
+
+// ============================================================================
+// Constants
+// ============================================================================
+
+/// Maximum number of operations to keep
+const MAX_OPERATIONS: usize = 100;
+
+// ============================================================================
+// Operation results
+// ============================================================================
+
+/// Results of measurement operations, kept so they can be exported as reports
+#[derive(Clone)]
+pub struct OperationResults {
+    /// Next operation id to allocate
+    next_id: Arc<AtomicU64>,
+    /// Recorded operations, oldest first
+    records: Arc<ParkingLotRwLock<VecDeque<OperationRecord>>>,
+}
+
+impl OperationResults {
+    /// Allocate the id of an operation about to run
+    pub fn next_id(&self) -> u64 {
+        self.next_id.fetch_add(1, Ordering::Relaxed)
+    }
+
+    /// Record the result of a completed operation with the configs of the involved
+    /// runtimes and a snapshot of the current topology
+    pub async fn record(
+        &self,
+        runtimes: &ZenohRuntimes,
+        summary: OperationSummary,
+        result: &impl Serialize,
+    ) -> Result<(), String> {
+        let result = serde_json::to_value(result)
+            .map_err(|e| format!("Failed to serialize operation result: {}", e))?;
+        let configs = runtimes.declared_configs(&summary.runtime_ids).await;
+        let topology = topology_snapshot(runtimes).await;
+
+        let mut records = self.records.write();
+        records.push_back(OperationRecord {
+            summary,
+            result,
+            configs,
+            topology,
+        });
+        if records.len() > MAX_OPERATIONS {
+            records.pop_front();
+        }
+        Ok(())
+    }
+}
+
+impl OperationResults {
+    /// Report of all recorded operations
+    pub(crate) fn full_report(&self) -> Report {
+        Report {
+            generated_at: Utc::now(),
+            sandbox_version: env!("CARGO_PKG_VERSION").to_string(),
+            zenoh_version: zenoh::GIT_VERSION.to_string(),
+            operations: self.records.read().iter().cloned().collect(),
+        }
+    }
+
+    /// Forget all recorded operations
+    pub fn clear(&self) {
+        self.records.write().clear();
+    }
+}
+
+impl Default for OperationResults {
+    fn default() -> Self {
+        Self {
+            next_id: Arc::new(AtomicU64::new(0)),
+            records: Arc::new(ParkingLotRwLock::new(VecDeque::new())),
+        }
+    }
+}
+
+/// Build the summary of an operation completing now
+pub fn operation_summary(
+    id: u64,
+    kind: OperationKind,
+    started_at: DateTime<Utc>,
+    runtime_ids: Vec<RuntimeId>,
+) -> OperationSummary {
+    OperationSummary {
+        id,
+        kind,
+        started_at,
+        completed_at: Utc::now(),
+        runtime_ids,
+    }
+}
+
+/// Snapshot the runtime statuses and the sessions between running runtimes
+pub(crate) async fn topology_snapshot(runtimes: &ZenohRuntimes) -> TopologySnapshot {
+    let statuses = runtimes.statuses().await;
+    let by_zid: Vec<(String, RuntimeId)> = statuses
+        .iter()
+        .filter_map(|status| Some((status.zenoh_id.clone()?, status.runtime_id)))
+        .collect();
+
+    let mut links = BTreeSet::new();
+    for status in statuses
+        .iter()
+        .filter(|status| status.state == RuntimeState::Running)
+    {
+        let Ok(sessions) = zenoh_admin::local_sessions(runtimes, status.runtime_id).await else {
+            continue;
+        };
+        for session in sessions {
+            if let Some((_, to)) = by_zid.iter().find(|(zid, _)| *zid == session.peer) {
+                links.insert(TopologyLink {
+                    from: status.runtime_id,
+                    to: *to,
+                });
+            }
+        }
+    }
+
+    TopologySnapshot {
+        timestamp: Utc::now(),
+        runtimes: statuses,
+        links: links.into_iter().collect(),
+    }
+}
+
+// ============================================================================
+// Report formats
+// ============================================================================
+
+/// Write the report as CSV rows `operation_id,kind,path,value`, one per leaf of the results
+fn report_to_csv(report: &Report) -> String {
+    let mut csv = String::from("operation_id,kind,path,value\n");
+    for operation in &report.operations {
+        let kind = serde_json::to_value(operation.summary.kind)
+            .ok()
+            .and_then(|kind| kind.as_str().map(str::to_string))
+            .unwrap_or_default();
+        let mut rows = Vec::new();
+        flatten_json(&operation.result, String::new(), &mut rows);
+        for (path, value) in rows {
+            csv.push_str(&format!(
+                "{},{},{},{}\n",
+                operation.summary.id,
+                csv_field(&kind),
+                csv_field(&path),
+                csv_field(&value)
+            ));
+        }
+    }
+    csv
+}
+
+/// Collect (path, value) pairs for every leaf of a JSON value, paths like `cells/0/1/min_us`
+fn flatten_json(value: &serde_json::Value, path: String, rows: &mut Vec<(String, String)>) {
+    let child = |key: &str| {
+        if path.is_empty() {
+            key.to_string()
+        } else {
+            format!("{path}/{key}")
+        }
+    };
+    match value {
+        serde_json::Value::Object(map) => {
+            for (key, value) in map {
+                flatten_json(value, child(key), rows);
+            }
+        }
+        serde_json::Value::Array(items) => {
+            for (index, value) in items.iter().enumerate() {
+                flatten_json(value, child(&index.to_string()), rows);
+            }
+        }
+        serde_json::Value::String(s) => rows.push((path, s.clone())),
+        serde_json::Value::Null => rows.push((path, String::new())),
+        other => rows.push((path, other.to_string())),
+    }
+}
+
+/// Quote a CSV field if needed
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+// ============================================================================
+// Tauri commands
+// ============================================================================
+
+/// List the recorded operations, oldest first
+#[tauri::command]
+#[tracing::instrument(skip(results))]
+pub async fn list_operations(
+    results: State<'_, OperationResults>,
+) -> Result<Vec<OperationSummary>, SandboxError> {
+    Ok(results
+        .records
+        .read()
+        .iter()
+        .map(|record| record.summary.clone())
+        .collect())
+}
+
+/// Export the given operations, with the configs and topology they ran with,
+/// to a report file at `path`. Returns the absolute path of the written file.
+#[tauri::command]
+#[tracing::instrument(skip(results))]
+pub async fn export_report(
+    operation_ids: Vec<u64>,
+    format: ReportFormat,
+    path: String,
+    results: State<'_, OperationResults>,
+) -> Result<String, SandboxError> {
+    let operations = {
+        let records = results.records.read();
+        operation_ids
+            .iter()
+            .map(|id| {
+                records
+                    .iter()
+                    .find(|record| record.summary.id == *id)
+                    .cloned()
+                    .ok_or_else(|| format!("Operation {} not found", id))
+            })
+            .collect::<Result<Vec<_>, String>>()?
+    };
+
+    let report = Report {
+        generated_at: Utc::now(),
+        sandbox_version: env!("CARGO_PKG_VERSION").to_string(),
+        zenoh_version: zenoh::GIT_VERSION.to_string(),
+        operations,
+    };
+    let contents = match format {
+        ReportFormat::Json => serde_json::to_string_pretty(&report)
+            .map_err(|e| format!("Failed to serialize report: {}", e))?,
+        ReportFormat::Csv => report_to_csv(&report),
+    };
+
+    let path = std::path::absolute(PathBuf::from(&path))
+        .map_err(|e| format!("Invalid report path {}: {}", path, e))?;
+    tokio::fs::write(&path, contents)
+        .await
+        .map_err(|e| format!("Failed to write report {}: {}", path.display(), e))?;
+    tracing::info!("Report written to {}", path.display());
+    Ok(path.to_string_lossy().to_string())
+}
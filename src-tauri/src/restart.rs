@@ -0,0 +1,188 @@
+use std::{process::ExitStatus, time::Duration};
+
+use tauri::{AppHandle, Manager};
+
+use crate::{
+    RuntimeId, ZenohRuntimes,
+    logs::LogStorage,
+    ts::{
+        log::LogEntryLevel,
+        runtime::{RestartMode, RestartPolicy, RuntimeState},
+    },
+};
+
+// ============================================================================
+// Constants
+// ============================================================================
+
+/// Maximum number of consecutive restarts, unless set in the policy
+const DEFAULT_MAX_ATTEMPTS: u32 = 5;
+
+/// Delay before the first restart, unless set in the policy
+const DEFAULT_BACKOFF: Duration = Duration::from_secs(1);
+
+/// Upper bound of the delay, which doubles with each consecutive restart
+const MAX_BACKOFF: Duration = Duration::from_secs(60);
+
+/// Time a runtime must have run before exiting for its restarts to be counted from 0 again
+pub(crate) const STABLE_RUN_PERIOD: Duration = Duration::from_secs(60);
+
+// ============================================================================
+// Automatic restart
+// ============================================================================
+
+/// Next step of the automatic restart of a runtime
+enum RestartStep {
+    /// The policy doesn't restart the runtime, or it is no longer crashed
+    None,
+    /// Restart the runtime after `backoff`
+    Attempt {
+        attempt: u32,
+        max_attempts: u32,
+        backoff: Duration,
+    },
+    /// The maximum number of consecutive restarts is reached
+    Exhausted(u32),
+}
+
+/// Whether the policy restarts a runtime whose process exited unexpectedly with `status`.
+/// A process failing to start has no status and counts as crashed.
+fn restarts_after(policy: &RestartPolicy, status: Option<ExitStatus>) -> bool {
+    match policy.mode {
+        RestartMode::Never => false,
+        RestartMode::OnCrash => !status.is_some_and(|status| status.success()),
+        RestartMode::Always => true,
+    }
+}
+
+/// Delay before the restart `attempt`, counted from 1
+fn backoff(policy: &RestartPolicy, attempt: u32) -> Duration {
+    let initial = policy
+        .backoff_ms
+        .map_or(DEFAULT_BACKOFF, Duration::from_millis);
+    initial
+        .saturating_mul(2u32.saturating_pow(attempt.saturating_sub(1)))
+        .min(MAX_BACKOFF)
+}
+
+impl ZenohRuntimes {
+    /// Count a restart of a crashed runtime, if its policy allows one more
+    async fn next_restart_step(
+        &self,
+        runtime_id: RuntimeId,
+        status: Option<ExitStatus>,
+    ) -> RestartStep {
+        let mut runtimes = self.runtimes.write().await;
+        let Some(runtime_process) = runtimes.get_mut(&runtime_id) else {
+            return RestartStep::None;
+        };
+        let policy = &runtime_process.options.restart;
+        if runtime_process.state != RuntimeState::Crashed || !restarts_after(policy, status) {
+            return RestartStep::None;
+        }
+        let max_attempts = policy.max_attempts.unwrap_or(DEFAULT_MAX_ATTEMPTS);
+        if runtime_process.restart_attempts >= max_attempts {
+            return RestartStep::Exhausted(max_attempts);
+        }
+        runtime_process.restart_attempts += 1;
+        RestartStep::Attempt {
+            attempt: runtime_process.restart_attempts,
+            max_attempts,
+            backoff: backoff(policy, runtime_process.restart_attempts),
+        }
+    }
+
+    /// Whether the runtime is still crashed, i.e. was not started, stopped or removed
+    async fn is_crashed(&self, runtime_id: RuntimeId) -> bool {
+        self.runtimes
+            .read()
+            .await
+            .get(&runtime_id)
+            .is_some_and(|runtime_process| runtime_process.state == RuntimeState::Crashed)
+    }
+
+    /// Mark a runtime that failed to restart as crashed again, unless it was started meanwhile
+    async fn restart_failed(&self, runtime_id: RuntimeId) {
+        if let Some(runtime_process) = self.runtimes.write().await.get_mut(&runtime_id)
+            && runtime_process.state == RuntimeState::Stopped
+        {
+            runtime_process.state = RuntimeState::Crashed;
+        }
+    }
+}
+
+/// Start a runtime again after its process exited unexpectedly with `status`, as its
+/// restart policy says, keeping its RuntimeId and port. Restarts failing to start the
+/// runtime are retried until the maximum number of attempts. Every attempt is reported
+/// in the runtime's log stream.
+pub(crate) async fn restart_after_exit(
+    app: AppHandle,
+    runtime_id: RuntimeId,
+    mut status: Option<ExitStatus>,
+) {
+    let runtimes = app.state::<ZenohRuntimes>();
+    let logs = app.state::<LogStorage>();
+    loop {
+        let (attempt, max_attempts, backoff) =
+            match runtimes.next_restart_step(runtime_id, status).await {
+                RestartStep::None => return,
+                RestartStep::Attempt {
+                    attempt,
+                    max_attempts,
+                    backoff,
+                } => (attempt, max_attempts, backoff),
+                RestartStep::Exhausted(max_attempts) => {
+                    logs.add_sandbox_log(
+                        runtime_id,
+                        LogEntryLevel::ERROR,
+                        format!(
+                            "Not restarting the runtime, it was restarted {} times in a row",
+                            max_attempts
+                        ),
+                    );
+                    return;
+                }
+            };
+
+        logs.add_sandbox_log(
+            runtime_id,
+            LogEntryLevel::WARN,
+            format!(
+                "Restarting the runtime in {} ms (attempt {}/{})",
+                backoff.as_millis(),
+                attempt,
+                max_attempts
+            ),
+        );
+        tokio::time::sleep(backoff).await;
+        if !runtimes.is_crashed(runtime_id).await {
+            return;
+        }
+
+        match runtimes.launch(&app, runtime_id).await {
+            Ok(_) => {
+                logs.add_sandbox_log(
+                    runtime_id,
+                    LogEntryLevel::WARN,
+                    format!(
+                        "Restarted the runtime (attempt {}/{})",
+                        attempt, max_attempts
+                    ),
+                );
+                return;
+            }
+            Err(e) => {
+                logs.add_sandbox_log(
+                    runtime_id,
+                    LogEntryLevel::WARN,
+                    format!(
+                        "Failed to restart the runtime (attempt {}/{}): {}",
+                        attempt, max_attempts, e
+                    ),
+                );
+                runtimes.restart_failed(runtime_id).await;
+                status = None;
+            }
+        }
+    }
+}
@@ -0,0 +1,79 @@
+use parking_lot::RwLock as ParkingLotRwLock;
+use tracing_subscriber::{
+    EnvFilter, Registry, layer::SubscriberExt, reload, util::SubscriberInitExt,
+};
+
+use crate::logs::{LogStorage, LogStorageLayer};
+
+// ============================================================================
+// Constants
+// ============================================================================
+
+/// Environment variable used to set the initial sandbox log filter
+pub const SANDBOX_LOG_ENV: &str = "ZENOH_SANDBOX_LOG";
+
+/// Filter used when `ZENOH_SANDBOX_LOG` is not set or invalid
+pub const DEFAULT_SANDBOX_LOG_FILTER: &str = "zenoh_sandbox_lib=debug";
+
+// ============================================================================
+// Sandbox Tracing
+// ============================================================================
+
+/// Tracing of the sandbox backend itself (not of the zenoh runtimes).
+/// Events are printed to stderr and stored in `LogStorage` under the sandbox pseudo-runtime.
+pub struct SandboxTracing {
+    /// Currently applied filter directive
+    filter: ParkingLotRwLock<String>,
+    /// Handle to swap the filter at runtime
+    handle: reload::Handle<EnvFilter, Registry>,
+}
+
+impl SandboxTracing {
+    /// Install the global tracing subscriber for the main process
+    pub fn init(storage: LogStorage) -> Self {
+        let requested = std::env::var(SANDBOX_LOG_ENV).ok();
+        let (env_filter, filter) = match requested
+            .as_deref()
+            .map(|filter| (EnvFilter::try_new(filter), filter))
+        {
+            Some((Ok(env_filter), filter)) => (env_filter, filter.to_string()),
+            _ => (
+                EnvFilter::new(DEFAULT_SANDBOX_LOG_FILTER),
+                DEFAULT_SANDBOX_LOG_FILTER.to_string(),
+            ),
+        };
+
+        let (filter_layer, handle) = reload::Layer::new(env_filter);
+        // Ignore failure if a global subscriber is already installed
+        let _ = tracing_subscriber::registry()
+            .with(filter_layer)
+            .with(
+                tracing_subscriber::fmt::layer()
+                    .with_target(true)
+                    .with_writer(std::io::stderr),
+            )
+            .with(LogStorageLayer::new(storage))
+            .try_init();
+
+        Self {
+            filter: ParkingLotRwLock::new(filter),
+            handle,
+        }
+    }
+
+    /// Get the currently applied filter directive
+    pub fn filter(&self) -> String {
+        self.filter.read().clone()
+    }
+
+    /// Replace the filter directive, e.g. `zenoh_sandbox_lib=trace`
+    pub fn set_filter(&self, filter: &str) -> Result<(), String> {
+        let env_filter =
+            EnvFilter::try_new(filter).map_err(|e| format!("Invalid log filter: {}", e))?;
+        self.handle
+            .reload(env_filter)
+            .map_err(|e| format!("Failed to apply log filter: {}", e))?;
+        *self.filter.write() = filter.to_string();
+        Ok(())
+    }
+}
@@ -0,0 +1,473 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use tauri::{AppHandle, Manager};
+use tokio::sync::Mutex;
+
+use crate::{
+    RuntimeId, ZenohRuntimes, config_to_json,
+    error::SandboxError,
+    links::with_connect_endpoints,
+    logs::LogStorage,
+    privileges, templating,
+    timeline::Timeline,
+    ts::{
+        config::ZenohConfigJson,
+        report::TopologyLink,
+        runtime::{BulkOperationReport, RuntimeOperationOutcome, RuntimeOptions},
+        scenario::{ScenarioDocument, ScenarioInfo, ScenarioRuntimeInfo},
+        timeline::ConfigChangeSource,
+    },
+    zenoh_admin,
+};
+
+// ============================================================================
+// Scenarios
+// ============================================================================
+
+/// Runtime of a validated scenario
+struct ScenarioEntry {
+    name: String,
+    config: ZenohConfigJson,
+    options: RuntimeOptions,
+    connect: Vec<String>,
+    /// JSON5 text of the config if it is a template, rendered when the runtime starts
+    template: Option<String>,
+    /// Set once the runtime has been declared by `start_scenario`
+    runtime_id: Option<RuntimeId>,
+}
+
+/// Validated scenario, with its runtimes in start order
+struct Scenario {
+    name: String,
+    runtimes: Vec<ScenarioEntry>,
+}
+
+impl Scenario {
+    fn info(&self) -> ScenarioInfo {
+        ScenarioInfo {
+            name: self.name.clone(),
+            runtimes: self
+                .runtimes
+                .iter()
+                .map(|entry| ScenarioRuntimeInfo {
+                    name: entry.name.clone(),
+                    runtime_id: entry.runtime_id,
+                    connect: entry.connect.clone(),
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Loaded scenarios by name.
+/// Operations on scenarios are serialized, so a scenario can't be started and stopped concurrently.
+#[derive(Default)]
+pub struct Scenarios {
+    scenarios: Mutex<BTreeMap<String, Scenario>>,
+}
+
+impl Scenarios {
+    /// Names of the runtimes declared by scenarios, as `<scenario>/<runtime>`
+    pub(crate) async fn runtime_names(&self) -> HashMap<RuntimeId, String> {
+        let scenarios = self.scenarios.lock().await;
+        scenarios
+            .values()
+            .flat_map(|scenario| {
+                scenario.runtimes.iter().filter_map(|entry| {
+                    entry
+                        .runtime_id
+                        .map(|runtime_id| (runtime_id, format!("{}/{}", scenario.name, entry.name)))
+                })
+            })
+            .collect()
+    }
+}
+
+impl Scenarios {
+    /// Unload all scenarios
+    pub(crate) async fn clear(&self) {
+        self.scenarios.lock().await.clear();
+    }
+
+    /// Links between the declared runtimes of scenarios, from their connect edges
+    pub(crate) async fn intended_links(&self) -> Vec<TopologyLink> {
+        let scenarios = self.scenarios.lock().await;
+        let mut links = Vec::new();
+        for scenario in scenarios.values() {
+            let ids: HashMap<&str, RuntimeId> = scenario
+                .runtimes
+                .iter()
+                .filter_map(|entry| Some((entry.name.as_str(), entry.runtime_id?)))
+                .collect();
+            for entry in &scenario.runtimes {
+                let Some(runtime_id) = entry.runtime_id else {
+                    continue;
+                };
+                links.extend(
+                    entry
+                        .connect
+                        .iter()
+                        .filter_map(|target| ids.get(target.as_str()))
+                        .map(|target_id| TopologyLink::undirected(runtime_id, *target_id)),
+                );
+            }
+        }
+        links
+    }
+}
+
+/// Validate a scenario document: unique names, known connect targets, no connection cycles,
+/// valid configs and options. Runtimes are sorted so that connect targets, and the runtimes
+/// their config templates refer to, come first.
+fn validate(document: ScenarioDocument) -> Result<Scenario, String> {
+    if document.runtimes.is_empty() {
+        return Err(format!("Scenario {} has no runtimes", document.name));
+    }
+
+    let mut names = HashSet::new();
+    for runtime in &document.runtimes {
+        if runtime.name.is_empty() {
+            return Err("Scenario runtimes must have a name".to_string());
+        }
+        if !names.insert(runtime.name.as_str()) {
+            return Err(format!("Duplicate runtime name: {}", runtime.name));
+        }
+    }
+    for runtime in &document.runtimes {
+        for target in &runtime.connect {
+            if *target == runtime.name {
+                return Err(format!("Runtime {} connects to itself", runtime.name));
+            }
+            if !names.contains(target.as_str()) {
+                return Err(format!(
+                    "Runtime {} connects to unknown runtime {}",
+                    runtime.name, target
+                ));
+            }
+        }
+    }
+
+    // A runtime also starts after the runtimes of the scenario its config template refers to
+    let mut dependencies: HashMap<String, HashSet<String>> = HashMap::new();
+    for runtime in &document.runtimes {
+        let mut requires: HashSet<String> = runtime.connect.iter().cloned().collect();
+        let content = runtime.config.to_string();
+        if templating::is_template(&content) {
+            requires.extend(
+                templating::peer_names(&content)
+                    .map_err(|e| format!("Invalid config of runtime {}: {}", runtime.name, e))?
+                    .into_iter()
+                    .filter(|peer| *peer != runtime.name && names.contains(peer.as_str())),
+            );
+        }
+        dependencies.insert(runtime.name.clone(), requires);
+    }
+
+    // Order the runtimes so that each one starts after the runtimes it depends on,
+    // keeping the document order among runtimes that are ready
+    let mut pending: Vec<_> = document.runtimes.into_iter().collect();
+    let mut started: HashSet<String> = HashSet::new();
+    let mut runtimes = Vec::new();
+    while !pending.is_empty() {
+        let Some(index) = pending.iter().position(|runtime| {
+            dependencies[&runtime.name]
+                .iter()
+                .all(|target| started.contains(target))
+        }) else {
+            let names: Vec<&str> = pending
+                .iter()
+                .map(|runtime| runtime.name.as_str())
+                .collect();
+            return Err(format!(
+                "Connection cycle between runtimes: {}",
+                names.join(", ")
+            ));
+        };
+        let runtime = pending.remove(index);
+
+        let template =
+            Some(runtime.config.to_string()).filter(|content| templating::is_template(content));
+        let config = if runtime.config.is_null() {
+            zenoh::Config::default()
+        } else {
+            let content = match &template {
+                Some(template) => templating::preview(template)?,
+                None => runtime.config.to_string(),
+            };
+            zenoh::Config::from_json5(&content)
+                .map_err(|e| format!("Invalid config of runtime {}: {}", runtime.name, e))?
+        };
+        let options = runtime.options.unwrap_or_default();
+        privileges::resolve(&options)
+            .map_err(|e| format!("Invalid options of runtime {}: {}", runtime.name, e))?;
+
+        started.insert(runtime.name.clone());
+        runtimes.push(ScenarioEntry {
+            name: runtime.name,
+            config: config_to_json(&config)?,
+            options,
+            connect: runtime.connect,
+            template,
+            runtime_id: None,
+        });
+    }
+
+    Ok(Scenario {
+        name: document.name,
+        runtimes,
+    })
+}
+
+/// Annotate a change of the declared config of a scenario runtime in the timeline and its
+/// log stream
+fn annotate_config_change(
+    app: &AppHandle,
+    runtime_id: RuntimeId,
+    source: ConfigChangeSource,
+    old_config: &ZenohConfigJson,
+    new_config: &ZenohConfigJson,
+) {
+    app.state::<Timeline>().annotate_config_change(
+        runtime_id,
+        source,
+        old_config,
+        new_config,
+        Some(app.state::<LogStorage>().inner()),
+    );
+}
+
+/// Declare (or reuse) and start the runtimes of a scenario in order.
+/// Each runtime connects to the locators of its connect targets, read from their adminspace.
+async fn start(
+    app: &AppHandle,
+    runtimes: &ZenohRuntimes,
+    scenario: &mut Scenario,
+) -> Result<(), String> {
+    let targets: HashSet<String> = scenario
+        .runtimes
+        .iter()
+        .flat_map(|entry| entry.connect.iter().cloned())
+        .collect();
+    let mut locators: HashMap<String, Vec<String>> = HashMap::new();
+    // Config templates refer to the runtimes of the scenario by name
+    let mut names: HashMap<RuntimeId, String> = scenario
+        .runtimes
+        .iter()
+        .filter_map(|entry| {
+            Some((
+                entry.runtime_id?,
+                format!("{}/{}", scenario.name, entry.name),
+            ))
+        })
+        .collect();
+
+    for entry in &mut scenario.runtimes {
+        let endpoints: Vec<String> = entry
+            .connect
+            .iter()
+            .flat_map(|target| locators.get(target).cloned().unwrap_or_default())
+            .collect();
+        let config = with_connect_endpoints(&entry.config, &endpoints)?;
+
+        // Reuse the runtime declared by a previous start, unless it was removed since
+        let existing = match entry.runtime_id {
+            Some(runtime_id) => {
+                let mut runtime_processes = runtimes.runtimes.write().await;
+                runtime_processes
+                    .get_mut(&runtime_id)
+                    .map(|runtime_process| {
+                        let old_config = runtime_process
+                            .replace_config(config.clone(), ConfigChangeSource::Patch);
+                        (runtime_id, old_config)
+                    })
+            }
+            None => None,
+        };
+        let runtime_id = match existing {
+            Some((runtime_id, old_config)) => {
+                annotate_config_change(
+                    app,
+                    runtime_id,
+                    ConfigChangeSource::Patch,
+                    &old_config,
+                    &config,
+                );
+                runtime_id
+            }
+            None => {
+                runtimes
+                    .declare(config, entry.options.clone(), None)
+                    .await?
+                    .0
+            }
+        };
+        entry.runtime_id = Some(runtime_id);
+        names.insert(runtime_id, format!("{}/{}", scenario.name, entry.name));
+
+        if let Some(template) = &entry.template {
+            let rendered = runtimes
+                .render_config_template(runtime_id, template, names.clone())
+                .await
+                .map_err(|e| format!("Runtime {}: {}", entry.name, e))?;
+            let config = with_connect_endpoints(&rendered, &endpoints)?;
+            let old_config =
+                runtimes
+                    .runtimes
+                    .write()
+                    .await
+                    .get_mut(&runtime_id)
+                    .map(|runtime_process| {
+                        runtime_process.replace_config(config.clone(), ConfigChangeSource::Template)
+                    });
+            if let Some(old_config) = old_config {
+                annotate_config_change(
+                    app,
+                    runtime_id,
+                    ConfigChangeSource::Template,
+                    &old_config,
+                    &config,
+                );
+            }
+        }
+
+        runtimes
+            .launch(app, runtime_id)
+            .await
+            .map_err(|e| format!("Failed to start runtime {}: {}", entry.name, e))?;
+        tracing::info!(
+            "Scenario {}: runtime {} started as {}",
+            scenario.name,
+            entry.name,
+            runtime_id
+        );
+
+        if targets.contains(&entry.name) {
+            let runtime_locators = zenoh_admin::local_locators(runtimes, runtime_id)
+                .await
+                .map_err(|e| format!("Failed to get locators of runtime {}: {}", entry.name, e))?;
+            locators.insert(entry.name.clone(), runtime_locators);
+        }
+    }
+    Ok(())
+}
+
+/// Stop the active runtimes of a scenario in reverse start order
+async fn stop(runtimes: &ZenohRuntimes, scenario: &Scenario) -> Vec<RuntimeOperationOutcome> {
+    let mut outcomes = Vec::new();
+    for runtime_id in scenario
+        .runtimes
+        .iter()
+        .rev()
+        .filter_map(|entry| entry.runtime_id)
+    {
+        if !runtimes.is_active(runtime_id).await {
+            continue;
+        }
+        let result = runtimes.shutdown(runtime_id, true).await;
+        outcomes.push(RuntimeOperationOutcome {
+            runtime_id,
+            zenoh_id: None,
+            error: result.err().map(|e| e.to_string()),
+        });
+    }
+    outcomes
+}
+
+impl ZenohRuntimes {
+    /// Whether a runtime exists and has a runtime process
+    async fn is_active(&self, runtime_id: RuntimeId) -> bool {
+        self.runtimes
+            .read()
+            .await
+            .get(&runtime_id)
+            .is_some_and(|runtime_process| runtime_process.state.is_active())
+    }
+}
+
+// ============================================================================
+// Tauri commands
+// ============================================================================
+
+/// Load a scenario from a JSON or JSON5 document, replacing a stopped scenario with the same name.
+/// The scenario is validated but its runtimes are only declared when it is started.
+#[tauri::command]
+#[tracing::instrument(skip(content, app))]
+pub async fn load_scenario(content: String, app: AppHandle) -> Result<ScenarioInfo, SandboxError> {
+    let document: ScenarioDocument =
+        json5::from_str(&content).map_err(|e| format!("Invalid scenario document: {}", e))?;
+    let scenario = validate(document)?;
+    let info = scenario.info();
+
+    let runtimes = app.state::<ZenohRuntimes>();
+    let scenarios = app.state::<Scenarios>();
+    let mut scenarios = scenarios.scenarios.lock().await;
+    if let Some(existing) = scenarios.get(&scenario.name) {
+        for runtime_id in existing
+            .runtimes
+            .iter()
+            .filter_map(|entry| entry.runtime_id)
+        {
+            if runtimes.is_active(runtime_id).await {
+                return Err(format!(
+                    "Scenario {} is running, stop it before reloading it",
+                    scenario.name
+                )
+                .into());
+            }
+        }
+    }
+    scenarios.insert(scenario.name.clone(), scenario);
+
+    Ok(info)
+}
+
+/// Start the runtimes of a loaded scenario, each one after the runtimes it connects to.
+/// Runtimes declared by a previous start are reused. If a runtime fails to start,
+/// the runtimes already started are stopped.
+#[tauri::command]
+#[tracing::instrument(skip(app))]
+pub async fn start_scenario(name: String, app: AppHandle) -> Result<ScenarioInfo, SandboxError> {
+    let runtimes = app.state::<ZenohRuntimes>();
+    let scenarios = app.state::<Scenarios>();
+    let mut scenarios = scenarios.scenarios.lock().await;
+    let scenario = scenarios
+        .get_mut(&name)
+        .ok_or_else(|| format!("Scenario {} not found", name))?;
+
+    for runtime_id in scenario
+        .runtimes
+        .iter()
+        .filter_map(|entry| entry.runtime_id)
+    {
+        if runtimes.is_active(runtime_id).await {
+            return Err(format!("Scenario {} is already running", name).into());
+        }
+    }
+
+    if let Err(e) = start(&app, &runtimes, scenario).await {
+        stop(&runtimes, scenario).await;
+        return Err(format!("Scenario {}: {}", name, e).into());
+    }
+
+    Ok(scenario.info())
+}
+
+/// Stop the runtimes of a scenario in reverse start order.
+/// The runtimes stay declared, so the scenario can be started again.
+#[tauri::command]
+#[tracing::instrument(skip(app))]
+pub async fn stop_scenario(
+    name: String,
+    app: AppHandle,
+) -> Result<BulkOperationReport, SandboxError> {
+    let runtimes = app.state::<ZenohRuntimes>();
+    let scenarios = app.state::<Scenarios>();
+    let scenarios = scenarios.scenarios.lock().await;
+    let scenario = scenarios
+        .get(&name)
+        .ok_or_else(|| format!("Scenario {} not found", name))?;
+
+    let mut outcomes = stop(&runtimes, scenario).await;
+    outcomes.sort_by_key(|outcome| outcome.runtime_id);
+    Ok(BulkOperationReport::from_outcomes(outcomes))
+}
@@ -0,0 +1,185 @@
+use std::{collections::HashMap, time::Duration};
+
+use serde_json::Value as JsonValue;
+use tauri::{AppHandle, Manager, State};
+
+use crate::{
+    REQUEST_TIMEOUT, RuntimeId, RuntimeRequest, ZenohRuntimes,
+    error::SandboxError,
+    logs::LogStorage,
+    timeline::Timeline,
+    ts::{
+        config::{ZenohConfigJson, json_set_path},
+        scouting::{DiscoveredPeer, ScoutingSettings},
+        timeline::ConfigChangeSource,
+    },
+};
+
+// ============================================================================
+// Constants
+// ============================================================================
+
+/// Time to wait for scouting replies when none is given
+const DEFAULT_SCOUT_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Longest time to wait for scouting replies
+const MAX_SCOUT_TIMEOUT: Duration = Duration::from_secs(30);
+
+// ============================================================================
+// Peer discovery
+// ============================================================================
+
+impl ZenohRuntimes {
+    /// Scout for peers and routers from a running runtime during `timeout`.
+    /// Returns the nodes that answered and the connected ones, ordered by ZenohId.
+    async fn scout(
+        &self,
+        runtime_id: RuntimeId,
+        timeout: Duration,
+    ) -> Result<Vec<DiscoveredPeer>, SandboxError> {
+        let mut peers = self
+            .request_with_timeout(
+                runtime_id,
+                "scout",
+                timeout + REQUEST_TIMEOUT,
+                |response_tx| RuntimeRequest::Scout {
+                    timeout,
+                    response_tx,
+                },
+            )
+            .await?
+            .map_err(SandboxError::from)?;
+
+        let by_zid: HashMap<String, RuntimeId> = self
+            .runtimes
+            .read()
+            .await
+            .iter()
+            .filter_map(|(id, runtime_process)| Some((runtime_process.zenoh_id?.to_string(), *id)))
+            .collect();
+        for peer in &mut peers {
+            peer.runtime_id = by_zid.get(&peer.zid).copied();
+        }
+        peers.sort_by(|a, b| a.zid.cmp(&b.zid));
+        Ok(peers)
+    }
+}
+
+// ============================================================================
+// Discovery settings
+// ============================================================================
+
+/// Config path enabling multicast scouting
+const MULTICAST_ENABLED: &str = "scouting/multicast/enabled";
+
+/// Config path enabling gossip scouting
+const GOSSIP_ENABLED: &str = "scouting/gossip/enabled";
+
+/// Discovery settings of a config. Missing keys are enabled, as in the zenoh defaults.
+fn scouting_settings(config: &ZenohConfigJson) -> ScoutingSettings {
+    let enabled = |path: &str| {
+        config
+            .as_json()
+            .pointer(&format!("/{}", path))
+            .and_then(JsonValue::as_bool)
+            .unwrap_or(true)
+    };
+    ScoutingSettings {
+        multicast: enabled(MULTICAST_ENABLED),
+        gossip: enabled(GOSSIP_ENABLED),
+    }
+}
+
+/// Config with multicast and gossip scouting enabled or disabled, when given
+fn with_scouting(
+    config: &ZenohConfigJson,
+    multicast: Option<bool>,
+    gossip: Option<bool>,
+) -> Result<ZenohConfigJson, String> {
+    let mut json = config.as_json().clone();
+    for (path, enabled) in [(MULTICAST_ENABLED, multicast), (GOSSIP_ENABLED, gossip)] {
+        if let Some(enabled) = enabled {
+            json_set_path(&mut json, path, JsonValue::Bool(enabled));
+        }
+    }
+    ZenohConfigJson::from_json(json)
+}
+
+// ============================================================================
+// Tauri commands
+// ============================================================================
+
+/// Scout for peers and routers from a running runtime during `timeout_ms`, with the
+/// multicast scouting settings of its running config, to see how the discovery settings
+/// affect what each node sees. The nodes with a transport session are listed too,
+/// also when they were found by gossip or connected through a configured endpoint.
+#[tauri::command]
+#[tracing::instrument(skip(runtimes))]
+pub async fn get_discovered_peers(
+    runtime_id: RuntimeId,
+    timeout_ms: Option<u64>,
+    runtimes: State<'_, ZenohRuntimes>,
+) -> Result<Vec<DiscoveredPeer>, SandboxError> {
+    let timeout = timeout_ms
+        .map(Duration::from_millis)
+        .unwrap_or(DEFAULT_SCOUT_TIMEOUT)
+        .min(MAX_SCOUT_TIMEOUT);
+    runtimes.scout(runtime_id, timeout).await
+}
+
+/// Get the multicast and gossip scouting settings of the declared config of a runtime
+#[tauri::command]
+#[tracing::instrument(skip(state))]
+pub async fn get_runtime_scouting(
+    runtime_id: RuntimeId,
+    state: State<'_, ZenohRuntimes>,
+) -> Result<ScoutingSettings, SandboxError> {
+    let runtimes = state.runtimes.read().await;
+    let runtime_process = runtimes
+        .get(&runtime_id)
+        .ok_or(SandboxError::RuntimeNotFound(runtime_id))?;
+    Ok(scouting_settings(&runtime_process.sandbox_config))
+}
+
+/// Enable or disable multicast and gossip scouting in the declared config of a runtime,
+/// leaving the setting unchanged when not given. The change is annotated in the timeline
+/// and the runtime's log stream, and takes effect the next time the runtime is started.
+/// Returns the resulting settings.
+#[tauri::command]
+#[tracing::instrument(skip(app))]
+pub async fn set_runtime_scouting(
+    runtime_id: RuntimeId,
+    multicast: Option<bool>,
+    gossip: Option<bool>,
+    app: AppHandle,
+) -> Result<ScoutingSettings, SandboxError> {
+    let runtimes = app.state::<ZenohRuntimes>();
+    let (old_config, new_config) = {
+        let mut runtime_processes = runtimes.runtimes.write().await;
+        let runtime_process = runtime_processes
+            .get_mut(&runtime_id)
+            .ok_or(SandboxError::RuntimeNotFound(runtime_id))?;
+        let new_config = with_scouting(&runtime_process.sandbox_config, multicast, gossip)
+            .map_err(SandboxError::ConfigInvalid)?;
+        (
+            runtime_process.replace_config(new_config.clone(), ConfigChangeSource::Patch),
+            new_config,
+        )
+    };
+    app.state::<Timeline>().annotate_config_change(
+        runtime_id,
+        ConfigChangeSource::Patch,
+        &old_config,
+        &new_config,
+        Some(app.state::<LogStorage>().inner()),
+    );
+
+    let settings = scouting_settings(&new_config);
+    tracing::info!(
+        "Runtime {} scouting set to multicast {}, gossip {}",
+        runtime_id,
+        settings.multicast,
+        settings.gossip
+    );
+    Ok(settings)
+}
@@ -0,0 +1,509 @@
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use chrono::Utc;
+use tauri::{AppHandle, Manager};
+use tokio::sync::Mutex;
+
+use crate::{
+    RuntimeId, SANDBOX_RUNTIME_ID, ZenohRuntimes, bulk,
+    error::SandboxError,
+    journal::Journal,
+    log_archive::{self, ArchiveStaging},
+    logs::LogStorage,
+    privileges,
+    reports::OperationResults,
+    scenarios::Scenarios,
+    testing::Testing,
+    timeline::Timeline,
+    topology::Topology,
+    ts::{
+        config::{ZenohConfigJson, check_config_source},
+        log::{LogEntryLevel, LogExportFormat, LogTimeRange},
+        session::{
+            ImportedRuntime, SandboxSession, SessionEndReport, SessionImportReport, SessionRuntime,
+        },
+    },
+};
+
+// ============================================================================
+// Constants
+// ============================================================================
+
+/// Version of the session file format written by `export_session`
+const SESSION_FORMAT_VERSION: u32 = 1;
+
+/// Name of the session file in archives written by `end_session`
+const ARCHIVE_SESSION_FILE: &str = "session.json";
+
+/// Name of the report of recorded operations in archives written by `end_session`
+const ARCHIVE_OPERATIONS_FILE: &str = "operations.json";
+
+/// Name of the file in the app data directory the declared runtimes are saved to
+pub const AUTOSAVE_FILE_NAME: &str = "autosave_session.json";
+
+/// Interval at which the declared runtimes are checked for changes to save
+const AUTOSAVE_INTERVAL: Duration = Duration::from_secs(1);
+
+// ============================================================================
+// Session snapshots
+// ============================================================================
+
+impl ZenohRuntimes {
+    /// Snapshot of the declared runtimes, ordered by RuntimeId
+    async fn session_runtimes(&self, names: &HashMap<RuntimeId, String>) -> Vec<SessionRuntime> {
+        let runtimes = self.runtimes.read().await;
+        let mut session_runtimes: Vec<SessionRuntime> = runtimes
+            .iter()
+            .map(|(runtime_id, runtime_process)| SessionRuntime {
+                runtime_id: *runtime_id,
+                name: names.get(runtime_id).cloned(),
+                config: runtime_process.sandbox_config.clone(),
+                config_source: runtime_process.config_source.clone(),
+                options: runtime_process.options.clone(),
+                port: runtime_process.allocated_port,
+                state: runtime_process.state,
+                notes: runtime_process.notes.clone(),
+            })
+            .collect();
+        session_runtimes.sort_by_key(|runtime| runtime.runtime_id);
+        session_runtimes
+    }
+}
+
+/// Snapshot of the declared runtimes, as written by `export_session`
+async fn session_snapshot(app: &AppHandle) -> SandboxSession {
+    let names = app.state::<Scenarios>().runtime_names().await;
+    SandboxSession {
+        version: SESSION_FORMAT_VERSION,
+        exported_at: Utc::now(),
+        sandbox_version: env!("CARGO_PKG_VERSION").to_string(),
+        runtimes: app.state::<ZenohRuntimes>().session_runtimes(&names).await,
+        journal: app.state::<Journal>().entries(),
+    }
+}
+
+/// Write a session file, replacing it atomically.
+/// Returns the absolute path of the written file.
+async fn write_session_file(path: &Path, session: &SandboxSession) -> Result<PathBuf, String> {
+    let contents = serde_json::to_string_pretty(session)
+        .map_err(|e| format!("Failed to serialize session: {}", e))?;
+
+    let path = std::path::absolute(path)
+        .map_err(|e| format!("Invalid session path {}: {}", path.display(), e))?;
+    let tmp_path = path.with_extension("tmp");
+    tokio::fs::write(&tmp_path, contents)
+        .await
+        .map_err(|e| format!("Failed to write {}: {}", tmp_path.display(), e))?;
+    tokio::fs::rename(&tmp_path, &path)
+        .await
+        .map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+    Ok(path)
+}
+
+/// Read a session file written by this or an older version of the sandbox
+async fn read_session_file(path: &Path) -> Result<SandboxSession, String> {
+    let contents = tokio::fs::read_to_string(path)
+        .await
+        .map_err(|e| format!("Failed to read session {}: {}", path.display(), e))?;
+    let session: SandboxSession = serde_json::from_str(&contents)
+        .map_err(|e| format!("Invalid session file {}: {}", path.display(), e))?;
+    if session.version > SESSION_FORMAT_VERSION {
+        return Err(format!(
+            "Session file version {} is not supported (expected at most {})",
+            session.version, SESSION_FORMAT_VERSION
+        ));
+    }
+    Ok(session)
+}
+
+/// Declare the runtimes of a session, in addition to the current ones, and append its
+/// journal entries to the experiment journal. Nothing is declared if a runtime is invalid.
+/// Returns the runtimes of the session with the runtimes declared for them.
+async fn declare_session(
+    app: &AppHandle,
+    session: SandboxSession,
+) -> Result<Vec<(SessionRuntime, ImportedRuntime)>, String> {
+    // Validate everything before declaring anything
+    let mut session_runtimes = Vec::new();
+    for runtime in session.runtimes {
+        let config = ZenohConfigJson::from_json(runtime.config.as_json().clone())
+            .map_err(|e| format!("Runtime {}: {}", runtime.runtime_id, e))?;
+        check_config_source(runtime.config_source.clone(), &config)
+            .map_err(|e| format!("Runtime {}: {}", runtime.runtime_id, e))?;
+        privileges::resolve(&runtime.options)
+            .map_err(|e| format!("Runtime {}: {}", runtime.runtime_id, e))?;
+        session_runtimes.push(SessionRuntime { config, ..runtime });
+    }
+
+    let runtimes = app.state::<ZenohRuntimes>();
+    let mut imported = Vec::new();
+    for runtime in &session_runtimes {
+        let (runtime_id, port) = runtimes
+            .declare(
+                runtime.config.clone(),
+                runtime.options.clone(),
+                Some(runtime.port),
+            )
+            .await?;
+        runtimes
+            .set_notes(runtime_id, runtime.notes.clone())
+            .await?;
+        runtimes
+            .set_config_source(runtime_id, runtime.config_source.clone())
+            .await?;
+        imported.push(ImportedRuntime {
+            previous_runtime_id: runtime.runtime_id,
+            runtime_id,
+            name: runtime.name.clone(),
+            port,
+        });
+    }
+
+    // Expected peers refer to RuntimeIds of the session file
+    let new_ids: HashMap<RuntimeId, RuntimeId> = imported
+        .iter()
+        .map(|runtime| (runtime.previous_runtime_id, runtime.runtime_id))
+        .collect();
+    {
+        let mut runtime_processes = runtimes.runtimes.write().await;
+        for runtime in &imported {
+            if let Some(expected_peers) = runtime_processes
+                .get_mut(&runtime.runtime_id)
+                .and_then(|runtime_process| runtime_process.options.expected_peers.as_mut())
+            {
+                expected_peers.retain_mut(|peer| match new_ids.get(peer) {
+                    Some(new_id) => {
+                        *peer = *new_id;
+                        true
+                    }
+                    None => false,
+                });
+            }
+        }
+    }
+    app.state::<Journal>().import(session.journal, &new_ids);
+
+    Ok(session_runtimes.into_iter().zip(imported).collect())
+}
+
+// ============================================================================
+// Autosave
+// ============================================================================
+
+/// Saves the declared runtimes to the app data directory whenever they change,
+/// so that they are restored the next time the sandbox starts
+pub struct SessionAutosave {
+    /// File the declared runtimes are saved to
+    path: PathBuf,
+    /// Runtimes and journal last saved, locked until the saved session is restored
+    saved: Mutex<Option<String>>,
+}
+
+impl SessionAutosave {
+    pub fn new(path: PathBuf) -> Self {
+        Self {
+            path,
+            saved: Mutex::new(None),
+        }
+    }
+
+    /// Restore the runtimes saved by the previous run of the sandbox, without starting them,
+    /// then save the declared runtimes whenever they change
+    pub async fn run(app: AppHandle) {
+        let autosave = app.state::<SessionAutosave>();
+        {
+            let _saved = autosave.saved.lock().await;
+            if tokio::fs::try_exists(&autosave.path).await.unwrap_or(false) {
+                autosave.restore(&app).await;
+            }
+        }
+        loop {
+            autosave.save(&app).await;
+            tokio::time::sleep(AUTOSAVE_INTERVAL).await;
+        }
+    }
+
+    /// Declare the runtimes of the saved session. An invalid file is moved aside,
+    /// so that it isn't overwritten.
+    async fn restore(&self, app: &AppHandle) {
+        let restored = match read_session_file(&self.path).await {
+            Ok(session) => declare_session(app, session).await,
+            Err(e) => Err(e),
+        };
+        match restored {
+            Ok(restored) => tracing::info!(
+                "Restored {} runtimes from {}",
+                restored.len(),
+                self.path.display()
+            ),
+            Err(e) => {
+                let invalid_path = self.path.with_extension("json.invalid");
+                tracing::error!(
+                    "Failed to restore the saved session, moving it to {}: {}",
+                    invalid_path.display(),
+                    e
+                );
+                if let Err(e) = tokio::fs::rename(&self.path, &invalid_path).await {
+                    tracing::error!("Failed to move {}: {}", self.path.display(), e);
+                }
+            }
+        }
+    }
+
+    /// Save the declared runtimes if they changed since they were last saved
+    pub async fn save(&self, app: &AppHandle) {
+        let mut saved = self.saved.lock().await;
+        let session = session_snapshot(app).await;
+        let contents = serde_json::to_string(&(&session.runtimes, &session.journal)).ok();
+        if contents.is_none() || *saved == contents {
+            return;
+        }
+        if let Some(dir) = self.path.parent()
+            && let Err(e) = tokio::fs::create_dir_all(dir).await
+        {
+            tracing::warn!("Failed to create {}: {}", dir.display(), e);
+        }
+        if let Err(e) = write_session_file(&self.path, &session).await {
+            tracing::warn!("Failed to save the session: {}", e);
+        }
+        // Retry on the next change rather than on every check
+        *saved = contents;
+    }
+}
+
+// ============================================================================
+// End of session
+// ============================================================================
+
+/// Files of `dir` whose name matches `predicate`
+async fn files_matching(dir: &Path, predicate: impl Fn(&str) -> bool) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+    let Ok(mut entries) = tokio::fs::read_dir(dir).await else {
+        return files;
+    };
+    while let Ok(Some(entry)) = entries.next_entry().await {
+        if entry.file_name().to_str().is_some_and(&predicate) {
+            files.push(entry.path());
+        }
+    }
+    files
+}
+
+/// Stdout and stderr files of the runtime processes
+async fn process_output_files(log_dir: &Path) -> Vec<PathBuf> {
+    files_matching(log_dir, |name| {
+        name.ends_with("-stdout.log") || name.ends_with("-stderr.log")
+    })
+    .await
+}
+
+/// Write an archive with the logs, the recorded operations, the declared runtimes
+/// and the output of the runtime processes. Returns the absolute path of the archive.
+async fn write_session_archive(app: &AppHandle, path: &str) -> Result<String, String> {
+    let staging = ArchiveStaging::create(path).await?;
+    let dir = staging.dir().to_path_buf();
+    let staged = async {
+        let (log_files, _) = log_archive::stage_logs(
+            app,
+            &dir.join("logs"),
+            LogExportFormat::Jsonl,
+            LogTimeRange::default(),
+        )
+        .await?;
+        let mut files: Vec<String> = log_files
+            .into_iter()
+            .map(|file| format!("logs/{}", file))
+            .collect();
+
+        let session = serde_json::to_string_pretty(&session_snapshot(app).await)
+            .map_err(|e| format!("Failed to serialize session: {}", e))?;
+        let operations =
+            serde_json::to_string_pretty(&app.state::<OperationResults>().full_report())
+                .map_err(|e| format!("Failed to serialize operations: {}", e))?;
+        for (file, contents) in [
+            (ARCHIVE_SESSION_FILE, session),
+            (ARCHIVE_OPERATIONS_FILE, operations),
+        ] {
+            tokio::fs::write(dir.join(file), contents)
+                .await
+                .map_err(|e| format!("Failed to write {}: {}", file, e))?;
+            files.push(file.to_string());
+        }
+
+        let log_dir = app.state::<ZenohRuntimes>().dirs.read().log_dir.clone();
+        tokio::fs::create_dir_all(dir.join("process"))
+            .await
+            .map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+        for source in process_output_files(&log_dir).await {
+            let Some(name) = source.file_name() else {
+                continue;
+            };
+            let file = format!("process/{}", name.to_string_lossy());
+            tokio::fs::copy(&source, dir.join(&file))
+                .await
+                .map_err(|e| format!("Failed to copy {}: {}", source.display(), e))?;
+            files.push(file);
+        }
+        Ok::<_, String>(files)
+    }
+    .await;
+
+    match staged {
+        Ok(files) => Ok(staging.finish(files).await?.to_string_lossy().to_string()),
+        Err(e) => {
+            staging.abort().await;
+            Err(e)
+        }
+    }
+}
+
+/// Delete leftover IPC sockets and output files of runtime processes.
+/// Returns the number of deleted files.
+async fn remove_temp_files(runtimes: &ZenohRuntimes) -> usize {
+    let log_dir = runtimes.dirs.read().log_dir.clone();
+    runtimes.remove_socket_files().await + remove_files(process_output_files(&log_dir).await).await
+}
+
+/// Delete files, returning the number of files deleted
+async fn remove_files(files: Vec<PathBuf>) -> usize {
+    let mut removed = 0;
+    for file in files {
+        match tokio::fs::remove_file(&file).await {
+            Ok(()) => removed += 1,
+            Err(e) => tracing::warn!("Failed to remove {}: {}", file.display(), e),
+        }
+    }
+    removed
+}
+
+impl ZenohRuntimes {
+    /// Delete the socket files left in the socket directory.
+    /// Returns the number of files deleted.
+    pub(crate) async fn remove_socket_files(&self) -> usize {
+        let socket_dir = self.dirs.read().socket_dir.clone();
+        remove_files(files_matching(&socket_dir, |name| name.ends_with(".sock")).await).await
+    }
+}
+
+// ============================================================================
+// Tauri commands
+// ============================================================================
+
+/// Save all declared runtimes (configs, options, ports, states and notes)
+/// and the experiment journal to a JSON file.
+/// Returns the absolute path of the written file.
+#[tauri::command]
+#[tracing::instrument(skip(app))]
+pub async fn export_session(path: String, app: AppHandle) -> Result<String, SandboxError> {
+    let session = session_snapshot(&app).await;
+    let path = write_session_file(Path::new(&path), &session).await?;
+    tracing::info!(
+        "Session with {} runtimes written to {}",
+        session.runtimes.len(),
+        path.display()
+    );
+    Ok(path.to_string_lossy().to_string())
+}
+
+/// Declare the runtimes of a session file, in addition to the current ones,
+/// and append its journal entries to the experiment journal.
+/// Runtimes get new RuntimeIds (expected peers are remapped accordingly) and keep their
+/// port when it is free. Runtimes that were active at export time are started.
+#[tauri::command]
+#[tracing::instrument(skip(app))]
+pub async fn import_session(
+    path: String,
+    app: AppHandle,
+) -> Result<SessionImportReport, SandboxError> {
+    let declared = declare_session(&app, read_session_file(Path::new(&path)).await?).await?;
+    tracing::info!("Imported {} runtimes from {}", declared.len(), path);
+
+    let to_start: Vec<RuntimeId> = declared
+        .iter()
+        .filter(|(runtime, _)| runtime.state.is_active())
+        .map(|(_, imported)| imported.runtime_id)
+        .collect();
+    let started = bulk::run_bounded(&app, to_start, None, |app, runtime_id| async move {
+        let zid = app
+            .state::<ZenohRuntimes>()
+            .launch(&app, runtime_id)
+            .await?;
+        Ok(Some(zid.to_string()))
+    })
+    .await;
+
+    Ok(SessionImportReport {
+        runtimes: declared.into_iter().map(|(_, imported)| imported).collect(),
+        started,
+    })
+}
+
+/// Stop all runtimes, optionally archive the session, then reset the sandbox: runtimes,
+/// scenarios, timeline, journal, test entities, recorded operations and logs of the removed
+/// runtimes are cleared, and leftover sockets and process output files are deleted.
+/// The archive (`.tar.gz`) holds the logs, the recorded operations, the declared runtimes,
+/// the journal and the process output. Nothing is reset if it can't be written.
+#[tauri::command]
+#[tracing::instrument(skip(app))]
+pub async fn end_session(
+    archive_path: Option<String>,
+    app: AppHandle,
+) -> Result<SessionEndReport, SandboxError> {
+    let runtimes = app.state::<ZenohRuntimes>();
+    let active: Vec<RuntimeId> = runtimes
+        .statuses()
+        .await
+        .into_iter()
+        .filter(|status| status.state.is_active())
+        .map(|status| status.runtime_id)
+        .collect();
+    let stopped = bulk::run_bounded(&app, active, None, |app, runtime_id| async move {
+        app.state::<ZenohRuntimes>()
+            .shutdown(runtime_id, true)
+            .await?;
+        Ok(None)
+    })
+    .await;
+
+    let archive = match &archive_path {
+        Some(path) => Some(write_session_archive(&app, path).await?),
+        None => None,
+    };
+
+    let removed_runtimes = runtimes.remove_inactive().await;
+    let logs = app.state::<LogStorage>();
+    for runtime_id in &removed_runtimes {
+        logs.clear_logs(*runtime_id);
+    }
+    app.state::<Scenarios>().clear().await;
+    app.state::<Timeline>().clear();
+    app.state::<Journal>().clear();
+    app.state::<Testing>().clear();
+    app.state::<OperationResults>().clear();
+    app.state::<Topology>().reset();
+    let removed_files = remove_temp_files(&runtimes).await;
+
+    logs.add_sandbox_log(
+        SANDBOX_RUNTIME_ID,
+        LogEntryLevel::INFO,
+        format!(
+            "Session ended: {} runtimes removed, {} files deleted{}",
+            removed_runtimes.len(),
+            removed_files,
+            archive
+                .as_ref()
+                .map(|archive| format!(", archived to {}", archive))
+                .unwrap_or_default()
+        ),
+    );
+    Ok(SessionEndReport {
+        stopped,
+        archive,
+        removed_runtimes,
+        removed_files,
+    })
+}
@@ -0,0 +1,177 @@
+use std::time::Duration;
+
+use serde_json::Value as JsonValue;
+use tauri::State;
+use zenoh::key_expr::KeyExpr;
+
+use crate::{
+    RuntimeId, ZenohRuntimes,
+    error::SandboxError,
+    protocol::TestEntity,
+    testing::{self, Testing},
+    ts::testing::{ShmReport, TestEntityKind},
+    zenoh_admin,
+};
+
+// ============================================================================
+// Constants
+// ============================================================================
+
+/// Size of the published payloads when none is given, above the default size from which
+/// zenoh puts payloads in shared memory (`transport/shared_memory/transport_optimization`)
+const DEFAULT_PAYLOAD_SIZE: usize = 64 * 1024;
+
+/// Largest size of the published payloads
+const MAX_PAYLOAD_SIZE: usize = 4 * 1024 * 1024;
+
+/// Period of the publisher
+const PUBLISH_PERIOD_MS: u64 = 100;
+
+/// Time the publisher runs
+const PUBLISH_DURATION: Duration = Duration::from_secs(2);
+
+/// Time for the subscriber declaration to reach the publisher's runtime before publishing
+const SUBSCRIBER_SETTLE_DELAY: Duration = Duration::from_millis(500);
+
+/// Time for the samples in flight to arrive once the publisher is undeclared
+const DRAIN_DELAY: Duration = Duration::from_millis(200);
+
+// ============================================================================
+// Shared memory settings
+// ============================================================================
+
+impl ZenohRuntimes {
+    /// Whether shared memory is enabled for a runtime, by its options or declared config
+    async fn shared_memory_enabled(&self, runtime_id: RuntimeId) -> Result<bool, SandboxError> {
+        let runtimes = self.runtimes.read().await;
+        let runtime_process = runtimes
+            .get(&runtime_id)
+            .ok_or(SandboxError::RuntimeNotFound(runtime_id))?;
+        Ok(runtime_process.options.shared_memory
+            || runtime_process
+                .sandbox_config
+                .as_json()
+                .pointer("/transport/shared_memory/enabled")
+                .and_then(JsonValue::as_bool)
+                .unwrap_or(false))
+    }
+}
+
+// ============================================================================
+// Tauri commands
+// ============================================================================
+
+/// Put payloads of `payload_size` bytes on `key_expr` from a running runtime to a
+/// subscriber on another running runtime of the host, and report whether their transport
+/// session uses shared memory, to check the shared memory settings of both runtimes.
+/// The test publisher and subscriber are undeclared once done.
+#[tauri::command]
+#[tracing::instrument(skip(runtimes, testing))]
+pub async fn verify_shared_memory(
+    publisher_runtime_id: RuntimeId,
+    subscriber_runtime_id: RuntimeId,
+    key_expr: String,
+    payload_size: Option<usize>,
+    runtimes: State<'_, ZenohRuntimes>,
+    testing: State<'_, Testing>,
+) -> Result<ShmReport, SandboxError> {
+    if publisher_runtime_id == subscriber_runtime_id {
+        return Err("Shared memory applies to transports: \
+             the publisher and subscriber must be on different runtimes"
+            .into());
+    }
+    KeyExpr::try_from(key_expr.as_str())
+        .map_err(|e| format!("Invalid key expression '{}': {}", key_expr, e))?;
+    let payload_size = payload_size.unwrap_or(DEFAULT_PAYLOAD_SIZE);
+    if payload_size > MAX_PAYLOAD_SIZE {
+        return Err(format!(
+            "Payload of {} bytes too large, the limit is {} bytes",
+            payload_size, MAX_PAYLOAD_SIZE
+        )
+        .into());
+    }
+    let publisher_enabled = runtimes.shared_memory_enabled(publisher_runtime_id).await?;
+    let subscriber_enabled = runtimes
+        .shared_memory_enabled(subscriber_runtime_id)
+        .await?;
+    let subscriber_zid = runtimes.running_zenoh_id(subscriber_runtime_id).await?;
+
+    // The sequence number keeps the payloads distinct, the padding gives them their size
+    let payload_template = format!(
+        "{}{}",
+        testing::SEQ_PLACEHOLDER,
+        "x".repeat(payload_size.saturating_sub(testing::SEQ_PLACEHOLDER.len()))
+    );
+    let subscriber = testing::declare(
+        &runtimes,
+        &testing,
+        subscriber_runtime_id,
+        key_expr.clone(),
+        TestEntityKind::Subscriber,
+        TestEntity::Subscriber {
+            keyexpr: key_expr.clone(),
+        },
+    )
+    .await?;
+    tokio::time::sleep(SUBSCRIBER_SETTLE_DELAY).await;
+    let publisher = testing::declare(
+        &runtimes,
+        &testing,
+        publisher_runtime_id,
+        key_expr.clone(),
+        TestEntityKind::PeriodicPublisher {
+            payload_template: payload_template.clone(),
+            period_ms: PUBLISH_PERIOD_MS,
+            jitter_ms: 0,
+            burst: 1,
+        },
+        TestEntity::PeriodicPublisher {
+            keyexpr: key_expr.clone(),
+            payload_template,
+            period_ms: PUBLISH_PERIOD_MS,
+            jitter_ms: 0,
+            burst: 1,
+        },
+    )
+    .await;
+    let publisher = match publisher {
+        Ok(publisher) => publisher,
+        Err(e) => {
+            let _ = testing::undeclare(&runtimes, &testing, subscriber.id).await;
+            return Err(e);
+        }
+    };
+
+    tokio::time::sleep(PUBLISH_DURATION).await;
+    // Read the session while the transport is in use
+    let sessions = zenoh_admin::local_sessions(&runtimes, publisher_runtime_id).await;
+    let stopped = testing::undeclare(&runtimes, &testing, publisher.id).await;
+    tokio::time::sleep(DRAIN_DELAY).await;
+    let received = testing.received(subscriber.id);
+    testing::undeclare(&runtimes, &testing, subscriber.id).await?;
+    stopped?;
+
+    let subscriber_zid = subscriber_zid.to_string();
+    let transport_shm = sessions?
+        .into_iter()
+        .find(|session| session.peer == subscriber_zid)
+        .map(|session| session.shm);
+    tracing::info!(
+        "Shared memory from runtime {} to {}: {:?}, {} samples of {} bytes received",
+        publisher_runtime_id,
+        subscriber_runtime_id,
+        transport_shm,
+        received,
+        payload_size
+    );
+    Ok(ShmReport {
+        publisher_runtime_id,
+        subscriber_runtime_id,
+        key_expr,
+        publisher_enabled,
+        subscriber_enabled,
+        payload_size,
+        received,
+        transport_shm,
+    })
+}
@@ -0,0 +1,50 @@
+use std::collections::HashSet;
+
+use serde_json::{Map, Value as JsonValue, json};
+use zenoh::key_expr::KeyExpr;
+
+use crate::ts::runtime::StorageSpec;
+
+// ============================================================================
+// Storages of runtimes
+// ============================================================================
+//
+// Storages are run by the storage manager plugin of the runtime process, on the
+// memory volume built into the plugin.
+
+/// Validate the storages requested in the runtime options
+pub fn validate(storages: &[StorageSpec]) -> Result<(), String> {
+    let mut names = HashSet::new();
+    for storage in storages {
+        if storage.name.is_empty() {
+            return Err(format!("Storage on {} has no name", storage.keyexpr));
+        }
+        if !names.insert(storage.name.as_str()) {
+            return Err(format!("Duplicate storage name {:?}", storage.name));
+        }
+        KeyExpr::try_from(storage.keyexpr.as_str()).map_err(|e| {
+            format!(
+                "Invalid key expression {:?} of storage {:?}: {}",
+                storage.keyexpr, storage.name, e
+            )
+        })?;
+    }
+    Ok(())
+}
+
+/// JSON5 config of the storage manager plugin running `storages`, None if there are none
+pub fn plugin_config(storages: &[StorageSpec]) -> Option<String> {
+    if storages.is_empty() {
+        return None;
+    }
+    let storages: Map<String, JsonValue> = storages
+        .iter()
+        .map(|storage| {
+            (
+                storage.name.clone(),
+                json!({ "key_expr": storage.keyexpr, "volume": "memory" }),
+            )
+        })
+        .collect();
+    Some(json!({ "storages": storages }).to_string())
+}
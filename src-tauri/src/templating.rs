@@ -0,0 +1,364 @@
+use std::collections::{HashMap, HashSet};
+
+use tauri::{AppHandle, Manager};
+
+use crate::{
+    RuntimeId, ZenohRuntimes, config_to_json,
+    links::reachable_endpoints,
+    scenarios::Scenarios,
+    timeline::Timeline,
+    ts::{config::ZenohConfigJson, timeline::ConfigChangeSource},
+};
+
+// ============================================================================
+// Templates
+// ============================================================================
+//
+// A config template is the JSON5 text of a config with placeholders, resolved against
+// the runtimes of the sandbox each time the runtime is started:
+//
+// - `${PORT}`, `${REST_PORT}`: websocket and REST ports allocated to the runtime
+// - `${RUNTIME_ID}`, `${RUNTIME_NAME}`: RuntimeId and name of the runtime
+// - `${AUTO}`: port of a TCP listen endpoint, allocated as the runtime starts like port 0
+// - `${PEER(<name>).ENDPOINT}`, `${PEER(<name>).PORT}`, `${PEER(<name>).ID}`: first endpoint
+//   other runtimes can connect to, websocket port and RuntimeId of another runtime
+//
+// `$${` is written as a literal `${`.
+
+/// Start of a placeholder
+const PLACEHOLDER_START: &str = "${";
+
+/// Field of another runtime referenced by a placeholder
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum PeerField {
+    Endpoint,
+    Port,
+    Id,
+}
+
+/// Value referenced by a placeholder
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum Placeholder {
+    Port,
+    RestPort,
+    RuntimeId,
+    RuntimeName,
+    Auto,
+    /// Field of the runtime with the given name
+    Peer(String, PeerField),
+}
+
+/// Part of a template
+enum Segment<'a> {
+    Text(&'a str),
+    Placeholder(Placeholder),
+}
+
+fn parse_placeholder(expr: &str) -> Result<Placeholder, String> {
+    match expr.trim() {
+        "PORT" => Ok(Placeholder::Port),
+        "REST_PORT" => Ok(Placeholder::RestPort),
+        "RUNTIME_ID" => Ok(Placeholder::RuntimeId),
+        "RUNTIME_NAME" => Ok(Placeholder::RuntimeName),
+        "AUTO" => Ok(Placeholder::Auto),
+        expr => {
+            let (name, field) = expr
+                .strip_prefix("PEER(")
+                .and_then(|peer| peer.split_once(")."))
+                .ok_or_else(|| format!("Unknown placeholder: ${{{}}}", expr))?;
+            let field = match field {
+                "ENDPOINT" => PeerField::Endpoint,
+                "PORT" => PeerField::Port,
+                "ID" => PeerField::Id,
+                field => return Err(format!("Unknown field of PEER({}): {}", name, field)),
+            };
+            if name.trim().is_empty() {
+                return Err(format!(
+                    "Missing runtime name in placeholder: ${{{}}}",
+                    expr
+                ));
+            }
+            Ok(Placeholder::Peer(name.trim().to_string(), field))
+        }
+    }
+}
+
+/// Split a template into text and placeholders
+fn parse(template: &str) -> Result<Vec<Segment<'_>>, String> {
+    let mut segments = Vec::new();
+    let mut rest = template;
+    while let Some(start) = rest.find(PLACEHOLDER_START) {
+        // `$${` is an escaped `${`, keep the first `$` and skip the second
+        if rest[..start].ends_with('$') {
+            segments.push(Segment::Text(&rest[..start]));
+            rest = &rest[start + 1..];
+            continue;
+        }
+        segments.push(Segment::Text(&rest[..start]));
+        let end = rest[start..]
+            .find('}')
+            .map(|end| start + end)
+            .ok_or_else(|| format!("Unterminated placeholder: {}", &rest[start..]))?;
+        segments.push(Segment::Placeholder(parse_placeholder(
+            &rest[start + PLACEHOLDER_START.len()..end],
+        )?));
+        rest = &rest[end + 1..];
+    }
+    segments.push(Segment::Text(rest));
+    Ok(segments)
+}
+
+/// Join the segments of a template, replacing placeholders by their value
+fn render(segments: &[Segment], value: impl Fn(&Placeholder) -> String) -> String {
+    segments
+        .iter()
+        .map(|segment| match segment {
+            Segment::Text(text) => text.to_string(),
+            Segment::Placeholder(placeholder) => value(placeholder),
+        })
+        .collect()
+}
+
+/// Whether the JSON5 text of a config is a template
+pub(crate) fn is_template(content: &str) -> bool {
+    content.contains(PLACEHOLDER_START)
+}
+
+/// Names of the runtimes referenced by `PEER(<name>)` placeholders of a template
+pub(crate) fn peer_names(template: &str) -> Result<HashSet<String>, String> {
+    Ok(parse(template)?
+        .into_iter()
+        .filter_map(|segment| match segment {
+            Segment::Placeholder(Placeholder::Peer(name, _)) => Some(name),
+            _ => None,
+        })
+        .collect())
+}
+
+/// Render a template with neutral values in place of the placeholders, to validate it
+/// before the runtimes it references exist
+pub(crate) fn preview(template: &str) -> Result<String, String> {
+    Ok(render(&parse(template)?, |placeholder| {
+        match placeholder {
+            Placeholder::Port
+            | Placeholder::RestPort
+            | Placeholder::RuntimeId
+            | Placeholder::Auto
+            | Placeholder::Peer(_, PeerField::Port | PeerField::Id) => "0",
+            Placeholder::RuntimeName => "runtime",
+            Placeholder::Peer(_, PeerField::Endpoint) => "tcp/127.0.0.1:0",
+        }
+        .to_string()
+    }))
+}
+
+// ============================================================================
+// Resolution
+// ============================================================================
+
+/// Runtime as seen by the placeholders of a template
+struct TemplateRuntime {
+    runtime_id: RuntimeId,
+    /// Name from the runtime options
+    name: Option<String>,
+    port: u16,
+    rest_port: Option<u16>,
+}
+
+/// Runtimes of the sandbox a template is rendered against
+struct Registry {
+    runtimes: Vec<TemplateRuntime>,
+    /// Names of the runtimes declared by scenarios, as `<scenario>/<runtime>`
+    scenario_names: HashMap<RuntimeId, String>,
+}
+
+impl Registry {
+    fn get(&self, runtime_id: RuntimeId) -> Result<&TemplateRuntime, String> {
+        self.runtimes
+            .iter()
+            .find(|runtime| runtime.runtime_id == runtime_id)
+            .ok_or_else(|| format!("Runtime {} not found", runtime_id))
+    }
+
+    /// Name of a runtime: its name in the options, otherwise its name within its scenario
+    fn name(&self, runtime_id: RuntimeId) -> Result<String, String> {
+        self.get(runtime_id)?
+            .name
+            .clone()
+            .or_else(|| {
+                let name = self.scenario_names.get(&runtime_id)?;
+                Some(name.rsplit('/').next().unwrap_or(name).to_string())
+            })
+            .ok_or_else(|| format!("Runtime {} has no name", runtime_id))
+    }
+
+    /// Runtime referenced as `name` by `runtime_id`: a runtime with this name in its options,
+    /// a scenario runtime named `<scenario>/<name>` or `<name>` in the scenario of `runtime_id`,
+    /// or the RuntimeId `name`
+    fn find(&self, runtime_id: RuntimeId, name: &str) -> Result<&TemplateRuntime, String> {
+        let named: Vec<&TemplateRuntime> = self
+            .runtimes
+            .iter()
+            .filter(|runtime| runtime.name.as_deref() == Some(name))
+            .collect();
+        match named[..] {
+            [runtime] => return Ok(runtime),
+            [] => {}
+            _ => return Err(format!("Several runtimes are named {}", name)),
+        }
+
+        let in_scenario = self
+            .scenario_names
+            .get(&runtime_id)
+            .and_then(|own| own.rsplit_once('/'))
+            .map(|(scenario, _)| format!("{}/{}", scenario, name));
+        let scenario_runtime = self.scenario_names.iter().find(|(_, scenario_name)| {
+            **scenario_name == name || Some(*scenario_name) == in_scenario.as_ref()
+        });
+        if let Some((peer_id, _)) = scenario_runtime {
+            return self.get(*peer_id);
+        }
+
+        name.parse()
+            .ok()
+            .and_then(|peer_id| self.get(peer_id).ok())
+            .ok_or_else(|| format!("No runtime named {}", name))
+    }
+}
+
+impl ZenohRuntimes {
+    /// Snapshot of the runtimes a template can reference
+    async fn template_registry(&self, scenario_names: HashMap<RuntimeId, String>) -> Registry {
+        let runtimes = self
+            .runtimes
+            .read()
+            .await
+            .iter()
+            .map(|(runtime_id, runtime_process)| TemplateRuntime {
+                runtime_id: *runtime_id,
+                name: runtime_process.options.name.clone(),
+                port: runtime_process.allocated_port,
+                rest_port: runtime_process.rest_port,
+            })
+            .collect();
+        Registry {
+            runtimes,
+            scenario_names,
+        }
+    }
+
+    /// Value of a placeholder in the template of `runtime_id`
+    async fn resolve(
+        &self,
+        registry: &Registry,
+        runtime_id: RuntimeId,
+        placeholder: &Placeholder,
+    ) -> Result<String, String> {
+        let runtime = registry.get(runtime_id)?;
+        Ok(match placeholder {
+            Placeholder::Port => runtime.port.to_string(),
+            Placeholder::RestPort => runtime
+                .rest_port
+                .ok_or_else(|| format!("Runtime {} has no REST port", runtime_id))?
+                .to_string(),
+            Placeholder::RuntimeId => runtime_id.to_string(),
+            Placeholder::RuntimeName => registry.name(runtime_id)?,
+            // Allocated by `allocate_listen_ports`
+            Placeholder::Auto => "0".to_string(),
+            Placeholder::Peer(name, field) => {
+                let peer = registry.find(runtime_id, name)?;
+                match field {
+                    PeerField::Endpoint => reachable_endpoints(self, peer.runtime_id)
+                        .await?
+                        .into_iter()
+                        .next()
+                        .ok_or_else(|| format!("Runtime {} has no endpoint", peer.runtime_id))?,
+                    PeerField::Port => peer.port.to_string(),
+                    PeerField::Id => peer.runtime_id.to_string(),
+                }
+            }
+        })
+    }
+
+    /// Render the config template of a runtime against the runtimes of the sandbox.
+    /// `scenario_names` are the names of the runtimes declared by scenarios,
+    /// as `<scenario>/<runtime>`.
+    pub(crate) async fn render_config_template(
+        &self,
+        runtime_id: RuntimeId,
+        template: &str,
+        scenario_names: HashMap<RuntimeId, String>,
+    ) -> Result<ZenohConfigJson, String> {
+        let segments = parse(template)?;
+        let registry = self.template_registry(scenario_names).await;
+        let mut values = HashMap::new();
+        for segment in &segments {
+            if let Segment::Placeholder(placeholder) = segment
+                && !values.contains_key(placeholder)
+            {
+                let value = self
+                    .resolve(&registry, runtime_id, placeholder)
+                    .await
+                    .map_err(|e| format!("Failed to render the config template: {}", e))?;
+                values.insert(placeholder.clone(), value);
+            }
+        }
+        let content = render(&segments, |placeholder| values[placeholder].clone());
+        let config = zenoh::Config::from_json5(&content)
+            .map_err(|e| format!("Invalid config rendered from the template: {}", e))?;
+        config_to_json(&config)
+    }
+
+    /// Render the declared config of a runtime from its JSON5 source, if it is a template,
+    /// recording the rendered config in its history and timeline when it changed.
+    /// Returns the rendered config.
+    pub(crate) async fn apply_config_template(
+        &self,
+        app: &AppHandle,
+        runtime_id: RuntimeId,
+    ) -> Result<Option<ZenohConfigJson>, String> {
+        let template = self
+            .runtimes
+            .read()
+            .await
+            .get(&runtime_id)
+            .and_then(|runtime_process| runtime_process.config_source.clone())
+            .filter(|source| is_template(source));
+        let Some(template) = template else {
+            return Ok(None);
+        };
+
+        let scenario_names = app.state::<Scenarios>().runtime_names().await;
+        let config = self
+            .render_config_template(runtime_id, &template, scenario_names)
+            .await?;
+        let old_config = {
+            let mut runtimes = self.runtimes.write().await;
+            let runtime_process = runtimes
+                .get_mut(&runtime_id)
+                .ok_or(SandboxError::RuntimeNotFound(runtime_id))?;
+            if runtime_process.sandbox_config.etag() == config.etag() {
+                None
+            } else {
+                // Keep the template as the source, unlike `replace_config`
+                runtime_process
+                    .config_history
+                    .push(Some(ConfigChangeSource::Template), config.clone());
+                Some(std::mem::replace(
+                    &mut runtime_process.sandbox_config,
+                    config.clone(),
+                ))
+            }
+        };
+        if let Some(old_config) = old_config {
+            app.state::<Timeline>().annotate_config_change(
+                runtime_id,
+                ConfigChangeSource::Template,
+                &old_config,
+                &config,
+                None,
+            );
+        }
+        Ok(Some(config))
+    }
+}
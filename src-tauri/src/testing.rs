@@ -0,0 +1,1203 @@
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap, VecDeque},
+    io::Write,
+    path::PathBuf,
+    sync::{
+        Arc,
+        atomic::{AtomicU64, Ordering},
+    },
+    time::{Duration, Instant},
+};
+
+use base64::Engine;
+use chrono::{DateTime, Utc};
+use parking_lot::RwLock as ParkingLotRwLock;
+use serde::{Deserialize, Serialize};
+use tauri::State;
+use zenoh::key_expr::KeyExpr;
+
+use crate::{
+    RuntimeId, ZenohRuntimes,
+    error::SandboxError,
+    protocol::{QueryReply, TestEntity},
+    ts::testing::{
+        ActiveKeyExpr, DecodedPayload, KeyExprRole, KeyExprUser, LivelinessEvent, PayloadFormat,
+        SequenceKeyStats, SequenceReport, SubscriberStats, TestEntityInfo, TestEntityKind,
+        TestGetResult, TestReply, TestSample,
+    },
+};
+
+// ============================================================================
+// Constants
+// ============================================================================
+
+/// Number of samples per page
+pub const TEST_SAMPLES_PAGE_SIZE: usize = 100;
+
+/// Maximum number of samples kept per test subscriber
+const MAX_TEST_SAMPLES: usize = 1_000;
+
+/// Duration of the rolling window of subscriber statistics, in seconds
+const SUBSCRIBER_STATS_WINDOW_SECS: i64 = 10;
+
+/// Version of the recording format written by `start_recording`
+const RECORDING_FORMAT_VERSION: u32 = 1;
+
+/// Maximum number of liveliness events kept, across liveliness subscribers
+const MAX_LIVELINESS_EVENTS: usize = 1_000;
+
+/// Maximum number of skipped sequence numbers remembered per key.
+/// Older ones received late are counted as duplicates instead of reordered.
+const MAX_TRACKED_MISSING: usize = 10_000;
+
+/// Timeout of test queries when none is given
+const DEFAULT_TEST_GET_TIMEOUT: Duration = Duration::from_secs(5);
+
+// ============================================================================
+// Test entities
+// ============================================================================
+
+/// A sample received by a test subscriber, with its raw payload
+struct ReceivedSample {
+    sample: TestSample,
+    payload: Vec<u8>,
+}
+
+/// Test publishers and subscribers declared on runtime sessions,
+/// and the samples received by the subscribers
+#[derive(Clone)]
+pub struct Testing {
+    /// Next test entity id to allocate
+    next_id: Arc<AtomicU64>,
+    /// Next received sample id to allocate
+    next_sample_id: Arc<AtomicU64>,
+    /// Declared entities by id
+    entities: Arc<ParkingLotRwLock<HashMap<u64, TestEntityInfo>>>,
+    /// Ring buffer of received samples per subscriber id (most recent first)
+    samples: Arc<ParkingLotRwLock<HashMap<u64, VecDeque<ReceivedSample>>>>,
+    /// Sequence checkers by subscriber id
+    sequences: Arc<ParkingLotRwLock<HashMap<u64, SequenceChecker>>>,
+    /// Reception statistics by subscriber id
+    stats: Arc<ParkingLotRwLock<HashMap<u64, ReceptionStats>>>,
+    /// Recordings in progress by subscriber id
+    recordings: Arc<ParkingLotRwLock<HashMap<u64, SampleRecorder>>>,
+    /// Ring buffer of the events of all liveliness subscribers (most recent first)
+    liveliness_events: Arc<ParkingLotRwLock<VecDeque<LivelinessEvent>>>,
+}
+
+impl Testing {
+    /// Store a sample received by a test subscriber, dropping the oldest one when full.
+    /// Samples received by sequence checkers are only checked.
+    pub fn add_sample(&self, id: u64, mut sample: TestSample, payload: Vec<u8>) {
+        self.stats
+            .write()
+            .entry(id)
+            .or_default()
+            .record(sample.received_at, sample.payload_len);
+        self.record(id, &sample, &payload);
+        if let Some(checker) = self.sequences.write().get_mut(&id) {
+            checker.check(&sample);
+            return;
+        }
+        sample.sample_id = self.next_sample_id.fetch_add(1, Ordering::Relaxed);
+        let mut samples = self.samples.write();
+        let buffer = samples.entry(id).or_default();
+        buffer.push_front(ReceivedSample { sample, payload });
+        buffer.truncate(MAX_TEST_SAMPLES);
+    }
+
+    /// Append a sample to the recording of the subscriber, if any.
+    /// The recording stops if the sample can't be written.
+    fn record(&self, id: u64, sample: &TestSample, payload: &[u8]) {
+        let mut recordings = self.recordings.write();
+        let Some(recorder) = recordings.get_mut(&id) else {
+            return;
+        };
+        if let Err(e) = recorder.write(sample, payload) {
+            tracing::warn!(
+                "Stopped recording subscriber {} to {}: {}",
+                id,
+                recorder.path.display(),
+                e
+            );
+            recordings.remove(&id);
+        }
+    }
+
+    /// Store a liveliness event, dropping the oldest one when full
+    pub fn add_liveliness_event(&self, event: LivelinessEvent) {
+        let mut events = self.liveliness_events.write();
+        events.push_front(event);
+        events.truncate(MAX_LIVELINESS_EVENTS);
+    }
+
+    /// Forget all entities, received samples and liveliness events
+    pub fn clear(&self) {
+        self.entities.write().clear();
+        self.samples.write().clear();
+        self.sequences.write().clear();
+        self.stats.write().clear();
+        self.recordings.write().clear();
+        self.liveliness_events.write().clear();
+    }
+
+    /// Number of samples received by a subscriber since it was declared
+    pub(crate) fn received(&self, id: u64) -> u64 {
+        self.stats.read().get(&id).map_or(0, |stats| stats.received)
+    }
+
+    /// Forget the entities of a runtime whose process exited, keeping received samples
+    pub fn forget_runtime(&self, runtime_id: RuntimeId) {
+        self.entities
+            .write()
+            .retain(|_, entity| entity.runtime_id != runtime_id);
+    }
+}
+
+impl Default for Testing {
+    fn default() -> Self {
+        Self {
+            next_id: Arc::new(AtomicU64::new(0)),
+            next_sample_id: Arc::new(AtomicU64::new(0)),
+            entities: Arc::new(ParkingLotRwLock::new(HashMap::new())),
+            samples: Arc::new(ParkingLotRwLock::new(HashMap::new())),
+            sequences: Arc::new(ParkingLotRwLock::new(HashMap::new())),
+            stats: Arc::new(ParkingLotRwLock::new(HashMap::new())),
+            recordings: Arc::new(ParkingLotRwLock::new(HashMap::new())),
+            liveliness_events: Arc::new(ParkingLotRwLock::new(VecDeque::new())),
+        }
+    }
+}
+
+// ============================================================================
+// Payloads
+// ============================================================================
+
+/// Placeholder of payload templates replaced by the number of the sample, from 0
+pub const SEQ_PLACEHOLDER: &str = "{seq}";
+
+/// Placeholder of payload templates replaced by the time of the sample, in RFC 3339 format
+pub const TIMESTAMP_PLACEHOLDER: &str = "{timestamp}";
+
+/// Payload of the sample number `seq` of a periodic publisher, published at `timestamp`
+pub fn render_payload_template(template: &str, seq: u64, timestamp: DateTime<Utc>) -> String {
+    template
+        .replace(SEQ_PLACEHOLDER, &seq.to_string())
+        .replace(TIMESTAMP_PLACEHOLDER, &timestamp.to_rfc3339())
+}
+
+/// Render a payload in the given format
+fn decode_payload(payload: &[u8], format: PayloadFormat) -> Result<String, String> {
+    match format {
+        PayloadFormat::Utf8 => std::str::from_utf8(payload)
+            .map(str::to_string)
+            .map_err(|e| format!("Payload is not valid UTF-8: {}", e)),
+        PayloadFormat::Json => serde_json::from_slice::<serde_json::Value>(payload)
+            .and_then(|json| serde_json::to_string_pretty(&json))
+            .map_err(|e| format!("Payload is not valid JSON: {}", e)),
+        PayloadFormat::Hex => Ok(payload.iter().map(|byte| format!("{:02x}", byte)).collect()),
+        PayloadFormat::Base64 => Ok(base64::engine::general_purpose::STANDARD.encode(payload)),
+    }
+}
+
+// ============================================================================
+// Sequence numbers
+// ============================================================================
+
+/// Payload of a sequence publisher: the sequence number in decimal,
+/// padded with spaces to `size` bytes
+pub fn sequence_payload(seq: u64, size: usize) -> Vec<u8> {
+    let mut payload = seq.to_string().into_bytes();
+    if payload.len() < size {
+        payload.resize(size, b' ');
+    }
+    payload
+}
+
+/// Sequence number of a payload written by `sequence_payload`
+fn parse_sequence_payload(payload: &str) -> Option<u64> {
+    payload.trim_end_matches(' ').parse().ok()
+}
+
+/// Sequence numbers received on a key
+#[derive(Default)]
+struct KeySequence {
+    stats: SequenceKeyStats,
+    /// Skipped sequence numbers not received yet
+    missing: BTreeSet<u64>,
+}
+
+impl KeySequence {
+    fn check(&mut self, seq: u64) {
+        let stats = &mut self.stats;
+        stats.received += 1;
+        if stats.received == 1 {
+            // The checker may start in the middle of a sequence
+            stats.last_seq = seq;
+        } else if seq > stats.last_seq {
+            if seq > stats.last_seq + 1 {
+                stats.gaps += 1;
+                stats.missing += seq - stats.last_seq - 1;
+                self.missing.extend(stats.last_seq + 1..seq);
+                while self.missing.len() > MAX_TRACKED_MISSING {
+                    self.missing.pop_first();
+                }
+            }
+            stats.last_seq = seq;
+        } else if self.missing.remove(&seq) {
+            stats.reordered += 1;
+            stats.missing -= 1;
+        } else {
+            stats.duplicates += 1;
+        }
+    }
+}
+
+/// Gaps, reorders and duplicates of the sequence numbers received by a subscriber, per key
+#[derive(Default)]
+struct SequenceChecker {
+    keys: BTreeMap<String, KeySequence>,
+    /// Number of samples without a sequence number
+    invalid: u64,
+}
+
+impl SequenceChecker {
+    fn check(&mut self, sample: &TestSample) {
+        let Some(seq) = parse_sequence_payload(&sample.payload) else {
+            self.invalid += 1;
+            return;
+        };
+        self.keys
+            .entry(sample.key_expr.clone())
+            .or_insert_with(|| KeySequence {
+                stats: SequenceKeyStats {
+                    key_expr: sample.key_expr.clone(),
+                    ..Default::default()
+                },
+                missing: BTreeSet::new(),
+            })
+            .check(seq);
+    }
+
+    fn report(&self, id: u64) -> SequenceReport {
+        let keys: Vec<SequenceKeyStats> = self.keys.values().map(|key| key.stats.clone()).collect();
+        SequenceReport {
+            id,
+            received: keys.iter().map(|stats| stats.received).sum(),
+            missing: keys.iter().map(|stats| stats.missing).sum(),
+            reordered: keys.iter().map(|stats| stats.reordered).sum(),
+            duplicates: keys.iter().map(|stats| stats.duplicates).sum(),
+            invalid: self.invalid,
+            keys,
+        }
+    }
+}
+
+/// Declare a test entity on a running runtime and register it
+pub(crate) async fn declare(
+    runtimes: &ZenohRuntimes,
+    testing: &Testing,
+    runtime_id: RuntimeId,
+    keyexpr: String,
+    kind: TestEntityKind,
+    entity: TestEntity,
+) -> Result<TestEntityInfo, SandboxError> {
+    let id = testing.next_id.fetch_add(1, Ordering::Relaxed);
+    runtimes.declare_test_entity(runtime_id, id, entity).await?;
+    let info = TestEntityInfo {
+        id,
+        runtime_id,
+        keyexpr,
+        kind,
+    };
+    testing.entities.write().insert(id, info.clone());
+    Ok(info)
+}
+
+/// Undeclare a test entity and forget everything it received
+pub(crate) async fn undeclare(
+    runtimes: &ZenohRuntimes,
+    testing: &Testing,
+    id: u64,
+) -> Result<(), SandboxError> {
+    let entity = testing.entities.write().remove(&id);
+    testing.samples.write().remove(&id);
+    testing.sequences.write().remove(&id);
+    testing.stats.write().remove(&id);
+    testing.recordings.write().remove(&id);
+    testing
+        .liveliness_events
+        .write()
+        .retain(|event| event.subscriber_id != id);
+    match entity {
+        Some(entity) => runtimes.undeclare_test_entity(entity.runtime_id, id).await,
+        None => Err(format!("Test entity {} not found", id).into()),
+    }
+}
+
+// ============================================================================
+// Reception statistics
+// ============================================================================
+
+/// Samples received by a subscriber during one second
+struct ArrivalBucket {
+    /// Second since the Unix epoch
+    second: i64,
+    messages: u64,
+    bytes: u64,
+    /// Number, sum and sum of squares of the times since the previous sample, in milliseconds
+    intervals: u64,
+    interval_sum: f64,
+    interval_square_sum: f64,
+}
+
+/// Samples received by a subscriber, with the arrivals of the rolling window counted
+/// per second so that memory doesn't grow with the rate
+#[derive(Default)]
+struct ReceptionStats {
+    received: u64,
+    received_bytes: u64,
+    first_received_at: Option<DateTime<Utc>>,
+    last_received_at: Option<DateTime<Utc>>,
+    /// Arrivals of the rolling window, oldest first
+    buckets: VecDeque<ArrivalBucket>,
+}
+
+impl ReceptionStats {
+    fn record(&mut self, received_at: DateTime<Utc>, bytes: usize) {
+        // Samples may be received out of order by runtime callbacks
+        let interval = self.last_received_at.map(|last| {
+            (received_at - last).num_microseconds().unwrap_or(0).max(0) as f64 / 1000.0
+        });
+        self.received += 1;
+        self.received_bytes += bytes as u64;
+        self.first_received_at.get_or_insert(received_at);
+        self.last_received_at = self.last_received_at.max(Some(received_at));
+
+        let second = received_at.timestamp();
+        if self
+            .buckets
+            .back()
+            .is_none_or(|bucket| bucket.second < second)
+        {
+            self.buckets.push_back(ArrivalBucket {
+                second,
+                messages: 0,
+                bytes: 0,
+                intervals: 0,
+                interval_sum: 0.0,
+                interval_square_sum: 0.0,
+            });
+        }
+        let bucket = self.buckets.back_mut().expect("bucket was just pushed");
+        bucket.messages += 1;
+        bucket.bytes += bytes as u64;
+        if let Some(interval) = interval {
+            bucket.intervals += 1;
+            bucket.interval_sum += interval;
+            bucket.interval_square_sum += interval * interval;
+        }
+        self.expire(second);
+    }
+
+    /// Drop the arrivals older than the window ending at `second`
+    fn expire(&mut self, second: i64) {
+        while self
+            .buckets
+            .front()
+            .is_some_and(|bucket| bucket.second <= second - SUBSCRIBER_STATS_WINDOW_SECS)
+        {
+            self.buckets.pop_front();
+        }
+    }
+
+    fn report(&mut self, id: u64, now: DateTime<Utc>) -> SubscriberStats {
+        self.expire(now.timestamp());
+        // Rates of a subscriber declared during the window are over the time since its first sample
+        let window = self
+            .first_received_at
+            .map(|first| (now - first).num_milliseconds() as f64 / 1000.0)
+            .unwrap_or(0.0)
+            .clamp(1.0, SUBSCRIBER_STATS_WINDOW_SECS as f64);
+        let messages: u64 = self.buckets.iter().map(|bucket| bucket.messages).sum();
+        let bytes: u64 = self.buckets.iter().map(|bucket| bucket.bytes).sum();
+        let intervals: u64 = self.buckets.iter().map(|bucket| bucket.intervals).sum();
+        let interval_sum: f64 = self.buckets.iter().map(|bucket| bucket.interval_sum).sum();
+        let interval_square_sum: f64 = self
+            .buckets
+            .iter()
+            .map(|bucket| bucket.interval_square_sum)
+            .sum();
+        let (mean_interval_ms, jitter_ms) = if intervals > 0 {
+            let mean = interval_sum / intervals as f64;
+            let variance = (interval_square_sum / intervals as f64 - mean * mean).max(0.0);
+            (Some(mean), Some(variance.sqrt()))
+        } else {
+            (None, None)
+        };
+        SubscriberStats {
+            id,
+            received: self.received,
+            received_bytes: self.received_bytes,
+            window_secs: SUBSCRIBER_STATS_WINDOW_SECS as u64,
+            messages_per_sec: messages as f64 / window,
+            bytes_per_sec: bytes as f64 / window,
+            mean_interval_ms,
+            jitter_ms,
+            last_received_at: self.last_received_at,
+        }
+    }
+}
+
+// ============================================================================
+// Recordings
+// ============================================================================
+
+// A recording is a JSON Lines file: a `RecordingHeader` followed by one `RecordedSample`
+// per sample received by the recorded subscriber, in the order they were received.
+
+/// First line of a recording
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordingHeader {
+    pub version: u32,
+    /// Key expression of the recorded subscriber
+    pub keyexpr: String,
+    pub started_at: DateTime<Utc>,
+}
+
+/// A sample of a recording
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordedSample {
+    /// When the runtime received the sample
+    pub received_at: DateTime<Utc>,
+    pub key_expr: String,
+    /// Kind of the sample: put or delete
+    pub kind: String,
+    pub encoding: String,
+    /// Payload, in standard base64
+    pub payload: String,
+    /// Timestamp attached by the publisher, if any
+    pub timestamp: Option<String>,
+}
+
+impl RecordedSample {
+    /// Raw payload of the sample
+    pub fn payload_bytes(&self) -> Result<Vec<u8>, String> {
+        base64::engine::general_purpose::STANDARD
+            .decode(&self.payload)
+            .map_err(|e| format!("Invalid payload: {}", e))
+    }
+}
+
+/// Parse a recording written by `start_recording`
+pub fn parse_recording(contents: &str) -> Result<(RecordingHeader, Vec<RecordedSample>), String> {
+    let mut lines = contents
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty());
+    let (_, header) = lines.next().ok_or_else(|| "Empty recording".to_string())?;
+    let header: RecordingHeader =
+        serde_json::from_str(header).map_err(|e| format!("Invalid recording header: {}", e))?;
+    if header.version > RECORDING_FORMAT_VERSION {
+        return Err(format!(
+            "Recording version {} is not supported (expected at most {})",
+            header.version, RECORDING_FORMAT_VERSION
+        ));
+    }
+    let samples = lines
+        .map(|(index, line)| {
+            let sample: RecordedSample = serde_json::from_str(line)
+                .map_err(|e| format!("Invalid sample at line {}: {}", index + 1, e))?;
+            sample
+                .payload_bytes()
+                .map_err(|e| format!("Invalid sample at line {}: {}", index + 1, e))?;
+            Ok(sample)
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+    Ok((header, samples))
+}
+
+/// Recording in progress of the samples received by a subscriber
+struct SampleRecorder {
+    path: PathBuf,
+    writer: std::io::BufWriter<std::fs::File>,
+    /// Number of samples written
+    samples: u64,
+}
+
+impl SampleRecorder {
+    /// Create the recording at `path`, replacing any existing file
+    fn create(path: PathBuf, keyexpr: String) -> Result<Self, String> {
+        let file = std::fs::File::create(&path)
+            .map_err(|e| format!("Failed to create {}: {}", path.display(), e))?;
+        let mut recorder = Self {
+            path,
+            writer: std::io::BufWriter::new(file),
+            samples: 0,
+        };
+        let header = RecordingHeader {
+            version: RECORDING_FORMAT_VERSION,
+            keyexpr,
+            started_at: Utc::now(),
+        };
+        recorder
+            .write_line(&header)
+            .map_err(|e| format!("Failed to write {}: {}", recorder.path.display(), e))?;
+        Ok(recorder)
+    }
+
+    fn write(&mut self, sample: &TestSample, payload: &[u8]) -> std::io::Result<()> {
+        let recorded = RecordedSample {
+            received_at: sample.received_at,
+            key_expr: sample.key_expr.clone(),
+            kind: sample.kind.clone(),
+            encoding: sample.encoding.clone(),
+            payload: base64::engine::general_purpose::STANDARD.encode(payload),
+            timestamp: sample.timestamp.clone(),
+        };
+        self.write_line(&recorded)?;
+        self.samples += 1;
+        Ok(())
+    }
+
+    fn write_line(&mut self, value: &impl Serialize) -> std::io::Result<()> {
+        serde_json::to_writer(&mut self.writer, value)?;
+        self.writer.write_all(b"\n")
+    }
+}
+
+// ============================================================================
+// Key expressions
+// ============================================================================
+
+impl TestEntityInfo {
+    /// Key expression actually used by the entity.
+    /// Sequence publishers put on the children of their key expression.
+    fn used_keyexpr(&self) -> String {
+        match self.kind {
+            TestEntityKind::SequencePublisher { .. } => format!("{}/*", self.keyexpr),
+            _ => self.keyexpr.clone(),
+        }
+    }
+
+    fn role(&self) -> KeyExprRole {
+        match self.kind {
+            TestEntityKind::Publisher { .. }
+            | TestEntityKind::SequencePublisher { .. }
+            | TestEntityKind::PeriodicPublisher { .. }
+            | TestEntityKind::Replay { .. } => KeyExprRole::Producer,
+            TestEntityKind::Subscriber | TestEntityKind::SequenceChecker => KeyExprRole::Consumer,
+            TestEntityKind::Queryable { .. } => KeyExprRole::Queryable,
+            TestEntityKind::LivelinessToken => KeyExprRole::LivelinessToken,
+            TestEntityKind::LivelinessSubscriber { .. } => KeyExprRole::LivelinessSubscriber,
+        }
+    }
+}
+
+/// Group test entities by the key expression they use, finding the intersecting
+/// key expressions and the producers or consumers without counterpart
+fn active_keyexprs(entities: &[TestEntityInfo]) -> Vec<ActiveKeyExpr> {
+    let mut users: BTreeMap<String, Vec<KeyExprUser>> = BTreeMap::new();
+    for entity in entities {
+        users
+            .entry(entity.used_keyexpr())
+            .or_default()
+            .push(KeyExprUser {
+                entity_id: entity.id,
+                runtime_id: entity.runtime_id,
+                role: entity.role(),
+            });
+    }
+    // Declared key expressions are valid, but don't fail on a bad one
+    let keyexprs: Vec<(String, Option<KeyExpr<'static>>, Vec<KeyExprUser>)> = users
+        .into_iter()
+        .map(|(keyexpr, users)| {
+            let parsed = KeyExpr::try_from(keyexpr.clone()).ok();
+            (keyexpr, parsed, users)
+        })
+        .collect();
+    let intersects = |a: usize, b: usize| match (&keyexprs[a].1, &keyexprs[b].1) {
+        (Some(a), Some(b)) => a.intersects(b),
+        _ => false,
+    };
+
+    (0..keyexprs.len())
+        .map(|index| {
+            let (keyexpr, _, own_users) = &keyexprs[index];
+            let intersecting: Vec<usize> = (0..keyexprs.len())
+                .filter(|other| *other != index && intersects(index, *other))
+                .collect();
+            // Whether this key expression or an intersecting one has users with `role`
+            let matched = |role| {
+                std::iter::once(index)
+                    .chain(intersecting.iter().copied())
+                    .any(|other| keyexprs[other].2.iter().any(|user| user.role == role))
+            };
+            let has_role = |role| own_users.iter().any(|user| user.role == role);
+            let orphaned = (has_role(KeyExprRole::Producer) && !matched(KeyExprRole::Consumer))
+                || (has_role(KeyExprRole::Consumer) && !matched(KeyExprRole::Producer));
+            ActiveKeyExpr {
+                keyexpr: keyexpr.clone(),
+                users: own_users.clone(),
+                intersecting: intersecting
+                    .iter()
+                    .map(|other| keyexprs[*other].0.clone())
+                    .collect(),
+                orphaned,
+            }
+        })
+        .collect()
+}
+
+/// Convert a reply received by the runtime for display
+fn to_test_reply(reply: QueryReply) -> TestReply {
+    TestReply {
+        key_expr: reply.key_expr,
+        encoding: reply.encoding,
+        payload: String::from_utf8_lossy(&reply.payload).to_string(),
+        payload_len: reply.payload.len(),
+        is_error: reply.is_error,
+    }
+}
+
+// ============================================================================
+// Tauri commands
+// ============================================================================
+
+/// Declare a publisher on a running runtime, putting `payload` on `keyexpr` every `period_ms`
+#[tauri::command]
+#[tracing::instrument(skip(runtimes, testing, payload))]
+pub async fn declare_test_publisher(
+    runtime_id: RuntimeId,
+    keyexpr: String,
+    payload: String,
+    period_ms: u64,
+    runtimes: State<'_, ZenohRuntimes>,
+    testing: State<'_, Testing>,
+) -> Result<TestEntityInfo, SandboxError> {
+    let entity = TestEntity::Publisher {
+        keyexpr: keyexpr.clone(),
+        payload: payload.clone().into_bytes(),
+        period_ms,
+    };
+    let kind = TestEntityKind::Publisher { payload, period_ms };
+    declare(&runtimes, &testing, runtime_id, keyexpr, kind, entity).await
+}
+
+/// Start a publisher on a running runtime, putting `burst` samples on `keyexpr` every
+/// `period_ms`, give or take a random delay of up to `jitter_ms`. `{seq}` and `{timestamp}`
+/// in `payload` are replaced by the number and time of each sample.
+/// The publisher runs until stopped with `stop_periodic_publisher` or the runtime stops.
+#[tauri::command]
+#[tracing::instrument(skip(runtimes, testing, payload))]
+pub async fn start_periodic_publisher(
+    runtime_id: RuntimeId,
+    keyexpr: String,
+    payload: String,
+    period_ms: u64,
+    jitter_ms: Option<u64>,
+    burst: Option<u32>,
+    runtimes: State<'_, ZenohRuntimes>,
+    testing: State<'_, Testing>,
+) -> Result<TestEntityInfo, SandboxError> {
+    let jitter_ms = jitter_ms.unwrap_or(0);
+    let burst = burst.unwrap_or(1);
+    if burst == 0 {
+        return Err("Burst must publish at least one sample".into());
+    }
+    if jitter_ms > period_ms {
+        return Err(format!(
+            "Jitter of {} ms exceeds the period of {} ms",
+            jitter_ms, period_ms
+        )
+        .into());
+    }
+    let entity = TestEntity::PeriodicPublisher {
+        keyexpr: keyexpr.clone(),
+        payload_template: payload.clone(),
+        period_ms,
+        jitter_ms,
+        burst,
+    };
+    let kind = TestEntityKind::PeriodicPublisher {
+        payload_template: payload,
+        period_ms,
+        jitter_ms,
+        burst,
+    };
+    declare(&runtimes, &testing, runtime_id, keyexpr, kind, entity).await
+}
+
+/// Stop a publisher started with `start_periodic_publisher`
+#[tauri::command]
+#[tracing::instrument(skip(runtimes, testing))]
+pub async fn stop_periodic_publisher(
+    id: u64,
+    runtimes: State<'_, ZenohRuntimes>,
+    testing: State<'_, Testing>,
+) -> Result<(), SandboxError> {
+    let entity = {
+        let mut entities = testing.entities.write();
+        match entities.get(&id) {
+            Some(entity) if matches!(entity.kind, TestEntityKind::PeriodicPublisher { .. }) => {
+                entities.remove(&id)
+            }
+            Some(_) => return Err(format!("Test entity {} is not a periodic publisher", id).into()),
+            None => None,
+        }
+    };
+    match entity {
+        Some(entity) => runtimes.undeclare_test_entity(entity.runtime_id, id).await,
+        None => Err(format!("Periodic publisher {} not found", id).into()),
+    }
+}
+
+/// Declare a publisher on a running runtime, putting on each of `<keyexpr>/0` to
+/// `<keyexpr>/<keys - 1>` the next sequence number of the key every `period_ms`,
+/// padded to `payload_size` bytes. Pair it with `declare_sequence_checker` to measure
+/// losses, reorders and duplicates under given reliability and congestion control settings.
+#[tauri::command]
+#[tracing::instrument(skip(runtimes, testing))]
+#[allow(clippy::too_many_arguments)]
+pub async fn declare_sequence_publisher(
+    runtime_id: RuntimeId,
+    keyexpr: String,
+    keys: u32,
+    period_ms: u64,
+    payload_size: Option<usize>,
+    block: Option<bool>,
+    runtimes: State<'_, ZenohRuntimes>,
+    testing: State<'_, Testing>,
+) -> Result<TestEntityInfo, SandboxError> {
+    if keys == 0 {
+        return Err("A sequence publisher needs at least one key".into());
+    }
+    let payload_size = payload_size.unwrap_or(0);
+    let block = block.unwrap_or(false);
+    let entity = TestEntity::SequencePublisher {
+        keyexpr: keyexpr.clone(),
+        keys,
+        period_ms,
+        payload_size,
+        block,
+    };
+    let kind = TestEntityKind::SequencePublisher {
+        keys,
+        period_ms,
+        payload_size,
+        block,
+    };
+    declare(&runtimes, &testing, runtime_id, keyexpr, kind, entity).await
+}
+
+/// Declare a subscriber on a running runtime, storing the samples received on `keyexpr`
+#[tauri::command]
+#[tracing::instrument(skip(runtimes, testing))]
+pub async fn declare_test_subscriber(
+    runtime_id: RuntimeId,
+    keyexpr: String,
+    runtimes: State<'_, ZenohRuntimes>,
+    testing: State<'_, Testing>,
+) -> Result<TestEntityInfo, SandboxError> {
+    let entity = TestEntity::Subscriber {
+        keyexpr: keyexpr.clone(),
+    };
+    declare(
+        &runtimes,
+        &testing,
+        runtime_id,
+        keyexpr,
+        TestEntityKind::Subscriber,
+        entity,
+    )
+    .await
+}
+
+/// Declare a subscriber on a running runtime checking the sequence numbers of the samples
+/// received on `keyexpr` per key, as published by `declare_sequence_publisher`.
+/// Samples are not stored, see `get_sequence_report`.
+#[tauri::command]
+#[tracing::instrument(skip(runtimes, testing))]
+pub async fn declare_sequence_checker(
+    runtime_id: RuntimeId,
+    keyexpr: String,
+    runtimes: State<'_, ZenohRuntimes>,
+    testing: State<'_, Testing>,
+) -> Result<TestEntityInfo, SandboxError> {
+    let entity = TestEntity::Subscriber {
+        keyexpr: keyexpr.clone(),
+    };
+    let info = declare(
+        &runtimes,
+        &testing,
+        runtime_id,
+        keyexpr,
+        TestEntityKind::SequenceChecker,
+        entity,
+    )
+    .await?;
+    // Samples received before the checker was registered were stored, the checker
+    // starts from the next one
+    testing
+        .sequences
+        .write()
+        .insert(info.id, SequenceChecker::default());
+    testing.samples.write().remove(&info.id);
+    Ok(info)
+}
+
+/// Declare a queryable on a running runtime, replying `reply_payload` to queries on `keyexpr`.
+/// Received queries are stored like samples, see `get_test_samples`.
+#[tauri::command]
+#[tracing::instrument(skip(runtimes, testing, reply_payload))]
+pub async fn declare_test_queryable(
+    runtime_id: RuntimeId,
+    keyexpr: String,
+    reply_payload: String,
+    runtimes: State<'_, ZenohRuntimes>,
+    testing: State<'_, Testing>,
+) -> Result<TestEntityInfo, SandboxError> {
+    let entity = TestEntity::Queryable {
+        keyexpr: keyexpr.clone(),
+        reply_payload: reply_payload.clone().into_bytes(),
+    };
+    let kind = TestEntityKind::Queryable { reply_payload };
+    declare(&runtimes, &testing, runtime_id, keyexpr, kind, entity).await
+}
+
+/// Declare a liveliness token on `keyexpr` on a running runtime, alive until undeclared
+/// or the runtime stops
+#[tauri::command]
+#[tracing::instrument(skip(runtimes, testing))]
+pub async fn declare_liveliness_token(
+    runtime_id: RuntimeId,
+    keyexpr: String,
+    runtimes: State<'_, ZenohRuntimes>,
+    testing: State<'_, Testing>,
+) -> Result<TestEntityInfo, SandboxError> {
+    let entity = TestEntity::LivelinessToken {
+        keyexpr: keyexpr.clone(),
+    };
+    declare(
+        &runtimes,
+        &testing,
+        runtime_id,
+        keyexpr,
+        TestEntityKind::LivelinessToken,
+        entity,
+    )
+    .await
+}
+
+/// Subscribe a running runtime to the liveliness tokens matching `keyexpr`. Tokens
+/// appearing and disappearing are emitted as `liveliness-event` events and stored,
+/// see `get_liveliness_events`. Unless `history` is false, the tokens already alive
+/// are reported first.
+#[tauri::command]
+#[tracing::instrument(skip(runtimes, testing))]
+pub async fn subscribe_liveliness(
+    runtime_id: RuntimeId,
+    keyexpr: String,
+    history: Option<bool>,
+    runtimes: State<'_, ZenohRuntimes>,
+    testing: State<'_, Testing>,
+) -> Result<TestEntityInfo, SandboxError> {
+    let history = history.unwrap_or(true);
+    let entity = TestEntity::LivelinessSubscriber {
+        keyexpr: keyexpr.clone(),
+        history,
+    };
+    let kind = TestEntityKind::LivelinessSubscriber { history };
+    declare(&runtimes, &testing, runtime_id, keyexpr, kind, entity).await
+}
+
+/// Get the liveliness events received by liveliness subscribers, most recent first.
+/// Only the events of `subscriber_id` are returned if given.
+#[tauri::command]
+#[tracing::instrument(skip(testing))]
+pub async fn get_liveliness_events(
+    subscriber_id: Option<u64>,
+    testing: State<'_, Testing>,
+) -> Result<Vec<LivelinessEvent>, SandboxError> {
+    Ok(testing
+        .liveliness_events
+        .read()
+        .iter()
+        .filter(|event| subscriber_id.is_none_or(|id| event.subscriber_id == id))
+        .cloned()
+        .collect())
+}
+
+/// Run a `get` on `selector` from a running runtime and collect the replies
+/// received within `timeout_ms`
+#[tauri::command]
+#[tracing::instrument(skip(runtimes))]
+pub async fn run_test_get(
+    runtime_id: RuntimeId,
+    selector: String,
+    timeout_ms: Option<u64>,
+    runtimes: State<'_, ZenohRuntimes>,
+) -> Result<TestGetResult, SandboxError> {
+    let timeout = timeout_ms
+        .map(Duration::from_millis)
+        .unwrap_or(DEFAULT_TEST_GET_TIMEOUT);
+    let start = Instant::now();
+    let replies = runtimes.query(runtime_id, selector, timeout).await?;
+    Ok(TestGetResult {
+        elapsed_ms: start.elapsed().as_millis() as u64,
+        replies: replies.into_iter().map(to_test_reply).collect(),
+    })
+}
+
+/// Publish the bytes of a file through a running runtime, with `encoding` if given,
+/// to test with realistic payloads such as images or protobuf dumps.
+/// Returns the number of bytes published.
+#[tauri::command]
+#[tracing::instrument(skip(runtimes))]
+pub async fn publish_file(
+    runtime_id: RuntimeId,
+    keyexpr: String,
+    path: String,
+    encoding: Option<String>,
+    runtimes: State<'_, ZenohRuntimes>,
+) -> Result<usize, SandboxError> {
+    KeyExpr::try_from(keyexpr.as_str())
+        .map_err(|e| format!("Invalid key expression {}: {}", keyexpr, e))?;
+    // The runtime process reads the file, from its own working directory
+    let path = std::path::absolute(&path).map_err(|e| format!("Invalid path {}: {}", path, e))?;
+    let metadata = tokio::fs::metadata(&path)
+        .await
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    if !metadata.is_file() {
+        return Err(format!("{} is not a file", path.display()).into());
+    }
+    runtimes
+        .publish_file(runtime_id, keyexpr, path, encoding)
+        .await
+}
+
+/// Undeclare a test entity and drop its received samples, sequence statistics
+/// or liveliness events
+#[tauri::command]
+#[tracing::instrument(skip(runtimes, testing))]
+pub async fn undeclare_test_entity(
+    id: u64,
+    runtimes: State<'_, ZenohRuntimes>,
+    testing: State<'_, Testing>,
+) -> Result<(), SandboxError> {
+    undeclare(&runtimes, &testing, id).await
+}
+
+/// List the test entities of running runtimes, ordered by id
+#[tauri::command]
+#[tracing::instrument(skip(testing))]
+pub async fn list_test_entities(
+    testing: State<'_, Testing>,
+) -> Result<Vec<TestEntityInfo>, SandboxError> {
+    let mut entities: Vec<TestEntityInfo> = testing.entities.read().values().cloned().collect();
+    entities.sort_by_key(|entity| entity.id);
+    Ok(entities)
+}
+
+/// List the key expressions used by the test entities of running runtimes, ordered by
+/// key expression, with the runtimes producing or consuming them and the other key
+/// expressions they intersect. Producers reaching no consumer and consumers matching
+/// no producer are flagged as orphaned.
+#[tauri::command]
+#[tracing::instrument(skip(testing))]
+pub async fn list_active_keyexprs(
+    testing: State<'_, Testing>,
+) -> Result<Vec<ActiveKeyExpr>, SandboxError> {
+    let mut entities: Vec<TestEntityInfo> = testing.entities.read().values().cloned().collect();
+    entities.sort_by_key(|entity| entity.id);
+    Ok(active_keyexprs(&entities))
+}
+
+/// Get a page of samples received by a test subscriber.
+/// Page 0 returns the most recent samples.
+#[tauri::command]
+#[tracing::instrument(skip(testing))]
+pub async fn get_test_samples(
+    id: u64,
+    page: usize,
+    testing: State<'_, Testing>,
+) -> Result<Vec<TestSample>, SandboxError> {
+    let samples = testing.samples.read();
+    Ok(samples
+        .get(&id)
+        .map(|buffer| {
+            buffer
+                .iter()
+                .skip(page * TEST_SAMPLES_PAGE_SIZE)
+                .take(TEST_SAMPLES_PAGE_SIZE)
+                .map(|received| received.sample.clone())
+                .collect()
+        })
+        .unwrap_or_default())
+}
+
+/// Render the payload of a sample received by a test subscriber or queryable,
+/// to inspect binary or JSON payloads
+#[tauri::command]
+#[tracing::instrument(skip(testing))]
+pub async fn decode_sample(
+    sample_id: u64,
+    r#as: PayloadFormat,
+    testing: State<'_, Testing>,
+) -> Result<DecodedPayload, SandboxError> {
+    let samples = testing.samples.read();
+    let received = samples
+        .values()
+        .flatten()
+        .find(|received| received.sample.sample_id == sample_id)
+        .ok_or_else(|| format!("Sample {} not found", sample_id))?;
+    let text = decode_payload(&received.payload, r#as)
+        .map_err(|e| format!("Sample {}: {}", sample_id, e))?;
+    Ok(DecodedPayload {
+        sample_id,
+        encoding: received.sample.encoding.clone(),
+        format: r#as,
+        text,
+    })
+}
+
+/// Record the samples received by a test subscriber to `path`, with their key expression,
+/// encoding, payload and reception time, until `stop_recording` is called or the subscriber
+/// is undeclared. An existing file is replaced.
+#[tauri::command]
+#[tracing::instrument(skip(testing))]
+pub async fn start_recording(
+    subscriber_id: u64,
+    path: String,
+    testing: State<'_, Testing>,
+) -> Result<(), SandboxError> {
+    let keyexpr = match testing.entities.read().get(&subscriber_id) {
+        Some(entity) if matches!(entity.kind, TestEntityKind::Subscriber) => entity.keyexpr.clone(),
+        Some(_) => return Err(format!("Test entity {} is not a subscriber", subscriber_id).into()),
+        None => return Err(format!("Subscriber {} not found", subscriber_id).into()),
+    };
+    if testing.recordings.read().contains_key(&subscriber_id) {
+        return Err(format!("Subscriber {} is already recorded", subscriber_id).into());
+    }
+    let path = std::path::absolute(&path).map_err(|e| format!("Invalid path {}: {}", path, e))?;
+    let recorder = SampleRecorder::create(path, keyexpr)?;
+    tracing::info!(
+        "Recording subscriber {} to {}",
+        subscriber_id,
+        recorder.path.display()
+    );
+    testing.recordings.write().insert(subscriber_id, recorder);
+    Ok(())
+}
+
+/// Stop recording a test subscriber.
+/// Returns the number of samples recorded.
+#[tauri::command]
+#[tracing::instrument(skip(testing))]
+pub async fn stop_recording(
+    subscriber_id: u64,
+    testing: State<'_, Testing>,
+) -> Result<u64, SandboxError> {
+    let mut recorder = testing
+        .recordings
+        .write()
+        .remove(&subscriber_id)
+        .ok_or_else(|| format!("Subscriber {} is not recorded", subscriber_id))?;
+    recorder
+        .writer
+        .flush()
+        .map_err(|e| format!("Failed to write {}: {}", recorder.path.display(), e))?;
+    tracing::info!(
+        "Recorded {} samples of subscriber {} to {}",
+        recorder.samples,
+        subscriber_id,
+        recorder.path.display()
+    );
+    Ok(recorder.samples)
+}
+
+/// Republish through a running runtime the samples of a recording written by
+/// `start_recording`, preserving their relative timing `speed` times faster (1 by default).
+/// The replay can be stopped early by undeclaring the returned entity.
+#[tauri::command]
+#[tracing::instrument(skip(runtimes, testing))]
+pub async fn replay_recording(
+    runtime_id: RuntimeId,
+    path: String,
+    speed: Option<f64>,
+    runtimes: State<'_, ZenohRuntimes>,
+    testing: State<'_, Testing>,
+) -> Result<TestEntityInfo, SandboxError> {
+    let speed = speed.unwrap_or(1.0);
+    if !speed.is_finite() || speed <= 0.0 {
+        return Err(format!("Invalid replay speed {}", speed).into());
+    }
+    // The runtime process reads the recording, check it first for a clear error
+    let path = std::path::absolute(&path).map_err(|e| format!("Invalid path {}: {}", path, e))?;
+    let contents = tokio::fs::read_to_string(&path)
+        .await
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    let (header, samples) =
+        parse_recording(&contents).map_err(|e| format!("{}: {}", path.display(), e))?;
+    let path = path.to_string_lossy().to_string();
+    let entity = TestEntity::Replay {
+        path: path.clone(),
+        speed,
+    };
+    let kind = TestEntityKind::Replay {
+        path,
+        speed,
+        samples: samples.len(),
+    };
+    declare(
+        &runtimes,
+        &testing,
+        runtime_id,
+        header.keyexpr,
+        kind,
+        entity,
+    )
+    .await
+}
+
+/// Get the rates, inter-arrival times and last reception time of the samples received by
+/// a test subscriber or sequence checker, over a rolling window
+#[tauri::command]
+#[tracing::instrument(skip(testing))]
+pub async fn get_subscriber_stats(
+    subscriber_id: u64,
+    testing: State<'_, Testing>,
+) -> Result<SubscriberStats, SandboxError> {
+    match testing.entities.read().get(&subscriber_id) {
+        Some(entity)
+            if matches!(
+                entity.kind,
+                TestEntityKind::Subscriber | TestEntityKind::SequenceChecker
+            ) => {}
+        Some(_) => {
+            return Err(format!("Test entity {} is not a subscriber", subscriber_id).into());
+        }
+        None => return Err(format!("Subscriber {} not found", subscriber_id).into()),
+    }
+    Ok(testing
+        .stats
+        .write()
+        .entry(subscriber_id)
+        .or_default()
+        .report(subscriber_id, Utc::now()))
+}
+
+/// Get the gaps, reorders and duplicates of the sequence numbers received by a sequence checker
+#[tauri::command]
+#[tracing::instrument(skip(testing))]
+pub async fn get_sequence_report(
+    id: u64,
+    testing: State<'_, Testing>,
+) -> Result<SequenceReport, SandboxError> {
+    testing
+        .sequences
+        .read()
+        .get(&id)
+        .map(|checker| checker.report(id))
+        .ok_or_else(|| format!("Sequence checker {} not found", id).into())
+}
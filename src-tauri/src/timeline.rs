@@ -0,0 +1,174 @@
+use std::{
+    collections::VecDeque,
+    sync::{
+        Arc,
+        atomic::{AtomicU64, Ordering},
+    },
+};
+
+use chrono::Utc;
+use parking_lot::RwLock as ParkingLotRwLock;
+
+use crate::{
+    RuntimeId,
+    events::{RuntimeEvent, RuntimeEvents},
+    logs::LogStorage,
+    ts::{
+        config::{ZenohConfigJson, json_diff, json_diff_paths},
+        log::LogEntryLevel,
+        runtime::RuntimeConfigChangedEvent,
+        timeline::{ConfigChangeSource, TimelineEvent, TimelineEventKind},
+    },
+};
+
+// ============================================================================
+// Constants
+// ============================================================================
+
+/// Number of timeline events per page
+pub const TIMELINE_PAGE_SIZE: usize = 100;
+
+/// Maximum number of events to keep in the timeline
+const MAX_TIMELINE_EVENTS: usize = 10_000;
+
+// ============================================================================
+// Timeline
+// ============================================================================
+
+/// Global, sandbox-wide timeline of annotated events
+#[derive(Clone)]
+pub struct Timeline {
+    /// Maximum number of events to keep
+    max_events: usize,
+    /// Events, oldest first
+    events: Arc<ParkingLotRwLock<VecDeque<TimelineEvent>>>,
+    /// Identifier of the next event
+    next_id: Arc<AtomicU64>,
+    /// Channel on which config changes are published as runtime events
+    runtime_events: RuntimeEvents,
+}
+
+impl Timeline {
+    pub fn new(max_events: usize) -> Self {
+        Self {
+            max_events,
+            events: Arc::new(ParkingLotRwLock::new(VecDeque::new())),
+            next_id: Arc::new(AtomicU64::new(0)),
+            runtime_events: RuntimeEvents::default(),
+        }
+    }
+
+    /// Publish the config changes annotated in the timeline on `events`
+    pub fn with_events(mut self, events: RuntimeEvents) -> Self {
+        self.runtime_events = events;
+        self
+    }
+
+    /// Add an event to the timeline, timestamped now. Returns its identifier.
+    pub fn add_event(
+        &self,
+        runtime_id: Option<RuntimeId>,
+        summary: String,
+        kind: TimelineEventKind,
+    ) -> u64 {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let event = TimelineEvent {
+            id,
+            timestamp: Utc::now(),
+            runtime_id,
+            summary,
+            kind,
+        };
+        let mut events = self.events.write();
+        events.push_back(event);
+        while events.len() > self.max_events {
+            events.pop_front();
+        }
+        id
+    }
+
+    /// Get the events with the given identifiers that are still in the timeline,
+    /// most recent first
+    pub fn get_events(&self, ids: &[u64]) -> Vec<TimelineEvent> {
+        self.events
+            .read()
+            .iter()
+            .rev()
+            .filter(|event| ids.contains(&event.id))
+            .cloned()
+            .collect()
+    }
+
+    /// Remove all events
+    pub fn clear(&self) {
+        self.events.write().clear();
+    }
+
+    /// Get a page of events, optionally restricted to one runtime.
+    /// Page 0 returns the most recent events.
+    pub fn get_page(&self, runtime_id: Option<RuntimeId>, page: usize) -> Vec<TimelineEvent> {
+        let events = self.events.read();
+        events
+            .iter()
+            .rev()
+            .filter(|event| runtime_id.is_none() || event.runtime_id == runtime_id)
+            .skip(page * TIMELINE_PAGE_SIZE)
+            .take(TIMELINE_PAGE_SIZE)
+            .cloned()
+            .collect()
+    }
+
+    /// Record a config change of a runtime.
+    /// The diff summary is also written to the runtime's log stream when `logs` is provided.
+    /// Returns the diff between the old and new config.
+    pub fn annotate_config_change(
+        &self,
+        runtime_id: RuntimeId,
+        source: ConfigChangeSource,
+        old: &ZenohConfigJson,
+        new: &ZenohConfigJson,
+        logs: Option<&LogStorage>,
+    ) -> serde_json::Value {
+        let diff = json_diff(old.as_json(), new.as_json());
+        let paths = json_diff_paths(&diff);
+        let source_name = match source {
+            ConfigChangeSource::Patch => "patch",
+            ConfigChangeSource::Restart => "restart",
+            ConfigChangeSource::LiveUpdate => "live update",
+            ConfigChangeSource::Revert => "revert",
+            ConfigChangeSource::Template => "template",
+        };
+        let summary = if paths.is_empty() {
+            format!("Config {source_name}: no changes")
+        } else {
+            format!("Config {source_name}: {}", paths.join(", "))
+        };
+
+        if let Some(logs) = logs {
+            logs.add_sandbox_log(runtime_id, LogEntryLevel::INFO, summary.clone());
+        }
+
+        self.add_event(
+            Some(runtime_id),
+            summary,
+            TimelineEventKind::ConfigChange {
+                source,
+                diff: diff.clone(),
+            },
+        );
+        self.runtime_events
+            .publish(RuntimeEvent::ConfigChanged(RuntimeConfigChangedEvent {
+                runtime_id,
+                source,
+                diff: diff.clone(),
+            }));
+
+        diff
+    }
+}
+
+impl Default for Timeline {
+    fn default() -> Self {
+        Self::new(MAX_TIMELINE_EVENTS)
+    }
+}
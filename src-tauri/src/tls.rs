@@ -0,0 +1,282 @@
+use std::path::{Path, PathBuf};
+
+use rcgen::{
+    BasicConstraints, CertificateParams, DnType, ExtendedKeyUsagePurpose, IsCa, Issuer, KeyPair,
+    KeyUsagePurpose,
+};
+use serde_json::Value as JsonValue;
+use tauri::{AppHandle, Manager};
+
+use crate::{
+    RuntimeId, ZenohRuntimes,
+    error::SandboxError,
+    logs::LogStorage,
+    timeline::Timeline,
+    ts::{
+        config::{ZenohConfigJson, json_set_path},
+        timeline::ConfigChangeSource,
+        tls::{TlsFiles, TlsRole},
+    },
+};
+
+// ============================================================================
+// Constants
+// ============================================================================
+
+/// Directory of the certificates, in the app data directory
+pub const TLS_DIR_NAME: &str = "tls";
+
+/// Common name of the certificate authority of the sandbox
+const CA_COMMON_NAME: &str = "Zenoh Sandbox CA";
+
+/// Names the certificates of runtimes are valid for, runtimes listening on the local host
+const RUNTIME_SUBJECT_ALT_NAMES: &[&str] = &["localhost", "127.0.0.1", "::1"];
+
+/// Config path of the TLS settings of the links
+const TLS_CONFIG_PATH: &str = "transport/link/tls";
+
+// ============================================================================
+// Certificates
+// ============================================================================
+//
+// A self-signed certificate authority is generated once and signs a certificate per runtime,
+// so that runtimes trusting the authority accept each other. Only the key of the authority
+// is needed to sign, its certificate parameters being rebuilt the same way each time.
+
+/// Certificate authority and runtime certificates stored in a directory
+pub struct TlsStore {
+    dir: PathBuf,
+    /// Serializes the generation of certificates
+    lock: tokio::sync::Mutex<()>,
+}
+
+/// Parameters of the certificate authority
+fn ca_params() -> Result<CertificateParams, rcgen::Error> {
+    let mut params = CertificateParams::new(Vec::<String>::new())?;
+    params
+        .distinguished_name
+        .push(DnType::CommonName, CA_COMMON_NAME);
+    params.is_ca = IsCa::Ca(BasicConstraints::Unconstrained);
+    params.key_usages = vec![
+        KeyUsagePurpose::KeyCertSign,
+        KeyUsagePurpose::CrlSign,
+        KeyUsagePurpose::DigitalSignature,
+    ];
+    Ok(params)
+}
+
+/// Parameters of the certificate of a runtime, valid as server and client
+fn runtime_params(runtime_id: RuntimeId) -> Result<CertificateParams, rcgen::Error> {
+    let names: Vec<String> = RUNTIME_SUBJECT_ALT_NAMES
+        .iter()
+        .map(|name| name.to_string())
+        .collect();
+    let mut params = CertificateParams::new(names)?;
+    params
+        .distinguished_name
+        .push(DnType::CommonName, format!("runtime-{}", runtime_id));
+    params.key_usages = vec![
+        KeyUsagePurpose::DigitalSignature,
+        KeyUsagePurpose::KeyEncipherment,
+    ];
+    params.extended_key_usages = vec![
+        ExtendedKeyUsagePurpose::ServerAuth,
+        ExtendedKeyUsagePurpose::ClientAuth,
+    ];
+    Ok(params)
+}
+
+/// Write a file, readable only by the user for private keys
+async fn write_file(path: &Path, contents: String, private: bool) -> Result<(), String> {
+    tokio::fs::write(path, contents)
+        .await
+        .map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+    #[cfg(unix)]
+    if private {
+        use std::os::unix::fs::PermissionsExt;
+        tokio::fs::set_permissions(path, std::fs::Permissions::from_mode(0o600))
+            .await
+            .map_err(|e| format!("Failed to restrict access to {}: {}", path.display(), e))?;
+    }
+    #[cfg(not(unix))]
+    let _ = private;
+    Ok(())
+}
+
+impl TlsStore {
+    pub fn new(dir: PathBuf) -> Self {
+        Self {
+            dir,
+            lock: tokio::sync::Mutex::new(()),
+        }
+    }
+
+    fn ca_certificate_path(&self) -> PathBuf {
+        self.dir.join("ca.pem")
+    }
+
+    fn ca_key_path(&self) -> PathBuf {
+        self.dir.join("ca.key")
+    }
+
+    /// Key of the certificate authority, generated with its certificate if missing
+    async fn ca_key(&self) -> Result<KeyPair, String> {
+        let key_path = self.ca_key_path();
+        if tokio::fs::try_exists(&key_path).await.unwrap_or(false)
+            && tokio::fs::try_exists(self.ca_certificate_path())
+                .await
+                .unwrap_or(false)
+        {
+            let pem = tokio::fs::read_to_string(&key_path)
+                .await
+                .map_err(|e| format!("Failed to read {}: {}", key_path.display(), e))?;
+            return KeyPair::from_pem(&pem)
+                .map_err(|e| format!("Invalid key in {}: {}", key_path.display(), e));
+        }
+
+        let key =
+            KeyPair::generate().map_err(|e| format!("Failed to generate the CA key: {}", e))?;
+        let certificate = ca_params()
+            .and_then(|params| params.self_signed(&key))
+            .map_err(|e| format!("Failed to generate the CA certificate: {}", e))?;
+        tokio::fs::create_dir_all(&self.dir)
+            .await
+            .map_err(|e| format!("Failed to create {}: {}", self.dir.display(), e))?;
+        write_file(&key_path, key.serialize_pem(), true).await?;
+        write_file(&self.ca_certificate_path(), certificate.pem(), false).await?;
+        tracing::info!("Generated the sandbox CA in {}", self.dir.display());
+        Ok(key)
+    }
+
+    /// Certificate and key files of a runtime, signed by the certificate authority.
+    /// They are generated on first use and reused afterwards.
+    pub async fn runtime_certificate(
+        &self,
+        runtime_id: RuntimeId,
+    ) -> Result<(PathBuf, PathBuf, PathBuf), String> {
+        let _guard = self.lock.lock().await;
+        let ca_key = self.ca_key().await?;
+        let certificate_path = self.dir.join(format!("runtime-{}.pem", runtime_id));
+        let key_path = self.dir.join(format!("runtime-{}.key", runtime_id));
+        let files = (self.ca_certificate_path(), certificate_path, key_path);
+        if tokio::fs::try_exists(&files.1).await.unwrap_or(false)
+            && tokio::fs::try_exists(&files.2).await.unwrap_or(false)
+        {
+            return Ok(files);
+        }
+
+        let issuer = Issuer::new(
+            ca_params().map_err(|e| format!("Invalid CA parameters: {}", e))?,
+            ca_key,
+        );
+        let key = KeyPair::generate().map_err(|e| {
+            format!(
+                "Failed to generate the key of runtime {}: {}",
+                runtime_id, e
+            )
+        })?;
+        let certificate = runtime_params(runtime_id)
+            .and_then(|params| params.signed_by(&key, &issuer))
+            .map_err(|e| {
+                format!(
+                    "Failed to generate the certificate of runtime {}: {}",
+                    runtime_id, e
+                )
+            })?;
+        write_file(&files.2, key.serialize_pem(), true).await?;
+        write_file(&files.1, certificate.pem(), false).await?;
+        tracing::info!("Generated the certificate of runtime {}", runtime_id);
+        Ok(files)
+    }
+}
+
+/// Config with the TLS settings of `role`, using the given certificate files
+fn with_tls_settings(
+    config: &ZenohConfigJson,
+    role: TlsRole,
+    root_ca_certificate: &Path,
+    certificate: &Path,
+    private_key: &Path,
+) -> Result<ZenohConfigJson, String> {
+    let path = |file: &Path| JsonValue::String(file.to_string_lossy().to_string());
+    let listen = matches!(role, TlsRole::Server | TlsRole::Mutual);
+    let connect = role == TlsRole::Mutual;
+
+    let mut settings = vec![("root_ca_certificate", path(root_ca_certificate))];
+    if listen {
+        settings.push(("listen_certificate", path(certificate)));
+        settings.push(("listen_private_key", path(private_key)));
+    }
+    if connect {
+        settings.push(("connect_certificate", path(certificate)));
+        settings.push(("connect_private_key", path(private_key)));
+    }
+    settings.push(("enable_mtls", JsonValue::Bool(role == TlsRole::Mutual)));
+
+    let mut json = config.as_json().clone();
+    for (key, value) in settings {
+        json_set_path(&mut json, &format!("{}/{}", TLS_CONFIG_PATH, key), value);
+    }
+    ZenohConfigJson::from_json(json)
+}
+
+// ============================================================================
+// Tauri commands
+// ============================================================================
+
+/// Set the TLS settings of the declared config of a runtime for `role`, generating the
+/// sandbox certificate authority and the certificate of the runtime if needed, so that
+/// `tls/` and `quic/` endpoints work between runtimes of the sandbox. Certificates are
+/// valid for `localhost` and the loopback addresses, which endpoints must use.
+/// The change is annotated in the timeline and the runtime's log stream, and takes effect
+/// the next time the runtime is started.
+#[tauri::command]
+#[tracing::instrument(skip(app))]
+pub async fn configure_tls(
+    runtime_id: RuntimeId,
+    role: TlsRole,
+    app: AppHandle,
+) -> Result<TlsFiles, SandboxError> {
+    let runtimes = app.state::<ZenohRuntimes>();
+    if !runtimes.runtimes.read().await.contains_key(&runtime_id) {
+        return Err(SandboxError::RuntimeNotFound(runtime_id));
+    }
+    let (root_ca_certificate, certificate, private_key) = app
+        .state::<TlsStore>()
+        .runtime_certificate(runtime_id)
+        .await?;
+
+    let (old_config, new_config) = {
+        let mut runtime_processes = runtimes.runtimes.write().await;
+        let runtime_process = runtime_processes
+            .get_mut(&runtime_id)
+            .ok_or(SandboxError::RuntimeNotFound(runtime_id))?;
+        let new_config = with_tls_settings(
+            &runtime_process.sandbox_config,
+            role,
+            &root_ca_certificate,
+            &certificate,
+            &private_key,
+        )
+        .map_err(SandboxError::ConfigInvalid)?;
+        (
+            runtime_process.replace_config(new_config.clone(), ConfigChangeSource::Patch),
+            new_config,
+        )
+    };
+    app.state::<Timeline>().annotate_config_change(
+        runtime_id,
+        ConfigChangeSource::Patch,
+        &old_config,
+        &new_config,
+        Some(app.state::<LogStorage>().inner()),
+    );
+
+    Ok(TlsFiles {
+        runtime_id,
+        role,
+        root_ca_certificate: root_ca_certificate.to_string_lossy().to_string(),
+        certificate: certificate.to_string_lossy().to_string(),
+        private_key: private_key.to_string_lossy().to_string(),
+    })
+}
@@ -0,0 +1,323 @@
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap},
+    time::Duration,
+};
+
+use chrono::{DateTime, Utc};
+use parking_lot::{Mutex as ParkingLotMutex, RwLock as ParkingLotRwLock};
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::{
+    RuntimeId, ZenohRuntimes,
+    delta::{SnapshotHistory, keyed_delta},
+    error::SandboxError,
+    ts::topology::{
+        InterestAnnotation, InterestMap, TopologyDelta, TopologyEdge, TopologyGraph, TopologyNode,
+    },
+    zenoh_admin,
+};
+
+// ============================================================================
+// Constants
+// ============================================================================
+
+/// Interval between two refreshes of the topology graph
+const TOPOLOGY_REFRESH_INTERVAL: Duration = Duration::from_secs(5);
+
+// ============================================================================
+// Topology graph
+// ============================================================================
+
+/// Last topology graph built from the adminspaces of the running runtimes,
+/// and the snapshots sent to the frontend
+pub struct Topology {
+    graph: ParkingLotRwLock<TopologyGraph>,
+    history: ParkingLotMutex<SnapshotHistory<GraphSnapshot>>,
+}
+
+impl Default for Topology {
+    fn default() -> Self {
+        Self {
+            graph: ParkingLotRwLock::new(empty_graph()),
+            history: ParkingLotMutex::new(SnapshotHistory::default()),
+        }
+    }
+}
+
+/// Topology graph sent to the frontend, with nodes and edges by key
+#[derive(Clone)]
+struct GraphSnapshot {
+    timestamp: DateTime<Utc>,
+    nodes: BTreeMap<String, TopologyNode>,
+    edges: BTreeMap<(String, String), TopologyEdge>,
+}
+
+impl From<&TopologyGraph> for GraphSnapshot {
+    fn from(graph: &TopologyGraph) -> Self {
+        Self {
+            timestamp: graph.timestamp,
+            nodes: graph
+                .nodes
+                .iter()
+                .map(|node| (node.zid.clone(), node.clone()))
+                .collect(),
+            edges: graph
+                .edges
+                .iter()
+                .map(|edge| (edge_key(&edge.source, &edge.target), edge.clone()))
+                .collect(),
+        }
+    }
+}
+
+/// Key of the session between two nodes, whichever reports it
+fn edge_key(zid: &str, peer: &str) -> (String, String) {
+    if zid < peer {
+        (zid.to_string(), peer.to_string())
+    } else {
+        (peer.to_string(), zid.to_string())
+    }
+}
+
+fn empty_graph() -> TopologyGraph {
+    TopologyGraph {
+        timestamp: Utc::now(),
+        nodes: Vec::new(),
+        edges: Vec::new(),
+    }
+}
+
+impl Topology {
+    /// Forget the last graph. The next delta sent to the frontend is a full snapshot.
+    pub(crate) fn reset(&self) {
+        *self.graph.write() = empty_graph();
+        self.history.lock().reset_ack();
+    }
+
+    /// Replace the graph. Returns the delta to send to the frontend if its nodes or edges changed.
+    fn update(&self, graph: TopologyGraph) -> Option<TopologyDelta> {
+        let mut current = self.graph.write();
+        let changed = current.nodes != graph.nodes || current.edges != graph.edges;
+        let delta = changed.then(|| {
+            let snapshot = GraphSnapshot::from(&graph);
+            let mut history = self.history.lock();
+            let seq = history.push(snapshot.clone());
+            delta(&history, seq, &snapshot)
+        });
+        *current = graph;
+        delta
+    }
+
+    /// Record that the frontend applied the snapshot `seq`
+    fn ack(&self, seq: u64) -> Result<(), String> {
+        self.history.lock().ack(seq)
+    }
+
+    /// Full snapshot of the current graph, to replace the frontend's graph.
+    /// The snapshot gets a new sequence number, to acknowledge once applied.
+    fn resync(&self) -> TopologyDelta {
+        let snapshot = GraphSnapshot::from(&*self.graph.read());
+        let mut history = self.history.lock();
+        history.reset_ack();
+        let seq = history.push(snapshot.clone());
+        delta(&history, seq, &snapshot)
+    }
+}
+
+/// Delta from the snapshot acknowledged by the frontend to the latest snapshot `seq`,
+/// or the full latest snapshot if there is no usable acknowledged snapshot
+fn delta(
+    history: &SnapshotHistory<GraphSnapshot>,
+    seq: u64,
+    latest: &GraphSnapshot,
+) -> TopologyDelta {
+    let (base_seq, previous) = match history.since_ack() {
+        Some((base_seq, previous)) => (Some(base_seq), previous),
+        None => (None, Vec::new()),
+    };
+    let previous_nodes: Vec<_> = previous.iter().map(|snapshot| &snapshot.nodes).collect();
+    let previous_edges: Vec<_> = previous.iter().map(|snapshot| &snapshot.edges).collect();
+    let nodes = keyed_delta(&previous_nodes, &latest.nodes);
+    let edges = keyed_delta(&previous_edges, &latest.edges);
+    TopologyDelta {
+        seq,
+        base_seq,
+        timestamp: latest.timestamp,
+        nodes: nodes.upserted,
+        removed_nodes: nodes.removed,
+        edges: edges.upserted,
+        removed_edges: edges.removed,
+    }
+}
+
+/// Query the adminspace of every running runtime for the nodes it can reach
+/// and build the graph of their sessions
+async fn build_graph(runtimes: &ZenohRuntimes) -> TopologyGraph {
+    let timestamp = Utc::now();
+    let running = runtimes.running_zenoh_ids().await;
+    let by_zid: HashMap<String, RuntimeId> = running
+        .iter()
+        .map(|(runtime_id, zid)| (zid.to_string(), *runtime_id))
+        .collect();
+
+    let mut nodes: BTreeMap<String, TopologyNode> = BTreeMap::new();
+    let mut edges: BTreeMap<(String, String), TopologyEdge> = BTreeMap::new();
+    for (runtime_id, _) in &running {
+        let known_nodes = match zenoh_admin::known_nodes(runtimes, *runtime_id).await {
+            Ok(known_nodes) => known_nodes,
+            Err(e) => {
+                tracing::debug!("Failed to get nodes known to runtime {}: {}", runtime_id, e);
+                continue;
+            }
+        };
+        for node in known_nodes {
+            for session in &node.sessions {
+                // Neighbours whose adminspace isn't reachable are known only through the session
+                nodes
+                    .entry(session.peer.clone())
+                    .or_insert_with(|| TopologyNode {
+                        zid: session.peer.clone(),
+                        whatami: session.whatami.clone(),
+                        runtime_id: by_zid.get(&session.peer).copied(),
+                        version: None,
+                        locators: Vec::new(),
+                    });
+                edges
+                    .entry(edge_key(&node.zid, &session.peer))
+                    .or_insert_with(|| TopologyEdge {
+                        source: node.zid.clone(),
+                        target: session.peer.clone(),
+                        links: session.links.clone(),
+                        shm: session.shm,
+                    });
+            }
+            nodes.insert(
+                node.zid.clone(),
+                TopologyNode {
+                    runtime_id: by_zid.get(&node.zid).copied(),
+                    zid: node.zid,
+                    whatami: node.whatami,
+                    version: node.version,
+                    locators: node.locators,
+                },
+            );
+        }
+    }
+
+    TopologyGraph {
+        timestamp,
+        nodes: nodes.into_values().collect(),
+        edges: edges.into_values().collect(),
+    }
+}
+
+/// Rebuild the graph and emit `topology-delta` if it changed
+async fn refresh(app: &AppHandle) -> TopologyGraph {
+    let graph = build_graph(&app.state::<ZenohRuntimes>()).await;
+    if let Some(delta) = app.state::<Topology>().update(graph.clone()) {
+        let _ = app.emit("topology-delta", delta);
+    }
+    graph
+}
+
+/// Periodically refresh the topology graph, for the lifetime of the app
+pub(crate) async fn monitor_topology(app: AppHandle) {
+    let mut interval = tokio::time::interval(TOPOLOGY_REFRESH_INTERVAL);
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+    loop {
+        interval.tick().await;
+        refresh(&app).await;
+    }
+}
+
+// ============================================================================
+// Tauri commands
+// ============================================================================
+
+/// Get the topology graph: the nodes known to the running runtimes and their sessions.
+/// The graph is refreshed periodically; `refresh` rebuilds it right away.
+#[tauri::command]
+#[tracing::instrument(skip(app))]
+pub async fn get_topology(
+    refresh: Option<bool>,
+    app: AppHandle,
+) -> Result<TopologyGraph, SandboxError> {
+    if refresh.unwrap_or(false) {
+        return Ok(self::refresh(&app).await);
+    }
+    Ok(app.state::<Topology>().graph.read().clone())
+}
+
+/// Acknowledge the topology snapshot `seq` once its delta is applied,
+/// so that the next `topology-delta` events are relative to it.
+/// Fails if the snapshot is unknown: the frontend must then call `resync_topology`.
+#[tauri::command]
+#[tracing::instrument(skip(app))]
+pub async fn ack_topology(seq: u64, app: AppHandle) -> Result<(), SandboxError> {
+    Ok(app.state::<Topology>().ack(seq)?)
+}
+
+/// Get a full snapshot of the topology graph, when the frontend state is missing or
+/// inconsistent. The next `topology-delta` events are full snapshots until it is acknowledged.
+#[tauri::command]
+#[tracing::instrument(skip(app))]
+pub async fn resync_topology(app: AppHandle) -> Result<TopologyDelta, SandboxError> {
+    Ok(app.state::<Topology>().resync())
+}
+
+/// For a key expression, get from every running runtime whether it has a matching local
+/// subscriber, which remote subscribers it knows and which routing entries match,
+/// to visualize how subscription interest propagates through the topology
+#[tauri::command]
+#[tracing::instrument(skip(app))]
+pub async fn get_interest_map(
+    keyexpr: String,
+    app: AppHandle,
+) -> Result<InterestMap, SandboxError> {
+    zenoh::key_expr::KeyExpr::try_from(keyexpr.as_str())
+        .map_err(|e| format!("Invalid key expression {}: {}", keyexpr, e))?;
+    if keyexpr.starts_with('@') {
+        return Err(format!("Not a data key expression: {}", keyexpr).into());
+    }
+
+    let timestamp = Utc::now();
+    let runtimes = app.state::<ZenohRuntimes>();
+    let mut nodes = Vec::new();
+    for (runtime_id, zid) in runtimes.running_zenoh_ids().await {
+        let zid = zid.to_string();
+        let mut annotation = InterestAnnotation {
+            runtime_id,
+            zid: zid.clone(),
+            local_subscriber: false,
+            remote_subscribers: Vec::new(),
+            routing_entries: Vec::new(),
+            error: None,
+        };
+        match zenoh_admin::subscriber_sources(&runtimes, runtime_id, &keyexpr).await {
+            Ok(entries) => {
+                let mut remote = BTreeSet::new();
+                for (resource, sources) in entries {
+                    for source in sources {
+                        if source == zid {
+                            annotation.local_subscriber = true;
+                        } else {
+                            remote.insert(source);
+                        }
+                    }
+                    annotation.routing_entries.push(resource);
+                }
+                annotation.remote_subscribers = remote.into_iter().collect();
+                annotation.routing_entries.sort();
+            }
+            Err(e) => annotation.error = Some(e.to_string()),
+        }
+        nodes.push(annotation);
+    }
+    nodes.sort_by_key(|annotation| annotation.runtime_id);
+
+    Ok(InterestMap {
+        keyexpr,
+        timestamp,
+        nodes,
+    })
+}
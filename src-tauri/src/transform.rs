@@ -0,0 +1,96 @@
+use tauri::State;
+
+use crate::{
+    ZenohRuntimes,
+    error::SandboxError,
+    logs::LogStorage,
+    timeline::Timeline,
+    ts::{
+        config::{
+            ConfigTransformOutcome, ConfigTransformReport, ZenohConfigJson, json_diff,
+            json_merge_patch,
+        },
+        runtime::RuntimeSelector,
+        timeline::ConfigChangeSource,
+    },
+};
+
+// ============================================================================
+// Tauri commands
+// ============================================================================
+
+/// Apply the same JSON merge patch to the declared config of every runtime matching
+/// `runtime_selector`. Each transformed config is validated separately: runtimes whose
+/// config would become invalid are reported and left unchanged.
+/// With `dry_run`, only the diffs are computed and no config is changed.
+/// Like other declared config changes, it takes effect the next time a runtime is started.
+#[tauri::command]
+#[tracing::instrument(skip(json_patch, runtimes_state, logs_state, timeline_state))]
+pub async fn apply_config_transform(
+    runtime_selector: RuntimeSelector,
+    json_patch: serde_json::Value,
+    dry_run: Option<bool>,
+    annotate_log: Option<bool>,
+    runtimes_state: State<'_, ZenohRuntimes>,
+    logs_state: State<'_, LogStorage>,
+    timeline_state: State<'_, Timeline>,
+) -> Result<ConfigTransformReport, SandboxError> {
+    if !json_patch.is_object() {
+        return Err("Config patch must be a JSON object".into());
+    }
+    let dry_run = dry_run.unwrap_or(false);
+    let runtime_ids = runtimes_state.select(&runtime_selector).await;
+
+    let mut outcomes = Vec::new();
+    let mut changes = Vec::new();
+    {
+        let mut runtimes = runtimes_state.runtimes.write().await;
+        for runtime_id in runtime_ids {
+            let Some(runtime_process) = runtimes.get_mut(&runtime_id) else {
+                continue;
+            };
+            let old_json = runtime_process.sandbox_config.as_json();
+            let new_json = json_merge_patch(old_json, &json_patch);
+            let diff = json_diff(old_json, &new_json);
+            match ZenohConfigJson::from_json(new_json) {
+                Ok(new_config) => {
+                    if !dry_run {
+                        let old_config = runtime_process
+                            .replace_config(new_config.clone(), ConfigChangeSource::Patch);
+                        changes.push((runtime_id, old_config, new_config));
+                    }
+                    outcomes.push(ConfigTransformOutcome {
+                        runtime_id,
+                        diff,
+                        error: None,
+                    });
+                }
+                Err(e) => outcomes.push(ConfigTransformOutcome {
+                    runtime_id,
+                    diff,
+                    error: Some(e),
+                }),
+            }
+        }
+    }
+
+    let logs = annotate_log.unwrap_or(false).then(|| logs_state.inner());
+    for (runtime_id, old_config, new_config) in &changes {
+        timeline_state.annotate_config_change(
+            *runtime_id,
+            ConfigChangeSource::Patch,
+            old_config,
+            new_config,
+            logs,
+        );
+    }
+
+    let (failed, applied): (Vec<_>, Vec<_>) =
+        outcomes.iter().partition(|outcome| outcome.error.is_some());
+    Ok(ConfigTransformReport {
+        dry_run,
+        applied: applied.iter().map(|outcome| outcome.runtime_id).collect(),
+        failed: failed.iter().map(|outcome| outcome.runtime_id).collect(),
+        outcomes,
+    })
+}
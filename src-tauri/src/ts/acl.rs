@@ -0,0 +1,85 @@
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+use crate::ts::config::InterceptorFlow;
+
+/// Whether the messages matched by an access control rule are let through
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, TS, PartialEq, Eq)]
+#[ts(export, export_to = "../../src/types/generated/")]
+#[serde(rename_all = "snake_case")]
+pub enum AclPermission {
+    Allow,
+    Deny,
+}
+
+/// Kind of message an access control rule applies to
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, TS, PartialEq, Eq)]
+#[ts(export, export_to = "../../src/types/generated/")]
+#[serde(rename_all = "snake_case")]
+pub enum AclMessage {
+    Put,
+    Delete,
+    DeclareSubscriber,
+    Query,
+    DeclareQueryable,
+    Reply,
+    LivelinessToken,
+    DeclareLivelinessSubscriber,
+    LivelinessQuery,
+}
+
+/// Permission for messages on key expressions, referenced by policies through its id
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../src/types/generated/")]
+pub struct AclRule {
+    pub id: String,
+    pub key_exprs: Vec<String>,
+    pub messages: Vec<AclMessage>,
+    /// Directions the rule applies to, both if unset
+    pub flows: Option<Vec<InterceptorFlow>>,
+    pub permission: AclPermission,
+}
+
+/// Remote nodes matched by the transports they connect through, referenced by policies
+/// through its id. A transport matches if it matches all the set criteria.
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../src/types/generated/")]
+pub struct AclSubject {
+    pub id: String,
+    /// Network interfaces of the transports, e.g. `lo`
+    pub interfaces: Option<Vec<String>>,
+    /// Common names of the TLS certificates of the remote nodes
+    pub cert_common_names: Option<Vec<String>>,
+    /// Usernames the remote nodes authenticated with
+    pub usernames: Option<Vec<String>>,
+    /// Link protocols of the transports, e.g. `tcp`
+    pub link_protocols: Option<Vec<String>>,
+    /// ZenohIds of the remote nodes
+    pub zids: Option<Vec<String>>,
+}
+
+/// Application of rules to subjects
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../src/types/generated/")]
+pub struct AclPolicy {
+    pub id: Option<String>,
+    /// Ids of the rules
+    pub rules: Vec<String>,
+    /// Ids of the subjects
+    pub subjects: Vec<String>,
+}
+
+/// `access_control` section of a zenoh config
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../src/types/generated/")]
+pub struct AclConfig {
+    pub enabled: bool,
+    /// Permission of the messages matched by no policy
+    pub default_permission: AclPermission,
+    #[serde(default)]
+    pub rules: Vec<AclRule>,
+    #[serde(default)]
+    pub subjects: Vec<AclSubject>,
+    #[serde(default)]
+    pub policies: Vec<AclPolicy>,
+}
@@ -0,0 +1,67 @@
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+/// An entry of the `@/` adminspace
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../src/types/generated/")]
+pub struct AdminspaceEntry {
+    /// Key expression of the entry
+    pub key: String,
+    /// Value of the entry, parsed as JSON when possible, otherwise as a string
+    #[ts(type = "any")]
+    pub value: serde_json::Value,
+}
+
+/// A link of a transport session
+#[derive(Debug, Clone, Serialize, Deserialize, TS, PartialEq, Eq)]
+#[ts(export, export_to = "../../src/types/generated/")]
+pub struct AdminLinkInfo {
+    /// Source locator
+    pub src: String,
+    /// Destination locator
+    pub dst: String,
+}
+
+/// A transport session between a node and one of its neighbours
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../src/types/generated/")]
+pub struct AdminSessionInfo {
+    /// ZenohId of the remote node
+    pub peer: String,
+    /// Kind of the remote node: router, peer or client
+    pub whatami: String,
+    /// Links of the session
+    #[serde(default)]
+    pub links: Vec<AdminLinkInfo>,
+    /// Whether the session uses shared memory
+    #[serde(default)]
+    pub shm: bool,
+}
+
+/// Information published by a node at `@/<zid>/<whatami>`
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../src/types/generated/")]
+pub struct AdminNodeInfo {
+    /// ZenohId of the node
+    pub zid: String,
+    /// Kind of the node: router, peer or client
+    pub whatami: String,
+    /// Zenoh version of the node
+    pub version: Option<String>,
+    /// Locators the node listens on
+    pub locators: Vec<String>,
+    /// Transport sessions of the node
+    pub sessions: Vec<AdminSessionInfo>,
+}
+
+/// Next hop from `source` to `destination` in the router graph
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../src/types/generated/")]
+pub struct AdminRouteEntry {
+    /// ZenohId of the source router
+    pub source: String,
+    /// ZenohId of the destination router
+    pub destination: String,
+    /// ZenohId of the successor of `source` on the route
+    pub successor: String,
+}
@@ -0,0 +1,43 @@
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+use crate::RuntimeId;
+
+/// User-password credentials set in the configs of runtimes, see `configure_usrpwd`
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../src/types/generated/")]
+pub struct UsrPwdCredentials {
+    /// `transport/auth/usrpwd/user`
+    pub user: String,
+    /// `transport/auth/usrpwd/password`
+    pub password: String,
+    /// `transport/auth/usrpwd/dictionary_file`, listing the credentials accepted from others
+    pub dictionary_file: String,
+    /// Runtimes configured with the credentials
+    pub runtime_ids: Vec<RuntimeId>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, TS, PartialEq, Eq)]
+#[ts(export, export_to = "../../src/types/generated/")]
+#[serde(rename_all = "snake_case")]
+pub enum AuthMismatchReason {
+    /// The connecting runtime has no user or no password
+    MissingCredentials,
+    /// The credentials of the connecting runtime are not in the dictionary of the other
+    UnknownCredentials,
+    /// The dictionary file of the other runtime can't be read or parsed
+    InvalidDictionary,
+}
+
+/// Link between runtimes whose user-password authentication would fail,
+/// found before starting them
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../src/types/generated/")]
+pub struct AuthMismatch {
+    /// Runtime opening the link
+    pub from: RuntimeId,
+    /// Runtime accepting the link, checking the credentials
+    pub to: RuntimeId,
+    pub reason: AuthMismatchReason,
+    pub message: String,
+}
@@ -1,9 +1,12 @@
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use serde_json::Value as JsonValue;
 use ts_rs::TS;
 
+use crate::{RuntimeId, templating, ts::timeline::ConfigChangeSource};
+
 /// Zenoh mode enum for TypeScript
-#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[derive(Debug, Clone, Serialize, Deserialize, TS, PartialEq, Eq)]
 #[ts(export, export_to = "../../src/types/generated/")]
 #[serde(rename_all = "lowercase")]
 pub enum ZenohMode {
@@ -39,7 +42,8 @@ impl Default for ZenohMode {
 }
 
 /// Editable fields for Zenoh configuration.
-/// This represents the JSON5 string representation of the user-edited config.
+/// This represents the JSON5 string representation of the user-edited config,
+/// possibly a template with placeholders resolved when the runtime starts.
 #[derive(Debug, Clone, Serialize, Deserialize, TS)]
 #[ts(export, export_to = "../../src/types/generated/")]
 pub struct ZenohConfigEdit {
@@ -48,7 +52,8 @@ pub struct ZenohConfigEdit {
 }
 
 impl ZenohConfigEdit {
-    /// Validate and parse the JSON5 content into a zenoh::Config
+    /// Validate and parse the JSON5 content into a zenoh::Config.
+    /// The placeholders of a template are replaced by neutral values, see `templating::preview`.
     pub fn to_config(&self) -> Result<zenoh::config::Config, String> {
         // Parse JSON5 string directly into Config using zenoh's from_json5
         // If content is empty or "{}", this will create a default config
@@ -56,6 +61,11 @@ impl ZenohConfigEdit {
             return Ok(zenoh::config::Config::default());
         }
 
+        if templating::is_template(&self.content) {
+            return zenoh::config::Config::from_json5(&templating::preview(&self.content)?)
+                .map_err(|e| format!("Invalid JSON5 config template: {}", e));
+        }
+
         zenoh::config::Config::from_json5(&self.content)
             .map_err(|e| format!("Invalid JSON5 config: {}", e))
     }
@@ -66,6 +76,38 @@ impl ZenohConfigEdit {
             .map_err(|e| format!("Failed to serialize config: {}", e))?;
         Ok(Self { content: json })
     }
+
+    /// Check that the content describes `config`, so that it can be kept alongside it
+    /// as its source, with its comments and formatting
+    pub fn check_describes(&self, config: &ZenohConfigJson) -> Result<(), String> {
+        let serialize = |config: zenoh::config::Config| {
+            serde_json::to_value(config).map_err(|e| format!("Failed to serialize config: {}", e))
+        };
+        let from_content = serialize(self.to_config()?)?;
+        let declared = serialize(zenoh::config::Config::try_from(config.clone())?)?;
+        if from_content != declared {
+            return Err("The config source doesn't describe the config".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// Check an optional JSON5 source of `config`, see `ZenohConfigEdit::check_describes`.
+/// A template is only checked to be valid, `config` being rendered from it at start.
+pub fn check_config_source(
+    source: Option<String>,
+    config: &ZenohConfigJson,
+) -> Result<Option<String>, String> {
+    let Some(content) = source else {
+        return Ok(None);
+    };
+    let edit = ZenohConfigEdit { content };
+    if templating::is_template(&edit.content) {
+        edit.to_config()?;
+        return Ok(Some(edit.content));
+    }
+    edit.check_describes(config)?;
+    Ok(Some(edit.content))
 }
 
 impl Default for ZenohConfigEdit {
@@ -115,20 +157,265 @@ impl ZenohConfigJson {
         &self.config_json
     }
 
-    /// Get the websocket port from the config JSON
-    pub fn get_websocket_port(&self) -> Option<u16> {
+    /// Compute an entity tag identifying this exact config content
+    pub fn etag(&self) -> String {
+        use std::hash::{DefaultHasher, Hash, Hasher};
+
+        let mut hasher = DefaultHasher::new();
+        self.config_json.to_string().hash(&mut hasher);
+        format!("{:016x}", hasher.finish())
+    }
+
+    /// Get the mode from the config JSON, peer if unset
+    pub fn mode(&self) -> ZenohMode {
         self.config_json
+            .get("mode")
+            .and_then(|mode| serde_json::from_value::<ZenohMode>(mode.clone()).ok())
+            .unwrap_or_default()
+    }
+
+    /// Default config for `mode`, with the remote_api plugin listening on `websocket_port`
+    pub fn create_default(mode: ZenohMode, websocket_port: u16) -> Result<Self, String> {
+        let mut config = zenoh::config::Config::default();
+        config
+            .insert_json5(
+                "mode",
+                &format!(r#""{}""#, zenoh::config::WhatAmI::from(mode)),
+            )
+            .map_err(|e| format!("Failed to set mode: {e}"))?;
+        config
+            .insert_json5("plugins/remote_api", "{}")
+            .map_err(|e| format!("Failed to add remote_api plugin config: {e}"))?;
+        config
+            .insert_json5(
+                "plugins/remote_api/websocket_port",
+                &format!(r#""{}""#, websocket_port),
+            )
+            .map_err(|e| format!("Failed to set websocket_port: {e}"))?;
+        let json = serde_json::to_value(&config)
+            .map_err(|e| format!("Failed to serialize config: {}", e))?;
+        Self::from_json(json)
+    }
+
+    /// Get the websocket port from the config JSON.
+    /// The remote_api plugin takes it as a string, possibly with an address, but configs
+    /// written as a number are accepted too.
+    pub fn get_websocket_port(&self) -> Option<u16> {
+        let port = self
+            .config_json
             .get("plugins")
             .and_then(|p| p.get("remote_api"))
-            .and_then(|ra| ra.get("websocket_port"))
-            .and_then(|wp| wp.as_u64())
-            .map(|port| port as u16)
+            .and_then(|ra| ra.get("websocket_port"))?;
+        match port {
+            JsonValue::Number(port) => port.as_u64().and_then(|port| u16::try_from(port).ok()),
+            JsonValue::String(port) => port.rsplit(':').next()?.parse().ok(),
+            _ => None,
+        }
     }
 }
 
+/// Config of a runtime checked out for editing
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../src/types/generated/")]
+pub struct ConfigEditSession {
+    /// Current declared config of the runtime
+    pub config: ZenohConfigJson,
+    /// Entity tag of `config`, required to commit the edit
+    pub etag: String,
+    /// JSON5 text `config` was declared from, with its comments, if it was given
+    pub source: Option<String>,
+}
+
+/// Outcome of committing a config edit
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../src/types/generated/")]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum ConfigCommitResult {
+    /// The config was replaced
+    Committed {
+        /// Entity tag of the new config
+        etag: String,
+        /// Diff between the previous and the new config
+        #[ts(type = "Record<string, any>")]
+        diff: JsonValue,
+    },
+    /// The config was modified since the edit began, nothing was written
+    Conflict {
+        /// Config currently declared for the runtime
+        current: ZenohConfigJson,
+        /// Entity tag of the current config
+        etag: String,
+        /// Diff between the current config and the rejected one
+        #[ts(type = "Record<string, any>")]
+        diff: JsonValue,
+    },
+}
+
+/// Outcome of a config transform for one runtime
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../src/types/generated/")]
+pub struct ConfigTransformOutcome {
+    pub runtime_id: RuntimeId,
+    /// Diff between the current and the transformed config
+    #[ts(type = "Record<string, any>")]
+    pub diff: JsonValue,
+    /// Error message if the transformed config is invalid
+    pub error: Option<String>,
+}
+
+/// Result of applying a config transform to several runtimes
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../src/types/generated/")]
+pub struct ConfigTransformReport {
+    /// Whether the configs were left unchanged
+    pub dry_run: bool,
+    /// Runtimes whose transformed config is valid (and was applied, unless `dry_run`)
+    pub applied: Vec<RuntimeId>,
+    /// Runtimes whose transformed config is invalid
+    pub failed: Vec<RuntimeId>,
+    /// Per-runtime outcomes, ordered by RuntimeId
+    pub outcomes: Vec<ConfigTransformOutcome>,
+}
+
+/// Apply a JSON merge patch (RFC 7396) to a JSON value.
+/// Objects are merged recursively, null values remove keys, any other value replaces the target.
+pub fn json_merge_patch(target: &JsonValue, patch: &JsonValue) -> JsonValue {
+    let JsonValue::Object(patch_obj) = patch else {
+        return patch.clone();
+    };
+    let mut merged = match target {
+        JsonValue::Object(target_obj) => target_obj.clone(),
+        _ => serde_json::Map::new(),
+    };
+    for (key, value) in patch_obj {
+        if value.is_null() {
+            merged.remove(key);
+        } else {
+            let current = merged.get(key).cloned().unwrap_or(JsonValue::Null);
+            merged.insert(key.clone(), json_merge_patch(&current, value));
+        }
+    }
+    JsonValue::Object(merged)
+}
+
+/// Set the value at a config path, e.g. `scouting/multicast/enabled`, creating missing objects.
+/// Returns the previous value, null if unset.
+pub fn json_set_path(config: &mut JsonValue, path: &str, value: JsonValue) -> JsonValue {
+    let mut current = config;
+    for key in path.split('/') {
+        if !current.is_object() {
+            *current = JsonValue::Object(serde_json::Map::new());
+        }
+        current = current
+            .as_object_mut()
+            .expect("replaced by an object")
+            .entry(key)
+            .or_insert(JsonValue::Null);
+    }
+    std::mem::replace(current, value)
+}
+
+/// Key of the object representing changes of an array in a diff produced by `json_diff`,
+/// holding the list of `ArrayDiffOp` turning the base array into the modified one
+pub const ARRAY_DIFF_KEY: &str = "$array";
+
+/// Above this number of element pairs, arrays are diffed as a whole
+const MAX_ARRAY_DIFF_COST: usize = 1 << 20;
+
+/// Element-level change of an array, applied in order to the base array.
+/// Indices refer to the array as left by the previous operations.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../src/types/generated/")]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum ArrayDiffOp {
+    /// Insert `value` before the element at `index`, or at the end
+    Add {
+        index: usize,
+        #[ts(type = "any")]
+        value: JsonValue,
+    },
+    /// Remove the element at `index`
+    Remove { index: usize },
+    /// Replace the element at `index` with `value`
+    Replace {
+        index: usize,
+        #[ts(type = "any")]
+        value: JsonValue,
+    },
+}
+
+/// Operations turning `base` into `modified`, keeping their longest common subsequence
+fn array_diff(base: &[JsonValue], modified: &[JsonValue]) -> Vec<ArrayDiffOp> {
+    let (n, m) = (base.len(), modified.len());
+    // lcs[i][j]: length of the longest common subsequence of base[i..] and modified[j..]
+    let mut lcs = vec![vec![0usize; m + 1]; n + 1];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            lcs[i][j] = if base[i] == modified[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut ops = Vec::new();
+    let (mut i, mut j, mut index) = (0, 0, 0);
+    while i < n || j < m {
+        if i < n && j < m && base[i] == modified[j] {
+            i += 1;
+            j += 1;
+            index += 1;
+        } else if i < n && (j == m || lcs[i + 1][j] >= lcs[i][j + 1]) {
+            ops.push(ArrayDiffOp::Remove { index });
+            i += 1;
+        } else {
+            let value = modified[j].clone();
+            // A removal followed by an addition at the same place is a replacement
+            match ops.last() {
+                Some(ArrayDiffOp::Remove { index: removed }) if *removed == index => {
+                    ops.pop();
+                    ops.push(ArrayDiffOp::Replace { index, value });
+                }
+                _ => ops.push(ArrayDiffOp::Add { index, value }),
+            }
+            j += 1;
+            index += 1;
+        }
+    }
+    ops
+}
+
+/// Apply the operations produced by `array_diff` to `base`
+fn apply_array_diff(base: &[JsonValue], ops: &[ArrayDiffOp]) -> Result<Vec<JsonValue>, String> {
+    let mut array = base.to_vec();
+    for op in ops {
+        match op {
+            ArrayDiffOp::Add { index, value } if *index <= array.len() => {
+                array.insert(*index, value.clone());
+            }
+            ArrayDiffOp::Remove { index } if *index < array.len() => {
+                array.remove(*index);
+            }
+            ArrayDiffOp::Replace { index, value } if *index < array.len() => {
+                array[*index] = value.clone();
+            }
+            _ => {
+                return Err(format!(
+                    "Array diff operation {:?} out of range for an array of {} elements",
+                    op,
+                    array.len()
+                ));
+            }
+        }
+    }
+    Ok(array)
+}
+
 /// Compute the difference between two JSON values.
 /// Returns a JSON object containing only fields that differ from base.
-/// Deleted fields are represented as null.
+/// Deleted fields are represented as null. Changed arrays are represented as an object
+/// with the single key `ARRAY_DIFF_KEY`, holding the operations to apply to their elements.
 pub fn json_diff(base: &JsonValue, modified: &JsonValue) -> JsonValue {
     use serde_json::Map;
 
@@ -171,8 +458,11 @@ pub fn json_diff(base: &JsonValue, modified: &JsonValue) -> JsonValue {
         (JsonValue::Array(base_arr), JsonValue::Array(modified_arr)) => {
             if base_arr == modified_arr {
                 JsonValue::Null
-            } else {
+            } else if base_arr.len().saturating_mul(modified_arr.len()) > MAX_ARRAY_DIFF_COST {
                 modified.clone()
+            } else {
+                let ops = array_diff(base_arr, modified_arr);
+                serde_json::json!({ ARRAY_DIFF_KEY: ops })
             }
         }
         // Different types or primitive values
@@ -185,3 +475,339 @@ pub fn json_diff(base: &JsonValue, modified: &JsonValue) -> JsonValue {
         }
     }
 }
+
+/// Apply a diff produced by `json_diff` to `base`, the inverse of `json_diff`.
+/// Null values in the diff delete fields. The result is validated as a zenoh config.
+pub fn apply_config_diff(
+    base: &ZenohConfigJson,
+    diff: &JsonValue,
+) -> Result<ZenohConfigJson, String> {
+    if !diff.is_object() {
+        return Err("Config diff must be a JSON object".to_string());
+    }
+    ZenohConfigJson::from_json(json_diff_merge(base.as_json(), diff)?)
+}
+
+/// Apply a diff produced by `json_diff` to a JSON value.
+/// Like `json_merge_patch`, with the array operations of `ARRAY_DIFF_KEY` objects applied
+/// to the elements of the target array.
+pub fn json_diff_merge(target: &JsonValue, diff: &JsonValue) -> Result<JsonValue, String> {
+    let JsonValue::Object(diff_obj) = diff else {
+        return Ok(diff.clone());
+    };
+    if let Some(ops) = array_diff_ops(diff)? {
+        let JsonValue::Array(target_arr) = target else {
+            return Err("Array diff applied to a value that is not an array".to_string());
+        };
+        return Ok(JsonValue::Array(apply_array_diff(target_arr, &ops)?));
+    }
+    let mut merged = match target {
+        JsonValue::Object(target_obj) => target_obj.clone(),
+        _ => serde_json::Map::new(),
+    };
+    for (key, value) in diff_obj {
+        if value.is_null() {
+            merged.remove(key);
+        } else {
+            let current = merged.get(key).cloned().unwrap_or(JsonValue::Null);
+            merged.insert(key.clone(), json_diff_merge(&current, value)?);
+        }
+    }
+    Ok(JsonValue::Object(merged))
+}
+
+/// Operations of a diff value representing changes of an array, if it is one
+fn array_diff_ops(diff: &JsonValue) -> Result<Option<Vec<ArrayDiffOp>>, String> {
+    match diff.as_object() {
+        Some(obj) if obj.len() == 1 => match obj.get(ARRAY_DIFF_KEY) {
+            Some(ops) => serde_json::from_value(ops.clone())
+                .map(Some)
+                .map_err(|e| format!("Invalid array diff: {}", e)),
+            None => Ok(None),
+        },
+        _ => Ok(None),
+    }
+}
+
+/// Flatten a JSON patch object into (key, JSON5 value) pairs suitable for `insert_json5`.
+/// Objects are descended into, any other value (including arrays) is inserted as a whole.
+/// Null values are rejected since keys can't be removed from a running config.
+/// An empty patch gives no entries.
+pub fn json_patch_entries(patch: &JsonValue) -> Result<Vec<(String, String)>, String> {
+    fn collect(
+        value: &JsonValue,
+        prefix: &str,
+        entries: &mut Vec<(String, String)>,
+    ) -> Result<(), String> {
+        match value {
+            JsonValue::Object(obj) if !obj.is_empty() || prefix.is_empty() => {
+                for (key, nested) in obj {
+                    let path = if prefix.is_empty() {
+                        key.clone()
+                    } else {
+                        format!("{prefix}/{key}")
+                    };
+                    collect(nested, &path, entries)?;
+                }
+                Ok(())
+            }
+            JsonValue::Null => Err(format!("Cannot remove '{prefix}' from a running config")),
+            _ if prefix.is_empty() => Err("Config patch must be a JSON object".to_string()),
+            _ => {
+                entries.push((prefix.to_string(), value.to_string()));
+                Ok(())
+            }
+        }
+    }
+
+    let mut entries = Vec::new();
+    collect(patch, "", &mut entries)?;
+    Ok(entries)
+}
+
+/// List the paths of all leaf values of a diff produced by `json_diff`.
+/// Paths use the zenoh config key notation, e.g. `scouting/multicast/enabled`.
+/// Changed arrays are listed as a single path.
+pub fn json_diff_paths(diff: &JsonValue) -> Vec<String> {
+    fn collect(value: &JsonValue, prefix: &str, paths: &mut Vec<String>) {
+        match value {
+            JsonValue::Object(obj) if !obj.is_empty() && !obj.contains_key(ARRAY_DIFF_KEY) => {
+                for (key, nested) in obj {
+                    let path = if prefix.is_empty() {
+                        key.clone()
+                    } else {
+                        format!("{prefix}/{key}")
+                    };
+                    collect(nested, &path, paths);
+                }
+            }
+            _ => {
+                if !prefix.is_empty() {
+                    paths.push(prefix.to_string());
+                }
+            }
+        }
+    }
+
+    let mut paths = Vec::new();
+    collect(diff, "", &mut paths);
+    paths
+}
+
+/// Config field changed by a tuning wizard
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../src/types/generated/")]
+pub struct TuningChange {
+    /// Path of the field in the zenoh config key notation, e.g. `transport/unicast/lowlatency`
+    pub path: String,
+    /// Value before tuning, null if unset
+    #[ts(type = "any")]
+    pub old_value: JsonValue,
+    #[ts(type = "any")]
+    pub new_value: JsonValue,
+    /// Why the field was changed
+    pub explanation: String,
+}
+
+/// Built-in transport settings applied to the declared config of a runtime,
+/// see `apply_transport_profile`
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, TS, PartialEq, Eq)]
+#[ts(export, export_to = "../../src/types/generated/")]
+#[serde(rename_all = "snake_case")]
+pub enum TransportProfile {
+    /// Lowest latency, at the cost of throughput
+    LowLatency,
+    /// Highest throughput, at the cost of latency and memory
+    HighThroughput,
+    /// Sessions kept up over networks losing or delaying packets
+    LossyNetwork,
+}
+
+/// Config returned by a tuning wizard
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../src/types/generated/")]
+pub struct TunedConfig {
+    pub config: ZenohConfigJson,
+    /// Changed fields, in the order they were applied
+    pub changes: Vec<TuningChange>,
+}
+
+/// Revision of the config of a runtime
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../src/types/generated/")]
+pub struct ConfigRevision {
+    /// Number of the revision, increasing with each change of the runtime's config
+    #[ts(type = "number")]
+    pub revision: u64,
+    #[ts(type = "string")]
+    pub timestamp: DateTime<Utc>,
+    /// How the config changed, None for the config the runtime was declared with
+    pub source: Option<ConfigChangeSource>,
+    pub config: ZenohConfigJson,
+}
+
+/// Field of the running config of a runtime that differs from its declared config
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../src/types/generated/")]
+pub struct ConfigDriftField {
+    /// Path of the field in the zenoh config key notation, e.g. `adminspace/enabled`
+    pub path: String,
+    /// Value in the declared config, with the zenoh defaults; null if unset
+    #[ts(type = "any")]
+    pub declared: JsonValue,
+    /// Value in the running config; null if unset
+    #[ts(type = "any")]
+    pub effective: JsonValue,
+    /// What the sandbox changed the field for, None for changes it doesn't know about,
+    /// e.g. live updates
+    pub reason: Option<String>,
+}
+
+/// Differences between the running and declared configs of a runtime,
+/// see `zenoh_runtime_config_drift`
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../src/types/generated/")]
+pub struct ConfigDrift {
+    pub runtime_id: RuntimeId,
+    /// Diff from the declared config to the running config, as produced by `json_diff`
+    #[ts(type = "Record<string, any>")]
+    pub diff: JsonValue,
+    /// Differing fields, in the order of the diff
+    pub fields: Vec<ConfigDriftField>,
+}
+
+/// Error of a config field, found by `validate_config_fields`
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../src/types/generated/")]
+pub struct ConfigValidationError {
+    /// Path of the offending field in the zenoh config key notation, empty for the whole config
+    pub path: String,
+    pub message: String,
+}
+
+/// Direction of the messages a downsampling item or an access control rule applies to
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, TS, PartialEq, Eq)]
+#[ts(export, export_to = "../../src/types/generated/")]
+#[serde(rename_all = "snake_case")]
+pub enum InterceptorFlow {
+    /// Messages sent by the runtime
+    Egress,
+    /// Messages received by the runtime
+    Ingress,
+}
+
+/// Kind of data message a downsampling item applies to
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, TS, PartialEq, Eq)]
+#[ts(export, export_to = "../../src/types/generated/")]
+#[serde(rename_all = "snake_case")]
+pub enum DownsamplingMessage {
+    Put,
+    Delete,
+    Query,
+    Reply,
+}
+
+/// Maximum frequency of the messages on a key expression
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../src/types/generated/")]
+pub struct DownsamplingRule {
+    pub key_expr: String,
+    /// Maximum frequency in Hertz
+    pub freq: f64,
+}
+
+/// Item of the `downsampling` list of a zenoh config.
+/// Unset interfaces, link protocols and flows match all of them.
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../src/types/generated/")]
+pub struct DownsamplingItem {
+    pub id: Option<String>,
+    /// Network interfaces of the transports the item applies to
+    pub interfaces: Option<Vec<String>>,
+    /// Link protocols of the transports the item applies to, e.g. `tcp`
+    pub link_protocols: Option<Vec<String>>,
+    pub messages: Vec<DownsamplingMessage>,
+    pub rules: Vec<DownsamplingRule>,
+    pub flows: Option<Vec<InterceptorFlow>>,
+}
+
+impl DownsamplingItem {
+    /// Check the lists zenoh requires to be non-empty, the key expressions and the frequencies
+    pub fn validate(&self) -> Result<(), String> {
+        let name = self.id.as_deref().unwrap_or("without id");
+        let empty = [
+            (
+                "interfaces",
+                self.interfaces.as_ref().is_some_and(Vec::is_empty),
+            ),
+            (
+                "link_protocols",
+                self.link_protocols.as_ref().is_some_and(Vec::is_empty),
+            ),
+            ("messages", self.messages.is_empty()),
+            ("rules", self.rules.is_empty()),
+            ("flows", self.flows.as_ref().is_some_and(Vec::is_empty)),
+        ];
+        if let Some((field, _)) = empty.iter().find(|(_, empty)| *empty) {
+            return Err(format!(
+                "Downsampling item {}: {} must not be empty",
+                name, field
+            ));
+        }
+        for rule in &self.rules {
+            zenoh::key_expr::KeyExpr::try_from(rule.key_expr.as_str()).map_err(|e| {
+                format!(
+                    "Downsampling item {}: invalid key expression '{}': {}",
+                    name, rule.key_expr, e
+                )
+            })?;
+            if !rule.freq.is_finite() || rule.freq <= 0.0 {
+                return Err(format!(
+                    "Downsampling item {}: frequency of '{}' must be positive, got {}",
+                    name, rule.key_expr, rule.freq
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Whether the item applies to messages of `kind` going in direction `flow`
+    pub fn applies_to(&self, kind: DownsamplingMessage, flow: InterceptorFlow) -> bool {
+        self.messages.contains(&kind)
+            && self
+                .flows
+                .as_ref()
+                .is_none_or(|flows| flows.contains(&flow))
+    }
+}
+
+/// Items of the `downsampling` list of a config, empty if unset
+pub fn downsampling_items(config: &ZenohConfigJson) -> Result<Vec<DownsamplingItem>, String> {
+    match config.config_json.get("downsampling") {
+        None | Some(JsonValue::Null) => Ok(Vec::new()),
+        Some(items) => serde_json::from_value(items.clone())
+            .map_err(|e| format!("Failed to parse downsampling config: {}", e)),
+    }
+}
+
+/// Config with its `downsampling` list replaced by `items`, once validated.
+/// Item ids must be unique.
+pub fn with_downsampling(
+    config: &ZenohConfigJson,
+    items: &[DownsamplingItem],
+) -> Result<ZenohConfigJson, String> {
+    let mut ids = std::collections::HashSet::new();
+    for item in items {
+        item.validate()?;
+        if let Some(id) = &item.id
+            && !ids.insert(id)
+        {
+            return Err(format!("Duplicate downsampling item id '{}'", id));
+        }
+    }
+    let mut json = config.config_json.clone();
+    let items = serde_json::to_value(items)
+        .map_err(|e| format!("Failed to serialize downsampling config: {}", e))?;
+    json_set_path(&mut json, "downsampling", items);
+    ZenohConfigJson::from_json(json)
+}
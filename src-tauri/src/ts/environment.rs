@@ -0,0 +1,37 @@
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+/// Outcome of an environment check. Ordered by severity.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, TS, PartialEq, Eq, PartialOrd, Ord)]
+#[ts(export, export_to = "../../src/types/generated/")]
+#[serde(rename_all = "lowercase")]
+pub enum CheckStatus {
+    Pass,
+    /// Some features may not work
+    Warn,
+    /// Runtimes can't be started
+    Fail,
+}
+
+/// Result of a check of the environment the sandbox runs in
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../src/types/generated/")]
+pub struct EnvironmentCheck {
+    /// Identifier of the check, such as `runtime_binary` or `multicast`
+    pub name: String,
+    pub status: CheckStatus,
+    /// What was found
+    pub message: String,
+    /// How to fix the problem, unless the check passed
+    pub remediation: Option<String>,
+}
+
+/// Result of `environment_report`
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../src/types/generated/")]
+pub struct EnvironmentReport {
+    pub sandbox_version: String,
+    /// Worst status of the checks
+    pub status: CheckStatus,
+    pub checks: Vec<EnvironmentCheck>,
+}
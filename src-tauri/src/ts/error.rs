@@ -0,0 +1,36 @@
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+use crate::RuntimeId;
+
+/// Kind of an error returned by a command, for the frontend to branch on
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, TS, PartialEq, Eq)]
+#[ts(export, export_to = "../../src/types/generated/")]
+#[serde(rename_all = "snake_case")]
+pub enum SandboxErrorKind {
+    /// A configuration was rejected
+    ConfigInvalid,
+    /// No runtime has the given id
+    RuntimeNotFound,
+    /// The runtime has no running process
+    RuntimeNotStarted,
+    /// The runtime process could not be started
+    SpawnFailed,
+    /// The runtime process or another party did not answer in time
+    Timeout,
+    /// Communication with the runtime process failed
+    IpcError,
+    /// Any other error
+    Other,
+}
+
+/// Error returned by a command
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../src/types/generated/")]
+pub struct CommandError {
+    pub kind: SandboxErrorKind,
+    /// Human-readable description of the error
+    pub message: String,
+    /// Runtime the error is about, for `runtime_not_found` and `runtime_not_started`
+    pub runtime_id: Option<RuntimeId>,
+}
@@ -0,0 +1,24 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+use crate::{RuntimeId, ts::timeline::TimelineEvent};
+
+/// Timestamped observation of the experiment journal
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../src/types/generated/")]
+pub struct JournalEntry {
+    /// Identifier of the entry, increasing with each entry
+    #[ts(type = "number")]
+    pub id: u64,
+    #[ts(type = "string")]
+    pub timestamp: DateTime<Utc>,
+    /// Free text, in Markdown
+    pub text: String,
+    /// Runtime the entry is about, if any
+    pub runtime_id: Option<RuntimeId>,
+    /// Timeline events the entry refers to, as they were when the entry was added,
+    /// so that they outlive the timeline
+    #[serde(default)]
+    pub events: Vec<TimelineEvent>,
+}
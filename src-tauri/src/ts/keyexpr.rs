@@ -0,0 +1,14 @@
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+/// Result of `validate_keyexpr`
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../src/types/generated/")]
+pub struct KeyExprValidation {
+    /// Whether the expression is a valid key expression as is
+    pub valid: bool,
+    /// Canonical form of the expression, e.g. `a/**` for `a/**/**`, if it has one
+    pub canonical: Option<String>,
+    /// Why the expression is not valid
+    pub error: Option<String>,
+}
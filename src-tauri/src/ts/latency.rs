@@ -0,0 +1,117 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+use crate::RuntimeId;
+
+/// Round-trip time measurements from one runtime to another
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../src/types/generated/")]
+pub struct LatencyCell {
+    /// When the measurement of this pair completed
+    #[ts(type = "string")]
+    pub timestamp: DateTime<Utc>,
+    /// Minimum round-trip time in microseconds
+    #[ts(type = "number | null")]
+    pub min_us: Option<u64>,
+    /// Median round-trip time in microseconds
+    #[ts(type = "number | null")]
+    pub median_us: Option<u64>,
+    /// Maximum round-trip time in microseconds
+    #[ts(type = "number | null")]
+    pub max_us: Option<u64>,
+    /// Error if the pair could not be measured
+    pub error: Option<String>,
+}
+
+impl LatencyCell {
+    /// Build a cell from the round-trip times of one pair
+    pub fn from_result(result: Result<Vec<u64>, String>) -> Self {
+        let timestamp = Utc::now();
+        match result {
+            Ok(mut rtts) if !rtts.is_empty() => {
+                rtts.sort_unstable();
+                Self {
+                    timestamp,
+                    min_us: rtts.first().copied(),
+                    median_us: Some(rtts[rtts.len() / 2]),
+                    max_us: rtts.last().copied(),
+                    error: None,
+                }
+            }
+            Ok(_) => Self::error(timestamp, "No samples".to_string()),
+            Err(e) => Self::error(timestamp, e),
+        }
+    }
+
+    fn error(timestamp: DateTime<Utc>, error: String) -> Self {
+        Self {
+            timestamp,
+            min_us: None,
+            median_us: None,
+            max_us: None,
+            error: Some(error),
+        }
+    }
+}
+
+/// Round-trip time percentiles of a latency test between two runtimes
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../src/types/generated/")]
+pub struct LatencyTestResult {
+    /// Identifier of the test, to include it in exported reports
+    #[ts(type = "number")]
+    pub operation_id: u64,
+    #[ts(type = "string")]
+    pub started_at: DateTime<Utc>,
+    #[ts(type = "string")]
+    pub completed_at: DateTime<Utc>,
+    /// Runtime sending the queries
+    pub runtime_a: RuntimeId,
+    /// Runtime answering them with its echo queryable
+    pub runtime_b: RuntimeId,
+    /// Number of round trips measured
+    pub samples: u32,
+    /// Size in bytes of the payload of each query and reply
+    #[ts(type = "number")]
+    pub payload_size: usize,
+    /// Round-trip times in microseconds
+    #[ts(type = "number")]
+    pub min_us: u64,
+    #[ts(type = "number")]
+    pub median_us: u64,
+    #[ts(type = "number")]
+    pub p95_us: u64,
+    #[ts(type = "number")]
+    pub p99_us: u64,
+    #[ts(type = "number")]
+    pub max_us: u64,
+}
+
+/// Nearest-rank percentile of sorted, non-empty round-trip times
+pub fn percentile(sorted: &[u64], percent: usize) -> u64 {
+    let rank = (percent * sorted.len()).div_ceil(100);
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+/// Pairwise round-trip times between all running runtimes
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../src/types/generated/")]
+pub struct MeshLatencyMatrix {
+    /// Identifier of the sweep, to include it in exported reports
+    #[ts(type = "number")]
+    pub operation_id: u64,
+    /// When the sweep started
+    #[ts(type = "string")]
+    pub started_at: DateTime<Utc>,
+    /// When the sweep completed
+    #[ts(type = "string")]
+    pub completed_at: DateTime<Utc>,
+    /// Runtimes included in the sweep, indexing rows and columns of `cells`
+    pub runtime_ids: Vec<RuntimeId>,
+    /// ZenohIds of the runtimes, in the same order as `runtime_ids`
+    pub zenoh_ids: Vec<String>,
+    /// `cells[i][j]` holds the round-trip times from runtime i to runtime j,
+    /// `None` on the diagonal
+    pub cells: Vec<Vec<Option<LatencyCell>>>,
+}
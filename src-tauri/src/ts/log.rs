@@ -1,7 +1,10 @@
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use tracing::Level;
 use ts_rs::TS;
 
+use crate::{RuntimeId, logs::LogEntry, ts::runtime::RuntimeStatus};
+
 /// Zenoh mode enum for TypeScript
 #[derive(Debug, Clone, Copy, TS, Default, Eq, PartialEq, Hash)]
 #[ts(export, export_to = "../../src/types/generated/")]
@@ -103,4 +106,165 @@ impl Ord for LogEntryLevel {
         let other: Level = other.into();
         this.cmp(&other)
     }
-}
\ No newline at end of file
+}
+
+/// Search in the message of log entries
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../src/types/generated/")]
+pub struct LogSearch {
+    /// Case-insensitive substring, or regular expression if `regex` is set
+    pub query: String,
+    #[serde(default)]
+    pub regex: bool,
+}
+
+/// File format of an exported log
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../src/types/generated/")]
+#[serde(rename_all = "lowercase")]
+pub enum LogExportFormat {
+    /// One JSON object per line
+    Jsonl,
+    /// Comma-separated values with a header row
+    Csv,
+    /// Human-readable lines, as printed on a terminal
+    Text,
+}
+
+/// Output stream of a runtime process, written to a file in the log directory
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../src/types/generated/")]
+#[serde(rename_all = "lowercase")]
+pub enum OutputStream {
+    Stdout,
+    Stderr,
+}
+
+/// Time range of log entries, bounds included. Unset bounds are open.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../src/types/generated/")]
+pub struct LogTimeRange {
+    #[ts(type = "string | null")]
+    pub start: Option<DateTime<Utc>>,
+    #[ts(type = "string | null")]
+    pub end: Option<DateTime<Utc>>,
+}
+
+impl LogTimeRange {
+    pub fn contains(&self, timestamp: &DateTime<Utc>) -> bool {
+        self.start.is_none_or(|start| *timestamp >= start)
+            && self.end.is_none_or(|end| *timestamp <= end)
+    }
+}
+
+/// Manifest of a log archive written by `export_all_logs`
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../src/types/generated/")]
+pub struct LogArchiveManifest {
+    #[ts(type = "string")]
+    pub exported_at: DateTime<Utc>,
+    pub sandbox_version: String,
+    pub format: LogExportFormat,
+    pub range: LogTimeRange,
+    /// Name of the file merging the logs of all runtimes chronologically
+    pub merged_file: String,
+    pub runtimes: Vec<LogArchiveRuntime>,
+}
+
+/// Log file of a runtime in a log archive
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../src/types/generated/")]
+pub struct LogArchiveRuntime {
+    pub runtime_id: RuntimeId,
+    /// Name of the file in the archive
+    pub file: String,
+    /// Number of log entries in the file
+    pub entries: usize,
+    /// Status at export time, unset for the sandbox logs and removed runtimes
+    pub status: Option<RuntimeStatus>,
+}
+
+/// Whether log entries are persisted to disk
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../src/types/generated/")]
+pub struct LogPersistenceStatus {
+    pub enabled: bool,
+    /// Directory of the persisted log files, if enabled
+    pub dir: Option<String>,
+    /// Size in bytes above which a log file is rotated
+    #[ts(type = "number")]
+    pub max_file_size: u64,
+}
+
+/// Page of log entries returned by `zenoh_runtime_log`
+#[derive(Debug, Clone, Default, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../src/types/generated/")]
+pub struct LogPage {
+    /// Entries, most recent first. The `seq` of the last one is the `before_seq` cursor
+    /// of the next older page, the `seq` of the first one the `after_seq`
+    /// cursor of the next newer page.
+    pub entries: Vec<LogEntry>,
+    /// Number of entries matching the filter and cursor, including those not in the page
+    pub total_matching: usize,
+    /// Whether more entries match than the page holds
+    pub has_more: bool,
+}
+
+/// Log entry of `get_merged_logs`, tagged with its runtime
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../src/types/generated/")]
+pub struct MergedLogEntry {
+    pub runtime_id: RuntimeId,
+    #[serde(flatten)]
+    pub entry: LogEntry,
+}
+
+/// Result of `get_log_stats`
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../src/types/generated/")]
+pub struct LogStats {
+    pub runtime_id: RuntimeId,
+    /// Number of stored entries
+    pub total: usize,
+    /// Number of stored entries per level
+    pub counts: LogLevelCounts,
+    /// Sequence numbers of the oldest and most recent stored entries
+    #[ts(type = "number | null")]
+    pub oldest_seq: Option<u64>,
+    #[ts(type = "number | null")]
+    pub newest_seq: Option<u64>,
+}
+
+/// Number of stored log entries per level
+#[derive(Debug, Clone, Default, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../src/types/generated/")]
+pub struct LogLevelCounts {
+    pub trace: usize,
+    pub debug: usize,
+    pub info: usize,
+    pub warn: usize,
+    pub error: usize,
+}
+
+impl LogLevelCounts {
+    /// Count an entry of the given level
+    pub fn add(&mut self, level: LogEntryLevel) {
+        match level {
+            LogEntryLevel::TRACE => self.trace += 1,
+            LogEntryLevel::DEBUG => self.debug += 1,
+            LogEntryLevel::INFO => self.info += 1,
+            LogEntryLevel::WARN => self.warn += 1,
+            LogEntryLevel::ERROR => self.error += 1,
+        }
+    }
+}
+
+/// Number of log entries per level within a time bucket of `get_log_timeline`
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../src/types/generated/")]
+pub struct LogTimelineBucket {
+    /// Start of the bucket, a multiple of the bucket duration since the Unix epoch
+    #[ts(type = "string")]
+    pub start: DateTime<Utc>,
+    pub counts: LogLevelCounts,
+}
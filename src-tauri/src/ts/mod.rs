@@ -1,2 +1,22 @@
+pub mod acl;
+pub mod admin;
+pub mod auth;
 pub mod config;
-pub mod log;
\ No newline at end of file
+pub mod environment;
+pub mod error;
+pub mod journal;
+pub mod keyexpr;
+pub mod latency;
+pub mod log;
+pub mod orphans;
+pub mod preset;
+pub mod report;
+pub mod runtime;
+pub mod scenario;
+pub mod scouting;
+pub mod session;
+pub mod storage;
+pub mod testing;
+pub mod timeline;
+pub mod tls;
+pub mod topology;
\ No newline at end of file
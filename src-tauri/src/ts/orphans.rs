@@ -0,0 +1,42 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+use crate::RuntimeId;
+
+/// Runtime process left running by a previous sandbox instance
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../src/types/generated/")]
+pub struct OrphanProcess {
+    pub pid: u32,
+    /// RuntimeId of the runtime in the previous sandbox instance
+    pub runtime_id: RuntimeId,
+    /// Port of its remote_api plugin
+    pub port: u16,
+    #[ts(type = "string")]
+    pub started_at: DateTime<Utc>,
+    /// Whether the process has been adopted by this sandbox instance
+    pub adopted: bool,
+}
+
+/// What `cleanup_orphans` does with orphan processes
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, TS, PartialEq, Eq)]
+#[ts(export, export_to = "../../src/types/generated/")]
+#[serde(rename_all = "snake_case")]
+pub enum OrphanAction {
+    /// Terminate the process, killing it if it doesn't exit in time
+    Kill,
+    /// Keep the process running, reserving its port.
+    /// It is stopped when this sandbox instance exits.
+    Adopt,
+}
+
+/// Result of `cleanup_orphans` for one orphan process
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../src/types/generated/")]
+pub struct OrphanCleanupOutcome {
+    pub pid: u32,
+    pub action: OrphanAction,
+    /// Error message if the action failed
+    pub error: Option<String>,
+}
@@ -0,0 +1,43 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+use crate::ts::config::ZenohConfigJson;
+
+/// A named configuration saved in the preset library
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../src/types/generated/")]
+pub struct ConfigPreset {
+    /// Unique name of the preset
+    pub name: String,
+    /// Free-form description, e.g. the role of the node
+    pub description: Option<String>,
+    #[ts(type = "string")]
+    pub created_at: DateTime<Utc>,
+    #[ts(type = "string")]
+    pub updated_at: DateTime<Utc>,
+    pub config: ZenohConfigJson,
+    /// JSON5 text of the config, with its comments, if it was given
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+}
+
+/// Preset without its configuration, for listings
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../src/types/generated/")]
+pub struct ConfigPresetSummary {
+    pub name: String,
+    pub description: Option<String>,
+    #[ts(type = "string")]
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<&ConfigPreset> for ConfigPresetSummary {
+    fn from(preset: &ConfigPreset) -> Self {
+        Self {
+            name: preset.name.clone(),
+            description: preset.description.clone(),
+            updated_at: preset.updated_at,
+        }
+    }
+}
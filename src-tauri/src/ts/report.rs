@@ -0,0 +1,130 @@
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+use crate::{
+    RuntimeId,
+    ts::{config::ZenohConfigJson, runtime::RuntimeStatus},
+};
+
+/// Kind of a recorded measurement operation
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, TS, PartialEq, Eq)]
+#[ts(export, export_to = "../../src/types/generated/")]
+#[serde(rename_all = "snake_case")]
+pub enum OperationKind {
+    /// Full-mesh latency sweep, see `run_mesh_latency_sweep`
+    LatencySweep,
+    /// Latency test between two runtimes, see `run_latency_test`
+    LatencyTest,
+}
+
+/// File format of an exported report
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, TS, PartialEq, Eq)]
+#[ts(export, export_to = "../../src/types/generated/")]
+#[serde(rename_all = "lowercase")]
+pub enum ReportFormat {
+    /// The full report as a JSON document
+    Json,
+    /// One row per result value: operation id, kind, JSON path and value
+    Csv,
+}
+
+/// A transport session between two sandbox runtimes
+#[derive(Debug, Clone, Serialize, Deserialize, TS, PartialEq, Eq, PartialOrd, Ord)]
+#[ts(export, export_to = "../../src/types/generated/")]
+pub struct TopologyLink {
+    pub from: RuntimeId,
+    pub to: RuntimeId,
+}
+
+impl TopologyLink {
+    /// Link between two runtimes regardless of direction, with `from` < `to`
+    pub fn undirected(a: RuntimeId, b: RuntimeId) -> Self {
+        Self {
+            from: a.min(b),
+            to: a.max(b),
+        }
+    }
+}
+
+/// Link wired by `connect_runtimes`
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../src/types/generated/")]
+pub struct RuntimeConnection {
+    pub link: TopologyLink,
+    /// Endpoints added to the connect endpoints of the `from` runtime
+    pub endpoints: Vec<String>,
+    /// Whether a transport was opened right away, both runtimes being running
+    pub live: bool,
+}
+
+/// Difference between the intended topology and the live sessions
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../src/types/generated/")]
+pub struct TopologyDrift {
+    #[ts(type = "string")]
+    pub timestamp: DateTime<Utc>,
+    /// Intended links without a live session
+    pub missing_links: Vec<TopologyLink>,
+    /// Live sessions between runtimes of the intended topology that are not intended
+    pub unexpected_links: Vec<TopologyLink>,
+    /// Runtimes of the intended topology that are not running
+    pub down_nodes: Vec<RuntimeId>,
+}
+
+/// State of the sandbox runtimes and of their sessions at a point in time
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../src/types/generated/")]
+pub struct TopologySnapshot {
+    #[ts(type = "string")]
+    pub timestamp: DateTime<Utc>,
+    /// Status of every runtime
+    pub runtimes: Vec<RuntimeStatus>,
+    /// Sessions between running runtimes, as reported by the `from` runtime
+    pub links: Vec<TopologyLink>,
+}
+
+/// Summary of a recorded operation
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../src/types/generated/")]
+pub struct OperationSummary {
+    #[ts(type = "number")]
+    pub id: u64,
+    pub kind: OperationKind,
+    #[ts(type = "string")]
+    pub started_at: DateTime<Utc>,
+    #[ts(type = "string")]
+    pub completed_at: DateTime<Utc>,
+    /// Runtimes involved in the operation
+    pub runtime_ids: Vec<RuntimeId>,
+}
+
+/// A recorded operation with its result and the context it ran in
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../src/types/generated/")]
+pub struct OperationRecord {
+    #[serde(flatten)]
+    pub summary: OperationSummary,
+    /// Result of the operation, as returned by its command
+    #[ts(type = "any")]
+    pub result: serde_json::Value,
+    /// Declared configuration of each involved runtime
+    pub configs: BTreeMap<RuntimeId, ZenohConfigJson>,
+    /// Topology when the operation completed
+    pub topology: TopologySnapshot,
+}
+
+/// Shareable bundle of recorded operations
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../src/types/generated/")]
+pub struct Report {
+    #[ts(type = "string")]
+    pub generated_at: DateTime<Utc>,
+    /// Version of the sandbox
+    pub sandbox_version: String,
+    /// Version of zenoh the runtimes are built with
+    pub zenoh_version: String,
+    pub operations: Vec<OperationRecord>,
+}
@@ -0,0 +1,545 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+use ts_rs::TS;
+
+use crate::{
+    RuntimeId,
+    ts::{config::ZenohMode, log::LogLevelCounts, timeline::ConfigChangeSource},
+};
+
+/// Lifecycle state of a sandbox runtime
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, TS, PartialEq, Eq)]
+#[ts(export, export_to = "../../src/types/generated/")]
+#[serde(rename_all = "lowercase")]
+pub enum RuntimeState {
+    /// Declared with resources allocated, never started
+    Declared,
+    /// Runtime process is being spawned
+    Starting,
+    /// Runtime process is running
+    Running,
+    /// Runtime process is being stopped
+    Stopping,
+    /// Runtime process was stopped
+    Stopped,
+    /// Runtime process exited unexpectedly
+    Crashed,
+    /// IPC connection to the runtime process broke, the process may still be running
+    Disconnected,
+    /// Runtime process is running but stopped answering, see `RuntimeStatus::watchdog`
+    Unresponsive,
+}
+
+impl RuntimeState {
+    /// Whether a runtime process exists or is being spawned/stopped
+    pub fn is_active(&self) -> bool {
+        matches!(
+            self,
+            RuntimeState::Starting
+                | RuntimeState::Running
+                | RuntimeState::Stopping
+                | RuntimeState::Disconnected
+                | RuntimeState::Unresponsive
+        )
+    }
+}
+
+/// Metrics of a running runtime, sampled periodically by the runtime process
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../src/types/generated/")]
+pub struct RuntimeStats {
+    #[ts(type = "string")]
+    pub timestamp: DateTime<Utc>,
+    /// Open transports, unicast and multicast
+    pub transports: usize,
+    /// Links of the open transports
+    pub links: usize,
+    /// Sessions with peers
+    pub peers: usize,
+    /// Sessions with routers
+    pub routers: usize,
+    /// Subscribers and queryables in the routing tables, if the admin space answered
+    pub routing_entries: Option<usize>,
+    /// Resident set size of the runtime process in bytes
+    #[ts(type = "number | null")]
+    pub rss_bytes: Option<u64>,
+    /// CPU usage of the runtime process since the previous sample, in percent of one core
+    pub cpu_percent: Option<f32>,
+}
+
+/// Resource usage of a runtime process, sampled by the sandbox
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../src/types/generated/")]
+pub struct ProcessMetrics {
+    pub runtime_id: RuntimeId,
+    /// Name of the runtime from its options
+    pub name: Option<String>,
+    pub pid: u32,
+    /// CPU usage since the previous sample, in percent of one core.
+    /// 0 for the first sample of a process.
+    pub cpu_percent: f32,
+    /// Resident set size in bytes
+    #[ts(type = "number")]
+    pub memory_bytes: u64,
+    /// Number of open file descriptors, if available on the platform
+    pub open_files: Option<usize>,
+    /// Number of threads, if available on the platform (Linux only)
+    pub threads: Option<usize>,
+}
+
+/// Resource usage of all runtime processes, see `get_sandbox_metrics`
+#[derive(Debug, Clone, Default, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../src/types/generated/")]
+pub struct SandboxMetrics {
+    /// When the processes were sampled, None before the first sample
+    #[ts(type = "string | null")]
+    pub sampled_at: Option<DateTime<Utc>>,
+    /// Running runtime processes, ordered by RuntimeId
+    pub runtimes: Vec<ProcessMetrics>,
+    /// Sum of the CPU usage of the runtime processes, in percent of one core
+    pub total_cpu_percent: f32,
+    /// Sum of the resident set sizes of the runtime processes in bytes
+    #[ts(type = "number")]
+    pub total_memory_bytes: u64,
+}
+
+/// How to connect to the remote_api plugin of a runtime
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../src/types/generated/")]
+pub struct RemoteApiInfo {
+    pub runtime_id: RuntimeId,
+    /// Port allocated for remote_api
+    pub port: u16,
+    /// WebSocket URL of the plugin, e.g. `ws://127.0.0.1:10000`
+    pub url: String,
+    /// Locator to open a zenoh-ts session with, e.g. `ws/127.0.0.1:10000`
+    pub locator: String,
+    /// Whether the port accepted a connection when probed
+    pub listening: bool,
+}
+
+/// Files and endpoints of the last process of a runtime, to find its raw output
+/// and the process itself
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../src/types/generated/")]
+pub struct RuntimeInfo {
+    pub runtime_id: RuntimeId,
+    /// PID of the runtime process, if running
+    pub pid: Option<u32>,
+    /// File receiving the stdout of the runtime process, once started
+    pub stdout_log: Option<String>,
+    /// File receiving the stderr of the runtime process, once started
+    pub stderr_log: Option<String>,
+    /// IPC endpoint the runtime process connected to: a socket path on Unix,
+    /// a loopback TCP address elsewhere. The socket file is removed once connected.
+    pub socket_path: Option<String>,
+}
+
+/// Status of a sandbox runtime
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../src/types/generated/")]
+pub struct RuntimeStatus {
+    pub runtime_id: RuntimeId,
+    pub state: RuntimeState,
+    /// ZenohId of the runtime, available once started
+    pub zenoh_id: Option<String>,
+    /// PID of the runtime process, if running
+    pub pid: Option<u32>,
+    /// Port allocated for remote_api
+    pub port: u16,
+    /// Port allocated for the REST plugin, if enabled
+    pub rest_port: Option<u16>,
+    /// Seconds since the runtime started, if running
+    #[ts(type = "number | null")]
+    pub uptime_secs: Option<u64>,
+    /// Exit code of the last runtime process, if it exited with one
+    pub exit_code: Option<i32>,
+    /// Difference between the expected and the actual peers, if any
+    pub peer_divergence: Option<PeerDivergence>,
+    /// User and umask the runtime process runs with
+    pub privileges: ProcessPrivileges,
+    /// Number of messages from the runtime process truncated or dropped
+    /// for exceeding the maximum message size
+    #[ts(type = "number")]
+    pub truncated_messages: u64,
+    /// Number of log entries dropped by the runtime process because it produced them
+    /// faster than they could be forwarded
+    #[ts(type = "number")]
+    pub dropped_logs: u64,
+    /// Signals observed by the watchdog while running
+    pub watchdog: WatchdogStatus,
+}
+
+/// Signals observed by the watchdog on a running runtime
+#[derive(Debug, Clone, Default, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../src/types/generated/")]
+pub struct WatchdogStatus {
+    /// Number of consecutive keepalive pings left unanswered
+    pub missed_pings: u32,
+    /// Seconds since the last message from the runtime process, as of the last check
+    #[ts(type = "number | null")]
+    pub last_message_secs: Option<u64>,
+    /// Whether the remote_api websocket port accepted a connection at the last check
+    pub websocket_reachable: Option<bool>,
+    /// Why the runtime was declared unresponsive, while it is
+    pub reason: Option<String>,
+}
+
+/// Action taken when the watchdog declares a runtime unresponsive
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, TS, PartialEq, Eq)]
+#[ts(export, export_to = "../../src/types/generated/")]
+#[serde(rename_all = "snake_case")]
+pub enum WatchdogAction {
+    /// Only report it in the logs, the timeline and a `runtime-unresponsive` event
+    #[default]
+    Alert,
+    /// Also send SIGQUIT to the runtime process (Unix only), which terminates it with
+    /// a core dump if enabled, to analyze where it hung
+    CaptureStack,
+    /// Also restart the runtime
+    Restart,
+}
+
+/// Payload of the `runtime-unresponsive` event
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../src/types/generated/")]
+pub struct RuntimeUnresponsiveEvent {
+    pub runtime_id: RuntimeId,
+    pub reason: String,
+    pub action: WatchdogAction,
+}
+
+/// Action taken when a runtime process exceeds the memory limit of its options
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, TS, PartialEq, Eq)]
+#[ts(export, export_to = "../../src/types/generated/")]
+#[serde(rename_all = "snake_case")]
+pub enum MemoryLimitAction {
+    /// Only report it in the logs and a `runtime-memory-limit` event
+    #[default]
+    Alert,
+    /// Also kill the runtime process, which is then handled as a crash
+    Kill,
+    /// Also restart the runtime
+    Restart,
+}
+
+/// Payload of the `runtime-memory-limit` event
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../src/types/generated/")]
+pub struct RuntimeMemoryLimitEvent {
+    pub runtime_id: RuntimeId,
+    /// Resident set size of the runtime process in bytes
+    #[ts(type = "number")]
+    pub rss_bytes: u64,
+    #[ts(type = "number")]
+    pub limit_bytes: u64,
+    pub action: MemoryLimitAction,
+}
+
+/// Summary of a sandbox runtime, as listed by `zenoh_runtime_list_detailed`
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../src/types/generated/")]
+pub struct RuntimeSummary {
+    pub id: RuntimeId,
+    /// Name of the runtime from its options, otherwise within its scenario,
+    /// as `<scenario>/<runtime>`
+    pub name: Option<String>,
+    pub state: RuntimeState,
+    /// ZenohId of the runtime, available once started
+    pub zid: Option<String>,
+    /// Port allocated for remote_api
+    pub port: u16,
+    /// Mode of the declared config
+    pub mode: ZenohMode,
+    /// Seconds since the runtime started, if running
+    #[ts(type = "number | null")]
+    pub uptime_secs: Option<u64>,
+    pub log_counts_by_level: LogLevelCounts,
+}
+
+/// User and umask a runtime process runs with, as validated when declaring the runtime.
+/// Unset fields are inherited from the sandbox.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../src/types/generated/")]
+pub struct ProcessPrivileges {
+    /// Name of the user
+    pub user: Option<String>,
+    pub uid: Option<u32>,
+    /// Primary group of the user
+    pub gid: Option<u32>,
+    /// File mode creation mask, in octal
+    pub umask: Option<String>,
+}
+
+/// Difference between the expected peers of a runtime and its live transport sessions
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../src/types/generated/")]
+pub struct PeerDivergence {
+    /// Expected peers without a transport session
+    pub missing: Vec<RuntimeId>,
+    /// Sandbox runtimes with a transport session that were not expected
+    pub unexpected: Vec<RuntimeId>,
+}
+
+/// Payload of the `runtime-peer-divergence` event, sent when the divergence changes
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../src/types/generated/")]
+pub struct PeerDivergenceEvent {
+    pub runtime_id: RuntimeId,
+    /// New divergence, `null` once the runtime is connected to exactly its expected peers
+    pub divergence: Option<PeerDivergence>,
+}
+
+/// Payload of the `runtime-declared` event
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../src/types/generated/")]
+pub struct RuntimeDeclaredEvent {
+    pub runtime_id: RuntimeId,
+    /// Mode of the declared config
+    pub mode: ZenohMode,
+}
+
+/// Payload of the `runtime-started` event
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../src/types/generated/")]
+pub struct RuntimeStartedEvent {
+    pub runtime_id: RuntimeId,
+    pub zenoh_id: String,
+}
+
+/// Payload of the `runtime-stopped` event, sent once the runtime process is stopped
+/// on request
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../src/types/generated/")]
+pub struct RuntimeStoppedEvent {
+    pub runtime_id: RuntimeId,
+}
+
+/// Payload of the `runtime-config-changed` event
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../src/types/generated/")]
+pub struct RuntimeConfigChangedEvent {
+    pub runtime_id: RuntimeId,
+    pub source: ConfigChangeSource,
+    /// Diff between the old and new config, as produced by `json_diff`
+    #[ts(type = "Record<string, any>")]
+    pub diff: JsonValue,
+}
+
+/// Payload of the `runtime-crashed` event
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../src/types/generated/")]
+pub struct RuntimeCrashedEvent {
+    pub runtime_id: RuntimeId,
+    /// Exit code of the process, if it exited with one
+    pub exit_code: Option<i32>,
+    /// Human readable exit status
+    pub status: String,
+}
+
+/// Outcome of an operation on one runtime within a bulk operation
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../src/types/generated/")]
+pub struct RuntimeOperationOutcome {
+    pub runtime_id: RuntimeId,
+    /// ZenohId of the runtime, for operations that start it
+    pub zenoh_id: Option<String>,
+    /// Error message if the operation failed for this runtime
+    pub error: Option<String>,
+}
+
+/// Aggregated result of an operation applied to several runtimes
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../src/types/generated/")]
+pub struct BulkOperationReport {
+    /// Runtimes for which the operation succeeded
+    pub succeeded: Vec<RuntimeId>,
+    /// Runtimes for which the operation failed
+    pub failed: Vec<RuntimeId>,
+    /// Per-runtime outcomes, ordered by RuntimeId
+    pub outcomes: Vec<RuntimeOperationOutcome>,
+}
+
+/// Named set of runtimes managed together, e.g. the routers of a backbone
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../src/types/generated/")]
+pub struct RuntimeGroup {
+    pub name: String,
+    /// Runtimes of the group, sorted. A runtime belongs to one group at most.
+    pub runtime_ids: Vec<RuntimeId>,
+}
+
+/// Why a runtime can't listen on a port of its config
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, TS, PartialEq, Eq)]
+#[ts(export, export_to = "../../src/types/generated/")]
+#[serde(rename_all = "snake_case")]
+pub enum PortConflictReason {
+    /// Another runtime of the sandbox uses the port
+    Runtime,
+    /// The sandbox reserved the port for another purpose
+    Reserved,
+    /// Another process listens on the port
+    InUse,
+}
+
+/// Port of a runtime's config that it can't listen on, found before starting it
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../src/types/generated/")]
+pub struct PortConflict {
+    pub port: u16,
+    /// Config entry using the port: a listen endpoint, `plugins/remote_api` or `plugins/rest`
+    pub usage: String,
+    pub reason: PortConflictReason,
+    /// Runtime using the port, for `runtime` conflicts
+    pub runtime_id: Option<RuntimeId>,
+}
+
+impl BulkOperationReport {
+    pub fn from_outcomes(outcomes: Vec<RuntimeOperationOutcome>) -> Self {
+        let (failed, succeeded): (Vec<_>, Vec<_>) =
+            outcomes.iter().partition(|outcome| outcome.error.is_some());
+        Self {
+            succeeded: succeeded.iter().map(|outcome| outcome.runtime_id).collect(),
+            failed: failed.iter().map(|outcome| outcome.runtime_id).collect(),
+            outcomes,
+        }
+    }
+}
+
+/// Selection of runtimes by id, mode and state. Unset criteria match every runtime.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../src/types/generated/")]
+#[serde(default)]
+pub struct RuntimeSelector {
+    pub runtime_ids: Option<Vec<RuntimeId>>,
+    /// Modes of the declared configs
+    pub modes: Option<Vec<ZenohMode>>,
+    pub states: Option<Vec<RuntimeState>>,
+}
+
+impl RuntimeSelector {
+    pub fn matches(&self, runtime_id: RuntimeId, mode: &ZenohMode, state: RuntimeState) -> bool {
+        self.runtime_ids
+            .as_ref()
+            .is_none_or(|runtime_ids| runtime_ids.contains(&runtime_id))
+            && self.modes.as_ref().is_none_or(|modes| modes.contains(mode))
+            && self
+                .states
+                .as_ref()
+                .is_none_or(|states| states.contains(&state))
+    }
+}
+
+/// Per-runtime options given when declaring a runtime
+#[derive(Debug, Clone, Default, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../src/types/generated/")]
+#[serde(default)]
+pub struct RuntimeOptions {
+    /// Name of the runtime, by which the config templates of other runtimes refer to it
+    pub name: Option<String>,
+    /// File containing an env-filter directive (e.g. `zenoh=debug,zenoh_transport=trace`)
+    /// watched by the runtime process to adjust its log verbosity while running
+    pub log_filter_file: Option<String>,
+    /// Runtimes this runtime is expected to have a transport session with.
+    /// Compared continuously against the live sessions while running.
+    pub expected_peers: Option<Vec<RuntimeId>>,
+    /// User name or numeric uid to run the runtime process as (Unix only).
+    /// The sandbox must run as root unless it is the current user.
+    pub user: Option<String>,
+    /// File mode creation mask of the runtime process in octal, e.g. `077` (Unix only)
+    pub umask: Option<String>,
+    /// Interval in seconds at which a "runtime alive" summary with the number of peers
+    /// and the message rate is added to the runtime's log stream
+    #[ts(type = "number | null")]
+    pub heartbeat_interval_secs: Option<u64>,
+    /// Maximum size in bytes of a message between the sandbox and the runtime process.
+    /// Larger log entries are truncated and other larger messages are dropped. Defaults to 1 MiB.
+    #[ts(type = "number | null")]
+    pub max_message_size: Option<usize>,
+    /// Action taken when the watchdog declares the runtime unresponsive. Defaults to alert.
+    pub watchdog_action: Option<WatchdogAction>,
+    /// In-memory storages run by the storage manager plugin of the runtime
+    pub storages: Vec<StorageSpec>,
+    /// Enable the REST plugin on a port allocated by the sandbox, to query the runtime
+    /// over HTTP from outside the sandbox
+    pub rest: bool,
+    /// Environment variables of the runtime process, in addition to the sandbox's.
+    /// The variables the sandbox uses to talk to the runtime process can't be overridden.
+    pub env: HashMap<String, String>,
+    /// `RUST_LOG` directive of the runtime process, e.g. `zenoh=debug`, setting the verbosity
+    /// of its logs when there is no log filter file. Takes precedence over a `RUST_LOG` in
+    /// `env`. Defaults to `trace`.
+    pub rust_log: Option<String>,
+    /// Time in milliseconds for the runtime process to connect to the sandbox once spawned.
+    /// Defaults to 10 s.
+    #[ts(type = "number | null")]
+    pub start_timeout_ms: Option<u64>,
+    /// Time in milliseconds for the runtime process to stop gracefully before it is killed.
+    /// Defaults to 7 s.
+    #[ts(type = "number | null")]
+    pub stop_timeout_ms: Option<u64>,
+    /// Number of times the runtime process is spawned again when it doesn't connect
+    /// within the start timeout. Defaults to 0.
+    pub connect_retries: Option<u32>,
+    /// Whether the runtime is started again when its process exits unexpectedly
+    pub restart: RestartPolicy,
+    /// Niceness of the runtime process, from -20 (highest priority) to 19 (Unix only).
+    /// Negative values require privileges.
+    pub nice: Option<i32>,
+    /// CPUs the runtime process is restricted to, by index (Linux only)
+    pub cpu_affinity: Option<Vec<usize>>,
+    /// Resident set size in MiB above which the runtime process is reported,
+    /// checked every 2 s
+    #[ts(type = "number | null")]
+    pub memory_limit_mb: Option<u64>,
+    /// Action taken when the runtime process exceeds the memory limit. Defaults to alert.
+    pub memory_limit_action: Option<MemoryLimitAction>,
+    /// Enable zenoh shared memory, so that large payloads go through shared memory on the
+    /// transports with co-located runtimes enabling it too. When unset, shared memory is
+    /// disabled unless the config sets `transport/shared_memory/enabled`.
+    pub shared_memory: bool,
+}
+
+/// Exits of the runtime process after which the runtime is started again
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, TS, PartialEq, Eq)]
+#[ts(export, export_to = "../../src/types/generated/")]
+#[serde(rename_all = "snake_case")]
+pub enum RestartMode {
+    /// Leave the runtime crashed
+    #[default]
+    Never,
+    /// Exits with a failure code or by a signal
+    OnCrash,
+    /// Any exit not requested by the sandbox, including successful ones
+    Always,
+}
+
+/// Automatic restart of a runtime whose process exits unexpectedly,
+/// keeping its RuntimeId and port
+#[derive(Debug, Clone, Default, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../src/types/generated/")]
+#[serde(default)]
+pub struct RestartPolicy {
+    pub mode: RestartMode,
+    /// Maximum number of consecutive restarts. Defaults to 5.
+    /// The count starts over once the runtime runs for a minute, or is stopped.
+    pub max_attempts: Option<u32>,
+    /// Delay in milliseconds before the first restart, doubled for each consecutive one
+    /// up to a minute. Defaults to 1 s.
+    #[ts(type = "number | null")]
+    pub backoff_ms: Option<u64>,
+}
+
+/// A storage of the storage manager plugin, keeping in memory the samples published on
+/// its key expression and answering queries on it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../src/types/generated/")]
+pub struct StorageSpec {
+    /// Key expression of the stored samples, e.g. `demo/**`
+    pub keyexpr: String,
+    /// Name of the storage, unique within the runtime
+    pub name: String,
+}
@@ -0,0 +1,51 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+use ts_rs::TS;
+
+use crate::{RuntimeId, ts::runtime::RuntimeOptions};
+
+/// Document describing a set of runtimes and the connections between them
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../src/types/generated/")]
+pub struct ScenarioDocument {
+    /// Unique name of the scenario
+    pub name: String,
+    pub runtimes: Vec<ScenarioRuntime>,
+}
+
+/// Runtime of a scenario document
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../src/types/generated/")]
+pub struct ScenarioRuntime {
+    /// Name of the runtime, unique within the scenario
+    pub name: String,
+    /// Zenoh config, possibly partial. Missing fields take their default value.
+    /// Its strings may contain placeholders like `${PEER(<name>).ENDPOINT}`, resolved at start.
+    #[serde(default)]
+    #[ts(type = "Record<string, any> | null")]
+    pub config: JsonValue,
+    #[serde(default)]
+    pub options: Option<RuntimeOptions>,
+    /// Names of the runtimes this runtime connects to. They are started before it.
+    #[serde(default)]
+    pub connect: Vec<String>,
+}
+
+/// Loaded scenario
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../src/types/generated/")]
+pub struct ScenarioInfo {
+    pub name: String,
+    /// Runtimes in start order
+    pub runtimes: Vec<ScenarioRuntimeInfo>,
+}
+
+/// Runtime of a loaded scenario
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../src/types/generated/")]
+pub struct ScenarioRuntimeInfo {
+    pub name: String,
+    /// RuntimeId, once the scenario has been started
+    pub runtime_id: Option<RuntimeId>,
+    pub connect: Vec<String>,
+}
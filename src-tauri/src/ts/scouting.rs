@@ -0,0 +1,30 @@
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+use crate::{RuntimeId, ts::config::ZenohMode};
+
+/// Zenoh node seen by a runtime, found by scouting or through an open transport
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../src/types/generated/")]
+pub struct DiscoveredPeer {
+    pub zid: String,
+    pub mode: ZenohMode,
+    /// Locators the node advertised in its scouting reply
+    pub locators: Vec<String>,
+    /// Whether the node answered the multicast scouting of the runtime
+    pub scouted: bool,
+    /// Whether the runtime has a transport session with the node
+    pub connected: bool,
+    /// Runtime of the sandbox with this ZenohId, if any
+    pub runtime_id: Option<RuntimeId>,
+}
+
+/// Discovery settings of the declared config of a runtime
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../src/types/generated/")]
+pub struct ScoutingSettings {
+    /// `scouting/multicast/enabled`
+    pub multicast: bool,
+    /// `scouting/gossip/enabled`
+    pub gossip: bool,
+}
@@ -0,0 +1,83 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+use crate::{
+    RuntimeId,
+    ts::{
+        config::ZenohConfigJson,
+        journal::JournalEntry,
+        runtime::{BulkOperationReport, RuntimeOptions, RuntimeState},
+    },
+};
+
+/// Declared runtimes of the sandbox, as saved by `export_session`
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../src/types/generated/")]
+pub struct SandboxSession {
+    /// Version of the session file format
+    pub version: u32,
+    #[ts(type = "string")]
+    pub exported_at: DateTime<Utc>,
+    pub sandbox_version: String,
+    pub runtimes: Vec<SessionRuntime>,
+    /// Experiment journal
+    #[serde(default)]
+    pub journal: Vec<JournalEntry>,
+}
+
+/// Declared runtime in a session file
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../src/types/generated/")]
+pub struct SessionRuntime {
+    /// RuntimeId at export time. Runtimes get new ids when imported.
+    pub runtime_id: RuntimeId,
+    /// Name of the runtime within its scenario, as `<scenario>/<runtime>`
+    pub name: Option<String>,
+    pub config: ZenohConfigJson,
+    /// JSON5 text of the config, with its comments, if it was given
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub config_source: Option<String>,
+    pub options: RuntimeOptions,
+    /// Allocated remote_api port, reused on import if it is free
+    pub port: u16,
+    /// State at export time. Runtimes that were active are started on import.
+    pub state: RuntimeState,
+    /// Notes about the runtime, in Markdown
+    #[serde(default)]
+    pub notes: String,
+}
+
+/// Runtime declared by `import_session`
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../src/types/generated/")]
+pub struct ImportedRuntime {
+    /// RuntimeId in the session file
+    pub previous_runtime_id: RuntimeId,
+    pub runtime_id: RuntimeId,
+    pub name: Option<String>,
+    pub port: u16,
+}
+
+/// Result of `import_session`
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../src/types/generated/")]
+pub struct SessionImportReport {
+    pub runtimes: Vec<ImportedRuntime>,
+    /// Outcome of starting the runtimes that were active at export time
+    pub started: BulkOperationReport,
+}
+
+/// Result of `end_session`
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../src/types/generated/")]
+pub struct SessionEndReport {
+    /// Outcome of stopping the active runtimes
+    pub stopped: BulkOperationReport,
+    /// Absolute path of the written archive, if one was requested
+    pub archive: Option<String>,
+    /// Runtimes removed from the sandbox. Runtimes that failed to stop are kept.
+    pub removed_runtimes: Vec<RuntimeId>,
+    /// Number of leftover sockets and process output files deleted
+    pub removed_files: usize,
+}
@@ -0,0 +1,24 @@
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+/// Directories used for files of runtime processes
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../src/types/generated/")]
+pub struct StorageLocations {
+    /// Directory of the stdout/stderr logs of runtime processes
+    pub log_dir: String,
+    /// Directory of the IPC sockets
+    pub socket_dir: String,
+}
+
+/// Result of relocating the storage directories
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../src/types/generated/")]
+pub struct StorageRelocation {
+    /// Directories used by runtimes started from now on
+    pub locations: StorageLocations,
+    /// Log files moved to the new log directory
+    pub migrated_files: Vec<String>,
+    /// Log files that could not be moved, with the reason
+    pub failed_files: Vec<String>,
+}
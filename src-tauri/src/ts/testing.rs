@@ -0,0 +1,336 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+use crate::RuntimeId;
+
+/// A sample received by a test subscriber, or a query received by a test queryable
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../src/types/generated/")]
+pub struct TestSample {
+    /// Identifier of the sample, unique across test subscribers, see `decode_sample`.
+    /// Assigned when the sample is stored.
+    #[ts(type = "number")]
+    #[serde(default)]
+    pub sample_id: u64,
+    /// When the runtime received the sample
+    #[ts(type = "string")]
+    pub received_at: DateTime<Utc>,
+    /// Key expression of the sample
+    pub key_expr: String,
+    /// Kind of the sample: put or delete, or query for queries
+    pub kind: String,
+    /// Encoding of the payload
+    pub encoding: String,
+    /// Payload, decoded as UTF-8 with invalid sequences replaced
+    pub payload: String,
+    /// Size of the payload in bytes
+    pub payload_len: usize,
+    /// Timestamp attached by the publisher, if any
+    pub timestamp: Option<String>,
+}
+
+/// Rendering of the payload of a received sample
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../src/types/generated/")]
+#[serde(rename_all = "lowercase")]
+pub enum PayloadFormat {
+    /// Text, the payload must be valid UTF-8
+    Utf8,
+    /// Pretty-printed JSON, the payload must be valid JSON
+    Json,
+    /// Lowercase hexadecimal digits, two per byte
+    Hex,
+    /// Standard base64 with padding
+    Base64,
+}
+
+/// Payload of a received sample rendered in a given format
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../src/types/generated/")]
+pub struct DecodedPayload {
+    #[ts(type = "number")]
+    pub sample_id: u64,
+    /// Encoding of the payload, as sent by the publisher
+    pub encoding: String,
+    pub format: PayloadFormat,
+    /// Payload rendered in `format`
+    pub text: String,
+}
+
+/// Kind of a test entity, with its parameters
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../src/types/generated/")]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum TestEntityKind {
+    /// Publishes `payload` every `period_ms` milliseconds
+    Publisher {
+        payload: String,
+        #[ts(type = "number")]
+        period_ms: u64,
+    },
+    /// Publishes a per-key sequence number on `<keyexpr>/0` to `<keyexpr>/<keys - 1>`
+    /// every `period_ms` milliseconds, in payloads padded to `payload_size` bytes
+    SequencePublisher {
+        keys: u32,
+        #[ts(type = "number")]
+        period_ms: u64,
+        payload_size: usize,
+        /// Whether publications block instead of being dropped on congestion
+        block: bool,
+    },
+    /// Publishes `burst` samples every `period_ms` milliseconds, give or take up to
+    /// `jitter_ms`, with `{seq}` and `{timestamp}` of `payload_template` substituted,
+    /// see `start_periodic_publisher`
+    PeriodicPublisher {
+        payload_template: String,
+        #[ts(type = "number")]
+        period_ms: u64,
+        #[ts(type = "number")]
+        jitter_ms: u64,
+        burst: u32,
+    },
+    /// Republishes the samples of a recording `speed` times faster than they were received,
+    /// see `replay_recording`
+    Replay {
+        path: String,
+        speed: f64,
+        /// Number of samples in the recording
+        samples: usize,
+    },
+    /// Stores received samples, see `get_test_samples`
+    Subscriber,
+    /// Checks the sequence numbers of received samples, see `get_sequence_report`
+    SequenceChecker,
+    /// Replies `reply_payload` to queries and stores them, see `get_test_samples`
+    Queryable { reply_payload: String },
+    /// Liveliness token, alive until undeclared or its runtime stops
+    LivelinessToken,
+    /// Reports the liveliness tokens appearing and disappearing, see `get_liveliness_events`
+    LivelinessSubscriber {
+        /// Whether the tokens alive when subscribing were reported
+        history: bool,
+    },
+}
+
+/// A test publisher or subscriber declared on a runtime's session
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../src/types/generated/")]
+pub struct TestEntityInfo {
+    /// Identifier of the entity, unique across runtimes
+    #[ts(type = "number")]
+    pub id: u64,
+    pub runtime_id: RuntimeId,
+    /// Key expression the entity publishes on or subscribes to
+    pub keyexpr: String,
+    pub kind: TestEntityKind,
+}
+
+/// A reply received by a test query
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../src/types/generated/")]
+pub struct TestReply {
+    /// Key expression of the reply, or the selector for error replies
+    pub key_expr: String,
+    /// Encoding of the payload
+    pub encoding: String,
+    /// Payload, decoded as UTF-8 with invalid sequences replaced
+    pub payload: String,
+    /// Size of the payload in bytes
+    pub payload_len: usize,
+    /// Whether the replier answered with an error
+    pub is_error: bool,
+}
+
+/// Result of a test query
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../src/types/generated/")]
+pub struct TestGetResult {
+    /// Time until the query completed, in milliseconds
+    #[ts(type = "number")]
+    pub elapsed_ms: u64,
+    /// Replies in the order they were received
+    pub replies: Vec<TestReply>,
+}
+
+/// Delivery statistics of the sequence numbers received on a key
+#[derive(Debug, Clone, Default, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../src/types/generated/")]
+pub struct SequenceKeyStats {
+    pub key_expr: String,
+    /// Number of samples with a sequence number
+    #[ts(type = "number")]
+    pub received: u64,
+    /// Highest sequence number received
+    #[ts(type = "number")]
+    pub last_seq: u64,
+    /// Number of times sequence numbers were skipped
+    #[ts(type = "number")]
+    pub gaps: u64,
+    /// Number of skipped sequence numbers not received since
+    #[ts(type = "number")]
+    pub missing: u64,
+    /// Number of sequence numbers received after a higher one
+    #[ts(type = "number")]
+    pub reordered: u64,
+    /// Number of sequence numbers received more than once
+    #[ts(type = "number")]
+    pub duplicates: u64,
+}
+
+/// Result of `get_sequence_report`
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../src/types/generated/")]
+pub struct SequenceReport {
+    /// Identifier of the sequence checker
+    #[ts(type = "number")]
+    pub id: u64,
+    /// Statistics per key, ordered by key expression
+    pub keys: Vec<SequenceKeyStats>,
+    /// Sum of the statistics of all keys
+    #[ts(type = "number")]
+    pub received: u64,
+    #[ts(type = "number")]
+    pub missing: u64,
+    #[ts(type = "number")]
+    pub reordered: u64,
+    #[ts(type = "number")]
+    pub duplicates: u64,
+    /// Number of samples without a sequence number
+    #[ts(type = "number")]
+    pub invalid: u64,
+}
+
+/// Reception statistics of a test subscriber, see `get_subscriber_stats`
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../src/types/generated/")]
+pub struct SubscriberStats {
+    /// Identifier of the subscriber
+    #[ts(type = "number")]
+    pub id: u64,
+    /// Number of samples received since the subscriber was declared
+    #[ts(type = "number")]
+    pub received: u64,
+    /// Number of payload bytes received since the subscriber was declared
+    #[ts(type = "number")]
+    pub received_bytes: u64,
+    /// Duration of the rolling window of the rates and intervals, in seconds
+    #[ts(type = "number")]
+    pub window_secs: u64,
+    /// Samples per second over the window
+    pub messages_per_sec: f64,
+    /// Payload bytes per second over the window
+    pub bytes_per_sec: f64,
+    /// Mean time between two samples over the window, in milliseconds
+    pub mean_interval_ms: Option<f64>,
+    /// Standard deviation of the time between two samples over the window, in milliseconds
+    pub jitter_ms: Option<f64>,
+    /// When the last sample was received
+    #[ts(type = "string | null")]
+    pub last_received_at: Option<DateTime<Utc>>,
+}
+
+/// How a test entity uses its key expression
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, TS, PartialEq, Eq)]
+#[ts(export, export_to = "../../src/types/generated/")]
+#[serde(rename_all = "snake_case")]
+pub enum KeyExprRole {
+    /// Publishes samples, as publishers and sequence publishers do
+    Producer,
+    /// Receives samples, as subscribers and sequence checkers do
+    Consumer,
+    /// Replies to queries
+    Queryable,
+    /// Declares a liveliness token
+    LivelinessToken,
+    /// Watches liveliness tokens
+    LivelinessSubscriber,
+}
+
+/// A liveliness token appearing or disappearing, as seen by a liveliness subscriber.
+/// Payload of the `liveliness-event` event.
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../src/types/generated/")]
+pub struct LivelinessEvent {
+    /// Identifier of the liveliness subscriber
+    #[ts(type = "number")]
+    pub subscriber_id: u64,
+    /// Runtime of the liveliness subscriber
+    pub runtime_id: RuntimeId,
+    #[ts(type = "string")]
+    pub received_at: DateTime<Utc>,
+    /// Key expression of the token
+    pub key_expr: String,
+    /// Whether the token appeared, or disappeared
+    pub alive: bool,
+}
+
+/// Test entity using a key expression
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../src/types/generated/")]
+pub struct KeyExprUser {
+    /// Identifier of the test entity
+    #[ts(type = "number")]
+    pub entity_id: u64,
+    pub runtime_id: RuntimeId,
+    pub role: KeyExprRole,
+}
+
+/// Key expression used by test entities, see `list_active_keyexprs`
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../src/types/generated/")]
+pub struct ActiveKeyExpr {
+    pub keyexpr: String,
+    /// Entities using the key expression, ordered by id
+    pub users: Vec<KeyExprUser>,
+    /// Other active key expressions intersecting this one
+    pub intersecting: Vec<String>,
+    /// Whether samples produced on the key expression reach no consumer,
+    /// or its consumers match no producer
+    pub orphaned: bool,
+}
+
+/// Rate observed by a subscriber while a publisher puts at a known rate, see `verify_downsampling`
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../src/types/generated/")]
+pub struct DownsamplingReport {
+    pub publisher_runtime_id: RuntimeId,
+    pub subscriber_runtime_id: RuntimeId,
+    pub key_expr: String,
+    /// Rate of the publisher in Hertz, from its period rounded to the millisecond
+    pub published_rate_hz: f64,
+    /// Lowest frequency of the downsampling rules of the publisher's egress or the
+    /// subscriber's ingress matching the key expression, None if no rule matches
+    pub expected_rate_hz: Option<f64>,
+    /// Number of samples received by the subscriber
+    #[ts(type = "number")]
+    pub received: u64,
+    /// Duration of the publication in milliseconds
+    #[ts(type = "number")]
+    pub duration_ms: u64,
+    /// Rate of the samples received by the subscriber in Hertz
+    pub observed_rate_hz: f64,
+}
+
+/// Whether samples between two runtimes went through shared memory, see
+/// `verify_shared_memory`
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../src/types/generated/")]
+pub struct ShmReport {
+    pub publisher_runtime_id: RuntimeId,
+    pub subscriber_runtime_id: RuntimeId,
+    pub key_expr: String,
+    /// Whether shared memory is enabled in the declared options or config of the publisher
+    pub publisher_enabled: bool,
+    /// Whether shared memory is enabled in the declared options or config of the subscriber
+    pub subscriber_enabled: bool,
+    /// Size in bytes of the published payloads
+    #[ts(type = "number")]
+    pub payload_size: usize,
+    /// Number of samples received by the subscriber
+    #[ts(type = "number")]
+    pub received: u64,
+    /// Whether the transport session between the runtimes uses shared memory, as reported
+    /// by the publisher's adminspace. None if the runtimes have no direct session.
+    pub transport_shm: Option<bool>,
+}
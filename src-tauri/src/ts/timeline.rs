@@ -0,0 +1,66 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+use ts_rs::TS;
+
+use crate::{RuntimeId, ts::runtime::WatchdogAction};
+
+/// Origin of a configuration change
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, TS, PartialEq, Eq)]
+#[ts(export, export_to = "../../src/types/generated/")]
+#[serde(rename_all = "snake_case")]
+pub enum ConfigChangeSource {
+    /// The declared config was patched from the sandbox
+    Patch,
+    /// The runtime was restarted with a config differing from its last run
+    Restart,
+    /// The config of a running runtime was updated live
+    LiveUpdate,
+    /// The declared config was reverted to a previous revision
+    Revert,
+    /// The declared config was rendered from its template as the runtime started
+    Template,
+}
+
+/// Kind of event recorded in the timeline
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../src/types/generated/")]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum TimelineEventKind {
+    /// Configuration of a runtime changed
+    ConfigChange {
+        source: ConfigChangeSource,
+        /// Diff between the old and new config, as produced by `json_diff`
+        #[ts(type = "Record<string, any>")]
+        diff: JsonValue,
+    },
+    /// IPC connection to a runtime process broke
+    IpcFailure {
+        /// The I/O error, or the reason the connection was considered lost
+        error: String,
+    },
+    /// The watchdog declared a runtime unresponsive
+    Unresponsive {
+        /// Signals that led to it
+        reason: String,
+        action: WatchdogAction,
+    },
+}
+
+/// A single annotated event of the sandbox timeline
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../src/types/generated/")]
+pub struct TimelineEvent {
+    /// Identifier of the event, increasing with each event of the sandbox
+    #[ts(type = "number")]
+    pub id: u64,
+    /// Timestamp of the event
+    #[ts(type = "string")]
+    pub timestamp: DateTime<Utc>,
+    /// Runtime affected by the event, if any
+    pub runtime_id: Option<RuntimeId>,
+    /// Short human readable summary
+    pub summary: String,
+    /// Event details
+    pub kind: TimelineEventKind,
+}
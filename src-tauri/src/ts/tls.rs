@@ -0,0 +1,31 @@
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+use crate::RuntimeId;
+
+/// Side of the TLS links a runtime is configured for, see `configure_tls`
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, TS, PartialEq, Eq)]
+#[ts(export, export_to = "../../src/types/generated/")]
+#[serde(rename_all = "snake_case")]
+pub enum TlsRole {
+    /// Accepts TLS and QUIC links with its certificate
+    Server,
+    /// Opens TLS and QUIC links, checking the certificate of the other side
+    Client,
+    /// Both, with the certificates of both sides checked (mTLS)
+    Mutual,
+}
+
+/// Files of the sandbox certificate authority and of the certificate of a runtime
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../src/types/generated/")]
+pub struct TlsFiles {
+    pub runtime_id: RuntimeId,
+    pub role: TlsRole,
+    /// PEM certificate of the self-signed certificate authority of the sandbox
+    pub root_ca_certificate: String,
+    /// PEM certificate of the runtime, signed by the certificate authority
+    pub certificate: String,
+    /// PEM private key of the runtime
+    pub private_key: String,
+}
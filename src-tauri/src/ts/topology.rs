@@ -0,0 +1,100 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+use crate::{RuntimeId, ts::admin::AdminLinkInfo};
+
+/// A zenoh node known to the sandbox runtimes
+#[derive(Debug, Clone, Serialize, Deserialize, TS, PartialEq, Eq)]
+#[ts(export, export_to = "../../src/types/generated/")]
+pub struct TopologyNode {
+    pub zid: String,
+    /// Kind of the node: router, peer or client
+    pub whatami: String,
+    /// RuntimeId if the node is a sandbox runtime
+    pub runtime_id: Option<RuntimeId>,
+    /// Zenoh version, if the node's adminspace was reachable
+    pub version: Option<String>,
+    /// Locators the node listens on, if its adminspace was reachable
+    pub locators: Vec<String>,
+}
+
+/// A transport session between two nodes
+#[derive(Debug, Clone, Serialize, Deserialize, TS, PartialEq, Eq)]
+#[ts(export, export_to = "../../src/types/generated/")]
+pub struct TopologyEdge {
+    /// ZenohId of the node reporting the session
+    pub source: String,
+    /// ZenohId of the remote node
+    pub target: String,
+    /// Links of the session, as seen from `source`
+    pub links: Vec<AdminLinkInfo>,
+    /// Whether the session uses shared memory
+    pub shm: bool,
+}
+
+/// Graph of the nodes known to the running runtimes and of their sessions
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../src/types/generated/")]
+pub struct TopologyGraph {
+    /// When the adminspaces were queried
+    #[ts(type = "string")]
+    pub timestamp: DateTime<Utc>,
+    /// Nodes, ordered by ZenohId
+    pub nodes: Vec<TopologyNode>,
+    /// Sessions, each reported once, ordered by ZenohIds
+    pub edges: Vec<TopologyEdge>,
+}
+
+/// Subscription interest of a runtime in a key expression
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../src/types/generated/")]
+pub struct InterestAnnotation {
+    pub runtime_id: RuntimeId,
+    pub zid: String,
+    /// Whether a session of the runtime itself has a matching subscriber
+    pub local_subscriber: bool,
+    /// Other nodes with a matching subscriber, known to the runtime
+    pub remote_subscribers: Vec<String>,
+    /// Resources of the runtime's subscriber routing table intersecting the key expression
+    pub routing_entries: Vec<String>,
+    /// Error if the runtime's adminspace couldn't be queried
+    pub error: Option<String>,
+}
+
+/// Subscription interest of every running runtime in a key expression,
+/// to be overlaid on the topology graph
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../src/types/generated/")]
+pub struct InterestMap {
+    pub keyexpr: String,
+    #[ts(type = "string")]
+    pub timestamp: DateTime<Utc>,
+    /// Annotations, ordered by RuntimeId
+    pub nodes: Vec<InterestAnnotation>,
+}
+
+/// Changes of the topology graph since the snapshot acknowledged by the frontend,
+/// sent with the `topology-delta` event.
+/// The delta can be applied on top of any snapshot from `base_seq` to `seq`.
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../src/types/generated/")]
+pub struct TopologyDelta {
+    /// Sequence number of the resulting snapshot, to acknowledge once applied
+    #[ts(type = "number")]
+    pub seq: u64,
+    /// Snapshot the delta applies to, None if the delta is a full snapshot
+    /// replacing the current graph
+    #[ts(type = "number | null")]
+    pub base_seq: Option<u64>,
+    #[ts(type = "string")]
+    pub timestamp: DateTime<Utc>,
+    /// Nodes added or changed, ordered by ZenohId
+    pub nodes: Vec<TopologyNode>,
+    /// ZenohIds of the removed nodes
+    pub removed_nodes: Vec<String>,
+    /// Sessions added or changed, ordered by ZenohIds
+    pub edges: Vec<TopologyEdge>,
+    /// Removed sessions, as ordered pairs of ZenohIds
+    pub removed_edges: Vec<(String, String)>,
+}
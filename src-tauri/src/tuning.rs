@@ -0,0 +1,227 @@
+use serde_json::{Value as JsonValue, json};
+use tauri::{AppHandle, Manager};
+
+use crate::error::SandboxError;
+use crate::logs::LogStorage;
+use crate::timeline::Timeline;
+use crate::ts::config::{
+    TransportProfile, TunedConfig, TuningChange, ZenohConfigJson, json_set_path,
+};
+use crate::ts::timeline::ConfigChangeSource;
+use crate::{RuntimeId, ZenohRuntimes};
+
+// ============================================================================
+// Tuning goals
+// ============================================================================
+//
+// Each goal is a list of config fields with the value serving it and the reason
+// for that value. Fields already set to that value are left out of the changes.
+
+/// Config field set by a tuning goal, with the reason for its value
+struct Setting {
+    path: &'static str,
+    value: JsonValue,
+    explanation: &'static str,
+}
+
+fn setting(path: &'static str, value: JsonValue, explanation: &'static str) -> Setting {
+    Setting {
+        path,
+        value,
+        explanation,
+    }
+}
+
+fn low_latency() -> Vec<Setting> {
+    vec![
+        setting(
+            "transport/unicast/lowlatency",
+            json!(true),
+            "Use the LowLatency transport, which sends each message as soon as it is written",
+        ),
+        setting(
+            "transport/unicast/qos/enabled",
+            json!(false),
+            "The LowLatency transport doesn't support QoS priorities",
+        ),
+        setting(
+            "transport/link/tx/queue/batching/enabled",
+            json!(false),
+            "Don't hold messages back to batch them when the network pushes back",
+        ),
+        setting(
+            "transport/link/tx/queue/allocation/mode",
+            json!("init"),
+            "Allocate the batches of the queues upfront rather than on the first messages",
+        ),
+        setting(
+            "transport/link/tx/queue/congestion_control/drop/wait_before_drop",
+            json!(100),
+            "Drop droppable messages after 100µs of congestion rather than delay the next ones",
+        ),
+    ]
+}
+
+fn high_throughput() -> Vec<Setting> {
+    vec![
+        setting(
+            "transport/unicast/lowlatency",
+            json!(false),
+            "The LowLatency transport doesn't batch messages",
+        ),
+        setting(
+            "transport/link/tx/batch_size",
+            json!(65535),
+            "Use the largest batches, so that small messages share the per-batch overhead",
+        ),
+        setting(
+            "transport/link/tx/queue/batching/enabled",
+            json!(true),
+            "Batch small messages together when the network pushes back",
+        ),
+        setting(
+            "transport/link/tx/queue/batching/time_limit",
+            json!(10),
+            "Hold messages up to 10ms to fill batches under back-pressure",
+        ),
+        setting(
+            "transport/link/tx/queue/size/data",
+            json!(16),
+            "Queue up to 16 batches of data messages to absorb bursts",
+        ),
+        setting(
+            "transport/link/rx/buffer_size",
+            json!(16_777_216),
+            "Receive up to 16MiB of in-flight data per link, for large messages",
+        ),
+    ]
+}
+
+fn lossy_network() -> Vec<Setting> {
+    vec![
+        setting(
+            "transport/link/tx/lease",
+            json!(30_000),
+            "Keep links open through up to 30s without any message, instead of 10s",
+        ),
+        setting(
+            "transport/link/tx/keep_alive",
+            json!(10),
+            "Send 10 keep-alive messages per lease, so that losing a few doesn't expire it",
+        ),
+        setting(
+            "transport/unicast/open_timeout",
+            json!(30_000),
+            "Give up to 30s to open links, for handshakes needing retransmissions",
+        ),
+        setting(
+            "transport/unicast/accept_timeout",
+            json!(30_000),
+            "Give up to 30s to accept links, for handshakes needing retransmissions",
+        ),
+        setting(
+            "transport/link/tx/queue/congestion_control/block/wait_before_close",
+            json!(30_000_000),
+            "Wait up to 30s for congested queues to drain before closing the session",
+        ),
+    ]
+}
+
+/// Settings of a transport profile
+fn profile_settings(profile: TransportProfile) -> Vec<Setting> {
+    match profile {
+        TransportProfile::LowLatency => low_latency(),
+        TransportProfile::HighThroughput => high_throughput(),
+        TransportProfile::LossyNetwork => lossy_network(),
+    }
+}
+
+/// Apply the settings of a goal to a config, validating the result
+fn tune(config: ZenohConfigJson, settings: Vec<Setting>) -> Result<TunedConfig, String> {
+    let mut json = config.as_json().clone();
+    let mut changes = Vec::new();
+    for setting in settings {
+        let old_value = json_set_path(&mut json, setting.path, setting.value.clone());
+        if old_value != setting.value {
+            changes.push(TuningChange {
+                path: setting.path.to_string(),
+                old_value,
+                new_value: setting.value,
+                explanation: setting.explanation.to_string(),
+            });
+        }
+    }
+    Ok(TunedConfig {
+        config: ZenohConfigJson::from_json(json)?,
+        changes,
+    })
+}
+
+// ============================================================================
+// Tauri commands
+// ============================================================================
+
+/// Tune a config for the lowest latency, at the cost of throughput
+#[tauri::command]
+#[tracing::instrument(skip_all)]
+pub async fn tune_for_low_latency(config: ZenohConfigJson) -> Result<TunedConfig, SandboxError> {
+    Ok(tune(config, low_latency())?)
+}
+
+/// Tune a config for the highest throughput, at the cost of latency and memory
+#[tauri::command]
+#[tracing::instrument(skip_all)]
+pub async fn tune_for_high_throughput(
+    config: ZenohConfigJson,
+) -> Result<TunedConfig, SandboxError> {
+    Ok(tune(config, high_throughput())?)
+}
+
+/// Tune a config to keep sessions up over networks losing or delaying packets,
+/// at the cost of detecting dead peers later
+#[tauri::command]
+#[tracing::instrument(skip_all)]
+pub async fn tune_for_lossy_network(config: ZenohConfigJson) -> Result<TunedConfig, SandboxError> {
+    Ok(tune(config, lossy_network())?)
+}
+
+/// Apply the settings of a transport profile to the declared config of a runtime.
+/// The change is annotated in the timeline and the runtime's log stream, and takes effect
+/// the next time the runtime is started.
+/// Returns the diff between the old and new config.
+#[tauri::command]
+#[tracing::instrument(skip(app))]
+pub async fn apply_transport_profile(
+    runtime_id: RuntimeId,
+    profile: TransportProfile,
+    app: AppHandle,
+) -> Result<JsonValue, SandboxError> {
+    let runtimes = app.state::<ZenohRuntimes>();
+    let (old_config, new_config) = {
+        let mut runtime_processes = runtimes.runtimes.write().await;
+        let runtime_process = runtime_processes
+            .get_mut(&runtime_id)
+            .ok_or(SandboxError::RuntimeNotFound(runtime_id))?;
+        let tuned = tune(
+            runtime_process.sandbox_config.clone(),
+            profile_settings(profile),
+        )
+        .map_err(SandboxError::ConfigInvalid)?;
+        (
+            runtime_process.replace_config(tuned.config.clone(), ConfigChangeSource::Patch),
+            tuned.config,
+        )
+    };
+    tracing::info!(
+        "Transport profile {:?} applied to runtime {}",
+        profile,
+        runtime_id
+    );
+    Ok(app.state::<Timeline>().annotate_config_change(
+        runtime_id,
+        ConfigChangeSource::Patch,
+        &old_config,
+        &new_config,
+        Some(app.state::<LogStorage>().inner()),
+    ))
+}
@@ -0,0 +1,286 @@
+use std::{
+    sync::atomic::Ordering,
+    time::{Duration, Instant},
+};
+
+use tauri::{AppHandle, Emitter, Manager};
+use tokio::task::JoinSet;
+
+use crate::{
+    RuntimeId, ZenohRuntimes, is_port_listening,
+    logs::LogStorage,
+    protocol::MISSED_PINGS_LIMIT,
+    timeline::Timeline,
+    ts::{
+        log::LogEntryLevel,
+        runtime::{RuntimeState, RuntimeUnresponsiveEvent, WatchdogAction},
+        timeline::TimelineEventKind,
+    },
+};
+
+// ============================================================================
+// Constants
+// ============================================================================
+
+/// Interval between two checks of the running runtimes
+const WATCHDOG_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Time without any message from a runtime process after which its stream is stalled
+const STALL_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Time for the remote_api websocket port to accept a connection
+const WEBSOCKET_PROBE_TIMEOUT: Duration = Duration::from_secs(2);
+
+// ============================================================================
+// Watchdog
+// ============================================================================
+//
+// A runtime process may hang without exiting. Every running runtime is checked
+// periodically for three signals: keepalive pings left unanswered, a stalled message
+// stream and an unreachable remote_api websocket port. A runtime missing several
+// pings in a row while one of the other signals also fails is declared
+// Unresponsive, and the action of its options is taken. It is Running again as soon
+// as it answers a ping.
+
+/// Running runtime checked by the watchdog
+struct Watched {
+    runtime_id: RuntimeId,
+    missed_pings: u32,
+    port: u16,
+    last_message_at: Instant,
+}
+
+/// Signals observed on a runtime by a check
+struct Probe {
+    runtime_id: RuntimeId,
+    missed_pings: u32,
+    websocket_reachable: bool,
+    last_message_at: Instant,
+}
+
+/// Change of the state of a runtime after a check
+enum Transition {
+    Unresponsive {
+        reason: String,
+        action: WatchdogAction,
+        pid: Option<u32>,
+    },
+    Recovered,
+}
+
+impl ZenohRuntimes {
+    /// Runtimes to check: the running and unresponsive ones
+    async fn watched(&self) -> Vec<Watched> {
+        let runtimes = self.runtimes.read().await;
+        runtimes
+            .iter()
+            .filter(|(_, runtime_process)| {
+                matches!(
+                    runtime_process.state,
+                    RuntimeState::Running | RuntimeState::Unresponsive
+                )
+            })
+            .map(|(runtime_id, runtime_process)| Watched {
+                runtime_id: *runtime_id,
+                missed_pings: runtime_process.missed_pings.load(Ordering::Relaxed),
+                port: runtime_process.allocated_port,
+                last_message_at: *runtime_process.last_message_at.lock(),
+            })
+            .collect()
+    }
+
+    /// Record the signals of a check and update the state of the runtime
+    async fn record_probe(&self, probe: Probe) -> Option<Transition> {
+        let mut runtimes = self.runtimes.write().await;
+        let runtime_process = runtimes.get_mut(&probe.runtime_id)?;
+        if !matches!(
+            runtime_process.state,
+            RuntimeState::Running | RuntimeState::Unresponsive
+        ) {
+            return None;
+        }
+
+        let watchdog = &mut runtime_process.watchdog;
+        watchdog.missed_pings = probe.missed_pings;
+        let silence = probe.last_message_at.elapsed();
+        watchdog.last_message_secs = Some(silence.as_secs());
+        watchdog.websocket_reachable = Some(probe.websocket_reachable);
+
+        let stalled = silence >= STALL_TIMEOUT;
+        let unresponsive =
+            watchdog.missed_pings >= MISSED_PINGS_LIMIT && (stalled || !probe.websocket_reachable);
+        match runtime_process.state {
+            RuntimeState::Running if unresponsive => {
+                let reason = format!(
+                    "{} missed pings, no message for {}s, websocket port {} {}",
+                    watchdog.missed_pings,
+                    silence.as_secs(),
+                    runtime_process.allocated_port,
+                    if probe.websocket_reachable {
+                        "reachable"
+                    } else {
+                        "unreachable"
+                    }
+                );
+                watchdog.reason = Some(reason.clone());
+                runtime_process.state = RuntimeState::Unresponsive;
+                Some(Transition::Unresponsive {
+                    reason,
+                    action: runtime_process.options.watchdog_action.unwrap_or_default(),
+                    pid: runtime_process.pid,
+                })
+            }
+            RuntimeState::Unresponsive if probe.missed_pings == 0 => {
+                watchdog.reason = None;
+                runtime_process.state = RuntimeState::Running;
+                Some(Transition::Recovered)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Check the signals of a runtime
+async fn probe(watched: Watched) -> Probe {
+    Probe {
+        runtime_id: watched.runtime_id,
+        missed_pings: watched.missed_pings,
+        websocket_reachable: is_port_listening(watched.port, WEBSOCKET_PROBE_TIMEOUT).await,
+        last_message_at: watched.last_message_at,
+    }
+}
+
+/// Send SIGQUIT to a runtime process
+#[cfg(unix)]
+fn capture_stack(pid: u32) -> Result<(), String> {
+    // SAFETY: kill has no memory safety preconditions
+    if unsafe { libc::kill(pid as libc::pid_t, libc::SIGQUIT) } != 0 {
+        return Err(std::io::Error::last_os_error().to_string());
+    }
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn capture_stack(_pid: u32) -> Result<(), String> {
+    Err("Capturing the stack is only supported on Unix".to_string())
+}
+
+/// Report a runtime declared unresponsive and take the action of its options
+async fn handle_unresponsive(
+    app: &AppHandle,
+    runtime_id: RuntimeId,
+    reason: String,
+    action: WatchdogAction,
+    pid: Option<u32>,
+) {
+    tracing::warn!(runtime_id, "Runtime unresponsive: {}", reason);
+    let logs = app.state::<LogStorage>();
+    logs.add_sandbox_log(
+        runtime_id,
+        LogEntryLevel::ERROR,
+        format!("Runtime unresponsive: {}", reason),
+    );
+    app.state::<Timeline>().add_event(
+        Some(runtime_id),
+        format!("Runtime unresponsive: {}", reason),
+        TimelineEventKind::Unresponsive {
+            reason: reason.clone(),
+            action,
+        },
+    );
+    let _ = app.emit(
+        "runtime-unresponsive",
+        RuntimeUnresponsiveEvent {
+            runtime_id,
+            reason,
+            action,
+        },
+    );
+
+    match action {
+        WatchdogAction::Alert => {}
+        WatchdogAction::CaptureStack => {
+            let result = pid
+                .ok_or_else(|| "PID unknown".to_string())
+                .and_then(capture_stack);
+            let (level, message) = match result {
+                Ok(()) => (
+                    LogEntryLevel::WARN,
+                    "Sent SIGQUIT to the runtime process, see its stderr output and core dump"
+                        .to_string(),
+                ),
+                Err(e) => (
+                    LogEntryLevel::ERROR,
+                    format!("Failed to capture the stack of the runtime process: {}", e),
+                ),
+            };
+            logs.add_sandbox_log(runtime_id, level, message);
+        }
+        WatchdogAction::Restart => {
+            let runtimes = app.state::<ZenohRuntimes>();
+            let result = match runtimes.shutdown(runtime_id, false).await {
+                Ok(()) => runtimes.launch(app, runtime_id).await.map(|_| ()),
+                Err(e) => Err(e),
+            };
+            let (level, message) = match result {
+                Ok(()) => (
+                    LogEntryLevel::WARN,
+                    "Restarted the unresponsive runtime".to_string(),
+                ),
+                Err(e) => (
+                    LogEntryLevel::ERROR,
+                    format!("Failed to restart the unresponsive runtime: {}", e),
+                ),
+            };
+            logs.add_sandbox_log(runtime_id, level, message);
+        }
+    }
+}
+
+/// Check every running runtime, reporting runtimes becoming unresponsive or recovering
+async fn check_runtimes(app: &AppHandle) {
+    let runtimes = app.state::<ZenohRuntimes>();
+    let mut probes = JoinSet::new();
+    for watched in runtimes.watched().await {
+        probes.spawn(probe(watched));
+    }
+
+    while let Some(joined) = probes.join_next().await {
+        let Ok(probe) = joined else {
+            continue;
+        };
+        let runtime_id = probe.runtime_id;
+        match runtimes.record_probe(probe).await {
+            Some(Transition::Unresponsive {
+                reason,
+                action,
+                pid,
+            }) => {
+                // Restarting takes a while, don't delay the other runtimes
+                let app = app.clone();
+                tauri::async_runtime::spawn(async move {
+                    handle_unresponsive(&app, runtime_id, reason, action, pid).await;
+                });
+            }
+            Some(Transition::Recovered) => {
+                tracing::info!(runtime_id, "Runtime responsive again");
+                app.state::<LogStorage>().add_sandbox_log(
+                    runtime_id,
+                    LogEntryLevel::INFO,
+                    "Runtime responsive again".to_string(),
+                );
+            }
+            None => {}
+        }
+    }
+}
+
+/// Periodically check the running runtimes, for the lifetime of the app
+pub(crate) async fn monitor_runtimes(app: AppHandle) {
+    let mut interval = tokio::time::interval(WATCHDOG_INTERVAL);
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+    loop {
+        interval.tick().await;
+        check_runtimes(&app).await;
+    }
+}
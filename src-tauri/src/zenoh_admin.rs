@@ -0,0 +1,229 @@
+use std::time::Duration;
+
+use serde::Deserialize;
+use tauri::State;
+
+use crate::{
+    RuntimeId, ZenohRuntimes,
+    error::SandboxError,
+    protocol::QueryReply,
+    ts::admin::{AdminNodeInfo, AdminRouteEntry, AdminSessionInfo, AdminspaceEntry},
+};
+
+// ============================================================================
+// Constants
+// ============================================================================
+
+/// Maximum time to wait for adminspace replies
+const ADMIN_QUERY_TIMEOUT: Duration = Duration::from_secs(2);
+
+// ============================================================================
+// Adminspace queries
+// ============================================================================
+
+/// Query the adminspace through the session of a running runtime
+async fn query_adminspace(
+    runtimes: &ZenohRuntimes,
+    runtime_id: RuntimeId,
+    selector: String,
+) -> Result<Vec<AdminspaceEntry>, SandboxError> {
+    let replies = runtimes
+        .query(runtime_id, selector, ADMIN_QUERY_TIMEOUT)
+        .await?;
+    let mut entries: Vec<AdminspaceEntry> = replies
+        .into_iter()
+        .filter(|reply| !reply.is_error)
+        .map(to_entry)
+        .collect();
+    entries.sort_by(|a, b| a.key.cmp(&b.key));
+    Ok(entries)
+}
+
+/// Convert a reply into an adminspace entry
+fn to_entry(reply: QueryReply) -> AdminspaceEntry {
+    let value = serde_json::from_slice(&reply.payload).unwrap_or_else(|_| {
+        serde_json::Value::String(String::from_utf8_lossy(&reply.payload).to_string())
+    });
+    AdminspaceEntry {
+        key: reply.key_expr,
+        value,
+    }
+}
+
+/// Get the node information of a running runtime from its own adminspace
+async fn local_node(
+    runtimes: &ZenohRuntimes,
+    runtime_id: RuntimeId,
+) -> Result<AdminNodeInfo, SandboxError> {
+    let zid = runtimes.running_zenoh_id(runtime_id).await?;
+    let entries = query_adminspace(runtimes, runtime_id, format!("@/{zid}/*")).await?;
+    entries.into_iter().find_map(to_node_info).ok_or_else(|| {
+        SandboxError::Other(format!(
+            "No adminspace information for runtime {}",
+            runtime_id
+        ))
+    })
+}
+
+/// Get the transport sessions of a running runtime from its adminspace
+pub(crate) async fn local_sessions(
+    runtimes: &ZenohRuntimes,
+    runtime_id: RuntimeId,
+) -> Result<Vec<AdminSessionInfo>, SandboxError> {
+    Ok(local_node(runtimes, runtime_id).await?.sessions)
+}
+
+/// Get the locators a running runtime listens on, from its adminspace.
+/// Loopback locators are preferred when there are any, since runtimes share the host.
+pub(crate) async fn local_locators(
+    runtimes: &ZenohRuntimes,
+    runtime_id: RuntimeId,
+) -> Result<Vec<String>, SandboxError> {
+    let locators = local_node(runtimes, runtime_id).await?.locators;
+    let loopback: Vec<String> = locators
+        .iter()
+        .filter(|locator| locator.contains("/127.") || locator.contains("/[::1]"))
+        .cloned()
+        .collect();
+    Ok(if loopback.is_empty() {
+        locators
+    } else {
+        loopback
+    })
+}
+
+/// Get the nodes whose adminspace is reachable from a running runtime, including itself
+pub(crate) async fn known_nodes(
+    runtimes: &ZenohRuntimes,
+    runtime_id: RuntimeId,
+) -> Result<Vec<AdminNodeInfo>, SandboxError> {
+    let entries = query_adminspace(runtimes, runtime_id, "@/*/*".to_string()).await?;
+    Ok(entries
+        .into_iter()
+        .filter_map(to_node_info)
+        .filter(|node| matches!(node.whatami.as_str(), "router" | "peer" | "client"))
+        .collect())
+}
+
+/// Payload of `@/<zid>/<whatami>/subscriber/<resource>`: the nodes that declared a subscriber
+#[derive(Deserialize)]
+struct SubscriberSources {
+    #[serde(default)]
+    routers: Vec<String>,
+    #[serde(default)]
+    peers: Vec<String>,
+    #[serde(default)]
+    clients: Vec<String>,
+}
+
+/// Get the resources intersecting `keyexpr` in the subscriber table of a running runtime,
+/// with the ZenohIds of the nodes subscribed to each of them
+pub(crate) async fn subscriber_sources(
+    runtimes: &ZenohRuntimes,
+    runtime_id: RuntimeId,
+    keyexpr: &str,
+) -> Result<Vec<(String, Vec<String>)>, SandboxError> {
+    let zid = runtimes.running_zenoh_id(runtime_id).await?;
+    let entries = query_adminspace(
+        runtimes,
+        runtime_id,
+        format!("@/{zid}/*/subscriber/{keyexpr}"),
+    )
+    .await?;
+    Ok(entries
+        .into_iter()
+        .filter_map(|entry| {
+            let (_, resource) = entry.key.split_once("/subscriber/")?;
+            let sources: SubscriberSources = serde_json::from_value(entry.value).ok()?;
+            let zids = [sources.routers, sources.peers, sources.clients].concat();
+            Some((resource.to_string(), zids))
+        })
+        .collect())
+}
+
+/// Payload of `@/<zid>/<whatami>`, only the fields rendered by the UI
+#[derive(Deserialize)]
+struct NodeData {
+    zid: String,
+    version: Option<String>,
+    #[serde(default)]
+    locators: Vec<String>,
+    #[serde(default)]
+    sessions: Vec<AdminSessionInfo>,
+}
+
+/// Parse a node entry, whose key is `@/<zid>/<whatami>`
+fn to_node_info(entry: AdminspaceEntry) -> Option<AdminNodeInfo> {
+    let whatami = entry.key.rsplit('/').next()?.to_string();
+    let data: NodeData = serde_json::from_value(entry.value).ok()?;
+    Some(AdminNodeInfo {
+        zid: data.zid,
+        whatami,
+        version: data.version,
+        locators: data.locators,
+        sessions: data.sessions,
+    })
+}
+
+// ============================================================================
+// Tauri commands
+// ============================================================================
+
+/// Get the adminspace entries matching `selector` (e.g. `@/**`) as seen by a running runtime
+#[tauri::command]
+#[tracing::instrument(skip(state))]
+pub async fn admin_query(
+    runtime_id: RuntimeId,
+    selector: String,
+    state: State<'_, ZenohRuntimes>,
+) -> Result<Vec<AdminspaceEntry>, SandboxError> {
+    if !selector.starts_with("@/") {
+        return Err(format!("Not an adminspace selector: {}", selector).into());
+    }
+    query_adminspace(&state, runtime_id, selector).await
+}
+
+/// List the routers reachable from a running runtime
+#[tauri::command]
+#[tracing::instrument(skip(state))]
+pub async fn admin_list_routers(
+    runtime_id: RuntimeId,
+    state: State<'_, ZenohRuntimes>,
+) -> Result<Vec<AdminNodeInfo>, SandboxError> {
+    let entries = query_adminspace(&state, runtime_id, "@/*/router".to_string()).await?;
+    Ok(entries.into_iter().filter_map(to_node_info).collect())
+}
+
+/// List the transport sessions of a running runtime
+#[tauri::command]
+#[tracing::instrument(skip(state))]
+pub async fn admin_list_sessions(
+    runtime_id: RuntimeId,
+    state: State<'_, ZenohRuntimes>,
+) -> Result<Vec<AdminSessionInfo>, SandboxError> {
+    local_sessions(&state, runtime_id).await
+}
+
+/// Get the routing table of a running runtime: the successor on the route
+/// between each pair of routers. Empty if the runtime is not a router.
+#[tauri::command]
+#[tracing::instrument(skip(state))]
+pub async fn admin_get_routes(
+    runtime_id: RuntimeId,
+    state: State<'_, ZenohRuntimes>,
+) -> Result<Vec<AdminRouteEntry>, SandboxError> {
+    let zid = state.running_zenoh_id(runtime_id).await?;
+    let prefix = format!("@/{zid}/router/route/successor/src/");
+    let entries = query_adminspace(&state, runtime_id, format!("{prefix}**")).await?;
+    Ok(entries
+        .into_iter()
+        .filter_map(|entry| {
+            let (source, destination) = entry.key.strip_prefix(&prefix)?.split_once("/dst/")?;
+            Some(AdminRouteEntry {
+                source: source.to_string(),
+                destination: destination.to_string(),
+                successor: entry.value.as_str()?.to_string(),
+            })
+        })
+        .collect())
+}
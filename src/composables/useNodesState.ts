@@ -11,6 +11,7 @@ import {
   computeConfigDiff,
 } from '../types/zenohConfig';
 import { LogEntryLevel } from '../types/generated/LogEntryLevel';
+import { errorMessage } from '../utils/errors';
 
 interface ActivityLogEntry {
   timestamp: Date;
@@ -175,7 +176,7 @@ export function useNodesState() {
       addActivityLog('success', `Cloned ${entry.config.mode} config`);
       return newConfigId;
     } catch (error) {
-      addActivityLog('error', `Failed to clone config: ${error}`);
+      addActivityLog('error', `Failed to clone config: ${errorMessage(error)}`);
       throw error;
     }
   };
@@ -295,7 +296,7 @@ export function useNodesState() {
 
       // Don't navigate away - keep viewing the stopped runtime's logs
     } catch (error) {
-      addActivityLog('error', `Failed to stop runtime: ${error}`);
+      addActivityLog('error', `Failed to stop runtime: ${errorMessage(error)}`);
     }
   };
 
@@ -326,7 +327,7 @@ export function useNodesState() {
         navigateToActivityLog();
       }
     } catch (error) {
-      addActivityLog('error', `Failed to cleanup runtime: ${error}`);
+      addActivityLog('error', `Failed to cleanup runtime: ${errorMessage(error)}`);
     }
   };
 
@@ -356,7 +357,7 @@ export function useNodesState() {
 
       addActivityLog('info', `Initialized with default config`);
     } catch (error) {
-      addActivityLog('error', `Failed to initialize config: ${error}`);
+      addActivityLog('error', `Failed to initialize config: ${errorMessage(error)}`);
     }
   };
 
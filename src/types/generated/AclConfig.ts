@@ -0,0 +1,14 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { AclPermission } from "./AclPermission";
+import type { AclPolicy } from "./AclPolicy";
+import type { AclRule } from "./AclRule";
+import type { AclSubject } from "./AclSubject";
+
+/**
+ * `access_control` section of a zenoh config
+ */
+export type AclConfig = { enabled: boolean, 
+/**
+ * Permission of the messages matched by no policy
+ */
+default_permission: AclPermission, rules: Array<AclRule>, subjects: Array<AclSubject>, policies: Array<AclPolicy>, };
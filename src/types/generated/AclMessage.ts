@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Kind of message an access control rule applies to
+ */
+export type AclMessage = "put" | "delete" | "declare_subscriber" | "query" | "declare_queryable" | "reply" | "liveliness_token" | "declare_liveliness_subscriber" | "liveliness_query";
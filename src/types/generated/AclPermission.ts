@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Whether the messages matched by an access control rule are let through
+ */
+export type AclPermission = "allow" | "deny";
@@ -0,0 +1,14 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Application of rules to subjects
+ */
+export type AclPolicy = { id: string | null, 
+/**
+ * Ids of the rules
+ */
+rules: Array<string>, 
+/**
+ * Ids of the subjects
+ */
+subjects: Array<string>, };
@@ -0,0 +1,13 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { AclMessage } from "./AclMessage";
+import type { AclPermission } from "./AclPermission";
+import type { InterceptorFlow } from "./InterceptorFlow";
+
+/**
+ * Permission for messages on key expressions, referenced by policies through its id
+ */
+export type AclRule = { id: string, key_exprs: Array<string>, messages: Array<AclMessage>, 
+/**
+ * Directions the rule applies to, both if unset
+ */
+flows: Array<InterceptorFlow> | null, permission: AclPermission, };
@@ -0,0 +1,27 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Remote nodes matched by the transports they connect through, referenced by policies
+ * through its id. A transport matches if it matches all the set criteria.
+ */
+export type AclSubject = { id: string, 
+/**
+ * Network interfaces of the transports, e.g. `lo`
+ */
+interfaces: Array<string> | null, 
+/**
+ * Common names of the TLS certificates of the remote nodes
+ */
+cert_common_names: Array<string> | null, 
+/**
+ * Usernames the remote nodes authenticated with
+ */
+usernames: Array<string> | null, 
+/**
+ * Link protocols of the transports, e.g. `tcp`
+ */
+link_protocols: Array<string> | null, 
+/**
+ * ZenohIds of the remote nodes
+ */
+zids: Array<string> | null, };
@@ -0,0 +1,20 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { KeyExprUser } from "./KeyExprUser";
+
+/**
+ * Key expression used by test entities, see `list_active_keyexprs`
+ */
+export type ActiveKeyExpr = { keyexpr: string, 
+/**
+ * Entities using the key expression, ordered by id
+ */
+users: Array<KeyExprUser>, 
+/**
+ * Other active key expressions intersecting this one
+ */
+intersecting: Array<string>, 
+/**
+ * Whether samples produced on the key expression reach no consumer,
+ * or its consumers match no producer
+ */
+orphaned: boolean, };
@@ -0,0 +1,14 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * A link of a transport session
+ */
+export type AdminLinkInfo = { 
+/**
+ * Source locator
+ */
+src: string, 
+/**
+ * Destination locator
+ */
+dst: string, };
@@ -0,0 +1,27 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { AdminSessionInfo } from "./AdminSessionInfo";
+
+/**
+ * Information published by a node at `@/<zid>/<whatami>`
+ */
+export type AdminNodeInfo = { 
+/**
+ * ZenohId of the node
+ */
+zid: string, 
+/**
+ * Kind of the node: router, peer or client
+ */
+whatami: string, 
+/**
+ * Zenoh version of the node
+ */
+version: string | null, 
+/**
+ * Locators the node listens on
+ */
+locators: Array<string>, 
+/**
+ * Transport sessions of the node
+ */
+sessions: Array<AdminSessionInfo>, };
@@ -0,0 +1,18 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Next hop from `source` to `destination` in the router graph
+ */
+export type AdminRouteEntry = { 
+/**
+ * ZenohId of the source router
+ */
+source: string, 
+/**
+ * ZenohId of the destination router
+ */
+destination: string, 
+/**
+ * ZenohId of the successor of `source` on the route
+ */
+successor: string, };
@@ -0,0 +1,23 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { AdminLinkInfo } from "./AdminLinkInfo";
+
+/**
+ * A transport session between a node and one of its neighbours
+ */
+export type AdminSessionInfo = { 
+/**
+ * ZenohId of the remote node
+ */
+peer: string, 
+/**
+ * Kind of the remote node: router, peer or client
+ */
+whatami: string, 
+/**
+ * Links of the session
+ */
+links: Array<AdminLinkInfo>, 
+/**
+ * Whether the session uses shared memory
+ */
+shm: boolean, };
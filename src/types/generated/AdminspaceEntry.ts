@@ -0,0 +1,14 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * An entry of the `@/` adminspace
+ */
+export type AdminspaceEntry = { 
+/**
+ * Key expression of the entry
+ */
+key: string, 
+/**
+ * Value of the entry, parsed as JSON when possible, otherwise as a string
+ */
+value: any, };
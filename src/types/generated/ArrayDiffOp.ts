@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Element-level change of an array, applied in order to the base array.
+ * Indices refer to the array as left by the previous operations.
+ */
+export type ArrayDiffOp = { "op": "add", index: number, value: any, } | { "op": "remove", index: number, } | { "op": "replace", index: number, value: any, };
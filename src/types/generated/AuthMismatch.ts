@@ -0,0 +1,16 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { AuthMismatchReason } from "./AuthMismatchReason";
+
+/**
+ * Link between runtimes whose user-password authentication would fail,
+ * found before starting them
+ */
+export type AuthMismatch = { 
+/**
+ * Runtime opening the link
+ */
+from: number, 
+/**
+ * Runtime accepting the link, checking the credentials
+ */
+to: number, reason: AuthMismatchReason, message: string, };
@@ -0,0 +1,19 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { RuntimeOperationOutcome } from "./RuntimeOperationOutcome";
+
+/**
+ * Aggregated result of an operation applied to several runtimes
+ */
+export type BulkOperationReport = { 
+/**
+ * Runtimes for which the operation succeeded
+ */
+succeeded: Array<number>, 
+/**
+ * Runtimes for which the operation failed
+ */
+failed: Array<number>, 
+/**
+ * Per-runtime outcomes, ordered by RuntimeId
+ */
+outcomes: Array<RuntimeOperationOutcome>, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Outcome of an environment check. Ordered by severity.
+ */
+export type CheckStatus = "pass" | "warn" | "fail";
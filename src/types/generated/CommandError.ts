@@ -0,0 +1,15 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { SandboxErrorKind } from "./SandboxErrorKind";
+
+/**
+ * Error returned by a command
+ */
+export type CommandError = { kind: SandboxErrorKind, 
+/**
+ * Human-readable description of the error
+ */
+message: string, 
+/**
+ * Runtime the error is about, for `runtime_not_found` and `runtime_not_started`
+ */
+runtime_id: number | null, };
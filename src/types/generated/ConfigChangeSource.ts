@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Origin of a configuration change
+ */
+export type ConfigChangeSource = "patch" | "restart" | "live_update" | "revert" | "template";
@@ -0,0 +1,27 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ZenohConfigJson } from "./ZenohConfigJson";
+
+/**
+ * Outcome of committing a config edit
+ */
+export type ConfigCommitResult = { "status": "committed", 
+/**
+ * Entity tag of the new config
+ */
+etag: string, 
+/**
+ * Diff between the previous and the new config
+ */
+diff: Record<string, any>, } | { "status": "conflict", 
+/**
+ * Config currently declared for the runtime
+ */
+current: ZenohConfigJson, 
+/**
+ * Entity tag of the current config
+ */
+etag: string, 
+/**
+ * Diff between the current config and the rejected one
+ */
+diff: Record<string, any>, };
@@ -0,0 +1,16 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ConfigDriftField } from "./ConfigDriftField";
+
+/**
+ * Differences between the running and declared configs of a runtime,
+ * see `zenoh_runtime_config_drift`
+ */
+export type ConfigDrift = { runtime_id: number, 
+/**
+ * Diff from the declared config to the running config, as produced by `json_diff`
+ */
+diff: Record<string, any>, 
+/**
+ * Differing fields, in the order of the diff
+ */
+fields: Array<ConfigDriftField>, };
@@ -0,0 +1,23 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Field of the running config of a runtime that differs from its declared config
+ */
+export type ConfigDriftField = { 
+/**
+ * Path of the field in the zenoh config key notation, e.g. `adminspace/enabled`
+ */
+path: string, 
+/**
+ * Value in the declared config, with the zenoh defaults; null if unset
+ */
+declared: any, 
+/**
+ * Value in the running config; null if unset
+ */
+effective: any, 
+/**
+ * What the sandbox changed the field for, None for changes it doesn't know about,
+ * e.g. live updates
+ */
+reason: string | null, };
@@ -0,0 +1,19 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ZenohConfigJson } from "./ZenohConfigJson";
+
+/**
+ * Config of a runtime checked out for editing
+ */
+export type ConfigEditSession = { 
+/**
+ * Current declared config of the runtime
+ */
+config: ZenohConfigJson, 
+/**
+ * Entity tag of `config`, required to commit the edit
+ */
+etag: string, 
+/**
+ * JSON5 text `config` was declared from, with its comments, if it was given
+ */
+source: string | null, };
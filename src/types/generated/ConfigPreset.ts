@@ -0,0 +1,19 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ZenohConfigJson } from "./ZenohConfigJson";
+
+/**
+ * A named configuration saved in the preset library
+ */
+export type ConfigPreset = { 
+/**
+ * Unique name of the preset
+ */
+name: string, 
+/**
+ * Free-form description, e.g. the role of the node
+ */
+description: string | null, created_at: string, updated_at: string, config: ZenohConfigJson, 
+/**
+ * JSON5 text of the config, with its comments, if it was given
+ */
+source?: string | null, };
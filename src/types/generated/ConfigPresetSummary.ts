@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Preset without its configuration, for listings
+ */
+export type ConfigPresetSummary = { name: string, description: string | null, updated_at: string, };
@@ -0,0 +1,16 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ConfigChangeSource } from "./ConfigChangeSource";
+import type { ZenohConfigJson } from "./ZenohConfigJson";
+
+/**
+ * Revision of the config of a runtime
+ */
+export type ConfigRevision = { 
+/**
+ * Number of the revision, increasing with each change of the runtime's config
+ */
+revision: number, timestamp: string, 
+/**
+ * How the config changed, None for the config the runtime was declared with
+ */
+source: ConfigChangeSource | null, config: ZenohConfigJson, };
@@ -0,0 +1,14 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Outcome of a config transform for one runtime
+ */
+export type ConfigTransformOutcome = { runtime_id: number, 
+/**
+ * Diff between the current and the transformed config
+ */
+diff: Record<string, any>, 
+/**
+ * Error message if the transformed config is invalid
+ */
+error: string | null, };
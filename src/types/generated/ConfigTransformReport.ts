@@ -0,0 +1,23 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ConfigTransformOutcome } from "./ConfigTransformOutcome";
+
+/**
+ * Result of applying a config transform to several runtimes
+ */
+export type ConfigTransformReport = { 
+/**
+ * Whether the configs were left unchanged
+ */
+dry_run: boolean, 
+/**
+ * Runtimes whose transformed config is valid (and was applied, unless `dry_run`)
+ */
+applied: Array<number>, 
+/**
+ * Runtimes whose transformed config is invalid
+ */
+failed: Array<number>, 
+/**
+ * Per-runtime outcomes, ordered by RuntimeId
+ */
+outcomes: Array<ConfigTransformOutcome>, };
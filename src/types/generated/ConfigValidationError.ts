@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Error of a config field, found by `validate_config_fields`
+ */
+export type ConfigValidationError = { 
+/**
+ * Path of the offending field in the zenoh config key notation, empty for the whole config
+ */
+path: string, message: string, };
@@ -0,0 +1,15 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { PayloadFormat } from "./PayloadFormat";
+
+/**
+ * Payload of a received sample rendered in a given format
+ */
+export type DecodedPayload = { sample_id: number, 
+/**
+ * Encoding of the payload, as sent by the publisher
+ */
+encoding: string, format: PayloadFormat, 
+/**
+ * Payload rendered in `format`
+ */
+text: string, };
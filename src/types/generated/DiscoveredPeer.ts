@@ -0,0 +1,23 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ZenohMode } from "./ZenohMode";
+
+/**
+ * Zenoh node seen by a runtime, found by scouting or through an open transport
+ */
+export type DiscoveredPeer = { zid: string, mode: ZenohMode, 
+/**
+ * Locators the node advertised in its scouting reply
+ */
+locators: Array<string>, 
+/**
+ * Whether the node answered the multicast scouting of the runtime
+ */
+scouted: boolean, 
+/**
+ * Whether the runtime has a transport session with the node
+ */
+connected: boolean, 
+/**
+ * Runtime of the sandbox with this ZenohId, if any
+ */
+runtime_id: number | null, };
@@ -0,0 +1,18 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { DownsamplingMessage } from "./DownsamplingMessage";
+import type { DownsamplingRule } from "./DownsamplingRule";
+import type { InterceptorFlow } from "./InterceptorFlow";
+
+/**
+ * Item of the `downsampling` list of a zenoh config.
+ * Unset interfaces, link protocols and flows match all of them.
+ */
+export type DownsamplingItem = { id: string | null, 
+/**
+ * Network interfaces of the transports the item applies to
+ */
+interfaces: Array<string> | null, 
+/**
+ * Link protocols of the transports the item applies to, e.g. `tcp`
+ */
+link_protocols: Array<string> | null, messages: Array<DownsamplingMessage>, rules: Array<DownsamplingRule>, flows: Array<InterceptorFlow> | null, };
@@ -0,0 +1,27 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Rate observed by a subscriber while a publisher puts at a known rate, see `verify_downsampling`
+ */
+export type DownsamplingReport = { publisher_runtime_id: number, subscriber_runtime_id: number, key_expr: string, 
+/**
+ * Rate of the publisher in Hertz, from its period rounded to the millisecond
+ */
+published_rate_hz: number, 
+/**
+ * Lowest frequency of the downsampling rules of the publisher's egress or the
+ * subscriber's ingress matching the key expression, None if no rule matches
+ */
+expected_rate_hz: number | null, 
+/**
+ * Number of samples received by the subscriber
+ */
+received: number, 
+/**
+ * Duration of the publication in milliseconds
+ */
+duration_ms: number, 
+/**
+ * Rate of the samples received by the subscriber in Hertz
+ */
+observed_rate_hz: number, };
@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Maximum frequency of the messages on a key expression
+ */
+export type DownsamplingRule = { key_expr: string, 
+/**
+ * Maximum frequency in Hertz
+ */
+freq: number, };
@@ -0,0 +1,19 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { CheckStatus } from "./CheckStatus";
+
+/**
+ * Result of a check of the environment the sandbox runs in
+ */
+export type EnvironmentCheck = { 
+/**
+ * Identifier of the check, such as `runtime_binary` or `multicast`
+ */
+name: string, status: CheckStatus, 
+/**
+ * What was found
+ */
+message: string, 
+/**
+ * How to fix the problem, unless the check passed
+ */
+remediation: string | null, };
@@ -0,0 +1,12 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { CheckStatus } from "./CheckStatus";
+import type { EnvironmentCheck } from "./EnvironmentCheck";
+
+/**
+ * Result of `environment_report`
+ */
+export type EnvironmentReport = { sandbox_version: string, 
+/**
+ * Worst status of the checks
+ */
+status: CheckStatus, checks: Array<EnvironmentCheck>, };
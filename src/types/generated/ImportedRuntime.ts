@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Runtime declared by `import_session`
+ */
+export type ImportedRuntime = { 
+/**
+ * RuntimeId in the session file
+ */
+previous_runtime_id: number, runtime_id: number, name: string | null, port: number, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Direction of the messages a downsampling item or an access control rule applies to
+ */
+export type InterceptorFlow = "egress" | "ingress";
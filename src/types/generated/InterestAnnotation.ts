@@ -0,0 +1,22 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Subscription interest of a runtime in a key expression
+ */
+export type InterestAnnotation = { runtime_id: number, zid: string, 
+/**
+ * Whether a session of the runtime itself has a matching subscriber
+ */
+local_subscriber: boolean, 
+/**
+ * Other nodes with a matching subscriber, known to the runtime
+ */
+remote_subscribers: Array<string>, 
+/**
+ * Resources of the runtime's subscriber routing table intersecting the key expression
+ */
+routing_entries: Array<string>, 
+/**
+ * Error if the runtime's adminspace couldn't be queried
+ */
+error: string | null, };
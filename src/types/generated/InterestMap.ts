@@ -0,0 +1,12 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { InterestAnnotation } from "./InterestAnnotation";
+
+/**
+ * Subscription interest of every running runtime in a key expression,
+ * to be overlaid on the topology graph
+ */
+export type InterestMap = { keyexpr: string, timestamp: string, 
+/**
+ * Annotations, ordered by RuntimeId
+ */
+nodes: Array<InterestAnnotation>, };
@@ -0,0 +1,24 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { TimelineEvent } from "./TimelineEvent";
+
+/**
+ * Timestamped observation of the experiment journal
+ */
+export type JournalEntry = { 
+/**
+ * Identifier of the entry, increasing with each entry
+ */
+id: number, timestamp: string, 
+/**
+ * Free text, in Markdown
+ */
+text: string, 
+/**
+ * Runtime the entry is about, if any
+ */
+runtime_id: number | null, 
+/**
+ * Timeline events the entry refers to, as they were when the entry was added,
+ * so that they outlive the timeline
+ */
+events: Array<TimelineEvent>, };
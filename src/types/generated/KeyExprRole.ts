@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * How a test entity uses its key expression
+ */
+export type KeyExprRole = "producer" | "consumer" | "queryable" | "liveliness_token" | "liveliness_subscriber";
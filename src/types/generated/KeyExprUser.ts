@@ -0,0 +1,11 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { KeyExprRole } from "./KeyExprRole";
+
+/**
+ * Test entity using a key expression
+ */
+export type KeyExprUser = { 
+/**
+ * Identifier of the test entity
+ */
+entity_id: number, runtime_id: number, role: KeyExprRole, };
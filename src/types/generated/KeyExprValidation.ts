@@ -0,0 +1,18 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Result of `validate_keyexpr`
+ */
+export type KeyExprValidation = { 
+/**
+ * Whether the expression is a valid key expression as is
+ */
+valid: boolean, 
+/**
+ * Canonical form of the expression, e.g. `a/**` for `a/**/**`, if it has one
+ */
+canonical: string | null, 
+/**
+ * Why the expression is not valid
+ */
+error: string | null, };
@@ -0,0 +1,26 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Round-trip time measurements from one runtime to another
+ */
+export type LatencyCell = { 
+/**
+ * When the measurement of this pair completed
+ */
+timestamp: string, 
+/**
+ * Minimum round-trip time in microseconds
+ */
+min_us: number | null, 
+/**
+ * Median round-trip time in microseconds
+ */
+median_us: number | null, 
+/**
+ * Maximum round-trip time in microseconds
+ */
+max_us: number | null, 
+/**
+ * Error if the pair could not be measured
+ */
+error: string | null, };
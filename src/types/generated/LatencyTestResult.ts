@@ -0,0 +1,30 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Round-trip time percentiles of a latency test between two runtimes
+ */
+export type LatencyTestResult = { 
+/**
+ * Identifier of the test, to include it in exported reports
+ */
+operation_id: number, started_at: string, completed_at: string, 
+/**
+ * Runtime sending the queries
+ */
+runtime_a: number, 
+/**
+ * Runtime answering them with its echo queryable
+ */
+runtime_b: number, 
+/**
+ * Number of round trips measured
+ */
+samples: number, 
+/**
+ * Size in bytes of the payload of each query and reply
+ */
+payload_size: number, 
+/**
+ * Round-trip times in microseconds
+ */
+min_us: number, median_us: number, p95_us: number, p99_us: number, max_us: number, };
@@ -0,0 +1,23 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * A liveliness token appearing or disappearing, as seen by a liveliness subscriber.
+ * Payload of the `liveliness-event` event.
+ */
+export type LivelinessEvent = { 
+/**
+ * Identifier of the liveliness subscriber
+ */
+subscriber_id: number, 
+/**
+ * Runtime of the liveliness subscriber
+ */
+runtime_id: number, received_at: string, 
+/**
+ * Key expression of the token
+ */
+key_expr: string, 
+/**
+ * Whether the token appeared, or disappeared
+ */
+alive: boolean, };
@@ -0,0 +1,13 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { LogArchiveRuntime } from "./LogArchiveRuntime";
+import type { LogExportFormat } from "./LogExportFormat";
+import type { LogTimeRange } from "./LogTimeRange";
+
+/**
+ * Manifest of a log archive written by `export_all_logs`
+ */
+export type LogArchiveManifest = { exported_at: string, sandbox_version: string, format: LogExportFormat, range: LogTimeRange, 
+/**
+ * Name of the file merging the logs of all runtimes chronologically
+ */
+merged_file: string, runtimes: Array<LogArchiveRuntime>, };
@@ -0,0 +1,19 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { RuntimeStatus } from "./RuntimeStatus";
+
+/**
+ * Log file of a runtime in a log archive
+ */
+export type LogArchiveRuntime = { runtime_id: number, 
+/**
+ * Name of the file in the archive
+ */
+file: string, 
+/**
+ * Number of log entries in the file
+ */
+entries: number, 
+/**
+ * Status at export time, unset for the sandbox logs and removed runtimes
+ */
+status: RuntimeStatus | null, };
@@ -0,0 +1,37 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { LogEntryLevel } from "./LogEntryLevel";
+
+/**
+ * A single log entry
+ */
+export type LogEntry = { 
+/**
+ * Sequence number of the entry among the entries of its runtime, assigned when stored.
+ * Increases with each stored entry, so it can be used as a pagination cursor and to
+ * deduplicate entries. Entries dropped by the runtime process leave a gap in the numbers.
+ */
+seq: number, 
+/**
+ * Timestamp of the log entry
+ */
+timestamp: string, 
+/**
+ * Log level (e.g., "INFO", "DEBUG", "ERROR")
+ */
+level: LogEntryLevel, 
+/**
+ * The target module/component that produced the log
+ */
+target: string, 
+/**
+ * The log message
+ */
+message: string, 
+/**
+ * Fields of the event other than the message
+ */
+fields: { [key in string]?: string }, 
+/**
+ * Spans the event occurred in, outermost first, as `name{field=value ...}`
+ */
+spans: Array<string>, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Number of stored log entries per level
+ */
+export type LogLevelCounts = { trace: number, debug: number, info: number, warn: number, error: number, };
@@ -0,0 +1,21 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { LogEntry } from "./LogEntry";
+
+/**
+ * Page of log entries returned by `zenoh_runtime_log`
+ */
+export type LogPage = { 
+/**
+ * Entries, most recent first. The `seq` of the last one is the `before_seq` cursor
+ * of the next older page, the `seq` of the first one the `after_seq`
+ * cursor of the next newer page.
+ */
+entries: Array<LogEntry>, 
+/**
+ * Number of entries matching the filter and cursor, including those not in the page
+ */
+total_matching: number, 
+/**
+ * Whether more entries match than the page holds
+ */
+has_more: boolean, };
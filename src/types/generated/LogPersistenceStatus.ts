@@ -0,0 +1,14 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Whether log entries are persisted to disk
+ */
+export type LogPersistenceStatus = { enabled: boolean, 
+/**
+ * Directory of the persisted log files, if enabled
+ */
+dir: string | null, 
+/**
+ * Size in bytes above which a log file is rotated
+ */
+max_file_size: number, };
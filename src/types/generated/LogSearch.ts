@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Search in the message of log entries
+ */
+export type LogSearch = { 
+/**
+ * Case-insensitive substring, or regular expression if `regex` is set
+ */
+query: string, regex: boolean, };
@@ -0,0 +1,19 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { LogLevelCounts } from "./LogLevelCounts";
+
+/**
+ * Result of `get_log_stats`
+ */
+export type LogStats = { runtime_id: number, 
+/**
+ * Number of stored entries
+ */
+total: number, 
+/**
+ * Number of stored entries per level
+ */
+counts: LogLevelCounts, 
+/**
+ * Sequence numbers of the oldest and most recent stored entries
+ */
+oldest_seq: number | null, newest_seq: number | null, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Time range of log entries, bounds included. Unset bounds are open.
+ */
+export type LogTimeRange = { start: string | null, end: string | null, };
@@ -0,0 +1,11 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { LogLevelCounts } from "./LogLevelCounts";
+
+/**
+ * Number of log entries per level within a time bucket of `get_log_timeline`
+ */
+export type LogTimelineBucket = { 
+/**
+ * Start of the bucket, a multiple of the bucket duration since the Unix epoch
+ */
+start: string, counts: LogLevelCounts, };
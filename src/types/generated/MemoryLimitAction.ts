@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Action taken when a runtime process exceeds the memory limit of its options
+ */
+export type MemoryLimitAction = "alert" | "kill" | "restart";
@@ -0,0 +1,32 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { LatencyCell } from "./LatencyCell";
+
+/**
+ * Pairwise round-trip times between all running runtimes
+ */
+export type MeshLatencyMatrix = { 
+/**
+ * Identifier of the sweep, to include it in exported reports
+ */
+operation_id: number, 
+/**
+ * When the sweep started
+ */
+started_at: string, 
+/**
+ * When the sweep completed
+ */
+completed_at: string, 
+/**
+ * Runtimes included in the sweep, indexing rows and columns of `cells`
+ */
+runtime_ids: Array<number>, 
+/**
+ * ZenohIds of the runtimes, in the same order as `runtime_ids`
+ */
+zenoh_ids: Array<string>, 
+/**
+ * `cells[i][j]` holds the round-trip times from runtime i to runtime j,
+ * `None` on the diagonal
+ */
+cells: Array<Array<LatencyCell | null>>, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Kind of a recorded measurement operation
+ */
+export type OperationKind = "latency_sweep" | "latency_test";
@@ -0,0 +1,25 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { OperationKind } from "./OperationKind";
+import type { TopologySnapshot } from "./TopologySnapshot";
+import type { ZenohConfigJson } from "./ZenohConfigJson";
+
+/**
+ * A recorded operation with its result and the context it ran in
+ */
+export type OperationRecord = { 
+/**
+ * Result of the operation, as returned by its command
+ */
+result: any, 
+/**
+ * Declared configuration of each involved runtime
+ */
+configs: { [key in number]?: ZenohConfigJson }, 
+/**
+ * Topology when the operation completed
+ */
+topology: TopologySnapshot, id: number, kind: OperationKind, started_at: string, completed_at: string, 
+/**
+ * Runtimes involved in the operation
+ */
+runtime_ids: Array<number>, };
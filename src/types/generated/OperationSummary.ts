@@ -0,0 +1,11 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { OperationKind } from "./OperationKind";
+
+/**
+ * Summary of a recorded operation
+ */
+export type OperationSummary = { id: number, kind: OperationKind, started_at: string, completed_at: string, 
+/**
+ * Runtimes involved in the operation
+ */
+runtime_ids: Array<number>, };
@@ -0,0 +1,11 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { OrphanAction } from "./OrphanAction";
+
+/**
+ * Result of `cleanup_orphans` for one orphan process
+ */
+export type OrphanCleanupOutcome = { pid: number, action: OrphanAction, 
+/**
+ * Error message if the action failed
+ */
+error: string | null, };
@@ -0,0 +1,18 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Runtime process left running by a previous sandbox instance
+ */
+export type OrphanProcess = { pid: number, 
+/**
+ * RuntimeId of the runtime in the previous sandbox instance
+ */
+runtime_id: number, 
+/**
+ * Port of its remote_api plugin
+ */
+port: number, started_at: string, 
+/**
+ * Whether the process has been adopted by this sandbox instance
+ */
+adopted: boolean, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Output stream of a runtime process, written to a file in the log directory
+ */
+export type OutputStream = "stdout" | "stderr";
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Rendering of the payload of a received sample
+ */
+export type PayloadFormat = "utf8" | "json" | "hex" | "base64";
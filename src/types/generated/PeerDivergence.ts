@@ -0,0 +1,14 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Difference between the expected peers of a runtime and its live transport sessions
+ */
+export type PeerDivergence = { 
+/**
+ * Expected peers without a transport session
+ */
+missing: Array<number>, 
+/**
+ * Sandbox runtimes with a transport session that were not expected
+ */
+unexpected: Array<number>, };
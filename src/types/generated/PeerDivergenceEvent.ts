@@ -0,0 +1,11 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { PeerDivergence } from "./PeerDivergence";
+
+/**
+ * Payload of the `runtime-peer-divergence` event, sent when the divergence changes
+ */
+export type PeerDivergenceEvent = { runtime_id: number, 
+/**
+ * New divergence, `null` once the runtime is connected to exactly its expected peers
+ */
+divergence: PeerDivergence | null, };
@@ -0,0 +1,15 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { PortConflictReason } from "./PortConflictReason";
+
+/**
+ * Port of a runtime's config that it can't listen on, found before starting it
+ */
+export type PortConflict = { port: number, 
+/**
+ * Config entry using the port: a listen endpoint, `plugins/remote_api` or `plugins/rest`
+ */
+usage: string, reason: PortConflictReason, 
+/**
+ * Runtime using the port, for `runtime` conflicts
+ */
+runtime_id: number | null, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Why a runtime can't listen on a port of its config
+ */
+export type PortConflictReason = "runtime" | "reserved" | "in_use";
@@ -0,0 +1,27 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Resource usage of a runtime process, sampled by the sandbox
+ */
+export type ProcessMetrics = { runtime_id: number, 
+/**
+ * Name of the runtime from its options
+ */
+name: string | null, pid: number, 
+/**
+ * CPU usage since the previous sample, in percent of one core.
+ * 0 for the first sample of a process.
+ */
+cpu_percent: number, 
+/**
+ * Resident set size in bytes
+ */
+memory_bytes: number, 
+/**
+ * Number of open file descriptors, if available on the platform
+ */
+open_files: number | null, 
+/**
+ * Number of threads, if available on the platform (Linux only)
+ */
+threads: number | null, };
@@ -0,0 +1,19 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * User and umask a runtime process runs with, as validated when declaring the runtime.
+ * Unset fields are inherited from the sandbox.
+ */
+export type ProcessPrivileges = { 
+/**
+ * Name of the user
+ */
+user: string | null, uid: number | null, 
+/**
+ * Primary group of the user
+ */
+gid: number | null, 
+/**
+ * File mode creation mask, in octal
+ */
+umask: string | null, };
@@ -0,0 +1,22 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * How to connect to the remote_api plugin of a runtime
+ */
+export type RemoteApiInfo = { runtime_id: number, 
+/**
+ * Port allocated for remote_api
+ */
+port: number, 
+/**
+ * WebSocket URL of the plugin, e.g. `ws://127.0.0.1:10000`
+ */
+url: string, 
+/**
+ * Locator to open a zenoh-ts session with, e.g. `ws/127.0.0.1:10000`
+ */
+locator: string, 
+/**
+ * Whether the port accepted a connection when probed
+ */
+listening: boolean, };
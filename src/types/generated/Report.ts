@@ -0,0 +1,15 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { OperationRecord } from "./OperationRecord";
+
+/**
+ * Shareable bundle of recorded operations
+ */
+export type Report = { generated_at: string, 
+/**
+ * Version of the sandbox
+ */
+sandbox_version: string, 
+/**
+ * Version of zenoh the runtimes are built with
+ */
+zenoh_version: string, operations: Array<OperationRecord>, };
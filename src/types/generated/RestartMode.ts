@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Exits of the runtime process after which the runtime is started again
+ */
+export type RestartMode = "never" | "on_crash" | "always";
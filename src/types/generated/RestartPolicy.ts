@@ -0,0 +1,18 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { RestartMode } from "./RestartMode";
+
+/**
+ * Automatic restart of a runtime whose process exits unexpectedly,
+ * keeping its RuntimeId and port
+ */
+export type RestartPolicy = { mode: RestartMode, 
+/**
+ * Maximum number of consecutive restarts. Defaults to 5.
+ * The count starts over once the runtime runs for a minute, or is stopped.
+ */
+max_attempts: number | null, 
+/**
+ * Delay in milliseconds before the first restart, doubled for each consecutive one
+ * up to a minute. Defaults to 1 s.
+ */
+backoff_ms: number | null, };
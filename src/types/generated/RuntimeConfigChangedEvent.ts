@@ -0,0 +1,11 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ConfigChangeSource } from "./ConfigChangeSource";
+
+/**
+ * Payload of the `runtime-config-changed` event
+ */
+export type RuntimeConfigChangedEvent = { runtime_id: number, source: ConfigChangeSource, 
+/**
+ * Diff between the old and new config, as produced by `json_diff`
+ */
+diff: Record<string, any>, };
@@ -0,0 +1,15 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { TopologyLink } from "./TopologyLink";
+
+/**
+ * Link wired by `connect_runtimes`
+ */
+export type RuntimeConnection = { link: TopologyLink, 
+/**
+ * Endpoints added to the connect endpoints of the `from` runtime
+ */
+endpoints: Array<string>, 
+/**
+ * Whether a transport was opened right away, both runtimes being running
+ */
+live: boolean, };
@@ -0,0 +1,14 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Payload of the `runtime-crashed` event
+ */
+export type RuntimeCrashedEvent = { runtime_id: number, 
+/**
+ * Exit code of the process, if it exited with one
+ */
+exit_code: number | null, 
+/**
+ * Human readable exit status
+ */
+status: string, };
@@ -0,0 +1,11 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ZenohMode } from "./ZenohMode";
+
+/**
+ * Payload of the `runtime-declared` event
+ */
+export type RuntimeDeclaredEvent = { runtime_id: number, 
+/**
+ * Mode of the declared config
+ */
+mode: ZenohMode, };
@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Named set of runtimes managed together, e.g. the routers of a backbone
+ */
+export type RuntimeGroup = { name: string, 
+/**
+ * Runtimes of the group, sorted. A runtime belongs to one group at most.
+ */
+runtime_ids: Array<number>, };
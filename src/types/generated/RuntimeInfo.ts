@@ -0,0 +1,24 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Files and endpoints of the last process of a runtime, to find its raw output
+ * and the process itself
+ */
+export type RuntimeInfo = { runtime_id: number, 
+/**
+ * PID of the runtime process, if running
+ */
+pid: number | null, 
+/**
+ * File receiving the stdout of the runtime process, once started
+ */
+stdout_log: string | null, 
+/**
+ * File receiving the stderr of the runtime process, once started
+ */
+stderr_log: string | null, 
+/**
+ * IPC endpoint the runtime process connected to: a socket path on Unix,
+ * a loopback TCP address elsewhere. The socket file is removed once connected.
+ */
+socket_path: string | null, };
@@ -0,0 +1,11 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { MemoryLimitAction } from "./MemoryLimitAction";
+
+/**
+ * Payload of the `runtime-memory-limit` event
+ */
+export type RuntimeMemoryLimitEvent = { runtime_id: number, 
+/**
+ * Resident set size of the runtime process in bytes
+ */
+rss_bytes: number, limit_bytes: number, action: MemoryLimitAction, };
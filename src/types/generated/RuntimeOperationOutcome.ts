@@ -0,0 +1,14 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Outcome of an operation on one runtime within a bulk operation
+ */
+export type RuntimeOperationOutcome = { runtime_id: number, 
+/**
+ * ZenohId of the runtime, for operations that start it
+ */
+zenoh_id: string | null, 
+/**
+ * Error message if the operation failed for this runtime
+ */
+error: string | null, };
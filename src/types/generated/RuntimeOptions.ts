@@ -0,0 +1,110 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { MemoryLimitAction } from "./MemoryLimitAction";
+import type { RestartPolicy } from "./RestartPolicy";
+import type { StorageSpec } from "./StorageSpec";
+import type { WatchdogAction } from "./WatchdogAction";
+
+/**
+ * Per-runtime options given when declaring a runtime
+ */
+export type RuntimeOptions = { 
+/**
+ * Name of the runtime, by which the config templates of other runtimes refer to it
+ */
+name: string | null, 
+/**
+ * File containing an env-filter directive (e.g. `zenoh=debug,zenoh_transport=trace`)
+ * watched by the runtime process to adjust its log verbosity while running
+ */
+log_filter_file: string | null, 
+/**
+ * Runtimes this runtime is expected to have a transport session with.
+ * Compared continuously against the live sessions while running.
+ */
+expected_peers: Array<number> | null, 
+/**
+ * User name or numeric uid to run the runtime process as (Unix only).
+ * The sandbox must run as root unless it is the current user.
+ */
+user: string | null, 
+/**
+ * File mode creation mask of the runtime process in octal, e.g. `077` (Unix only)
+ */
+umask: string | null, 
+/**
+ * Interval in seconds at which a "runtime alive" summary with the number of peers
+ * and the message rate is added to the runtime's log stream
+ */
+heartbeat_interval_secs: number | null, 
+/**
+ * Maximum size in bytes of a message between the sandbox and the runtime process.
+ * Larger log entries are truncated and other larger messages are dropped. Defaults to 1 MiB.
+ */
+max_message_size: number | null, 
+/**
+ * Action taken when the watchdog declares the runtime unresponsive. Defaults to alert.
+ */
+watchdog_action: WatchdogAction | null, 
+/**
+ * In-memory storages run by the storage manager plugin of the runtime
+ */
+storages: Array<StorageSpec>, 
+/**
+ * Enable the REST plugin on a port allocated by the sandbox, to query the runtime
+ * over HTTP from outside the sandbox
+ */
+rest: boolean, 
+/**
+ * Environment variables of the runtime process, in addition to the sandbox's.
+ * The variables the sandbox uses to talk to the runtime process can't be overridden.
+ */
+env: { [key in string]?: string }, 
+/**
+ * `RUST_LOG` directive of the runtime process, e.g. `zenoh=debug`, setting the verbosity
+ * of its logs when there is no log filter file. Takes precedence over a `RUST_LOG` in
+ * `env`. Defaults to `trace`.
+ */
+rust_log: string | null, 
+/**
+ * Time in milliseconds for the runtime process to connect to the sandbox once spawned.
+ * Defaults to 10 s.
+ */
+start_timeout_ms: number | null, 
+/**
+ * Time in milliseconds for the runtime process to stop gracefully before it is killed.
+ * Defaults to 7 s.
+ */
+stop_timeout_ms: number | null, 
+/**
+ * Number of times the runtime process is spawned again when it doesn't connect
+ * within the start timeout. Defaults to 0.
+ */
+connect_retries: number | null, 
+/**
+ * Whether the runtime is started again when its process exits unexpectedly
+ */
+restart: RestartPolicy, 
+/**
+ * Niceness of the runtime process, from -20 (highest priority) to 19 (Unix only).
+ * Negative values require privileges.
+ */
+nice: number | null, 
+/**
+ * CPUs the runtime process is restricted to, by index (Linux only)
+ */
+cpu_affinity: Array<number> | null, 
+/**
+ * Resident set size in MiB above which the runtime process is reported,
+ * checked every 2 s
+ */
+memory_limit_mb: number | null, 
+/**
+ * Action taken when the runtime process exceeds the memory limit. Defaults to alert.
+ */
+memory_limit_action: MemoryLimitAction | null, 
+/**
+ * Enable zenoh shared memory, so that large payloads go through shared memory on the
+ * transports with co-located runtimes enabling it too. When unset, shared memory is
+ * disabled unless the config sets `transport/shared_memory/enabled`.
+ */
+shared_memory: boolean, };
@@ -0,0 +1,12 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { RuntimeState } from "./RuntimeState";
+import type { ZenohMode } from "./ZenohMode";
+
+/**
+ * Selection of runtimes by id, mode and state. Unset criteria match every runtime.
+ */
+export type RuntimeSelector = { runtime_ids: Array<number> | null, 
+/**
+ * Modes of the declared configs
+ */
+modes: Array<ZenohMode> | null, states: Array<RuntimeState> | null, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Payload of the `runtime-started` event
+ */
+export type RuntimeStartedEvent = { runtime_id: number, zenoh_id: string, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Lifecycle state of a sandbox runtime
+ */
+export type RuntimeState = "declared" | "starting" | "running" | "stopping" | "stopped" | "crashed" | "disconnected" | "unresponsive";
@@ -0,0 +1,34 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Metrics of a running runtime, sampled periodically by the runtime process
+ */
+export type RuntimeStats = { timestamp: string, 
+/**
+ * Open transports, unicast and multicast
+ */
+transports: number, 
+/**
+ * Links of the open transports
+ */
+links: number, 
+/**
+ * Sessions with peers
+ */
+peers: number, 
+/**
+ * Sessions with routers
+ */
+routers: number, 
+/**
+ * Subscribers and queryables in the routing tables, if the admin space answered
+ */
+routing_entries: number | null, 
+/**
+ * Resident set size of the runtime process in bytes
+ */
+rss_bytes: number | null, 
+/**
+ * CPU usage of the runtime process since the previous sample, in percent of one core
+ */
+cpu_percent: number | null, };
@@ -0,0 +1,56 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { PeerDivergence } from "./PeerDivergence";
+import type { ProcessPrivileges } from "./ProcessPrivileges";
+import type { RuntimeState } from "./RuntimeState";
+import type { WatchdogStatus } from "./WatchdogStatus";
+
+/**
+ * Status of a sandbox runtime
+ */
+export type RuntimeStatus = { runtime_id: number, state: RuntimeState, 
+/**
+ * ZenohId of the runtime, available once started
+ */
+zenoh_id: string | null, 
+/**
+ * PID of the runtime process, if running
+ */
+pid: number | null, 
+/**
+ * Port allocated for remote_api
+ */
+port: number, 
+/**
+ * Port allocated for the REST plugin, if enabled
+ */
+rest_port: number | null, 
+/**
+ * Seconds since the runtime started, if running
+ */
+uptime_secs: number | null, 
+/**
+ * Exit code of the last runtime process, if it exited with one
+ */
+exit_code: number | null, 
+/**
+ * Difference between the expected and the actual peers, if any
+ */
+peer_divergence: PeerDivergence | null, 
+/**
+ * User and umask the runtime process runs with
+ */
+privileges: ProcessPrivileges, 
+/**
+ * Number of messages from the runtime process truncated or dropped
+ * for exceeding the maximum message size
+ */
+truncated_messages: number, 
+/**
+ * Number of log entries dropped by the runtime process because it produced them
+ * faster than they could be forwarded
+ */
+dropped_logs: number, 
+/**
+ * Signals observed by the watchdog while running
+ */
+watchdog: WatchdogStatus, };
@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Payload of the `runtime-stopped` event, sent once the runtime process is stopped
+ * on request
+ */
+export type RuntimeStoppedEvent = { runtime_id: number, };
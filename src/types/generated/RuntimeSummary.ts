@@ -0,0 +1,30 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { LogLevelCounts } from "./LogLevelCounts";
+import type { RuntimeState } from "./RuntimeState";
+import type { ZenohMode } from "./ZenohMode";
+
+/**
+ * Summary of a sandbox runtime, as listed by `zenoh_runtime_list_detailed`
+ */
+export type RuntimeSummary = { id: number, 
+/**
+ * Name of the runtime from its options, otherwise within its scenario,
+ * as `<scenario>/<runtime>`
+ */
+name: string | null, state: RuntimeState, 
+/**
+ * ZenohId of the runtime, available once started
+ */
+zid: string | null, 
+/**
+ * Port allocated for remote_api
+ */
+port: number, 
+/**
+ * Mode of the declared config
+ */
+mode: ZenohMode, 
+/**
+ * Seconds since the runtime started, if running
+ */
+uptime_secs: number | null, log_counts_by_level: LogLevelCounts, };
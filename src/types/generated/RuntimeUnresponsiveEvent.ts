@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { WatchdogAction } from "./WatchdogAction";
+
+/**
+ * Payload of the `runtime-unresponsive` event
+ */
+export type RuntimeUnresponsiveEvent = { runtime_id: number, reason: string, action: WatchdogAction, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Kind of an error returned by a command, for the frontend to branch on
+ */
+export type SandboxErrorKind = "config_invalid" | "runtime_not_found" | "runtime_not_started" | "spawn_failed" | "timeout" | "ipc_error" | "other";
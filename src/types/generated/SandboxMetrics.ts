@@ -0,0 +1,23 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ProcessMetrics } from "./ProcessMetrics";
+
+/**
+ * Resource usage of all runtime processes, see `get_sandbox_metrics`
+ */
+export type SandboxMetrics = { 
+/**
+ * When the processes were sampled, None before the first sample
+ */
+sampled_at: string | null, 
+/**
+ * Running runtime processes, ordered by RuntimeId
+ */
+runtimes: Array<ProcessMetrics>, 
+/**
+ * Sum of the CPU usage of the runtime processes, in percent of one core
+ */
+total_cpu_percent: number, 
+/**
+ * Sum of the resident set sizes of the runtime processes in bytes
+ */
+total_memory_bytes: number, };
@@ -0,0 +1,16 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { JournalEntry } from "./JournalEntry";
+import type { SessionRuntime } from "./SessionRuntime";
+
+/**
+ * Declared runtimes of the sandbox, as saved by `export_session`
+ */
+export type SandboxSession = { 
+/**
+ * Version of the session file format
+ */
+version: number, exported_at: string, sandbox_version: string, runtimes: Array<SessionRuntime>, 
+/**
+ * Experiment journal
+ */
+journal: Array<JournalEntry>, };
@@ -0,0 +1,11 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ScenarioRuntime } from "./ScenarioRuntime";
+
+/**
+ * Document describing a set of runtimes and the connections between them
+ */
+export type ScenarioDocument = { 
+/**
+ * Unique name of the scenario
+ */
+name: string, runtimes: Array<ScenarioRuntime>, };
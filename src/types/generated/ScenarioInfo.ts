@@ -0,0 +1,11 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ScenarioRuntimeInfo } from "./ScenarioRuntimeInfo";
+
+/**
+ * Loaded scenario
+ */
+export type ScenarioInfo = { name: string, 
+/**
+ * Runtimes in start order
+ */
+runtimes: Array<ScenarioRuntimeInfo>, };
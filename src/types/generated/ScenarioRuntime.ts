@@ -0,0 +1,20 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { RuntimeOptions } from "./RuntimeOptions";
+
+/**
+ * Runtime of a scenario document
+ */
+export type ScenarioRuntime = { 
+/**
+ * Name of the runtime, unique within the scenario
+ */
+name: string, 
+/**
+ * Zenoh config, possibly partial. Missing fields take their default value.
+ * Its strings may contain placeholders like `${PEER(<name>).ENDPOINT}`, resolved at start.
+ */
+config: Record<string, any> | null, options: RuntimeOptions | null, 
+/**
+ * Names of the runtimes this runtime connects to. They are started before it.
+ */
+connect: Array<string>, };
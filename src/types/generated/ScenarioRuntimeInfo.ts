@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Runtime of a loaded scenario
+ */
+export type ScenarioRuntimeInfo = { name: string, 
+/**
+ * RuntimeId, once the scenario has been started
+ */
+runtime_id: number | null, connect: Array<string>, };
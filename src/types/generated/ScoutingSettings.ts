@@ -0,0 +1,14 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Discovery settings of the declared config of a runtime
+ */
+export type ScoutingSettings = { 
+/**
+ * `scouting/multicast/enabled`
+ */
+multicast: boolean, 
+/**
+ * `scouting/gossip/enabled`
+ */
+gossip: boolean, };
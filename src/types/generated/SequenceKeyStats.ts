@@ -0,0 +1,30 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Delivery statistics of the sequence numbers received on a key
+ */
+export type SequenceKeyStats = { key_expr: string, 
+/**
+ * Number of samples with a sequence number
+ */
+received: number, 
+/**
+ * Highest sequence number received
+ */
+last_seq: number, 
+/**
+ * Number of times sequence numbers were skipped
+ */
+gaps: number, 
+/**
+ * Number of skipped sequence numbers not received since
+ */
+missing: number, 
+/**
+ * Number of sequence numbers received after a higher one
+ */
+reordered: number, 
+/**
+ * Number of sequence numbers received more than once
+ */
+duplicates: number, };
@@ -0,0 +1,23 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { SequenceKeyStats } from "./SequenceKeyStats";
+
+/**
+ * Result of `get_sequence_report`
+ */
+export type SequenceReport = { 
+/**
+ * Identifier of the sequence checker
+ */
+id: number, 
+/**
+ * Statistics per key, ordered by key expression
+ */
+keys: Array<SequenceKeyStats>, 
+/**
+ * Sum of the statistics of all keys
+ */
+received: number, missing: number, reordered: number, duplicates: number, 
+/**
+ * Number of samples without a sequence number
+ */
+invalid: number, };
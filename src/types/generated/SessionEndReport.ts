@@ -0,0 +1,23 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { BulkOperationReport } from "./BulkOperationReport";
+
+/**
+ * Result of `end_session`
+ */
+export type SessionEndReport = { 
+/**
+ * Outcome of stopping the active runtimes
+ */
+stopped: BulkOperationReport, 
+/**
+ * Absolute path of the written archive, if one was requested
+ */
+archive: string | null, 
+/**
+ * Runtimes removed from the sandbox. Runtimes that failed to stop are kept.
+ */
+removed_runtimes: Array<number>, 
+/**
+ * Number of leftover sockets and process output files deleted
+ */
+removed_files: number, };
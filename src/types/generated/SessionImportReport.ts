@@ -0,0 +1,12 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { BulkOperationReport } from "./BulkOperationReport";
+import type { ImportedRuntime } from "./ImportedRuntime";
+
+/**
+ * Result of `import_session`
+ */
+export type SessionImportReport = { runtimes: Array<ImportedRuntime>, 
+/**
+ * Outcome of starting the runtimes that were active at export time
+ */
+started: BulkOperationReport, };
@@ -0,0 +1,33 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { RuntimeOptions } from "./RuntimeOptions";
+import type { RuntimeState } from "./RuntimeState";
+import type { ZenohConfigJson } from "./ZenohConfigJson";
+
+/**
+ * Declared runtime in a session file
+ */
+export type SessionRuntime = { 
+/**
+ * RuntimeId at export time. Runtimes get new ids when imported.
+ */
+runtime_id: number, 
+/**
+ * Name of the runtime within its scenario, as `<scenario>/<runtime>`
+ */
+name: string | null, config: ZenohConfigJson, 
+/**
+ * JSON5 text of the config, with its comments, if it was given
+ */
+config_source?: string | null, options: RuntimeOptions, 
+/**
+ * Allocated remote_api port, reused on import if it is free
+ */
+port: number, 
+/**
+ * State at export time. Runtimes that were active are started on import.
+ */
+state: RuntimeState, 
+/**
+ * Notes about the runtime, in Markdown
+ */
+notes: string, };
@@ -0,0 +1,28 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Whether samples between two runtimes went through shared memory, see
+ * `verify_shared_memory`
+ */
+export type ShmReport = { publisher_runtime_id: number, subscriber_runtime_id: number, key_expr: string, 
+/**
+ * Whether shared memory is enabled in the declared options or config of the publisher
+ */
+publisher_enabled: boolean, 
+/**
+ * Whether shared memory is enabled in the declared options or config of the subscriber
+ */
+subscriber_enabled: boolean, 
+/**
+ * Size in bytes of the published payloads
+ */
+payload_size: number, 
+/**
+ * Number of samples received by the subscriber
+ */
+received: number, 
+/**
+ * Whether the transport session between the runtimes uses shared memory, as reported
+ * by the publisher's adminspace. None if the runtimes have no direct session.
+ */
+transport_shm: boolean | null, };
@@ -0,0 +1,14 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Directories used for files of runtime processes
+ */
+export type StorageLocations = { 
+/**
+ * Directory of the stdout/stderr logs of runtime processes
+ */
+log_dir: string, 
+/**
+ * Directory of the IPC sockets
+ */
+socket_dir: string, };
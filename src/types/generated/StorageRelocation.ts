@@ -0,0 +1,19 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { StorageLocations } from "./StorageLocations";
+
+/**
+ * Result of relocating the storage directories
+ */
+export type StorageRelocation = { 
+/**
+ * Directories used by runtimes started from now on
+ */
+locations: StorageLocations, 
+/**
+ * Log files moved to the new log directory
+ */
+migrated_files: Array<string>, 
+/**
+ * Log files that could not be moved, with the reason
+ */
+failed_files: Array<string>, };
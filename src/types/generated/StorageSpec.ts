@@ -0,0 +1,15 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * A storage of the storage manager plugin, keeping in memory the samples published on
+ * its key expression and answering queries on it
+ */
+export type StorageSpec = { 
+/**
+ * Key expression of the stored samples, e.g. `demo/**`
+ */
+keyexpr: string, 
+/**
+ * Name of the storage, unique within the runtime
+ */
+name: string, };
@@ -0,0 +1,42 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Reception statistics of a test subscriber, see `get_subscriber_stats`
+ */
+export type SubscriberStats = { 
+/**
+ * Identifier of the subscriber
+ */
+id: number, 
+/**
+ * Number of samples received since the subscriber was declared
+ */
+received: number, 
+/**
+ * Number of payload bytes received since the subscriber was declared
+ */
+received_bytes: number, 
+/**
+ * Duration of the rolling window of the rates and intervals, in seconds
+ */
+window_secs: number, 
+/**
+ * Samples per second over the window
+ */
+messages_per_sec: number, 
+/**
+ * Payload bytes per second over the window
+ */
+bytes_per_sec: number, 
+/**
+ * Mean time between two samples over the window, in milliseconds
+ */
+mean_interval_ms: number | null, 
+/**
+ * Standard deviation of the time between two samples over the window, in milliseconds
+ */
+jitter_ms: number | null, 
+/**
+ * When the last sample was received
+ */
+last_received_at: string | null, };
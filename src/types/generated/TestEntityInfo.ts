@@ -0,0 +1,15 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { TestEntityKind } from "./TestEntityKind";
+
+/**
+ * A test publisher or subscriber declared on a runtime's session
+ */
+export type TestEntityInfo = { 
+/**
+ * Identifier of the entity, unique across runtimes
+ */
+id: number, runtime_id: number, 
+/**
+ * Key expression the entity publishes on or subscribes to
+ */
+keyexpr: string, kind: TestEntityKind, };
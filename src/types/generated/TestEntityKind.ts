@@ -0,0 +1,18 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Kind of a test entity, with its parameters
+ */
+export type TestEntityKind = { "type": "publisher", payload: string, period_ms: number, } | { "type": "sequence_publisher", keys: number, period_ms: number, payload_size: number, 
+/**
+ * Whether publications block instead of being dropped on congestion
+ */
+block: boolean, } | { "type": "periodic_publisher", payload_template: string, period_ms: number, jitter_ms: number, burst: number, } | { "type": "replay", path: string, speed: number, 
+/**
+ * Number of samples in the recording
+ */
+samples: number, } | { "type": "subscriber" } | { "type": "sequence_checker" } | { "type": "queryable", reply_payload: string, } | { "type": "liveliness_token" } | { "type": "liveliness_subscriber", 
+/**
+ * Whether the tokens alive when subscribing were reported
+ */
+history: boolean, };
@@ -0,0 +1,15 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { TestReply } from "./TestReply";
+
+/**
+ * Result of a test query
+ */
+export type TestGetResult = { 
+/**
+ * Time until the query completed, in milliseconds
+ */
+elapsed_ms: number, 
+/**
+ * Replies in the order they were received
+ */
+replies: Array<TestReply>, };
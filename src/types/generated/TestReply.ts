@@ -0,0 +1,26 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * A reply received by a test query
+ */
+export type TestReply = { 
+/**
+ * Key expression of the reply, or the selector for error replies
+ */
+key_expr: string, 
+/**
+ * Encoding of the payload
+ */
+encoding: string, 
+/**
+ * Payload, decoded as UTF-8 with invalid sequences replaced
+ */
+payload: string, 
+/**
+ * Size of the payload in bytes
+ */
+payload_len: number, 
+/**
+ * Whether the replier answered with an error
+ */
+is_error: boolean, };
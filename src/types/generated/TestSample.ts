@@ -0,0 +1,39 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * A sample received by a test subscriber, or a query received by a test queryable
+ */
+export type TestSample = { 
+/**
+ * Identifier of the sample, unique across test subscribers, see `decode_sample`.
+ * Assigned when the sample is stored.
+ */
+sample_id: number, 
+/**
+ * When the runtime received the sample
+ */
+received_at: string, 
+/**
+ * Key expression of the sample
+ */
+key_expr: string, 
+/**
+ * Kind of the sample: put or delete, or query for queries
+ */
+kind: string, 
+/**
+ * Encoding of the payload
+ */
+encoding: string, 
+/**
+ * Payload, decoded as UTF-8 with invalid sequences replaced
+ */
+payload: string, 
+/**
+ * Size of the payload in bytes
+ */
+payload_len: number, 
+/**
+ * Timestamp attached by the publisher, if any
+ */
+timestamp: string | null, };
@@ -0,0 +1,27 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { TimelineEventKind } from "./TimelineEventKind";
+
+/**
+ * A single annotated event of the sandbox timeline
+ */
+export type TimelineEvent = { 
+/**
+ * Identifier of the event, increasing with each event of the sandbox
+ */
+id: number, 
+/**
+ * Timestamp of the event
+ */
+timestamp: string, 
+/**
+ * Runtime affected by the event, if any
+ */
+runtime_id: number | null, 
+/**
+ * Short human readable summary
+ */
+summary: string, 
+/**
+ * Event details
+ */
+kind: TimelineEventKind, };
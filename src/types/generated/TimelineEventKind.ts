@@ -0,0 +1,20 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ConfigChangeSource } from "./ConfigChangeSource";
+import type { WatchdogAction } from "./WatchdogAction";
+
+/**
+ * Kind of event recorded in the timeline
+ */
+export type TimelineEventKind = { "type": "config_change", source: ConfigChangeSource, 
+/**
+ * Diff between the old and new config, as produced by `json_diff`
+ */
+diff: Record<string, any>, } | { "type": "ipc_failure", 
+/**
+ * The I/O error, or the reason the connection was considered lost
+ */
+error: string, } | { "type": "unresponsive", 
+/**
+ * Signals that led to it
+ */
+reason: string, action: WatchdogAction, };
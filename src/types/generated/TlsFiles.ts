@@ -0,0 +1,19 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { TlsRole } from "./TlsRole";
+
+/**
+ * Files of the sandbox certificate authority and of the certificate of a runtime
+ */
+export type TlsFiles = { runtime_id: number, role: TlsRole, 
+/**
+ * PEM certificate of the self-signed certificate authority of the sandbox
+ */
+root_ca_certificate: string, 
+/**
+ * PEM certificate of the runtime, signed by the certificate authority
+ */
+certificate: string, 
+/**
+ * PEM private key of the runtime
+ */
+private_key: string, };
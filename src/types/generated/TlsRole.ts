@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Side of the TLS links a runtime is configured for, see `configure_tls`
+ */
+export type TlsRole = "server" | "client" | "mutual";
@@ -0,0 +1,35 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { TopologyEdge } from "./TopologyEdge";
+import type { TopologyNode } from "./TopologyNode";
+
+/**
+ * Changes of the topology graph since the snapshot acknowledged by the frontend,
+ * sent with the `topology-delta` event.
+ * The delta can be applied on top of any snapshot from `base_seq` to `seq`.
+ */
+export type TopologyDelta = { 
+/**
+ * Sequence number of the resulting snapshot, to acknowledge once applied
+ */
+seq: number, 
+/**
+ * Snapshot the delta applies to, None if the delta is a full snapshot
+ * replacing the current graph
+ */
+base_seq: number | null, timestamp: string, 
+/**
+ * Nodes added or changed, ordered by ZenohId
+ */
+nodes: Array<TopologyNode>, 
+/**
+ * ZenohIds of the removed nodes
+ */
+removed_nodes: Array<string>, 
+/**
+ * Sessions added or changed, ordered by ZenohIds
+ */
+edges: Array<TopologyEdge>, 
+/**
+ * Removed sessions, as ordered pairs of ZenohIds
+ */
+removed_edges: Array<[string, string]>, };
@@ -0,0 +1,19 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { TopologyLink } from "./TopologyLink";
+
+/**
+ * Difference between the intended topology and the live sessions
+ */
+export type TopologyDrift = { timestamp: string, 
+/**
+ * Intended links without a live session
+ */
+missing_links: Array<TopologyLink>, 
+/**
+ * Live sessions between runtimes of the intended topology that are not intended
+ */
+unexpected_links: Array<TopologyLink>, 
+/**
+ * Runtimes of the intended topology that are not running
+ */
+down_nodes: Array<number>, };
@@ -0,0 +1,23 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { AdminLinkInfo } from "./AdminLinkInfo";
+
+/**
+ * A transport session between two nodes
+ */
+export type TopologyEdge = { 
+/**
+ * ZenohId of the node reporting the session
+ */
+source: string, 
+/**
+ * ZenohId of the remote node
+ */
+target: string, 
+/**
+ * Links of the session, as seen from `source`
+ */
+links: Array<AdminLinkInfo>, 
+/**
+ * Whether the session uses shared memory
+ */
+shm: boolean, };
@@ -0,0 +1,20 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { TopologyEdge } from "./TopologyEdge";
+import type { TopologyNode } from "./TopologyNode";
+
+/**
+ * Graph of the nodes known to the running runtimes and of their sessions
+ */
+export type TopologyGraph = { 
+/**
+ * When the adminspaces were queried
+ */
+timestamp: string, 
+/**
+ * Nodes, ordered by ZenohId
+ */
+nodes: Array<TopologyNode>, 
+/**
+ * Sessions, each reported once, ordered by ZenohIds
+ */
+edges: Array<TopologyEdge>, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * A transport session between two sandbox runtimes
+ */
+export type TopologyLink = { from: number, to: number, };
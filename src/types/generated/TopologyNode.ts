@@ -0,0 +1,22 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * A zenoh node known to the sandbox runtimes
+ */
+export type TopologyNode = { zid: string, 
+/**
+ * Kind of the node: router, peer or client
+ */
+whatami: string, 
+/**
+ * RuntimeId if the node is a sandbox runtime
+ */
+runtime_id: number | null, 
+/**
+ * Zenoh version, if the node's adminspace was reachable
+ */
+version: string | null, 
+/**
+ * Locators the node listens on, if its adminspace was reachable
+ */
+locators: Array<string>, };
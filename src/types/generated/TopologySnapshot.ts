@@ -0,0 +1,16 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { RuntimeStatus } from "./RuntimeStatus";
+import type { TopologyLink } from "./TopologyLink";
+
+/**
+ * State of the sandbox runtimes and of their sessions at a point in time
+ */
+export type TopologySnapshot = { timestamp: string, 
+/**
+ * Status of every runtime
+ */
+runtimes: Array<RuntimeStatus>, 
+/**
+ * Sessions between running runtimes, as reported by the `from` runtime
+ */
+links: Array<TopologyLink>, };
@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Built-in transport settings applied to the declared config of a runtime,
+ * see `apply_transport_profile`
+ */
+export type TransportProfile = "low_latency" | "high_throughput" | "lossy_network";
@@ -0,0 +1,12 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { TuningChange } from "./TuningChange";
+import type { ZenohConfigJson } from "./ZenohConfigJson";
+
+/**
+ * Config returned by a tuning wizard
+ */
+export type TunedConfig = { config: ZenohConfigJson, 
+/**
+ * Changed fields, in the order they were applied
+ */
+changes: Array<TuningChange>, };
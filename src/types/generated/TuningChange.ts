@@ -0,0 +1,18 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Config field changed by a tuning wizard
+ */
+export type TuningChange = { 
+/**
+ * Path of the field in the zenoh config key notation, e.g. `transport/unicast/lowlatency`
+ */
+path: string, 
+/**
+ * Value before tuning, null if unset
+ */
+old_value: any, new_value: any, 
+/**
+ * Why the field was changed
+ */
+explanation: string, };
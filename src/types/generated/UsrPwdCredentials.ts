@@ -0,0 +1,22 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * User-password credentials set in the configs of runtimes, see `configure_usrpwd`
+ */
+export type UsrPwdCredentials = { 
+/**
+ * `transport/auth/usrpwd/user`
+ */
+user: string, 
+/**
+ * `transport/auth/usrpwd/password`
+ */
+password: string, 
+/**
+ * `transport/auth/usrpwd/dictionary_file`, listing the credentials accepted from others
+ */
+dictionary_file: string, 
+/**
+ * Runtimes configured with the credentials
+ */
+runtime_ids: Array<number>, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Action taken when the watchdog declares a runtime unresponsive
+ */
+export type WatchdogAction = "alert" | "capture_stack" | "restart";
@@ -0,0 +1,22 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Signals observed by the watchdog on a running runtime
+ */
+export type WatchdogStatus = { 
+/**
+ * Number of consecutive keepalive pings left unanswered
+ */
+missed_pings: number, 
+/**
+ * Seconds since the last message from the runtime process, as of the last check
+ */
+last_message_secs: number | null, 
+/**
+ * Whether the remote_api websocket port accepted a connection at the last check
+ */
+websocket_reachable: boolean | null, 
+/**
+ * Why the runtime was declared unresponsive, while it is
+ */
+reason: string | null, };
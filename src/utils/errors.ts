@@ -0,0 +1,31 @@
+/**
+ * Utilities for errors returned by Tauri commands
+ */
+
+import type { CommandError } from '../types/generated/CommandError';
+
+/**
+ * Check whether an error rejected by `invoke` is a structured command error
+ * @param error - Error caught from a command
+ * @returns Whether the error has a kind and a message
+ */
+export function isCommandError(error: unknown): error is CommandError {
+  return (
+    typeof error === 'object' &&
+    error !== null &&
+    'kind' in error &&
+    'message' in error
+  );
+}
+
+/**
+ * Human-readable message of an error caught from a command
+ * @param error - Error caught from a command
+ * @returns Message of the command error, or the error as a string
+ */
+export function errorMessage(error: unknown): string {
+  if (isCommandError(error)) {
+    return error.message;
+  }
+  return String(error);
+}